@@ -50,6 +50,7 @@ impl Default for App {
 
         app.add_default_stages()
             .add_event::<AppExit>()
+            .add_event::<bevy_ecs::blueprint::BlueprintSpawned>()
             .add_system_to_stage(CoreStage::Last, World::clear_trackers.exclusive_system());
 
         #[cfg(feature = "bevy_ci_testing")]
@@ -576,6 +577,54 @@ impl App {
         }
         self
     }
+
+    /// Registers the blueprint `T` in the [`BlueprintRegistry`](bevy_ecs::reflect::BlueprintRegistry)
+    /// under the given category, so that tools can list it and spawn it by name. This also
+    /// registers `T` and its [`ReflectBlueprint`](bevy_ecs::reflect::ReflectBlueprint) type data
+    /// with the type registry.
+    #[cfg(feature = "bevy_reflect")]
+    pub fn register_blueprint<T>(&mut self, category: &str) -> &mut Self
+    where
+        T: bevy_ecs::blueprint::EntityBlueprint
+            + bevy_reflect::GetTypeRegistration
+            + bevy_reflect::Reflect
+            + Default,
+    {
+        let name = bevy_reflect::TypeRegistration::get_short_name(std::any::type_name::<T>());
+        self.register_blueprint_as::<T>(&name, category)
+    }
+
+    /// Registers the blueprint `T` like [`App::register_blueprint`], but under `name` instead of
+    /// its short type name, such as `"enemy/goblin"`, so that it is spawned with
+    /// [`Commands::spawn_blueprint_by_name`](bevy_ecs::system::Commands::spawn_blueprint_by_name)
+    /// under that name.
+    #[cfg(feature = "bevy_reflect")]
+    pub fn register_blueprint_as<T>(&mut self, name: &str, category: &str) -> &mut Self
+    where
+        T: bevy_ecs::blueprint::EntityBlueprint
+            + bevy_reflect::GetTypeRegistration
+            + bevy_reflect::Reflect
+            + Default,
+    {
+        {
+            let registry = self
+                .world
+                .get_resource_mut::<bevy_reflect::TypeRegistryArc>()
+                .unwrap();
+            let mut registry = registry.write();
+            registry.register::<T>();
+            registry
+                .get_mut(std::any::TypeId::of::<T>())
+                .unwrap()
+                .insert(
+                    <bevy_ecs::reflect::ReflectBlueprint as bevy_reflect::FromType<T>>::from_type(),
+                );
+        }
+        self.world
+            .get_resource_or_insert_with(bevy_ecs::reflect::BlueprintRegistry::default)
+            .register_as::<T>(name, category);
+        self
+    }
 }
 
 fn run_once(mut app: App) {
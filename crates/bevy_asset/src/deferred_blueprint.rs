@@ -0,0 +1,213 @@
+use crate::{Asset, AssetServer, Handle, HandleId, HandleUntyped, LoadState};
+use bevy_app::EventWriter;
+use bevy_ecs::{
+    blueprint::EntityBlueprint,
+    entity::Entity,
+    system::{Commands, EntityCommands, Query, Res},
+};
+
+/// A blueprint that is only built once the assets it depends on are loaded.
+///
+/// Building it inserts a [`PendingBlueprint`] on the entity right away, so the entity can be used
+/// as a placeholder (for example by composing this blueprint in a tuple with a blueprint of a
+/// loading indicator). When all the assets are loaded, the wrapped blueprint is built onto the
+/// entity and the [`PendingBlueprint`] is removed. [`DeferredBlueprintEvent`]s report the
+/// progress of the loading.
+pub struct DeferredBlueprint<B> {
+    blueprint: B,
+    assets: Vec<HandleUntyped>,
+}
+
+impl<B: EntityBlueprint + Send + Sync + 'static> DeferredBlueprint<B> {
+    pub fn new(blueprint: B) -> Self {
+        DeferredBlueprint {
+            blueprint,
+            assets: Vec::new(),
+        }
+    }
+
+    /// Waits for `handle` to be loaded before building the blueprint.
+    pub fn with_asset<T: Asset>(mut self, handle: &Handle<T>) -> Self {
+        self.assets.push(handle.clone_untyped());
+        self
+    }
+}
+
+impl<B: EntityBlueprint + Send + Sync + 'static> EntityBlueprint for DeferredBlueprint<B> {
+    type Output = ();
+
+    fn build(self, entity: &mut EntityCommands) {
+        let blueprint = self.blueprint;
+        entity.insert(PendingBlueprint {
+            build: Some(Box::new(move |entity| {
+                entity.insert_blueprint(blueprint);
+            })),
+            assets: self.assets,
+            loaded: 0,
+        });
+    }
+}
+
+type BuildBlueprint = Box<dyn FnOnce(&mut EntityCommands) + Send + Sync>;
+
+/// Holds a [`DeferredBlueprint`] until its assets are loaded.
+pub struct PendingBlueprint {
+    build: Option<BuildBlueprint>,
+    assets: Vec<HandleUntyped>,
+    loaded: usize,
+}
+
+impl PendingBlueprint {
+    /// The number of assets that are loaded, out of [`total`](PendingBlueprint::total).
+    pub fn loaded(&self) -> usize {
+        self.loaded
+    }
+
+    /// The number of assets the blueprint waits for.
+    pub fn total(&self) -> usize {
+        self.assets.len()
+    }
+}
+
+/// Reports the progress of [`DeferredBlueprint`]s.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeferredBlueprintEvent {
+    /// More assets of the blueprint have been loaded.
+    Progress {
+        entity: Entity,
+        loaded: usize,
+        total: usize,
+    },
+    /// All assets have been loaded and the blueprint has been built.
+    Completed { entity: Entity },
+    /// An asset failed to load. The blueprint is dropped without being built, leaving the
+    /// placeholder entity in place.
+    Failed { entity: Entity },
+}
+
+/// Builds the [`PendingBlueprint`]s whose assets are loaded.
+pub fn deferred_blueprint_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut events: EventWriter<DeferredBlueprintEvent>,
+    mut query: Query<(Entity, &mut PendingBlueprint)>,
+) {
+    for (entity, mut pending) in query.iter_mut() {
+        let mut loaded = 0;
+        let mut failed = false;
+        for handle in pending.assets.iter() {
+            match handle.id {
+                // assets added directly to `Assets` are available right away
+                HandleId::Id(..) => loaded += 1,
+                HandleId::AssetPathId(_) => match asset_server.get_load_state(handle) {
+                    LoadState::Loaded => loaded += 1,
+                    LoadState::Failed => failed = true,
+                    _ => {}
+                },
+            }
+        }
+
+        if failed {
+            commands.entity(entity).remove::<PendingBlueprint>();
+            events.send(DeferredBlueprintEvent::Failed { entity });
+            continue;
+        }
+        if loaded != pending.loaded {
+            pending.loaded = loaded;
+            events.send(DeferredBlueprintEvent::Progress {
+                entity,
+                loaded,
+                total: pending.total(),
+            });
+        }
+        if loaded == pending.total() {
+            let mut entity_commands = commands.entity(entity);
+            entity_commands.remove::<PendingBlueprint>();
+            if let Some(build) = pending.build.take() {
+                build(&mut entity_commands);
+            }
+            events.send(DeferredBlueprintEvent::Completed { entity });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        deferred_blueprint_system, DeferredBlueprint, DeferredBlueprintEvent, PendingBlueprint,
+    };
+    use crate::{AssetServer, FileAssetIo, Handle, HandleId};
+    use bevy_app::Events;
+    use bevy_ecs::{
+        blueprint::EntityBlueprint,
+        schedule::{Stage, SystemStage},
+        system::{CommandQueue, Commands, EntityCommands, IntoSystem},
+        world::World,
+    };
+    use bevy_reflect::TypeUuid;
+    use bevy_tasks::TaskPool;
+
+    #[derive(TypeUuid)]
+    #[uuid = "44115972-f31b-46e5-be5c-2b9aece6a52f"]
+    struct Mesh;
+
+    struct Value(u32);
+
+    impl EntityBlueprint for Value {
+        type Output = ();
+
+        fn build(self, entity: &mut EntityCommands) {
+            entity.insert(self.0);
+        }
+    }
+
+    #[test]
+    fn deferred_blueprint() {
+        let mut world = World::default();
+        world.insert_resource(AssetServer::new(FileAssetIo::new("."), TaskPool::new()));
+        world.insert_resource(Events::<DeferredBlueprintEvent>::default());
+
+        let ready = Handle::<Mesh>::weak(HandleId::random::<Mesh>());
+        let not_loaded = Handle::<Mesh>::weak(HandleId::from("mesh.gltf"));
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &world);
+        let ready_entity = commands
+            .spawn_blueprint(DeferredBlueprint::new(Value(1)).with_asset(&ready))
+            .id();
+        let waiting_entity = commands
+            .spawn_blueprint(DeferredBlueprint::new(Value(2)).with_asset(&not_loaded))
+            .id();
+        queue.apply(&mut world);
+
+        let mut stage = SystemStage::single(deferred_blueprint_system.system());
+        stage.run(&mut world);
+
+        assert_eq!(world.get::<u32>(ready_entity), Some(&1));
+        assert!(world.get::<PendingBlueprint>(ready_entity).is_none());
+        assert_eq!(world.get::<u32>(waiting_entity), None);
+        assert_eq!(
+            world
+                .get::<PendingBlueprint>(waiting_entity)
+                .unwrap()
+                .loaded(),
+            0
+        );
+
+        let events = world
+            .get_resource::<Events<DeferredBlueprintEvent>>()
+            .unwrap();
+        assert_eq!(
+            events.get_reader().iter(events).collect::<Vec<_>>(),
+            vec![
+                &DeferredBlueprintEvent::Progress {
+                    entity: ready_entity,
+                    loaded: 1,
+                    total: 1,
+                },
+                &DeferredBlueprintEvent::Completed {
+                    entity: ready_entity
+                },
+            ]
+        );
+    }
+}
@@ -1,5 +1,6 @@
 mod asset_server;
 mod assets;
+mod deferred_blueprint;
 pub mod diagnostic;
 #[cfg(all(
     feature = "filesystem_watcher",
@@ -20,6 +21,7 @@ pub mod prelude {
 pub use asset_server::*;
 pub use assets::*;
 pub use bevy_utils::BoxedFuture;
+pub use deferred_blueprint::*;
 pub use handle::*;
 pub use info::*;
 pub use io::*;
@@ -101,10 +103,12 @@ impl Plugin for AssetPlugin {
             SystemStage::parallel(),
         )
         .register_type::<HandleId>()
+        .add_event::<DeferredBlueprintEvent>()
         .add_system_to_stage(
             bevy_app::CoreStage::PreUpdate,
             asset_server::free_unused_assets_system,
-        );
+        )
+        .add_system_to_stage(bevy_app::CoreStage::PreUpdate, deferred_blueprint_system);
 
         #[cfg(all(
             feature = "filesystem_watcher",
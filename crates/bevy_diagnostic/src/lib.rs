@@ -2,10 +2,14 @@ mod diagnostic;
 mod entity_count_diagnostics_plugin;
 mod frame_time_diagnostics_plugin;
 mod log_diagnostics_plugin;
+#[cfg(feature = "window")]
+mod window_diagnostics_plugin;
 pub use diagnostic::*;
 pub use entity_count_diagnostics_plugin::EntityCountDiagnosticsPlugin;
 pub use frame_time_diagnostics_plugin::FrameTimeDiagnosticsPlugin;
 pub use log_diagnostics_plugin::LogDiagnosticsPlugin;
+#[cfg(feature = "window")]
+pub use window_diagnostics_plugin::WindowDiagnosticsPlugin;
 
 use bevy_app::prelude::*;
 
@@ -0,0 +1,104 @@
+use crate::{Diagnostic, DiagnosticId, Diagnostics};
+use bevy_app::prelude::*;
+use bevy_ecs::system::{Res, ResMut};
+use bevy_window::{WindowBackendStats, WindowId, Windows};
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+};
+
+/// Adds window diagnostics to an App: "window count", and the number of window events and the
+/// time spent by the window backend handling the events of the platform each frame.
+///
+/// Each window also gets "width", "height", "scale factor" and "vsync" diagnostics, added when
+/// the window is first seen. Their ids are given by
+/// [`WindowDiagnosticsPlugin::window_diagnostic_id`].
+///
+/// Requires the `window` feature.
+#[derive(Default)]
+pub struct WindowDiagnosticsPlugin;
+
+impl Plugin for WindowDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_startup_system(Self::setup_system)
+            .add_system(Self::diagnostic_system);
+    }
+}
+
+impl WindowDiagnosticsPlugin {
+    pub const WINDOW_COUNT: DiagnosticId =
+        DiagnosticId::from_u128(120163425437898209620736577328564012717);
+    pub const WINDOW_EVENTS: DiagnosticId =
+        DiagnosticId::from_u128(313376092541063254768207398402612587391);
+    pub const EVENT_HANDLER_TIME: DiagnosticId =
+        DiagnosticId::from_u128(66206347785384290131622213489125226430);
+
+    // the per-window diagnostics, combined with the window id
+    pub const WIDTH: DiagnosticId =
+        DiagnosticId::from_u128(203907745802458318813516183420446624301);
+    pub const HEIGHT: DiagnosticId =
+        DiagnosticId::from_u128(150239081646937018413064939541734816094);
+    pub const SCALE_FACTOR: DiagnosticId =
+        DiagnosticId::from_u128(247917006131637212567432766787226591529);
+    pub const VSYNC: DiagnosticId = DiagnosticId::from_u128(29416208457315911786002380290744136633);
+
+    /// The id of the `diagnostic` of the window `id`, where `diagnostic` is one of
+    /// [`WIDTH`](Self::WIDTH), [`HEIGHT`](Self::HEIGHT), [`SCALE_FACTOR`](Self::SCALE_FACTOR) or
+    /// [`VSYNC`](Self::VSYNC).
+    pub fn window_diagnostic_id(diagnostic: DiagnosticId, id: WindowId) -> DiagnosticId {
+        if id.is_primary() {
+            return diagnostic;
+        }
+        let mut hasher = DefaultHasher::new();
+        id.hash(&mut hasher);
+        DiagnosticId::from_u128(diagnostic.0.as_u128() ^ hasher.finish() as u128)
+    }
+
+    pub fn setup_system(mut diagnostics: ResMut<Diagnostics>) {
+        diagnostics.add(Diagnostic::new(Self::WINDOW_COUNT, "window_count", 20));
+        diagnostics.add(Diagnostic::new(Self::WINDOW_EVENTS, "window_events", 20));
+        diagnostics.add(
+            Diagnostic::new(Self::EVENT_HANDLER_TIME, "window_event_handler_time", 20)
+                .with_suffix("s"),
+        );
+    }
+
+    pub fn diagnostic_system(
+        mut diagnostics: ResMut<Diagnostics>,
+        windows: Res<Windows>,
+        stats: Res<WindowBackendStats>,
+    ) {
+        diagnostics.add_measurement(Self::WINDOW_COUNT, windows.iter().count() as f64);
+        diagnostics.add_measurement(Self::WINDOW_EVENTS, stats.window_events as f64);
+        diagnostics.add_measurement(
+            Self::EVENT_HANDLER_TIME,
+            stats.event_handler_time.as_secs_f64(),
+        );
+
+        for window in windows.iter() {
+            let id = window.id();
+            let label = if id.is_primary() {
+                "primary".to_string()
+            } else {
+                id.to_string()[..8].to_string()
+            };
+            let measurements = [
+                (Self::WIDTH, "width", window.width() as f64),
+                (Self::HEIGHT, "height", window.height() as f64),
+                (Self::SCALE_FACTOR, "scale_factor", window.scale_factor()),
+                (Self::VSYNC, "vsync", window.vsync() as u8 as f64),
+            ];
+            for (diagnostic, name, value) in measurements {
+                let diagnostic_id = Self::window_diagnostic_id(diagnostic, id);
+                if diagnostics.get(diagnostic_id).is_none() {
+                    diagnostics.add(Diagnostic::new(
+                        diagnostic_id,
+                        format!("window_{}_{}", label, name),
+                        20,
+                    ));
+                }
+                diagnostics.add_measurement(diagnostic_id, value);
+            }
+        }
+    }
+}
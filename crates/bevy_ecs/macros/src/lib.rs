@@ -170,6 +170,59 @@ pub fn derive_bundle(input: TokenStream) -> TokenStream {
     })
 }
 
+static BLUEPRINT_ATTRIBUTE_NAME: &str = "blueprint";
+
+#[proc_macro_derive(EntityBlueprint, attributes(bundle, blueprint))]
+pub fn derive_entity_blueprint(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    let ecs_path = bevy_ecs_path();
+
+    let named_fields = match &ast.data {
+        Data::Struct(DataStruct {
+            fields: Fields::Named(fields),
+            ..
+        }) => &fields.named,
+        _ => panic!("Expected a struct with named fields."),
+    };
+
+    let has_attribute = |field: &Field, name: &str| {
+        field
+            .attrs
+            .iter()
+            .any(|a| a.path.get_ident().is_some_and(|ident| *ident == name))
+    };
+    let field_builds = named_fields.iter().map(|field| {
+        let field_name = field.ident.as_ref().unwrap();
+        if has_attribute(field, BLUEPRINT_ATTRIBUTE_NAME) {
+            quote! {
+                #ecs_path::blueprint::EntityBlueprint::build(self.#field_name, entity);
+            }
+        } else if has_attribute(field, BUNDLE_ATTRIBUTE_NAME) {
+            quote! {
+                entity.insert_bundle(self.#field_name);
+            }
+        } else {
+            quote! {
+                entity.insert(self.#field_name);
+            }
+        }
+    });
+    let generics = ast.generics;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let struct_name = &ast.ident;
+
+    TokenStream::from(quote! {
+        impl #impl_generics #ecs_path::blueprint::EntityBlueprint for #struct_name#ty_generics #where_clause {
+            type Output = ();
+
+            #[allow(unused_variables)]
+            fn build(self, entity: &mut #ecs_path::system::EntityCommands) {
+                #(#field_builds)*
+            }
+        }
+    })
+}
+
 fn get_idents(fmt_string: fn(usize) -> String, count: usize) -> Vec<Ident> {
     (0..count)
         .map(|i| Ident::new(&fmt_string(i), Span::call_site()))
@@ -0,0 +1,681 @@
+pub use bevy_ecs_macros::EntityBlueprint;
+
+use crate::{
+    component::{Component, ComponentId},
+    entity::Entity,
+    event::Events,
+    system::{Command, CommandQueue, Commands, EntityCommands},
+    world::{get_component_and_ticks, Mut, World},
+};
+use bevy_ecs_macros::all_tuples;
+use bevy_utils::{tracing::warn, HashMap, HashSet};
+use std::{
+    any::TypeId,
+    sync::{Arc, Mutex},
+};
+
+/// A reusable recipe for building an entity.
+///
+/// Where a [`Bundle`](crate::bundle::Bundle) is a static collection of components, a blueprint
+/// is free to run arbitrary logic against the [`EntityCommands`] of the entity it is built onto:
+/// it can insert bundles, spawn related entities, or build other blueprints. This makes it
+/// possible to describe small entity hierarchies (like a button with a text label) once and reuse
+/// them declaratively.
+///
+/// Blueprints are built with [`Commands::spawn_blueprint`]
+/// or [`EntityCommands::insert_blueprint`]. To build hierarchies, a blueprint can spawn children
+/// of its entity (using `with_children` from `bevy_transform`) and build other blueprints onto
+/// them.
+///
+/// Building a blueprint returns its [`Output`](EntityBlueprint::Output), which is typically used
+/// to hand the entities it spawned back to the caller, so they can be referred to without
+/// querying for them afterwards.
+///
+/// For the common case of a blueprint that only inserts its fields, `EntityBlueprint` can be
+/// derived: each field is inserted as a component, or as a bundle if it is marked with
+/// `#[bundle]`, or built as a nested blueprint if it is marked with `#[blueprint]`.
+///
+/// ```
+/// use bevy_ecs::{blueprint::EntityBlueprint, prelude::*};
+///
+/// struct Health(u32);
+/// struct Speed(f32);
+///
+/// #[derive(EntityBlueprint)]
+/// struct Mover {
+///     speed: Speed,
+/// }
+///
+/// #[derive(EntityBlueprint)]
+/// struct Goblin {
+///     health: Health,
+///     #[blueprint]
+///     mover: Mover,
+///     #[bundle]
+///     tags: (&'static str, u8),
+/// }
+///
+/// fn spawn_goblin(mut commands: Commands) {
+///     commands.spawn_blueprint(Goblin {
+///         health: Health(5),
+///         mover: Mover { speed: Speed(3.0) },
+///         tags: ("goblin", 1),
+///     });
+/// }
+/// # spawn_goblin.system();
+/// ```
+///
+/// A tuple of blueprints is itself a blueprint, which builds each of them onto the same entity in
+/// order. This allows layering shared pieces (like a physics setup or a selectable marker) onto
+/// different kinds of entities. See [`BlueprintConflictDetection`] to find components that are
+/// overridden by a later blueprint of the tuple.
+///
+/// Blueprints that implement `Reflect` and [`Default`] can be
+/// registered with `#[reflect(Blueprint)]`, so that they can be spawned from their type name
+/// with `Commands::spawn_blueprint_by_name`.
+///
+/// # Example
+///
+/// ```
+/// use bevy_ecs::{blueprint::EntityBlueprint, prelude::*, system::EntityCommands};
+///
+/// struct Health(u32);
+/// struct Speed(f32);
+/// struct HealthBar {
+///     enemy: Entity,
+/// }
+///
+/// struct EnemyBlueprint {
+///     health: u32,
+/// }
+///
+/// impl EntityBlueprint for EnemyBlueprint {
+///     /// The health bar of the enemy.
+///     type Output = Entity;
+///
+///     fn build(self, entity: &mut EntityCommands) -> Entity {
+///         entity.insert_bundle((Health(self.health), Speed(2.0)));
+///         let enemy = entity.id();
+///         entity.commands().spawn().insert(HealthBar { enemy }).id()
+///     }
+/// }
+///
+/// fn spawn_enemy(mut commands: Commands) {
+///     commands.spawn_blueprint(EnemyBlueprint { health: 10 });
+///
+///     // build the blueprint directly to get its output
+///     let health_bar = EnemyBlueprint { health: 20 }.build(&mut commands.spawn());
+/// }
+/// # spawn_enemy.system();
+/// ```
+pub trait EntityBlueprint {
+    /// What building this blueprint returns. Use `()` if there is nothing to return.
+    type Output;
+
+    /// Builds this blueprint onto the given entity.
+    fn build(self, entity: &mut EntityCommands) -> Self::Output;
+}
+
+/// Sent when a blueprint has been built onto an entity with `spawn_blueprint` or
+/// `insert_blueprint`, so that systems can react to it (for example by playing a spawn effect)
+/// without polling for added components.
+///
+/// Blueprints built as part of another blueprint don't send this event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlueprintSpawned {
+    /// The entity the blueprint was built onto.
+    pub root: Entity,
+    /// The type of the blueprint. For a [`CommandsBlueprint`], this is the type of the
+    /// [`EntityBlueprint`] it wraps.
+    pub blueprint: TypeId,
+    /// The name of the type of the blueprint, as given by [`std::any::type_name`].
+    pub blueprint_name: &'static str,
+}
+
+/// Callbacks run with exclusive world access after a blueprint of a given type has been built,
+/// see [`BlueprintSpawned`].
+#[derive(Default)]
+pub struct BlueprintHooks {
+    hooks: HashMap<TypeId, Vec<BlueprintHook>>,
+}
+
+type BlueprintHook = Box<dyn Fn(&mut World, Entity) + Send + Sync>;
+
+impl BlueprintHooks {
+    /// Runs `hook` with the root entity every time a blueprint of type `B` is built.
+    pub fn add<B: 'static>(&mut self, hook: impl Fn(&mut World, Entity) + Send + Sync + 'static) {
+        self.hooks
+            .entry(TypeId::of::<B>())
+            .or_default()
+            .push(Box::new(hook));
+    }
+}
+
+/// Sends [`BlueprintSpawned`] and runs the [`BlueprintHooks`] of a blueprint.
+pub(crate) struct NotifyBlueprintSpawned {
+    event: BlueprintSpawned,
+}
+
+impl NotifyBlueprintSpawned {
+    pub(crate) fn new<B: 'static>(root: Entity) -> Self {
+        NotifyBlueprintSpawned {
+            event: BlueprintSpawned {
+                root,
+                blueprint: TypeId::of::<B>(),
+                blueprint_name: std::any::type_name::<B>(),
+            },
+        }
+    }
+
+    pub(crate) fn for_world_blueprint<B: WorldBlueprint + 'static>(root: Entity) -> Self {
+        let (blueprint, blueprint_name) = B::blueprint_type();
+        NotifyBlueprintSpawned {
+            event: BlueprintSpawned {
+                root,
+                blueprint,
+                blueprint_name,
+            },
+        }
+    }
+}
+
+impl Command for NotifyBlueprintSpawned {
+    fn write(self, world: &mut World) {
+        let BlueprintSpawned {
+            root, blueprint, ..
+        } = self.event;
+        if let Some(mut events) = world.get_resource_mut::<Events<BlueprintSpawned>>() {
+            events.send(self.event);
+        }
+        if world.contains_resource::<BlueprintHooks>() {
+            world.resource_scope(|world, hooks: Mut<BlueprintHooks>| {
+                for hook in hooks.hooks.get(&blueprint).into_iter().flatten() {
+                    hook(world, root);
+                }
+            });
+        }
+    }
+}
+
+/// Enables conflict detection for composed blueprints when inserted as a resource.
+///
+/// A tuple of blueprints builds each of them onto the same entity in order, so a component
+/// inserted by a later blueprint silently overrides the same component inserted by an earlier one.
+/// With this resource, every component written by more than one blueprint of a tuple is reported.
+/// This is meant for debugging, as it adds some work to each composed blueprint. It must be
+/// inserted before the [`Commands`](crate::system::Commands) building the blueprints are created,
+/// such as before the systems spawning them run.
+///
+/// Components that several blueprints legitimately write to, such as the `Children` of an entity
+/// when more than one blueprint spawns children, can be ignored with
+/// [`ignore`](BlueprintConflictDetection::ignore).
+#[derive(Debug, Clone, Default)]
+pub struct BlueprintConflictDetection {
+    /// Panic on conflicts instead of logging a warning.
+    pub panic: bool,
+    ignored: HashSet<TypeId>,
+}
+
+impl BlueprintConflictDetection {
+    /// Don't report conflicts on components of type `T`.
+    pub fn ignore<T: Component>(mut self) -> Self {
+        self.ignored.insert(TypeId::of::<T>());
+        self
+    }
+}
+
+/// Tracks the components written by each blueprint of a tuple, see [`BlueprintConflictDetection`].
+struct ConflictTracker {
+    entity: Entity,
+    next_layer: usize,
+    last_change_tick: u32,
+    written: HashMap<ComponentId, usize>,
+}
+
+/// Applied before each blueprint of a tuple, and after the last one.
+struct BlueprintLayerBoundary {
+    tracker: Arc<Mutex<ConflictTracker>>,
+}
+
+impl Command for BlueprintLayerBoundary {
+    fn write(self, world: &mut World) {
+        let mut tracker = self.tracker.lock().unwrap();
+        let detection = match world.get_resource::<BlueprintConflictDetection>() {
+            Some(detection) => detection,
+            None => return,
+        };
+
+        if tracker.next_layer > 0 {
+            let layer = tracker.next_layer - 1;
+            let change_tick = world.read_change_tick();
+            let entity = match world.get_entity(tracker.entity) {
+                Some(entity) => entity,
+                None => return,
+            };
+            let location = entity.location();
+            for component_id in entity.archetype().components() {
+                // SAFE: the component is in the archetype of the entity
+                let changed = unsafe {
+                    get_component_and_ticks(world, component_id, tracker.entity, location)
+                        .is_some_and(|(_, ticks)| {
+                            (*ticks).is_changed(tracker.last_change_tick, change_tick)
+                        })
+                };
+                if !changed {
+                    continue;
+                }
+                let info = world.components().get_info(component_id).unwrap();
+                if info
+                    .type_id()
+                    .is_some_and(|type_id| detection.ignored.contains(&type_id))
+                {
+                    continue;
+                }
+                if let Some(previous_layer) = tracker.written.insert(component_id, layer) {
+                    let message = format!(
+                        "Blueprints {} and {} composed onto {:?} both write `{}`, the latter \
+                        overrides the former",
+                        previous_layer,
+                        layer,
+                        tracker.entity,
+                        info.name()
+                    );
+                    if detection.panic {
+                        panic!("{}", message);
+                    }
+                    warn!("{}", message);
+                }
+            }
+        }
+        tracker.next_layer += 1;
+        tracker.last_change_tick = world.increment_change_tick();
+    }
+}
+
+macro_rules! impl_blueprint_tuple {
+    ($($name: ident),*) => {
+        /// Builds each blueprint of the tuple onto the entity, in order: a component inserted by a
+        /// blueprint overrides the same component inserted by the blueprints before it.
+        impl<$($name: EntityBlueprint),*> EntityBlueprint for ($($name,)*) {
+            type Output = ($($name::Output,)*);
+
+            #[allow(non_snake_case, unused_variables)]
+            fn build(self, entity: &mut EntityCommands) -> Self::Output {
+                let ($($name,)*) = self;
+                if !entity.commands().detects_blueprint_conflicts() {
+                    return ($($name.build(entity),)*);
+                }
+                let tracker = Arc::new(Mutex::new(ConflictTracker {
+                    entity: entity.id(),
+                    next_layer: 0,
+                    last_change_tick: 0,
+                    written: HashMap::default(),
+                }));
+                let output = ($({
+                    entity.commands().add(BlueprintLayerBoundary {
+                        tracker: tracker.clone(),
+                    });
+                    $name.build(entity)
+                },)*);
+                entity.commands().add(BlueprintLayerBoundary { tracker });
+                output
+            }
+        }
+    };
+}
+
+all_tuples!(impl_blueprint_tuple, 0, 15, B);
+
+/// A recipe for building an entity that needs direct access to the [`World`], for example to
+/// register resources or to run queries immediately, unlike an [`EntityBlueprint`] which can only
+/// queue commands.
+///
+/// World blueprints are built with [`World::spawn_blueprint`], typically from an exclusive
+/// system. An [`EntityBlueprint`] can be built the same way by wrapping it in a
+/// [`CommandsBlueprint`].
+pub trait WorldBlueprint {
+    /// What building this blueprint returns. Use `()` if there is nothing to return.
+    type Output;
+
+    /// Builds this blueprint onto `entity`.
+    fn build(self, world: &mut World, entity: Entity) -> Self::Output;
+
+    /// The type and type name of the blueprint that [`BlueprintSpawned`] and the
+    /// [`BlueprintHooks`] refer to when this blueprint is spawned: this type by default.
+    fn blueprint_type() -> (TypeId, &'static str)
+    where
+        Self: Sized + 'static,
+    {
+        (TypeId::of::<Self>(), std::any::type_name::<Self>())
+    }
+}
+
+/// Builds an [`EntityBlueprint`] as a [`WorldBlueprint`], applying the commands it queues right
+/// away.
+#[derive(Debug, Clone)]
+pub struct CommandsBlueprint<B>(pub B);
+
+impl<B: EntityBlueprint> WorldBlueprint for CommandsBlueprint<B> {
+    type Output = B::Output;
+
+    fn build(self, world: &mut World, entity: Entity) -> B::Output {
+        let mut queue = CommandQueue::default();
+        let output = self
+            .0
+            .build(&mut Commands::new(&mut queue, world).entity(entity));
+        queue.apply(world);
+        output
+    }
+
+    /// The wrapped blueprint, so that its hooks run when it is built by [`World::spawn_blueprint`].
+    fn blueprint_type() -> (TypeId, &'static str)
+    where
+        Self: Sized + 'static,
+    {
+        (TypeId::of::<B>(), std::any::type_name::<B>())
+    }
+}
+
+/// Builds blueprints into a scratch [`World`] and checks the result, so that crates shipping
+/// blueprints can unit test them without setting up an `App`.
+///
+/// ```
+/// use bevy_ecs::{blueprint::{BlueprintTester, EntityBlueprint}, system::EntityCommands};
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Health(u32);
+///
+/// struct EnemyBlueprint;
+///
+/// impl EntityBlueprint for EnemyBlueprint {
+///     type Output = ();
+///
+///     fn build(self, entity: &mut EntityCommands) {
+///         entity.insert(Health(10));
+///     }
+/// }
+///
+/// let mut tester = BlueprintTester::default();
+/// let (enemy, _) = tester.build(EnemyBlueprint);
+/// tester.entity(enemy).assert_component(&Health(10)).assert_lacks::<u32>();
+/// ```
+#[derive(Default)]
+pub struct BlueprintTester {
+    pub world: World,
+}
+
+impl BlueprintTester {
+    /// Inserts a resource the blueprints need, returning `self` for chaining.
+    pub fn with_resource<T: Component>(mut self, resource: T) -> Self {
+        self.world.insert_resource(resource);
+        self
+    }
+
+    /// Spawns an entity, builds `blueprint` onto it and applies its commands. Returns the entity
+    /// and the output of the blueprint.
+    pub fn build<B: EntityBlueprint + 'static>(&mut self, blueprint: B) -> (Entity, B::Output) {
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &self.world);
+        let mut entity = commands.spawn();
+        let output = blueprint.build(&mut entity);
+        let entity = entity.id();
+        commands.add(NotifyBlueprintSpawned::new::<B>(entity));
+        queue.apply(&mut self.world);
+        (entity, output)
+    }
+
+    /// Spawns an entity and builds the world blueprint `blueprint` onto it. Returns the entity
+    /// and the output of the blueprint.
+    pub fn build_world<B: WorldBlueprint + 'static>(
+        &mut self,
+        blueprint: B,
+    ) -> (Entity, B::Output) {
+        let entity = self.world.spawn().id();
+        let output = blueprint.build(&mut self.world, entity);
+        NotifyBlueprintSpawned::for_world_blueprint::<B>(entity).write(&mut self.world);
+        (entity, output)
+    }
+
+    /// Returns assertions about `entity`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the entity does not exist.
+    pub fn entity(&self, entity: Entity) -> EntityAssertions<'_> {
+        assert!(
+            self.world.get_entity(entity).is_some(),
+            "{:?} does not exist",
+            entity
+        );
+        EntityAssertions {
+            world: &self.world,
+            entity,
+        }
+    }
+}
+
+/// Assertions about an entity built by a [`BlueprintTester`]. All of them panic with a
+/// descriptive message when they fail.
+#[derive(Clone, Copy)]
+pub struct EntityAssertions<'w> {
+    pub world: &'w World,
+    pub entity: Entity,
+}
+
+impl<'w> EntityAssertions<'w> {
+    /// Returns the component `T` of the entity.
+    pub fn component<T: Component>(&self) -> &'w T {
+        self.world.get::<T>(self.entity).unwrap_or_else(|| {
+            panic!(
+                "{:?} has no component `{}`",
+                self.entity,
+                std::any::type_name::<T>()
+            )
+        })
+    }
+
+    /// Asserts that the entity has the component `T`.
+    pub fn assert_has<T: Component>(&self) -> &Self {
+        self.component::<T>();
+        self
+    }
+
+    /// Asserts that the entity does not have the component `T`.
+    pub fn assert_lacks<T: Component>(&self) -> &Self {
+        assert!(
+            self.world.get::<T>(self.entity).is_none(),
+            "{:?} has the component `{}`",
+            self.entity,
+            std::any::type_name::<T>()
+        );
+        self
+    }
+
+    /// Asserts that the component `T` of the entity is equal to `expected`.
+    pub fn assert_component<T: Component + PartialEq + std::fmt::Debug>(
+        &self,
+        expected: &T,
+    ) -> &Self {
+        assert_eq!(
+            self.component::<T>(),
+            expected,
+            "unexpected `{}` on {:?}",
+            std::any::type_name::<T>(),
+            self.entity
+        );
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        BlueprintConflictDetection, BlueprintHooks, BlueprintSpawned, CommandsBlueprint,
+        EntityBlueprint, WorldBlueprint,
+    };
+    use crate::{
+        self as bevy_ecs,
+        entity::Entity,
+        event::Events,
+        system::{CommandQueue, Commands, EntityCommands},
+        world::World,
+    };
+
+    struct Counter(u32);
+
+    struct CountedBlueprint;
+
+    impl WorldBlueprint for CountedBlueprint {
+        type Output = u32;
+
+        fn build(self, world: &mut World, entity: Entity) -> u32 {
+            let mut counter = world.get_resource_or_insert_with(|| Counter(0));
+            counter.0 += 1;
+            let count = counter.0;
+            world.entity_mut(entity).insert(count);
+            count
+        }
+    }
+
+    struct ValueBlueprint(u64);
+
+    impl EntityBlueprint for ValueBlueprint {
+        type Output = Entity;
+
+        fn build(self, entity: &mut EntityCommands) -> Entity {
+            entity.insert(self.0);
+            entity.commands().spawn().insert(self.0 + 1).id()
+        }
+    }
+
+    #[test]
+    fn world_blueprints() {
+        let mut world = World::default();
+        world.spawn_blueprint(CountedBlueprint);
+        let entity = world.spawn_blueprint(CountedBlueprint).id();
+        assert_eq!(world.get::<u32>(entity), Some(&2));
+
+        let entity = world.spawn().id();
+        let other = CommandsBlueprint(ValueBlueprint(1)).build(&mut world, entity);
+        assert_eq!(world.get::<u64>(entity), Some(&1));
+        assert_eq!(world.get::<u64>(other), Some(&2));
+    }
+
+    struct Insert<T>(T);
+
+    impl<T: Send + Sync + 'static> EntityBlueprint for Insert<T> {
+        type Output = ();
+
+        fn build(self, entity: &mut EntityCommands) {
+            entity.insert(self.0);
+        }
+    }
+
+    fn spawn(world: &mut World, blueprint: impl EntityBlueprint + 'static) -> Entity {
+        let mut queue = CommandQueue::default();
+        let entity = Commands::new(&mut queue, world)
+            .spawn_blueprint(blueprint)
+            .id();
+        queue.apply(world);
+        entity
+    }
+
+    #[test]
+    fn composed_blueprints() {
+        let mut world = World::default();
+        world.insert_resource(
+            BlueprintConflictDetection {
+                panic: true,
+                ..Default::default()
+            }
+            .ignore::<u64>(),
+        );
+        let entity = spawn(
+            &mut world,
+            (Insert(1u32), (Insert(2u64), Insert(3u64)), Insert("a")),
+        );
+        assert_eq!(world.get::<u32>(entity), Some(&1));
+        assert_eq!(world.get::<u64>(entity), Some(&3));
+        assert_eq!(world.get::<&str>(entity), Some(&"a"));
+    }
+
+    #[test]
+    #[should_panic(expected = "both write")]
+    fn composed_blueprint_conflict() {
+        let mut world = World::default();
+        world.insert_resource(BlueprintConflictDetection {
+            panic: true,
+            ..Default::default()
+        });
+        spawn(&mut world, (Insert(1u32), Insert(2u32)));
+    }
+
+    #[derive(EntityBlueprint)]
+    struct Derived {
+        value: u32,
+        #[bundle]
+        bundle: (u64, &'static str),
+        #[blueprint]
+        nested: Insert<u8>,
+    }
+
+    #[test]
+    fn derived_blueprint() {
+        let mut world = World::default();
+        let entity = spawn(
+            &mut world,
+            Derived {
+                value: 1,
+                bundle: (2, "a"),
+                nested: Insert(3),
+            },
+        );
+        assert_eq!(world.get::<u32>(entity), Some(&1));
+        assert_eq!(world.get::<u64>(entity), Some(&2));
+        assert_eq!(world.get::<&str>(entity), Some(&"a"));
+        assert_eq!(world.get::<u8>(entity), Some(&3));
+    }
+
+    #[test]
+    fn blueprint_spawned() {
+        let mut world = World::default();
+        world.insert_resource(Events::<BlueprintSpawned>::default());
+        let mut hooks = BlueprintHooks::default();
+        hooks.add::<Insert<u32>>(|world, entity| {
+            *world.get_mut::<u32>(entity).unwrap() += 1;
+        });
+        world.insert_resource(hooks);
+
+        let entity = spawn(&mut world, Insert(1u32));
+        let other = spawn(&mut world, Insert(1u64));
+        assert_eq!(world.get::<u32>(entity), Some(&2));
+        assert_eq!(world.get::<u64>(other), Some(&1));
+
+        let events = world.get_resource::<Events<BlueprintSpawned>>().unwrap();
+        let roots = events
+            .get_reader()
+            .iter(events)
+            .map(|event| event.root)
+            .collect::<Vec<_>>();
+        assert_eq!(roots, vec![entity, other]);
+    }
+
+    #[test]
+    fn commands_blueprint_spawned_as_inner_blueprint() {
+        let mut world = World::default();
+        world.insert_resource(Events::<BlueprintSpawned>::default());
+        let mut hooks = BlueprintHooks::default();
+        hooks.add::<Insert<u32>>(|world, entity| {
+            *world.get_mut::<u32>(entity).unwrap() += 1;
+        });
+        world.insert_resource(hooks);
+
+        let entity = world.spawn_blueprint(CommandsBlueprint(Insert(1u32))).id();
+        assert_eq!(world.get::<u32>(entity), Some(&2));
+
+        let events = world.get_resource::<Events<BlueprintSpawned>>().unwrap();
+        let event = events.get_reader().iter(events).next().unwrap().clone();
+        assert_eq!(event.blueprint, std::any::TypeId::of::<Insert<u32>>());
+        assert_eq!(event.blueprint_name, std::any::type_name::<Insert<u32>>());
+    }
+}
@@ -1,4 +1,5 @@
 pub mod archetype;
+pub mod blueprint;
 pub mod bundle;
 pub mod change_detection;
 pub mod component;
@@ -15,9 +16,10 @@ pub mod world;
 pub mod prelude {
     #[doc(hidden)]
     #[cfg(feature = "bevy_reflect")]
-    pub use crate::reflect::ReflectComponent;
+    pub use crate::reflect::{BlueprintRegistry, ReflectBlueprint, ReflectComponent};
     #[doc(hidden)]
     pub use crate::{
+        blueprint::{EntityBlueprint, WorldBlueprint},
         bundle::Bundle,
         change_detection::DetectChanges,
         entity::Entity,
@@ -1,10 +1,16 @@
 pub use crate::change_detection::ReflectMut;
 use crate::{
+    blueprint::EntityBlueprint,
     component::Component,
     entity::{Entity, EntityMap, MapEntities, MapEntitiesError},
+    system::{Command, CommandQueue, Commands, EntityCommands},
     world::{FromWorld, World},
 };
-use bevy_reflect::{impl_reflect_value, FromType, Reflect, ReflectDeserialize};
+use bevy_reflect::{
+    impl_reflect_value, FromType, Reflect, ReflectDeserialize, ReflectRef, TypeRegistration,
+    TypeRegistryArc,
+};
+use bevy_utils::tracing::{error, warn};
 
 #[derive(Clone)]
 pub struct ReflectComponent {
@@ -149,3 +155,396 @@ impl<C: Component + MapEntities> FromType<C> for ReflectMapEntities {
         }
     }
 }
+
+/// Type data that builds a reflected [`EntityBlueprint`] onto an entity, so that blueprints can
+/// be spawned from their type name at runtime, for example from a console command or an editor
+/// palette.
+///
+/// Register it by deriving [`Reflect`] with `#[reflect(Blueprint)]` on a blueprint that
+/// implements [`Default`], then spawn it with [`Commands::spawn_blueprint_by_name`]. The output
+/// of the blueprint is discarded.
+#[derive(Clone)]
+pub struct ReflectBlueprint {
+    build: fn(Option<&dyn Reflect>, &mut EntityCommands),
+}
+
+impl ReflectBlueprint {
+    /// Builds the blueprint onto `entity`, after applying `blueprint` to its default value.
+    pub fn build(&self, blueprint: &dyn Reflect, entity: &mut EntityCommands) {
+        (self.build)(Some(blueprint), entity);
+    }
+
+    /// Builds the default value of the blueprint onto `entity`.
+    pub fn build_default(&self, entity: &mut EntityCommands) {
+        (self.build)(None, entity);
+    }
+}
+
+impl<B: EntityBlueprint + Reflect + Default> FromType<B> for ReflectBlueprint {
+    fn from_type() -> Self {
+        ReflectBlueprint {
+            build: |reflected_blueprint, entity| {
+                let mut blueprint = B::default();
+                if let Some(reflected_blueprint) = reflected_blueprint {
+                    blueprint.apply(reflected_blueprint);
+                }
+                entity.insert_blueprint(blueprint);
+            },
+        }
+    }
+}
+
+/// A parameter of a blueprint, as listed by the [`BlueprintRegistry`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlueprintParameter {
+    /// The name of the field of the blueprint.
+    pub name: String,
+    /// The type name of the field.
+    pub type_name: String,
+}
+
+/// Describes a blueprint registered in the [`BlueprintRegistry`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlueprintInfo {
+    /// The name the blueprint is spawned with, see [`Commands::spawn_blueprint_by_name`].
+    pub name: String,
+    pub type_name: &'static str,
+    /// The category of the blueprint, used to group blueprints in palettes and menus.
+    pub category: String,
+    /// The fields of the blueprint, which can be set on a reflected value passed to
+    /// [`EntityCommands::insert_reflect_blueprint`].
+    pub parameters: Vec<BlueprintParameter>,
+}
+
+/// Lists the blueprints available to tools, such as editors and debug UIs, so they can show them
+/// and spawn them by name.
+///
+/// Blueprints are added with `App::register_blueprint`, which also registers their
+/// [`ReflectBlueprint`] type data.
+#[derive(Debug, Clone, Default)]
+pub struct BlueprintRegistry {
+    blueprints: Vec<BlueprintInfo>,
+}
+
+impl BlueprintRegistry {
+    /// Adds the blueprint `B` to the registry under its short type name, in the given category.
+    /// Its parameters are read from the fields of its default value. Registering a blueprint
+    /// again replaces its entry.
+    ///
+    /// A warning is logged if another blueprint is registered under the same name, in which case
+    /// [`BlueprintRegistry::get`] returns the first one: use [`BlueprintRegistry::register_as`]
+    /// to give them distinct names.
+    pub fn register<B: EntityBlueprint + Reflect + Default>(&mut self, category: &str) {
+        let name = TypeRegistration::get_short_name(std::any::type_name::<B>());
+        self.register_as::<B>(&name, category);
+    }
+
+    /// Adds the blueprint `B` to the registry under `name`, such as `"enemy/goblin"`, in the
+    /// given category. Registering a blueprint again replaces its entry, including its name.
+    pub fn register_as<B: EntityBlueprint + Reflect + Default>(
+        &mut self,
+        name: &str,
+        category: &str,
+    ) {
+        let type_name = std::any::type_name::<B>();
+        let default_value = B::default();
+        let parameters = match default_value.reflect_ref() {
+            ReflectRef::Struct(value) => (0..value.field_len())
+                .map(|i| BlueprintParameter {
+                    name: value.name_at(i).unwrap().to_string(),
+                    type_name: value.field_at(i).unwrap().type_name().to_string(),
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+        let info = BlueprintInfo {
+            name: name.to_string(),
+            type_name,
+            category: category.to_string(),
+            parameters,
+        };
+        if let Some(other) = self
+            .blueprints
+            .iter()
+            .find(|blueprint| blueprint.name == name && blueprint.type_name != type_name)
+        {
+            warn!(
+                "Blueprint `{}` is registered under the name `{}` of `{}`",
+                type_name, name, other.type_name
+            );
+        }
+        match self
+            .blueprints
+            .iter_mut()
+            .find(|blueprint| blueprint.type_name == type_name)
+        {
+            Some(blueprint) => *blueprint = info,
+            None => self.blueprints.push(info),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&BlueprintInfo> {
+        self.blueprints
+            .iter()
+            .find(|blueprint| blueprint.name == name)
+    }
+
+    /// Iterates over all blueprints, in registration order.
+    pub fn iter(&self) -> impl Iterator<Item = &BlueprintInfo> {
+        self.blueprints.iter()
+    }
+
+    /// Iterates over the blueprints of the given category, in registration order.
+    pub fn iter_category<'a>(
+        &'a self,
+        category: &'a str,
+    ) -> impl Iterator<Item = &'a BlueprintInfo> + 'a {
+        self.blueprints
+            .iter()
+            .filter(move |blueprint| blueprint.category == category)
+    }
+
+    /// Iterates over the categories of the registered blueprints, without duplicates.
+    pub fn categories(&self) -> impl Iterator<Item = &str> {
+        let mut categories = Vec::new();
+        for blueprint in self.blueprints.iter() {
+            if !categories.contains(&blueprint.category.as_str()) {
+                categories.push(blueprint.category.as_str());
+            }
+        }
+        categories.into_iter()
+    }
+}
+
+impl<'w, 's> Commands<'w, 's> {
+    /// Creates a new entity and builds the default value of the blueprint registered under
+    /// `name` onto it. `name` can be the name of the blueprint in the [`BlueprintRegistry`], or
+    /// its full or short type name. The blueprint must be registered with [`ReflectBlueprint`]
+    /// type data.
+    ///
+    /// A warning is logged if no such blueprint is registered.
+    pub fn spawn_blueprint_by_name<'a>(
+        &'a mut self,
+        name: impl Into<String>,
+    ) -> EntityCommands<'w, 's, 'a> {
+        let mut entity = self.spawn();
+        entity.insert_blueprint_by_name(name);
+        entity
+    }
+}
+
+impl<'w, 's, 'a> EntityCommands<'w, 's, 'a> {
+    /// Builds the default value of the blueprint registered under `name` onto this entity. See
+    /// [`Commands::spawn_blueprint_by_name`].
+    pub fn insert_blueprint_by_name(&mut self, name: impl Into<String>) -> &mut Self {
+        let entity = self.id();
+        self.commands().add(InsertReflectBlueprint {
+            entity,
+            name: name.into(),
+            blueprint: None,
+        });
+        self
+    }
+
+    /// Builds a reflected blueprint onto this entity. The blueprint is looked up by its
+    /// [`Reflect::type_name`], so it can also be a dynamic value, such as a deserialized
+    /// [`DynamicStruct`](bevy_reflect::DynamicStruct).
+    pub fn insert_reflect_blueprint(&mut self, blueprint: Box<dyn Reflect>) -> &mut Self {
+        let entity = self.id();
+        self.commands().add(InsertReflectBlueprint {
+            entity,
+            name: blueprint.type_name().to_string(),
+            blueprint: Some(blueprint),
+        });
+        self
+    }
+}
+
+/// Builds a blueprint registered with [`ReflectBlueprint`] onto an entity.
+pub struct InsertReflectBlueprint {
+    pub entity: Entity,
+    /// The name of the blueprint in the [`BlueprintRegistry`], or its full or short type name.
+    pub name: String,
+    /// The value applied to the default blueprint before building it.
+    pub blueprint: Option<Box<dyn Reflect>>,
+}
+
+impl Command for InsertReflectBlueprint {
+    fn write(self, world: &mut World) {
+        let type_registry = match world.get_resource::<TypeRegistryArc>() {
+            Some(type_registry) => type_registry.clone(),
+            None => {
+                error!(
+                    "Could not build blueprint `{}`: there is no TypeRegistryArc resource",
+                    self.name
+                );
+                return;
+            }
+        };
+        let type_registry = type_registry.read();
+        let registered_type_name = world
+            .get_resource::<BlueprintRegistry>()
+            .and_then(|blueprints| blueprints.get(&self.name))
+            .map(|blueprint| blueprint.type_name);
+        let reflect_blueprint = match registered_type_name
+            .and_then(|type_name| type_registry.get_with_name(type_name))
+            .or_else(|| type_registry.get_with_name(&self.name))
+            .or_else(|| type_registry.get_with_short_name(&self.name))
+            .and_then(|registration| registration.data::<ReflectBlueprint>())
+        {
+            Some(reflect_blueprint) => reflect_blueprint,
+            None => {
+                warn!(
+                    "Could not build blueprint `{}`: it is not registered with ReflectBlueprint",
+                    self.name
+                );
+                return;
+            }
+        };
+
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, world);
+        let mut entity = commands.entity(self.entity);
+        match &self.blueprint {
+            Some(blueprint) => reflect_blueprint.build(&**blueprint, &mut entity),
+            None => reflect_blueprint.build_default(&mut entity),
+        }
+        queue.apply(world);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BlueprintParameter, BlueprintRegistry, ReflectBlueprint};
+    use crate::{
+        blueprint::EntityBlueprint,
+        system::{CommandQueue, Commands, EntityCommands},
+        world::World,
+    };
+    use bevy_reflect::{DynamicStruct, Reflect, TypeRegistryArc};
+
+    #[derive(Reflect, Default)]
+    #[reflect(Blueprint)]
+    struct ValueBlueprint {
+        value: u32,
+    }
+
+    impl EntityBlueprint for ValueBlueprint {
+        type Output = ();
+
+        fn build(self, entity: &mut EntityCommands) {
+            entity.insert(self.value);
+        }
+    }
+
+    #[test]
+    fn spawn_blueprint_by_name() {
+        let mut world = World::default();
+        let type_registry = TypeRegistryArc::default();
+        type_registry.write().register::<ValueBlueprint>();
+        world.insert_resource(type_registry);
+
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &world);
+        let default_entity = commands.spawn_blueprint_by_name("ValueBlueprint").id();
+        let mut value = DynamicStruct::default();
+        value.set_name(std::any::type_name::<ValueBlueprint>().to_string());
+        value.insert("value", 3u32);
+        let value_entity = commands
+            .spawn()
+            .insert_reflect_blueprint(Box::new(value))
+            .id();
+        let missing_entity = commands.spawn_blueprint_by_name("MissingBlueprint").id();
+        queue.apply(&mut world);
+
+        assert_eq!(world.get::<u32>(default_entity), Some(&0));
+        assert_eq!(world.get::<u32>(value_entity), Some(&3));
+        assert_eq!(world.get::<u32>(missing_entity), None);
+    }
+
+    #[test]
+    fn blueprint_registry() {
+        let mut registry = BlueprintRegistry::default();
+        registry.register::<ValueBlueprint>("values");
+        registry.register::<ValueBlueprint>("values");
+
+        let info = registry.get("ValueBlueprint").unwrap();
+        assert_eq!(info.category, "values");
+        assert_eq!(
+            info.parameters,
+            vec![BlueprintParameter {
+                name: "value".to_string(),
+                type_name: "u32".to_string(),
+            }]
+        );
+        assert_eq!(registry.iter().count(), 1);
+        assert_eq!(registry.iter_category("values").count(), 1);
+        assert_eq!(registry.categories().collect::<Vec<_>>(), vec!["values"]);
+    }
+
+    mod other {
+        use crate::{blueprint::EntityBlueprint, system::EntityCommands};
+        use bevy_reflect::Reflect;
+
+        #[derive(Reflect, Default)]
+        pub struct ValueBlueprint;
+
+        impl EntityBlueprint for ValueBlueprint {
+            type Output = ();
+
+            fn build(self, _entity: &mut EntityCommands) {}
+        }
+    }
+
+    #[test]
+    fn blueprints_with_the_same_short_name() {
+        let mut registry = BlueprintRegistry::default();
+        registry.register::<ValueBlueprint>("values");
+        registry.register::<other::ValueBlueprint>("others");
+
+        assert_eq!(registry.iter().count(), 2);
+        assert_eq!(registry.get("ValueBlueprint").unwrap().category, "values");
+
+        registry.register_as::<other::ValueBlueprint>("OtherValueBlueprint", "others");
+        assert_eq!(registry.iter().count(), 2);
+        assert_eq!(
+            registry.get("OtherValueBlueprint").unwrap().type_name,
+            std::any::type_name::<other::ValueBlueprint>()
+        );
+    }
+
+    #[test]
+    fn spawn_blueprint_registered_as() {
+        let mut world = World::default();
+        let type_registry = TypeRegistryArc::default();
+        type_registry.write().register::<ValueBlueprint>();
+        world.insert_resource(type_registry);
+        let mut registry = BlueprintRegistry::default();
+        registry.register_as::<ValueBlueprint>("value/default", "values");
+        world.insert_resource(registry);
+
+        let registry = world.get_resource::<BlueprintRegistry>().unwrap();
+        let info = registry.get("value/default").unwrap();
+        assert_eq!(info.type_name, std::any::type_name::<ValueBlueprint>());
+        assert_eq!(info.category, "values");
+        assert!(registry.get("ValueBlueprint").is_none());
+
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &world);
+        let entity = commands.spawn_blueprint_by_name("value/default").id();
+        queue.apply(&mut world);
+
+        assert_eq!(world.get::<u32>(entity), Some(&0));
+    }
+
+    #[test]
+    fn spawn_blueprint_without_type_registry() {
+        let mut world = World::default();
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &world);
+        let entity = commands.spawn_blueprint_by_name("ValueBlueprint").id();
+        queue.apply(&mut world);
+
+        assert!(world.get::<u32>(entity).is_none());
+    }
+}
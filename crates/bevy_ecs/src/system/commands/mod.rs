@@ -1,6 +1,7 @@
 mod command_queue;
 
 use crate::{
+    blueprint::{BlueprintConflictDetection, EntityBlueprint, NotifyBlueprintSpawned},
     bundle::Bundle,
     component::Component,
     entity::{Entities, Entity},
@@ -19,6 +20,7 @@ pub trait Command: Send + Sync + 'static {
 pub struct Commands<'w, 's> {
     queue: &'s mut CommandQueue,
     entities: &'w Entities,
+    detects_blueprint_conflicts: bool,
 }
 
 impl<'w, 's> Commands<'w, 's> {
@@ -27,9 +29,16 @@ impl<'w, 's> Commands<'w, 's> {
         Self {
             queue,
             entities: world.entities(),
+            detects_blueprint_conflicts: world.contains_resource::<BlueprintConflictDetection>(),
         }
     }
 
+    /// Whether the [`BlueprintConflictDetection`] resource was in the world when these commands
+    /// were created, so that composed blueprints only track their components when it is.
+    pub(crate) fn detects_blueprint_conflicts(&self) -> bool {
+        self.detects_blueprint_conflicts
+    }
+
     /// Creates a new empty [`Entity`] and returns an [`EntityCommands`] builder for it.
     ///
     /// # Example
@@ -104,6 +113,91 @@ impl<'w, 's> Commands<'w, 's> {
         e
     }
 
+    /// Creates a new entity and builds `blueprint` onto it.
+    ///
+    /// This returns an [`EntityCommands`] builder, so more components, bundles or blueprints can
+    /// be inserted afterwards. The [`Output`](EntityBlueprint::Output) of the blueprint is
+    /// discarded: call [`EntityBlueprint::build`] directly to get it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bevy_ecs::{blueprint::EntityBlueprint, prelude::*, system::EntityCommands};
+    ///
+    /// struct Health(u32);
+    /// struct Boss;
+    ///
+    /// struct EnemyBlueprint {
+    ///     health: u32,
+    /// }
+    ///
+    /// impl EntityBlueprint for EnemyBlueprint {
+    ///     type Output = ();
+    ///
+    ///     fn build(self, entity: &mut EntityCommands) {
+    ///         entity.insert(Health(self.health));
+    ///     }
+    /// }
+    ///
+    /// fn example_system(mut commands: Commands) {
+    ///     commands
+    ///         .spawn_blueprint(EnemyBlueprint { health: 100 })
+    ///         .insert(Boss);
+    /// }
+    /// # example_system.system();
+    /// ```
+    pub fn spawn_blueprint<'a>(
+        &'a mut self,
+        blueprint: impl EntityBlueprint + 'static,
+    ) -> EntityCommands<'w, 's, 'a> {
+        let mut e = self.spawn();
+        e.insert_blueprint(blueprint);
+        e
+    }
+
+    /// Creates a new entity, builds `blueprint` onto it and applies `patch` on top of it.
+    ///
+    /// This allows spawning variants of a blueprint without defining a new blueprint type. See
+    /// [`EntityCommands::insert_blueprint_with`] for the precedence between the blueprint and the
+    /// patch.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use bevy_ecs::{blueprint::EntityBlueprint, prelude::*, system::EntityCommands};
+    ///
+    /// enum Team {
+    ///     Red,
+    ///     Blue,
+    /// }
+    ///
+    /// struct SoldierBlueprint;
+    ///
+    /// impl EntityBlueprint for SoldierBlueprint {
+    ///     type Output = ();
+    ///
+    ///     fn build(self, entity: &mut EntityCommands) {
+    ///         entity.insert(Team::Blue);
+    ///     }
+    /// }
+    ///
+    /// fn example_system(mut commands: Commands) {
+    ///     commands.spawn_blueprint_with(SoldierBlueprint, |soldier| {
+    ///         soldier.insert(Team::Red);
+    ///     });
+    /// }
+    /// # example_system.system();
+    /// ```
+    pub fn spawn_blueprint_with<'a>(
+        &'a mut self,
+        blueprint: impl EntityBlueprint + 'static,
+        patch: impl FnOnce(&mut EntityCommands<'w, 's, 'a>),
+    ) -> EntityCommands<'w, 's, 'a> {
+        let mut e = self.spawn();
+        e.insert_blueprint_with(blueprint, patch);
+        e
+    }
+
     /// Returns an [`EntityCommands`] builder for the requested [`Entity`].
     ///
     /// # Example
@@ -220,6 +314,36 @@ impl<'w, 's, 'a> EntityCommands<'w, 's, 'a> {
         self
     }
 
+    /// Builds `blueprint` onto this entity, discarding its [`Output`](EntityBlueprint::Output).
+    ///
+    /// Once built, a [`BlueprintSpawned`](crate::blueprint::BlueprintSpawned) event is sent and
+    /// the [`BlueprintHooks`](crate::blueprint::BlueprintHooks) of the blueprint are run.
+    pub fn insert_blueprint<B: EntityBlueprint + 'static>(&mut self, blueprint: B) -> &mut Self {
+        blueprint.build(self);
+        self.commands
+            .add(NotifyBlueprintSpawned::new::<B>(self.entity));
+        self
+    }
+
+    /// Builds `blueprint` onto this entity, then applies `patch` on top of it.
+    ///
+    /// The commands of the patch are applied after the ones of the blueprint, so a component
+    /// inserted by the patch overrides the same component inserted by the blueprint. The
+    /// [`BlueprintSpawned`](crate::blueprint::BlueprintSpawned) event is sent and the
+    /// [`BlueprintHooks`](crate::blueprint::BlueprintHooks) are run after the patch, so they see
+    /// the patched entity.
+    pub fn insert_blueprint_with<B: EntityBlueprint + 'static>(
+        &mut self,
+        blueprint: B,
+        patch: impl FnOnce(&mut Self),
+    ) -> &mut Self {
+        blueprint.build(self);
+        patch(self);
+        self.commands
+            .add(NotifyBlueprintSpawned::new::<B>(self.entity));
+        self
+    }
+
     /// See [`EntityMut::remove_bundle`](crate::world::EntityMut::remove_bundle).
     pub fn remove_bundle<T>(&mut self) -> &mut Self
     where
@@ -391,8 +515,9 @@ impl<T: Component> Command for RemoveResource<T> {
 #[allow(clippy::float_cmp, clippy::approx_constant)]
 mod tests {
     use crate::{
+        blueprint::EntityBlueprint,
         component::{ComponentDescriptor, StorageType},
-        system::{CommandQueue, Commands},
+        system::{CommandQueue, Commands, EntityCommands},
         world::World,
     };
     use std::sync::{
@@ -445,6 +570,44 @@ mod tests {
         assert_eq!(results2, vec![]);
     }
 
+    #[test]
+    fn spawn_blueprint() {
+        struct Blueprint(u32);
+
+        impl EntityBlueprint for Blueprint {
+            type Output = ();
+
+            fn build(self, entity: &mut EntityCommands) {
+                entity.insert(self.0);
+            }
+        }
+
+        let mut world = World::default();
+        let mut command_queue = CommandQueue::default();
+        let entity = Commands::new(&mut command_queue, &world)
+            .spawn_blueprint(Blueprint(1))
+            .insert(2u64)
+            .id();
+        command_queue.apply(&mut world);
+        assert_eq!(world.get::<u32>(entity), Some(&1));
+        assert_eq!(world.get::<u64>(entity), Some(&2));
+
+        Commands::new(&mut command_queue, &world)
+            .entity(entity)
+            .insert_blueprint(Blueprint(3));
+        command_queue.apply(&mut world);
+        assert_eq!(world.get::<u32>(entity), Some(&3));
+
+        let entity = Commands::new(&mut command_queue, &world)
+            .spawn_blueprint_with(Blueprint(4), |e| {
+                e.insert(5u32).insert(6u64);
+            })
+            .id();
+        command_queue.apply(&mut world);
+        assert_eq!(world.get::<u32>(entity), Some(&5));
+        assert_eq!(world.get::<u64>(entity), Some(&6));
+    }
+
     #[test]
     fn remove_components() {
         let mut world = World::default();
@@ -577,7 +577,7 @@ unsafe fn get_component(
 /// # Safety
 /// Caller must ensure that `component_id` is valid
 #[inline]
-unsafe fn get_component_and_ticks(
+pub(crate) unsafe fn get_component_and_ticks(
     world: &World,
     component_id: ComponentId,
     entity: Entity,
@@ -9,6 +9,7 @@ pub use world_cell::*;
 
 use crate::{
     archetype::{ArchetypeComponentId, ArchetypeComponentInfo, ArchetypeId, Archetypes},
+    blueprint::{NotifyBlueprintSpawned, WorldBlueprint},
     bundle::{Bundle, Bundles},
     change_detection::Ticks,
     component::{
@@ -18,6 +19,7 @@ use crate::{
     entity::{Entities, Entity},
     query::{FilterFetch, QueryState, WorldQuery},
     storage::{Column, SparseSet, Storages},
+    system::Command,
 };
 use std::{
     any::TypeId,
@@ -308,6 +310,19 @@ impl World {
         }
     }
 
+    /// Creates a new entity and builds `blueprint` onto it, returning an [EntityMut] of the
+    /// entity. The output of the blueprint is discarded: call [WorldBlueprint::build] directly to
+    /// get it.
+    ///
+    /// Once built, a [BlueprintSpawned](crate::blueprint::BlueprintSpawned) event is sent and the
+    /// [BlueprintHooks](crate::blueprint::BlueprintHooks) of the blueprint are run.
+    pub fn spawn_blueprint<B: WorldBlueprint + 'static>(&mut self, blueprint: B) -> EntityMut {
+        let entity = self.spawn().id();
+        blueprint.build(self, entity);
+        NotifyBlueprintSpawned::for_world_blueprint::<B>(entity).write(self);
+        self.entity_mut(entity)
+    }
+
     /// Spawns a batch of entities with the same component [Bundle] type. Takes a given [Bundle]
     /// iterator and returns a corresponding [Entity] iterator.
     /// This is more efficient than spawning entities and adding components to them individually,
@@ -4,10 +4,21 @@ use bevy_ecs::system::ResMut;
 
 /// A key input event from a keyboard device
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct KeyboardInput {
     pub scan_code: u32,
     pub key_code: Option<KeyCode>,
     pub state: ElementState,
+    /// `true` if this is a `Pressed` event generated by the platform because the key is held
+    /// down. Text inputs should honor repeats, while gameplay code usually ignores them.
+    pub repeat: bool,
+    /// `true` if this event was generated by the platform rather than by the user, such as the
+    /// release of the held keys when a window loses the focus.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - Only reported on **Windows** and **X11**.
+    pub synthetic: bool,
 }
 
 /// Updates the Input<KeyCode> resource with the latest KeyboardInput events
@@ -31,6 +42,44 @@ pub fn keyboard_input_system(
     }
 }
 
+/// The state of the modifier keys, as reported by the focused window.
+///
+/// Prefer this resource over checking both the left and right variants of each modifier in
+/// `Input<KeyCode>`: the platform reports it directly, so it stays correct when a modifier is
+/// pressed or released while another window has the focus, and it is cleared when the focus is
+/// lost.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct ModifierKeys {
+    pub shift: bool,
+    pub ctrl: bool,
+    pub alt: bool,
+    /// The "Windows" key on PC, "Command" key on Mac and "Super" key on Linux.
+    pub logo: bool,
+}
+
+impl ModifierKeys {
+    /// Returns `true` if no modifier key is pressed.
+    pub fn is_empty(&self) -> bool {
+        *self == ModifierKeys::default()
+    }
+}
+
+/// An event sent when the state of the modifier keys changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct ModifiersChanged(pub ModifierKeys);
+
+/// Updates the ModifierKeys resource with the latest ModifiersChanged events
+pub fn modifier_keys_system(
+    mut modifier_keys: ResMut<ModifierKeys>,
+    mut modifiers_changed_events: EventReader<ModifiersChanged>,
+) {
+    if let Some(ModifiersChanged(modifiers)) = modifiers_changed_events.iter().last() {
+        *modifier_keys = *modifiers;
+    }
+}
+
 /// The key code of a keyboard input.
 #[derive(Debug, Hash, Ord, PartialOrd, PartialEq, Eq, Clone, Copy)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
@@ -17,7 +17,7 @@ pub mod prelude {
             Gamepad, GamepadAxis, GamepadAxisType, GamepadButton, GamepadButtonType, GamepadEvent,
             GamepadEventType,
         },
-        keyboard::KeyCode,
+        keyboard::{KeyCode, ModifierKeys},
         mouse::MouseButton,
         touch::{TouchInput, Touches},
         Axis, Input,
@@ -25,7 +25,10 @@ pub mod prelude {
 }
 
 use bevy_app::prelude::*;
-use keyboard::{keyboard_input_system, KeyCode, KeyboardInput};
+use keyboard::{
+    keyboard_input_system, modifier_keys_system, KeyCode, KeyboardInput, ModifierKeys,
+    ModifiersChanged,
+};
 use mouse::{mouse_button_input_system, MouseButton, MouseButtonInput, MouseMotion, MouseWheel};
 use touch::{touch_screen_input_system, TouchInput, Touches};
 
@@ -41,6 +44,14 @@ pub struct InputPlugin;
 #[derive(Debug, PartialEq, Eq, Clone, Hash, SystemLabel)]
 pub struct InputSystem;
 
+/// The systems of the [`InputSystem`] label handling one kind of input, to run a system between
+/// them, such as one turning gamepad input into mouse input.
+#[derive(Debug, PartialEq, Eq, Clone, Hash, SystemLabel)]
+pub enum InputKindSystem {
+    Mouse,
+    Gamepad,
+}
+
 impl Plugin for InputPlugin {
     fn build(&self, app: &mut App) {
         app
@@ -51,6 +62,12 @@ impl Plugin for InputPlugin {
                 CoreStage::PreUpdate,
                 keyboard_input_system.label(InputSystem),
             )
+            .add_event::<ModifiersChanged>()
+            .init_resource::<ModifierKeys>()
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                modifier_keys_system.label(InputSystem),
+            )
             // mouse
             .add_event::<MouseButtonInput>()
             .add_event::<MouseMotion>()
@@ -58,7 +75,9 @@ impl Plugin for InputPlugin {
             .init_resource::<Input<MouseButton>>()
             .add_system_to_stage(
                 CoreStage::PreUpdate,
-                mouse_button_input_system.label(InputSystem),
+                mouse_button_input_system
+                    .label(InputSystem)
+                    .label(InputKindSystem::Mouse),
             )
             // gamepad
             .add_event::<GamepadEvent>()
@@ -69,7 +88,9 @@ impl Plugin for InputPlugin {
             .init_resource::<Axis<GamepadButton>>()
             .add_system_to_stage(
                 CoreStage::PreUpdate,
-                gamepad_event_system.label(InputSystem),
+                gamepad_event_system
+                    .label(InputSystem)
+                    .label(InputKindSystem::Gamepad),
             )
             // touch
             .add_event::<TouchInput>()
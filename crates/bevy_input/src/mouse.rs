@@ -1,9 +1,10 @@
-use crate::{ElementState, Input};
+use crate::{touch::TouchPhase, ElementState, Input};
 use bevy_ecs::{event::EventReader, system::ResMut};
 use bevy_math::Vec2;
 
 /// A mouse button input event
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct MouseButtonInput {
     pub button: MouseButton,
     pub state: ElementState,
@@ -19,14 +20,23 @@ pub enum MouseButton {
     Other(u16),
 }
 
-/// A mouse motion event
+/// A mouse motion event, reporting the raw motion of the mouse device
+///
+/// Unlike the cursor position, the motion is not affected by the pointer acceleration of the
+/// platform, and keeps being reported when the cursor reaches the edge of the window or screen.
+///
+/// ## Platform-specific
+///
+/// - On **macOS**, the motion includes the pointer acceleration.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct MouseMotion {
     pub delta: Vec2,
 }
 
 /// Unit of scroll
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub enum MouseScrollUnit {
     Line,
     Pixel,
@@ -35,10 +45,25 @@ pub enum MouseScrollUnit {
 /// A mouse scroll wheel event, where x represents horizontal scroll and y represents vertical
 /// scroll.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct MouseWheel {
     pub unit: MouseScrollUnit,
     pub x: f32,
     pub y: f32,
+    /// The phase of the scroll gesture.
+    ///
+    /// Scrolling with a touchpad starts with a `Started` event when the fingers touch the
+    /// touchpad and ends with an `Ended` event when they are lifted. Mouse wheels, and platforms
+    /// that don't report gestures, only send `Moved` events.
+    pub phase: TouchPhase,
+    /// `true` if this event is generated by the platform to continue the scrolling after the
+    /// fingers were lifted from the touchpad, so that UI implementing its own inertia can ignore
+    /// it.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - Only reported on **macOS**.
+    pub momentum: bool,
 }
 
 /// Updates the Input<MouseButton> resource with the latest MouseButtonInput events
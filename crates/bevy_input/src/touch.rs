@@ -20,6 +20,7 @@ use bevy_utils::HashMap;
 /// touch, such as when the window loses focus, or on iOS if the user moves the
 /// device against their face.
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct TouchInput {
     pub phase: TouchPhase,
     pub position: Vec2,
@@ -30,12 +31,23 @@ pub struct TouchInput {
     ///
     /// - Only available on **iOS** 9.0+ and **Windows** 8+.
     pub force: Option<ForceTouch>,
+    /// The radii of the contact area along the x and y axes, in logical pixels. May be `None` if
+    /// the platform does not report the contact area.
+    pub radius: Option<Vec2>,
+    /// The orientation of the stylus used for the touch. `None` for fingers, or if the platform
+    /// does not report it.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - Only available on **iOS** 9.1+, where the altitude of the Apple Pencil is reported.
+    pub tilt: Option<StylusTilt>,
     /// Unique identifier of a finger.
     pub id: u64,
 }
 
 /// Describes the force of a touch event
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub enum ForceTouch {
     /// On iOS, the force is calibrated so that the same number corresponds to
     /// roughly the same amount of pressure on the screen regardless of the
@@ -67,6 +79,41 @@ pub enum ForceTouch {
     Normalized(f64),
 }
 
+impl ForceTouch {
+    /// Returns the force of the touch in the range `0.0..=1.0`, where `1.0` is the maximum force
+    /// the device can report.
+    pub fn normalized(&self) -> f64 {
+        match *self {
+            ForceTouch::Calibrated {
+                force,
+                max_possible_force,
+                ..
+            } => {
+                if max_possible_force > 0.0 {
+                    (force / max_possible_force).clamp(0.0, 1.0)
+                } else {
+                    0.0
+                }
+            }
+            ForceTouch::Normalized(force) => force,
+        }
+    }
+}
+
+/// Describes the orientation of a stylus
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct StylusTilt {
+    /// The altitude (in radians) of the stylus.
+    ///
+    /// A value of 0 radians indicates that the stylus is parallel to the surface. The value is
+    /// Pi/2 when the stylus is perpendicular to the surface.
+    pub altitude: f64,
+    /// The azimuth (in radians) of the stylus, measured counterclockwise from the positive x
+    /// axis of the window. May be `None` if the platform only reports the altitude.
+    pub azimuth: Option<f64>,
+}
+
 /// Describes touch-screen input state.
 #[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
@@ -86,6 +133,8 @@ pub struct Touch {
     previous_force: Option<ForceTouch>,
     position: Vec2,
     force: Option<ForceTouch>,
+    radius: Option<Vec2>,
+    tilt: Option<StylusTilt>,
 }
 
 impl Touch {
@@ -126,6 +175,16 @@ impl Touch {
     pub fn force(&self) -> Option<ForceTouch> {
         self.force
     }
+
+    #[inline]
+    pub fn radius(&self) -> Option<Vec2> {
+        self.radius
+    }
+
+    #[inline]
+    pub fn tilt(&self) -> Option<StylusTilt> {
+        self.tilt
+    }
 }
 
 impl From<&TouchInput> for Touch {
@@ -138,6 +197,8 @@ impl From<&TouchInput> for Touch {
             previous_force: input.force,
             position: input.position,
             force: input.force,
+            radius: input.radius,
+            tilt: input.tilt,
         }
     }
 }
@@ -199,6 +260,8 @@ impl Touches {
                     new_touch.previous_force = new_touch.force;
                     new_touch.position = event.position;
                     new_touch.force = event.force;
+                    new_touch.radius = event.radius;
+                    new_touch.tilt = event.tilt;
                     self.pressed.insert(event.id, new_touch);
                 }
             }
@@ -250,6 +313,8 @@ mod test {
             previous_force: None,
             position: Vec2::new(0.0, 0.0),
             force: None,
+            radius: None,
+            tilt: None,
         };
 
         // Add a touch to `just_pressed`, 'just_released', and 'just cancelled'
@@ -279,6 +344,8 @@ mod test {
             phase: TouchPhase::Started,
             position: Vec2::new(4.0, 4.0),
             force: None,
+            radius: None,
+            tilt: None,
             id: 4,
         };
 
@@ -294,6 +361,8 @@ mod test {
             phase: TouchPhase::Moved,
             position: Vec2::new(5.0, 5.0),
             force: None,
+            radius: None,
+            tilt: None,
             id: touch_event.id,
         };
 
@@ -315,6 +384,8 @@ mod test {
             phase: TouchPhase::Cancelled,
             position: Vec2::new(1.0, 1.0),
             force: None,
+            radius: None,
+            tilt: None,
             id: touch_event.id,
         };
 
@@ -330,6 +401,8 @@ mod test {
             phase: TouchPhase::Ended,
             position: Vec2::new(4.0, 4.0),
             force: None,
+            radius: None,
+            tilt: None,
             id: 4,
         };
 
@@ -352,6 +425,8 @@ mod test {
             phase: TouchPhase::Started,
             position: Vec2::new(4.0, 4.0),
             force: None,
+            radius: None,
+            tilt: None,
             id: 4,
         };
 
@@ -374,6 +449,8 @@ mod test {
             phase: TouchPhase::Ended,
             position: Vec2::new(4.0, 4.0),
             force: None,
+            radius: None,
+            tilt: None,
             id: 4,
         };
 
@@ -396,6 +473,8 @@ mod test {
             phase: TouchPhase::Cancelled,
             position: Vec2::new(4.0, 4.0),
             force: None,
+            radius: None,
+            tilt: None,
             id: 4,
         };
 
@@ -405,4 +484,17 @@ mod test {
         assert!(touches.just_cancelled(touch_event.id));
         assert_eq!(touches.iter_just_cancelled().count(), 1);
     }
+
+    #[test]
+    fn force_normalized() {
+        use crate::touch::ForceTouch;
+
+        let calibrated = ForceTouch::Calibrated {
+            force: 1.0,
+            max_possible_force: 4.0,
+            altitude_angle: None,
+        };
+        assert_eq!(calibrated.normalized(), 0.25);
+        assert_eq!(ForceTouch::Normalized(0.5).normalized(), 0.5);
+    }
 }
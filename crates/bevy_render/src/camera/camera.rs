@@ -7,12 +7,13 @@ use bevy_ecs::{
     prelude::QueryState,
     query::Added,
     reflect::ReflectComponent,
-    system::{QuerySet, Res},
+    system::{Local, QuerySet, Res},
 };
 use bevy_math::{Mat4, Vec2, Vec3};
 use bevy_reflect::{Reflect, ReflectDeserialize};
 use bevy_transform::components::GlobalTransform;
-use bevy_window::{WindowCreated, WindowId, WindowResized, Windows};
+use bevy_utils::HashMap;
+use bevy_window::{Window, WindowCreated, WindowId, WindowRenderPolicy, WindowResized, Windows};
 use serde::{Deserialize, Serialize};
 
 #[derive(Default, Debug, Reflect)]
@@ -49,8 +50,7 @@ impl Camera {
         camera_transform: &GlobalTransform,
         world_position: Vec3,
     ) -> Option<Vec2> {
-        let window = windows.get(self.window)?;
-        let window_size = Vec2::new(window.width(), window.height());
+        let (viewport_position, viewport_size) = logical_viewport(windows.get(self.window)?);
         // Build a transform to convert from world to NDC using camera data
         let world_to_ndc: Mat4 =
             self.projection_matrix * camera_transform.compute_matrix().inverse();
@@ -60,9 +60,84 @@ impl Camera {
             return None;
         }
         // Once in NDC space, we can discard the z element and rescale x/y to fit the screen
-        let screen_space_coords = (ndc_space_coords.truncate() + Vec2::ONE) / 2.0 * window_size;
+        let screen_space_coords =
+            (ndc_space_coords.truncate() + Vec2::ONE) / 2.0 * viewport_size + viewport_position;
         Some(screen_space_coords)
     }
+
+    /// Given a position in screen space, in logical pixels from the bottom left of the window of
+    /// the camera, use the camera to compute the ray going through it in world space.
+    ///
+    /// The ray starts on the near plane of the camera. For an orthographic camera, all the rays
+    /// have the direction of the camera.
+    pub fn screen_to_ray(
+        &self,
+        windows: &Windows,
+        camera_transform: &GlobalTransform,
+        screen_position: Vec2,
+    ) -> Option<Ray> {
+        let (viewport_position, viewport_size) = logical_viewport(windows.get(self.window)?);
+        let ndc = (screen_position - viewport_position) / viewport_size * 2.0 - Vec2::ONE;
+        let ndc_to_world: Mat4 =
+            camera_transform.compute_matrix() * self.projection_matrix.inverse();
+        let near = ndc_to_world.project_point3(ndc.extend(0.0));
+        let far = ndc_to_world.project_point3(ndc.extend(1.0));
+        let direction = (far - near).normalize();
+        if !near.is_finite() || !direction.is_finite() {
+            return None;
+        }
+        Some(Ray {
+            origin: near,
+            direction,
+        })
+    }
+
+    /// The ray going through the cursor in world space, if the cursor is in the window of the
+    /// camera. See [`screen_to_ray`](Camera::screen_to_ray).
+    pub fn cursor_ray(&self, windows: &Windows, camera_transform: &GlobalTransform) -> Option<Ray> {
+        let cursor_position = windows.get(self.window)?.cursor_position()?;
+        self.screen_to_ray(windows, camera_transform, cursor_position)
+    }
+}
+
+/// The position from the bottom left and the size in logical pixels of the part of `window` the
+/// cameras draw to.
+fn logical_viewport(window: &Window) -> (Vec2, Vec2) {
+    let (position, size) = window.render_viewport();
+    let scale_factor = window.scale_factor() as f32;
+    let position = Vec2::new(
+        position.x as f32,
+        (window.physical_height() - position.y - size.y) as f32,
+    );
+    (position / scale_factor, size.as_f32() / scale_factor)
+}
+
+/// A half-line in world space, such as the one going through the cursor returned by
+/// [`Camera::cursor_ray`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ray {
+    pub origin: Vec3,
+    /// The normalized direction of the ray.
+    pub direction: Vec3,
+}
+
+impl Ray {
+    /// The point at `distance` from the origin along the ray.
+    pub fn point_at(&self, distance: f32) -> Vec3 {
+        self.origin + self.direction * distance
+    }
+
+    /// The distance along the ray at which it crosses the plane going through `plane_origin`
+    /// with `plane_normal`, or `None` if it doesn't cross it. For example, the point under the
+    /// cursor on the ground is at the intersection with the `Vec3::Y` plane.
+    pub fn intersect_plane(&self, plane_origin: Vec3, plane_normal: Vec3) -> Option<f32> {
+        let denominator = plane_normal.dot(self.direction);
+        if denominator.abs() <= f32::EPSILON {
+            return None;
+        }
+        let distance = plane_normal.dot(plane_origin - self.origin) / denominator;
+        (distance >= 0.0).then_some(distance)
+    }
 }
 
 #[allow(clippy::type_complexity)]
@@ -70,12 +145,20 @@ pub fn camera_system<T: CameraProjection + Component>(
     mut window_resized_events: EventReader<WindowResized>,
     mut window_created_events: EventReader<WindowCreated>,
     windows: Res<Windows>,
+    mut render_policies: Local<HashMap<WindowId, WindowRenderPolicy>>,
     mut queries: QuerySet<(
         QueryState<(Entity, &mut Camera, &mut T)>,
         QueryState<Entity, Added<Camera>>,
     )>,
 ) {
     let mut changed_window_ids = Vec::new();
+    for window in windows.iter() {
+        if render_policies.insert(window.id(), window.render_policy())
+            != Some(window.render_policy())
+        {
+            changed_window_ids.push(window.id());
+        }
+    }
     // handle resize events. latest events are handled first because we only want to resize each
     // window once
     for event in window_resized_events.iter().rev() {
@@ -102,14 +185,66 @@ pub fn camera_system<T: CameraProjection + Component>(
     }
     for (entity, mut camera, mut camera_projection) in queries.q0().iter_mut() {
         if let Some(window) = windows.get(camera.window) {
+            // the projections of minimized windows are updated when they are restored, which
+            // resizes them
+            if window.is_minimized() {
+                continue;
+            }
             if changed_window_ids.contains(&window.id())
                 || added_cameras.contains(&entity)
                 || camera_projection.is_changed()
             {
-                camera_projection.update(window.width(), window.height());
+                let size = window.render_size();
+                camera_projection.update(size.x, size.y);
                 camera.projection_matrix = camera_projection.get_projection_matrix();
                 camera.depth_calculation = camera_projection.depth_calculation();
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Camera, Ray};
+    use crate::camera::{CameraProjection, PerspectiveProjection};
+    use bevy_math::{Vec2, Vec3};
+    use bevy_transform::components::GlobalTransform;
+    use bevy_window::{Window, WindowDescriptor, WindowId, Windows};
+
+    #[test]
+    fn cursor_ray() {
+        let mut windows = Windows::default();
+        let mut window = Window::new(
+            WindowId::primary(),
+            &WindowDescriptor::default(),
+            1280,
+            720,
+            1.0,
+            None,
+        );
+        window.update_cursor_position_from_backend(Some(Vec2::new(640.0, 360.0)));
+        windows.add(window);
+
+        let mut projection = PerspectiveProjection::default();
+        projection.update(1280.0, 720.0);
+        let camera = Camera {
+            projection_matrix: projection.get_projection_matrix(),
+            ..Default::default()
+        };
+        let camera_transform =
+            GlobalTransform::from_xyz(0.0, 10.0, 10.0).looking_at(Vec3::ZERO, Vec3::Y);
+
+        let ray = camera.cursor_ray(&windows, &camera_transform).unwrap();
+        assert!(ray
+            .direction
+            .abs_diff_eq(Vec3::new(0.0, -1.0, -1.0).normalize(), 1e-4));
+        let distance = ray.intersect_plane(Vec3::ZERO, Vec3::Y).unwrap();
+        assert!(ray.point_at(distance).abs_diff_eq(Vec3::ZERO, 1e-3));
+
+        let ray = Ray {
+            origin: Vec3::ZERO,
+            direction: Vec3::X,
+        };
+        assert_eq!(ray.intersect_plane(Vec3::Y, Vec3::Y), None);
+    }
+}
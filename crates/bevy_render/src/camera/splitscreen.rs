@@ -0,0 +1,136 @@
+use super::{ActiveCameras, Camera};
+use crate::{
+    entity::PerspectiveCameraBundle,
+    render_graph::{
+        base::{self, Msaa},
+        RenderGraph,
+    },
+};
+use bevy_app::Events;
+use bevy_ecs::{
+    entity::Entity,
+    system::{Command, Commands, Res, ResMut},
+    world::World,
+};
+use bevy_window::{CreateWindow, SnapRegion, WindowDescriptor, WindowId, Windows};
+
+/// How [`SpawnSplitscreenCommands::spawn_splitscreen`] lays out its windows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SplitscreenLayout {
+    /// Tiles the windows on the monitor they open on, in a grid.
+    #[default]
+    Tiled,
+    /// Puts each window on its own monitor, filling it. The windows that don't have a monitor
+    /// keep the position and size they opened with.
+    AcrossMonitors,
+}
+
+impl SplitscreenLayout {
+    fn region(&self, index: u32, count: u32) -> SnapRegion {
+        match self {
+            SplitscreenLayout::Tiled => SnapRegion::Tile { index, count },
+            SplitscreenLayout::AcrossMonitors => SnapRegion::Monitor(index as usize),
+        }
+    }
+}
+
+/// The camera of the window `index` of a splitscreen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SplitscreenCamera {
+    pub index: u32,
+}
+
+/// The windows of the splitscreens waiting for the backend to create them, to be snapped to
+/// their region and drawn to once they exist.
+#[derive(Debug, Default)]
+pub struct PendingSplitscreenWindows {
+    windows: Vec<(WindowId, SnapRegion, String)>,
+}
+
+pub struct SpawnSplitscreenWindow {
+    id: WindowId,
+    descriptor: WindowDescriptor,
+    region: SnapRegion,
+    camera: String,
+}
+
+impl Command for SpawnSplitscreenWindow {
+    fn write(self, world: &mut World) {
+        let mut create_window_events = world.get_resource_mut::<Events<CreateWindow>>().unwrap();
+        create_window_events.send(CreateWindow {
+            id: self.id,
+            descriptor: self.descriptor,
+        });
+        let mut pending = world
+            .get_resource_mut::<PendingSplitscreenWindows>()
+            .unwrap();
+        pending.windows.push((self.id, self.region, self.camera));
+    }
+}
+
+pub trait SpawnSplitscreenCommands {
+    /// Opens `count` windows laid out according to `layout`, each drawn by its own 3D camera,
+    /// and returns the camera entities, marked with a [`SplitscreenCamera`].
+    ///
+    /// The windows are created from `descriptor`, and get a main pass in the render graph once
+    /// the window backend created them. Move the cameras by inserting their `Transform`.
+    fn spawn_splitscreen(
+        &mut self,
+        count: u32,
+        layout: SplitscreenLayout,
+        descriptor: WindowDescriptor,
+    ) -> Vec<Entity>;
+}
+
+impl<'w, 's> SpawnSplitscreenCommands for Commands<'w, 's> {
+    fn spawn_splitscreen(
+        &mut self,
+        count: u32,
+        layout: SplitscreenLayout,
+        descriptor: WindowDescriptor,
+    ) -> Vec<Entity> {
+        (0..count)
+            .map(|index| {
+                let id = WindowId::new();
+                let camera = format!("splitscreen_{}", id);
+                self.add(SpawnSplitscreenWindow {
+                    id,
+                    descriptor: descriptor.clone(),
+                    region: layout.region(index, count),
+                    camera: camera.clone(),
+                });
+                self.spawn_bundle(PerspectiveCameraBundle {
+                    camera: Camera {
+                        name: Some(camera),
+                        window: id,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .insert(SplitscreenCamera { index })
+                .id()
+            })
+            .collect()
+    }
+}
+
+/// Snaps the windows of the splitscreens once they are created, and adds their main pass to the
+/// render graph.
+pub fn splitscreen_window_system(
+    mut pending: ResMut<PendingSplitscreenWindows>,
+    mut windows: ResMut<Windows>,
+    mut render_graph: ResMut<RenderGraph>,
+    mut active_cameras: ResMut<ActiveCameras>,
+    msaa: Res<Msaa>,
+) {
+    pending.windows.retain(|(id, region, camera)| {
+        let window = match windows.get_mut(*id) {
+            Some(window) => window,
+            None => return true,
+        };
+        window.snap(*region);
+        base::add_window_pass(&mut render_graph, &msaa, *id, camera);
+        active_cameras.add(camera);
+        false
+    });
+}
@@ -26,6 +26,7 @@ pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
         base::Msaa,
+        camera::{SpawnSplitscreenCommands, SplitscreenLayout},
         color::Color,
         draw::{Draw, Visible},
         entity::*,
@@ -43,8 +44,8 @@ use bevy_app::prelude::*;
 use bevy_asset::{AddAsset, AssetStage};
 use bevy_ecs::schedule::{StageLabel, SystemLabel};
 use camera::{
-    ActiveCameras, Camera, DepthCalculation, OrthographicProjection, PerspectiveProjection,
-    RenderLayers, ScalingMode, VisibleEntities, WindowOrigin,
+    ActiveCameras, Camera, DepthCalculation, OrthographicProjection, PendingSplitscreenWindows,
+    PerspectiveProjection, RenderLayers, ScalingMode, VisibleEntities, WindowOrigin,
 };
 use pipeline::{
     IndexFormat, PipelineCompiler, PipelineDescriptor, PipelineSpecialization, PrimitiveTopology,
@@ -174,8 +175,10 @@ impl Plugin for RenderPlugin {
         .init_resource::<RenderResourceBindings>()
         .init_resource::<AssetRenderResourceBindings>()
         .init_resource::<ActiveCameras>()
+        .init_resource::<PendingSplitscreenWindows>()
         .add_startup_system_to_stage(StartupStage::PreStartup, check_for_render_resource_context)
         .add_system_to_stage(CoreStage::PreUpdate, draw::clear_draw_system)
+        .add_system_to_stage(CoreStage::PostUpdate, camera::splitscreen_window_system)
         .add_system_to_stage(CoreStage::PostUpdate, camera::active_cameras_system)
         .add_system_to_stage(
             CoreStage::PostUpdate,
@@ -246,3 +246,111 @@ pub(crate) fn add_base_graph(config: &BaseRenderGraphConfig, world: &mut World)
             .unwrap();
     }
 }
+
+/// Adds a main pass drawing the camera named `camera` to the window `window_id`, with its own
+/// depth texture, like the main pass of the base graph draws to the primary window.
+///
+/// The nodes are named after the camera, which must also be added to the
+/// [`ActiveCameras`](crate::camera::ActiveCameras). The window must exist when the graph is run.
+pub fn add_window_pass(graph: &mut RenderGraph, msaa: &Msaa, window_id: WindowId, camera: &str) {
+    let swap_chain = format!("{}_swap_chain", camera);
+    let depth_texture = format!("{}_depth_texture", camera);
+    let sampled_color_attachment = format!("{}_sampled_color_attachment", camera);
+    let camera_node = format!("{}_camera", camera);
+    let pass = format!("{}_pass", camera);
+
+    graph.add_node(swap_chain.clone(), WindowSwapChainNode::new(window_id));
+    graph.add_node(
+        depth_texture.clone(),
+        WindowTextureNode::new(
+            window_id,
+            TextureDescriptor {
+                format: TextureFormat::Depth32Float,
+                usage: TextureUsage::OUTPUT_ATTACHMENT,
+                sample_count: msaa.samples,
+                ..Default::default()
+            },
+        ),
+    );
+    graph.add_system_node(camera_node.clone(), CameraNode::new(camera.to_string()));
+
+    let mut pass_node = PassNode::<&MainPass>::new(PassDescriptor {
+        color_attachments: vec![msaa.color_attachment(
+            TextureAttachment::Input("color_attachment".to_string()),
+            TextureAttachment::Input("color_resolve_target".to_string()),
+            Operations {
+                load: LoadOp::Clear(Color::rgb(0.1, 0.1, 0.1)),
+                store: true,
+            },
+        )],
+        depth_stencil_attachment: Some(RenderPassDepthStencilAttachment {
+            attachment: TextureAttachment::Input("depth".to_string()),
+            depth_ops: Some(Operations {
+                load: LoadOp::Clear(1.0),
+                store: true,
+            }),
+            stencil_ops: None,
+        }),
+        sample_count: msaa.samples,
+    });
+    pass_node.use_default_clear_color(0);
+    pass_node.add_camera(camera);
+    graph.add_node(pass.clone(), pass_node);
+
+    graph
+        .add_slot_edge(
+            swap_chain,
+            WindowSwapChainNode::OUT_TEXTURE,
+            pass.clone(),
+            if msaa.samples > 1 {
+                "color_resolve_target"
+            } else {
+                "color_attachment"
+            },
+        )
+        .unwrap();
+    graph
+        .add_slot_edge(
+            depth_texture,
+            WindowTextureNode::OUT_TEXTURE,
+            pass.clone(),
+            "depth",
+        )
+        .unwrap();
+    graph.add_node_edge(camera_node, pass.clone()).unwrap();
+    graph
+        .add_node_edge(node::TEXTURE_COPY, pass.clone())
+        .unwrap();
+    graph
+        .add_node_edge(node::SHARED_BUFFERS, pass.clone())
+        .unwrap();
+
+    if msaa.samples > 1 {
+        graph.add_node(
+            sampled_color_attachment.clone(),
+            WindowTextureNode::new(
+                window_id,
+                TextureDescriptor {
+                    size: Extent3d {
+                        depth_or_array_layers: 1,
+                        width: 1,
+                        height: 1,
+                    },
+                    mip_level_count: 1,
+                    sample_count: msaa.samples,
+                    dimension: TextureDimension::D2,
+                    format: TextureFormat::default(),
+                    usage: TextureUsage::OUTPUT_ATTACHMENT,
+                },
+            ),
+        );
+        graph
+            .add_slot_edge(
+                sampled_color_attachment,
+                WindowSwapChainNode::OUT_TEXTURE,
+                pass,
+                "color_attachment",
+            )
+            .unwrap();
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{
-    camera::{ActiveCameras, VisibleEntities},
+    camera::{ActiveCameras, Camera, VisibleEntities},
     draw::{Draw, RenderCommand},
     pass::{ClearColor, LoadOp, PassDescriptor, TextureAttachment},
     pipeline::{IndexFormat, PipelineDescriptor},
@@ -14,7 +14,9 @@ use bevy_ecs::{
     query::{QueryState, ReadOnlyFetch, WorldQuery},
     world::{Mut, World},
 };
+use bevy_math::UVec2;
 use bevy_utils::{tracing::debug, HashMap};
+use bevy_window::{WindowRenderPolicy, Windows};
 use std::fmt;
 
 pub struct PassNode<Q: WorldQuery> {
@@ -27,6 +29,9 @@ pub struct PassNode<Q: WorldQuery> {
     default_clear_color_inputs: Vec<usize>,
     query_state: Option<QueryState<Q>>,
     commands: Vec<RenderCommand>,
+    /// The part of the window of the cameras to draw to, when its [`WindowRenderPolicy`] doesn't
+    /// fill the window.
+    viewport: Option<(UVec2, UVec2)>,
 }
 
 impl<Q: WorldQuery> fmt::Debug for PassNode<Q> {
@@ -103,6 +108,7 @@ impl<Q: WorldQuery> PassNode<Q> {
             default_clear_color_inputs: Vec::new(),
             query_state: None,
             commands: Vec::new(),
+            viewport: None,
         }
     }
 
@@ -127,7 +133,18 @@ where
         let query_state = self.query_state.get_or_insert_with(|| world.query());
         let cameras = &self.cameras;
         let commands = &mut self.commands;
+        let viewport = &mut self.viewport;
         world.resource_scope(|world, mut active_cameras: Mut<ActiveCameras>| {
+            *viewport = cameras
+                .iter()
+                .filter_map(|camera_name| active_cameras.get(camera_name)?.entity)
+                .filter_map(|entity| world.get::<Camera>(entity))
+                .find_map(|camera| {
+                    let window = world.get_resource::<Windows>()?.get(camera.window)?;
+                    (window.render_policy() != WindowRenderPolicy::Native)
+                        .then(|| window.render_viewport())
+                });
+
             let mut pipeline_camera_commands = HashMap::default();
             let pipelines = world.get_resource::<Assets<PipelineDescriptor>>().unwrap();
             let render_resource_context = &**world
@@ -142,11 +159,22 @@ where
                     continue;
                 };
 
-                let visible_entities = if let Some(entity) = active_camera.entity {
-                    world.get::<VisibleEntities>(entity).unwrap()
+                let entity = if let Some(entity) = active_camera.entity {
+                    entity
                 } else {
                     continue;
                 };
+                // nothing is drawn to minimized windows
+                let minimized = world.get::<Camera>(entity).is_some_and(|camera| {
+                    world
+                        .get_resource::<Windows>()
+                        .and_then(|windows| windows.get(camera.window))
+                        .is_some_and(|window| window.is_minimized())
+                });
+                if minimized {
+                    continue;
+                }
+                let visible_entities = world.get::<VisibleEntities>(entity).unwrap();
                 for visible_entity in visible_entities.iter() {
                     if query_state.get(world, visible_entity.entity).is_err() {
                         // visible entity does not match the Pass query
@@ -235,10 +263,21 @@ where
 
         let mut draw_state = DrawState::default();
         let commands = &mut self.commands;
+        let viewport = self.viewport;
         render_context.begin_pass(
             &self.descriptor,
             render_resource_bindings,
             &mut |render_pass| {
+            if let Some((position, size)) = viewport {
+                render_pass.set_viewport(
+                    position.x as f32,
+                    position.y as f32,
+                    size.x as f32,
+                    size.y as f32,
+                    0.0,
+                    1.0,
+                );
+            }
             for render_command in commands.drain(..) {
                 match render_command {
                     RenderCommand::SetPipeline { pipeline } => {
@@ -1,14 +1,22 @@
 use crate::{
     render_graph::{Node, ResourceSlotInfo, ResourceSlots},
-    renderer::{RenderContext, RenderResourceId, RenderResourceType},
+    renderer::{RenderContext, RenderResourceId, RenderResourceType, TextureId},
+    texture::{Extent3d, TextureDescriptor, TextureFormat, TextureUsage},
 };
 use bevy_app::{Events, ManualEventReader};
 use bevy_ecs::world::World;
 use bevy_window::{WindowCreated, WindowId, WindowResized, Windows};
 use std::borrow::Cow;
 
+/// Outputs the texture the window is rendered to: the next texture of its swap chain, or for an
+/// [offscreen](bevy_window::Window::is_offscreen) window, a texture of the size of the window.
+///
+/// While the window is [minimized](bevy_window::Window::is_minimized), it has no swap chain,
+/// and a 1x1 texture that is never presented is output instead.
 pub struct WindowSwapChainNode {
     window_id: WindowId,
+    offscreen_texture: Option<TextureId>,
+    minimized_texture: Option<TextureId>,
     window_created_event_reader: ManualEventReader<WindowCreated>,
     window_resized_event_reader: ManualEventReader<WindowResized>,
 }
@@ -19,10 +27,18 @@ impl WindowSwapChainNode {
     pub fn new(window_id: WindowId) -> Self {
         WindowSwapChainNode {
             window_id,
+            offscreen_texture: None,
+            minimized_texture: None,
             window_created_event_reader: Default::default(),
             window_resized_event_reader: Default::default(),
         }
     }
+
+    /// The texture an offscreen window is rendered to, to be copied or sampled once rendered.
+    /// It is replaced when the window is resized.
+    pub fn offscreen_texture(&self) -> Option<TextureId> {
+        self.offscreen_texture
+    }
 }
 
 impl Node for WindowSwapChainNode {
@@ -53,18 +69,54 @@ impl Node for WindowSwapChainNode {
         let render_resource_context = render_context.resources_mut();
 
         // create window swapchain when window is resized or created
-        if self
+        let changed = self
             .window_created_event_reader
             .iter(window_created_events)
             .any(|e| e.id == window.id())
             || self
                 .window_resized_event_reader
                 .iter(window_resized_events)
-                .any(|e| e.id == window.id())
-        {
-            render_resource_context.create_swap_chain(window);
+                .any(|e| e.id == window.id());
+        if window.is_minimized() && !window.is_offscreen() {
+            let minimized_texture = *self.minimized_texture.get_or_insert_with(|| {
+                render_resource_context.create_texture(TextureDescriptor {
+                    size: Extent3d::new(1, 1, 1),
+                    format: TextureFormat::default(),
+                    usage: TextureUsage::OUTPUT_ATTACHMENT,
+                    ..Default::default()
+                })
+            });
+            output.set(WINDOW_TEXTURE, RenderResourceId::Texture(minimized_texture));
+            return;
+        }
+        if changed {
+            if window.is_offscreen() {
+                if let Some(old_texture) = self.offscreen_texture {
+                    render_resource_context.remove_texture(old_texture);
+                }
+                self.offscreen_texture =
+                    Some(render_resource_context.create_texture(TextureDescriptor {
+                        size: Extent3d::new(
+                            window.physical_width().max(1),
+                            window.physical_height().max(1),
+                            1,
+                        ),
+                        // the format of the swap chains, so that the same pipelines can be used
+                        format: TextureFormat::default(),
+                        usage: TextureUsage::OUTPUT_ATTACHMENT
+                            | TextureUsage::SAMPLED
+                            | TextureUsage::COPY_SRC,
+                        ..Default::default()
+                    }));
+            } else {
+                render_resource_context.create_swap_chain(window);
+            }
         }
 
+        if let Some(offscreen_texture) = self.offscreen_texture {
+            output.set(WINDOW_TEXTURE, RenderResourceId::Texture(offscreen_texture));
+            return;
+        }
         let swap_chain_texture = render_resource_context.next_swap_chain_texture(window);
         output.set(
             WINDOW_TEXTURE,
@@ -0,0 +1,193 @@
+use super::{image_texture_conversion::TextureConversionError, Texture};
+use std::{
+    convert::TryFrom,
+    path::{Path, PathBuf},
+};
+use thiserror::Error;
+
+/// Setting this environment variable makes [`compare_to_reference`] overwrite the reference
+/// images with the compared textures, to create or update them.
+pub const UPDATE_REFERENCE_IMAGES_VAR: &str = "BEVY_UPDATE_REFERENCE_IMAGES";
+
+/// How much a texture may differ from its reference image in [`compare_to_reference`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImageTolerance {
+    /// The largest difference of a channel of a pixel, out of 255, for the pixel to still match
+    /// the reference.
+    pub channel: u8,
+    /// The fraction of the pixels that may not match the reference, from 0 to 1.
+    pub pixels: f32,
+}
+
+impl Default for ImageTolerance {
+    fn default() -> Self {
+        ImageTolerance {
+            channel: 2,
+            pixels: 0.0,
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum ImageComparisonError {
+    #[error(
+        "the reference image {0} doesn't exist, set BEVY_UPDATE_REFERENCE_IMAGES to create it"
+    )]
+    MissingReference(PathBuf),
+    #[error("the image is {actual:?} pixels but the reference is {expected:?} pixels")]
+    SizeMismatch {
+        actual: (u32, u32),
+        expected: (u32, u32),
+    },
+    #[error(
+        "{differing} of {total} pixels don't match the reference, by up to {max_difference}, \
+        the image is saved to {actual_path:?}"
+    )]
+    Mismatch {
+        differing: usize,
+        total: usize,
+        max_difference: u8,
+        actual_path: PathBuf,
+    },
+    #[error("the texture can't be converted to an image: {0}")]
+    Conversion(#[from] TextureConversionError),
+    #[error("failed to read or write an image: {0}")]
+    Image(#[from] image::ImageError),
+}
+
+/// Compares `texture` to the reference PNG image at `reference`, failing if they differ by more
+/// than `tolerance`.
+///
+/// When they don't match, `texture` is saved next to the reference with the `.actual.png`
+/// extension, to inspect the differences.
+///
+/// This is meant for golden-image tests of the rendering of textures produced on the CPU.
+/// Capturing the content of a window is not supported by the renderer yet.
+pub fn compare_to_reference(
+    texture: &Texture,
+    reference: impl AsRef<Path>,
+    tolerance: ImageTolerance,
+) -> Result<(), ImageComparisonError> {
+    let reference = reference.as_ref();
+    let actual = image::DynamicImage::try_from(texture.clone())?.into_rgba8();
+    if std::env::var_os(UPDATE_REFERENCE_IMAGES_VAR).is_some() {
+        actual.save(reference)?;
+        return Ok(());
+    }
+    if !reference.exists() {
+        return Err(ImageComparisonError::MissingReference(
+            reference.to_path_buf(),
+        ));
+    }
+    let expected = image::open(reference)?.into_rgba8();
+    if actual.dimensions() != expected.dimensions() {
+        return Err(ImageComparisonError::SizeMismatch {
+            actual: actual.dimensions(),
+            expected: expected.dimensions(),
+        });
+    }
+
+    let mut differing = 0;
+    let mut max_difference = 0;
+    for (actual_pixel, expected_pixel) in actual.pixels().zip(expected.pixels()) {
+        let difference = actual_pixel
+            .0
+            .iter()
+            .zip(expected_pixel.0.iter())
+            .map(|(a, b)| a.abs_diff(*b))
+            .max()
+            .unwrap_or(0);
+        max_difference = max_difference.max(difference);
+        if difference > tolerance.channel {
+            differing += 1;
+        }
+    }
+    let total = (actual.width() * actual.height()) as usize;
+    if differing as f32 > tolerance.pixels * total as f32 {
+        let actual_path = reference.with_extension("actual.png");
+        actual.save(&actual_path)?;
+        return Err(ImageComparisonError::Mismatch {
+            differing,
+            total,
+            max_difference,
+            actual_path,
+        });
+    }
+    Ok(())
+}
+
+/// Asserts that `texture` matches the reference image at `reference`, see
+/// [`compare_to_reference`].
+///
+/// # Panics
+///
+/// Panics if the texture doesn't match the reference image.
+#[track_caller]
+pub fn assert_matches_reference(
+    texture: &Texture,
+    reference: impl AsRef<Path>,
+    tolerance: ImageTolerance,
+) {
+    let reference = reference.as_ref();
+    if let Err(err) = compare_to_reference(texture, reference, tolerance) {
+        panic!("{} doesn't match: {}", reference.display(), err);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{compare_to_reference, ImageComparisonError, ImageTolerance};
+    use crate::texture::{Extent3d, Texture, TextureDimension, TextureFormat};
+
+    fn texture(pixels: &[[u8; 4]]) -> Texture {
+        Texture::new(
+            Extent3d::new(pixels.len() as u32, 1, 1),
+            TextureDimension::D2,
+            pixels.iter().flatten().copied().collect(),
+            TextureFormat::Rgba8UnormSrgb,
+        )
+    }
+
+    #[test]
+    fn reference_image() {
+        let dir = std::env::temp_dir().join("bevy_render_reference_image_test");
+        std::fs::create_dir_all(&dir).unwrap();
+        let reference = dir.join("reference.png");
+        let pixels = [[0, 0, 0, 255], [100, 100, 100, 255], [255, 0, 0, 255]];
+        image::RgbaImage::from_raw(3, 1, pixels.iter().flatten().copied().collect())
+            .unwrap()
+            .save(&reference)
+            .unwrap();
+
+        let close = texture(&[[1, 0, 0, 255], [100, 102, 100, 255], [255, 0, 0, 255]]);
+        assert!(compare_to_reference(&close, &reference, ImageTolerance::default()).is_ok());
+
+        let different = texture(&[[0, 0, 0, 255], [100, 100, 100, 255], [0, 0, 255, 255]]);
+        match compare_to_reference(&different, &reference, ImageTolerance::default()) {
+            Err(ImageComparisonError::Mismatch {
+                differing,
+                max_difference,
+                actual_path,
+                ..
+            }) => {
+                assert_eq!(differing, 1);
+                assert_eq!(max_difference, 255);
+                assert!(actual_path.exists());
+            }
+            result => panic!("unexpected comparison result: {:?}", result),
+        }
+        let tolerance = ImageTolerance {
+            pixels: 0.5,
+            ..Default::default()
+        };
+        assert!(compare_to_reference(&different, &reference, tolerance).is_ok());
+
+        let smaller = texture(&[[0, 0, 0, 255]]);
+        assert!(matches!(
+            compare_to_reference(&smaller, &reference, ImageTolerance::default()),
+            Err(ImageComparisonError::SizeMismatch { .. })
+        ));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,313 @@
+use crate::{serde::BlueprintAssetSerializer, serialize_ron, SceneSpawnError};
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::{
+    entity::Entity,
+    reflect::{ReflectBlueprint, ReflectComponent},
+    system::{Command, CommandQueue, Commands, EntityCommands},
+    world::{Mut, World},
+};
+use bevy_reflect::{Reflect, TypeRegistry, TypeRegistryArc, TypeUuid};
+use bevy_transform::hierarchy::BuildWorldChildren;
+use bevy_utils::tracing::error;
+
+/// A blueprint authored as a `.blueprint.ron` file: a list of reflected components and a list of
+/// reflected [`EntityBlueprint`]s, inserted and built on the entity the blueprint is spawned on,
+/// and the blueprints of its children.
+///
+/// [`EntityBlueprint`]: bevy_ecs::blueprint::EntityBlueprint
+///
+/// ```ron
+/// (
+///   components: [
+///     {
+///       "type": "game::Health",
+///       "struct": {
+///         "value": {
+///           "type": "u32",
+///           "value": 10,
+///         },
+///       },
+///     },
+///   ],
+///   blueprints: [
+///     {
+///       "type": "game::EnemyBlueprint",
+///       "struct": {
+///         "speed": {
+///           "type": "f32",
+///           "value": 2.0,
+///         },
+///       },
+///     },
+///   ],
+///   children: [
+///     (components: [/* ... */]),
+///   ],
+/// )
+/// ```
+///
+/// All components must be registered with `#[reflect(Component)]`, and all blueprints with
+/// `#[reflect(Blueprint)]`, the fields they don't list keeping their default value. Spawn a
+/// loaded blueprint with [`SpawnBlueprintAssetCommands::spawn_blueprint_asset`].
+#[derive(Default, TypeUuid)]
+#[uuid = "2df85b45-49c4-45d7-9d4c-701e99bc3c2a"]
+pub struct BlueprintAsset {
+    pub components: Vec<Box<dyn Reflect>>,
+    /// The reflected [`EntityBlueprint`](bevy_ecs::blueprint::EntityBlueprint)s, built after the
+    /// components are inserted.
+    pub blueprints: Vec<Box<dyn Reflect>>,
+    pub children: Vec<BlueprintAsset>,
+}
+
+impl BlueprintAsset {
+    /// Inserts the components of this blueprint on `entity`, builds its blueprints onto it, then
+    /// spawns its children.
+    pub fn write_to_world(&self, world: &mut World, entity: Entity) -> Result<(), SceneSpawnError> {
+        let registry = world.get_resource::<TypeRegistryArc>().unwrap().clone();
+        let type_registry = registry.read();
+        self.write_to_world_with(world, entity, &type_registry)
+    }
+
+    fn write_to_world_with(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        type_registry: &TypeRegistry,
+    ) -> Result<(), SceneSpawnError> {
+        for component in self.components.iter() {
+            let registration = type_registry
+                .get_with_name(component.type_name())
+                .ok_or_else(|| SceneSpawnError::UnregisteredType {
+                    type_name: component.type_name().to_string(),
+                })?;
+            let reflect_component = registration.data::<ReflectComponent>().ok_or_else(|| {
+                SceneSpawnError::UnregisteredComponent {
+                    type_name: component.type_name().to_string(),
+                }
+            })?;
+            if world
+                .entity(entity)
+                .contains_type_id(registration.type_id())
+            {
+                reflect_component.apply_component(world, entity, &**component);
+            } else {
+                reflect_component.add_component(world, entity, &**component);
+            }
+        }
+
+        if !self.blueprints.is_empty() {
+            let mut queue = CommandQueue::default();
+            let mut commands = Commands::new(&mut queue, world);
+            let mut entity_commands = commands.entity(entity);
+            for blueprint in self.blueprints.iter() {
+                let registration = type_registry
+                    .get_with_name(blueprint.type_name())
+                    .ok_or_else(|| SceneSpawnError::UnregisteredType {
+                        type_name: blueprint.type_name().to_string(),
+                    })?;
+                let reflect_blueprint =
+                    registration.data::<ReflectBlueprint>().ok_or_else(|| {
+                        SceneSpawnError::UnregisteredBlueprint {
+                            type_name: blueprint.type_name().to_string(),
+                        }
+                    })?;
+                reflect_blueprint.build(&**blueprint, &mut entity_commands);
+            }
+            queue.apply(world);
+        }
+
+        for child_blueprint in self.children.iter() {
+            let child = world.spawn().id();
+            world.entity_mut(entity).push_children(&[child]);
+            child_blueprint.write_to_world_with(world, child, type_registry)?;
+        }
+        Ok(())
+    }
+
+    // TODO: move to AssetSaver when it is implemented
+    pub fn serialize_ron(&self, registry: &TypeRegistryArc) -> Result<String, ron::Error> {
+        serialize_ron(BlueprintAssetSerializer::new(self, registry))
+    }
+}
+
+pub struct InsertBlueprintAsset {
+    pub entity: Entity,
+    pub handle: Handle<BlueprintAsset>,
+}
+
+impl Command for InsertBlueprintAsset {
+    fn write(self, world: &mut World) {
+        let result = world.resource_scope(|world, blueprints: Mut<Assets<BlueprintAsset>>| {
+            let blueprint = blueprints.get(&self.handle).ok_or_else(|| {
+                SceneSpawnError::NonExistentBlueprint {
+                    handle: self.handle.clone_weak(),
+                }
+            })?;
+            blueprint.write_to_world(world, self.entity)
+        });
+        if let Err(err) = result {
+            error!("Failed to build blueprint asset: {}", err);
+        }
+    }
+}
+
+pub trait SpawnBlueprintAssetCommands<'w, 's> {
+    /// Creates a new entity and builds the given [`BlueprintAsset`] onto it when the commands
+    /// are applied. The asset must be loaded by then.
+    fn spawn_blueprint_asset<'a>(
+        &'a mut self,
+        handle: Handle<BlueprintAsset>,
+    ) -> EntityCommands<'w, 's, 'a>;
+}
+
+impl<'w, 's> SpawnBlueprintAssetCommands<'w, 's> for Commands<'w, 's> {
+    fn spawn_blueprint_asset<'a>(
+        &'a mut self,
+        handle: Handle<BlueprintAsset>,
+    ) -> EntityCommands<'w, 's, 'a> {
+        let mut entity = self.spawn();
+        entity.insert_blueprint_asset(handle);
+        entity
+    }
+}
+
+pub trait InsertBlueprintAssetCommands {
+    /// Builds the given [`BlueprintAsset`] onto this entity when the commands are applied. The
+    /// asset must be loaded by then.
+    fn insert_blueprint_asset(&mut self, handle: Handle<BlueprintAsset>) -> &mut Self;
+}
+
+impl<'w, 's, 'a> InsertBlueprintAssetCommands for EntityCommands<'w, 's, 'a> {
+    fn insert_blueprint_asset(&mut self, handle: Handle<BlueprintAsset>) -> &mut Self {
+        let entity = self.id();
+        self.commands().add(InsertBlueprintAsset { entity, handle });
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BlueprintAsset;
+    use crate::serde::BlueprintAssetDeserializer;
+    use bevy_ecs::{
+        blueprint::EntityBlueprint,
+        reflect::{ReflectBlueprint, ReflectComponent},
+        system::EntityCommands,
+        world::World,
+    };
+    use bevy_reflect::{Reflect, TypeRegistryArc};
+    use bevy_transform::components::{Children, Parent};
+    use serde::de::DeserializeSeed;
+
+    #[derive(Debug, Default, PartialEq, Reflect)]
+    #[reflect(Component)]
+    struct Health {
+        value: u32,
+    }
+
+    #[derive(Debug, Default, PartialEq, Reflect)]
+    #[reflect(Component)]
+    struct Name {
+        value: String,
+    }
+
+    #[derive(Debug, Default, PartialEq, Reflect)]
+    #[reflect(Blueprint)]
+    struct NameBlueprint {
+        name: String,
+    }
+
+    impl EntityBlueprint for NameBlueprint {
+        type Output = ();
+
+        fn build(self, entity: &mut EntityCommands) {
+            entity.insert(Name { value: self.name });
+        }
+    }
+
+    fn registry() -> TypeRegistryArc {
+        let registry = TypeRegistryArc::default();
+        {
+            let mut registry = registry.write();
+            registry.register::<u32>();
+            registry.register::<String>();
+            registry.register::<Health>();
+            registry.register::<Name>();
+            registry.register::<NameBlueprint>();
+        }
+        registry
+    }
+
+    fn blueprint() -> BlueprintAsset {
+        BlueprintAsset {
+            components: vec![Box::new(Health { value: 10 })],
+            blueprints: vec![Box::new(NameBlueprint {
+                name: "knight".to_string(),
+            })],
+            children: vec![BlueprintAsset {
+                components: vec![Box::new(Name {
+                    value: "sword".to_string(),
+                })],
+                blueprints: Vec::new(),
+                children: Vec::new(),
+            }],
+        }
+    }
+
+    #[test]
+    fn ron_round_trip() {
+        let registry = registry();
+        let ron = blueprint().serialize_ron(&registry).unwrap();
+        let mut deserializer = ron::de::Deserializer::from_str(&ron).unwrap();
+        let blueprint = BlueprintAssetDeserializer {
+            type_registry: &registry.read(),
+        }
+        .deserialize(&mut deserializer)
+        .unwrap();
+
+        assert_eq!(blueprint.components.len(), 1);
+        assert!(blueprint.components[0].reflect_partial_eq(&Health { value: 10 }) == Some(true));
+        assert_eq!(blueprint.blueprints.len(), 1);
+        assert!(
+            blueprint.blueprints[0].reflect_partial_eq(&NameBlueprint {
+                name: "knight".to_string(),
+            }) == Some(true)
+        );
+        assert_eq!(blueprint.children.len(), 1);
+        let child = &blueprint.children[0];
+        assert!(
+            child.components[0].reflect_partial_eq(&Name {
+                value: "sword".to_string(),
+            }) == Some(true)
+        );
+        assert!(child.children.is_empty());
+        assert_eq!(blueprint.serialize_ron(&registry).unwrap(), ron);
+    }
+
+    #[test]
+    fn write_to_world() {
+        let mut world = World::new();
+        world.insert_resource(registry());
+        let entity = world.spawn().insert(Health { value: 1 }).id();
+        blueprint().write_to_world(&mut world, entity).unwrap();
+
+        // existing components are overwritten
+        assert_eq!(world.get::<Health>(entity), Some(&Health { value: 10 }));
+        assert_eq!(
+            world.get::<Name>(entity),
+            Some(&Name {
+                value: "knight".to_string()
+            })
+        );
+        let children = world.get::<Children>(entity).unwrap();
+        assert_eq!(children.len(), 1);
+        let child = children[0];
+        assert_eq!(
+            world.get::<Name>(child),
+            Some(&Name {
+                value: "sword".to_string()
+            })
+        );
+        assert_eq!(world.get::<Parent>(child), Some(&Parent(entity)));
+    }
+}
@@ -1,3 +1,4 @@
+mod blueprint_asset;
 mod command;
 mod dynamic_scene;
 mod scene;
@@ -5,6 +6,7 @@ mod scene_loader;
 mod scene_spawner;
 pub mod serde;
 
+pub use blueprint_asset::*;
 pub use command::*;
 pub use dynamic_scene::*;
 pub use scene::*;
@@ -14,7 +16,8 @@ pub use scene_spawner::*;
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        DynamicScene, Scene, SceneSpawner, SpawnSceneAsChildCommands, SpawnSceneCommands,
+        BlueprintAsset, DynamicScene, InsertBlueprintAssetCommands, Scene, SceneSpawner,
+        SpawnBlueprintAssetCommands, SpawnSceneAsChildCommands, SpawnSceneCommands,
     };
 }
 
@@ -29,7 +32,9 @@ impl Plugin for ScenePlugin {
     fn build(&self, app: &mut App) {
         app.add_asset::<DynamicScene>()
             .add_asset::<Scene>()
+            .add_asset::<BlueprintAsset>()
             .init_asset_loader::<SceneLoader>()
+            .init_asset_loader::<BlueprintAssetLoader>()
             .init_resource::<SceneSpawner>()
             .add_system_to_stage(
                 CoreStage::PreUpdate,
@@ -1,4 +1,4 @@
-use crate::serde::SceneDeserializer;
+use crate::serde::{BlueprintAssetDeserializer, SceneDeserializer};
 use anyhow::Result;
 use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
 use bevy_ecs::world::{FromWorld, World};
@@ -41,3 +41,39 @@ impl AssetLoader for SceneLoader {
         &["scn", "scn.ron"]
     }
 }
+
+#[derive(Debug)]
+pub struct BlueprintAssetLoader {
+    type_registry: TypeRegistryArc,
+}
+
+impl FromWorld for BlueprintAssetLoader {
+    fn from_world(world: &mut World) -> Self {
+        let type_registry = world.get_resource::<TypeRegistryArc>().unwrap();
+        BlueprintAssetLoader {
+            type_registry: type_registry.clone(),
+        }
+    }
+}
+
+impl AssetLoader for BlueprintAssetLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let mut deserializer = ron::de::Deserializer::from_bytes(bytes)?;
+            let blueprint_deserializer = BlueprintAssetDeserializer {
+                type_registry: &self.type_registry.read(),
+            };
+            let blueprint = blueprint_deserializer.deserialize(&mut deserializer)?;
+            load_context.set_default_asset(LoadedAsset::new(blueprint));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["blueprint.ron"]
+    }
+}
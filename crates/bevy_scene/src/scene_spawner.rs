@@ -1,4 +1,4 @@
-use crate::{DynamicScene, Scene};
+use crate::{BlueprintAsset, DynamicScene, Scene};
 use bevy_app::{Events, ManualEventReader};
 use bevy_asset::{AssetEvent, Assets, Handle};
 use bevy_ecs::{
@@ -44,10 +44,14 @@ pub enum SceneSpawnError {
     UnregisteredComponent { type_name: String },
     #[error("scene contains the unregistered type `{type_name}`. consider registering the type using `app.register_type::<T>()`")]
     UnregisteredType { type_name: String },
+    #[error("blueprint asset contains the unregistered blueprint `{type_name}`. consider adding `#[reflect(Blueprint)]` to your type")]
+    UnregisteredBlueprint { type_name: String },
     #[error("scene does not exist")]
     NonExistentScene { handle: Handle<DynamicScene> },
     #[error("scene does not exist")]
     NonExistentRealScene { handle: Handle<Scene> },
+    #[error("blueprint asset does not exist")]
+    NonExistentBlueprint { handle: Handle<BlueprintAsset> },
 }
 
 impl SceneSpawner {
@@ -1,4 +1,4 @@
-use crate::{DynamicScene, Entity};
+use crate::{BlueprintAsset, DynamicScene, Entity};
 use anyhow::Result;
 use bevy_reflect::{
     serde::{ReflectDeserializer, ReflectSerializer},
@@ -249,3 +249,202 @@ impl<'a, 'de> Visitor<'de> for ComponentSeqVisitor<'a> {
         Ok(dynamic_properties)
     }
 }
+
+pub struct BlueprintAssetSerializer<'a> {
+    pub blueprint: &'a BlueprintAsset,
+    pub registry: &'a TypeRegistryArc,
+}
+
+impl<'a> BlueprintAssetSerializer<'a> {
+    pub fn new(blueprint: &'a BlueprintAsset, registry: &'a TypeRegistryArc) -> Self {
+        BlueprintAssetSerializer {
+            blueprint,
+            registry,
+        }
+    }
+}
+
+impl<'a> Serialize for BlueprintAssetSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct(BLUEPRINT_STRUCT, 3)?;
+        state.serialize_field(
+            BLUEPRINT_FIELD_COMPONENTS,
+            &ComponentsSerializer {
+                components: &self.blueprint.components,
+                registry: self.registry,
+            },
+        )?;
+        state.serialize_field(
+            BLUEPRINT_FIELD_BLUEPRINTS,
+            &ComponentsSerializer {
+                components: &self.blueprint.blueprints,
+                registry: self.registry,
+            },
+        )?;
+        state.serialize_field(
+            BLUEPRINT_FIELD_CHILDREN,
+            &BlueprintAssetsSerializer {
+                blueprints: &self.blueprint.children,
+                registry: self.registry,
+            },
+        )?;
+        state.end()
+    }
+}
+
+struct BlueprintAssetsSerializer<'a> {
+    pub blueprints: &'a [BlueprintAsset],
+    pub registry: &'a TypeRegistryArc,
+}
+
+impl<'a> Serialize for BlueprintAssetsSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(self.blueprints.len()))?;
+        for blueprint in self.blueprints.iter() {
+            state.serialize_element(&BlueprintAssetSerializer::new(blueprint, self.registry))?;
+        }
+        state.end()
+    }
+}
+
+pub struct BlueprintAssetDeserializer<'a> {
+    pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for BlueprintAssetDeserializer<'a> {
+    type Value = BlueprintAsset;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            BLUEPRINT_STRUCT,
+            &[
+                BLUEPRINT_FIELD_COMPONENTS,
+                BLUEPRINT_FIELD_BLUEPRINTS,
+                BLUEPRINT_FIELD_CHILDREN,
+            ],
+            BlueprintAssetVisitor {
+                registry: self.type_registry,
+            },
+        )
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum BlueprintField {
+    Components,
+    Blueprints,
+    Children,
+}
+
+pub const BLUEPRINT_STRUCT: &str = "Blueprint";
+pub const BLUEPRINT_FIELD_COMPONENTS: &str = "components";
+pub const BLUEPRINT_FIELD_BLUEPRINTS: &str = "blueprints";
+pub const BLUEPRINT_FIELD_CHILDREN: &str = "children";
+
+struct BlueprintAssetVisitor<'a> {
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for BlueprintAssetVisitor<'a> {
+    type Value = BlueprintAsset;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("blueprint")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut components = None;
+        let mut blueprints = None;
+        let mut children = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                BlueprintField::Components => {
+                    if components.is_some() {
+                        return Err(Error::duplicate_field(BLUEPRINT_FIELD_COMPONENTS));
+                    }
+                    components = Some(map.next_value_seed(ComponentVecDeserializer {
+                        registry: self.registry,
+                    })?);
+                }
+                BlueprintField::Blueprints => {
+                    if blueprints.is_some() {
+                        return Err(Error::duplicate_field(BLUEPRINT_FIELD_BLUEPRINTS));
+                    }
+                    blueprints = Some(map.next_value_seed(ComponentVecDeserializer {
+                        registry: self.registry,
+                    })?);
+                }
+                BlueprintField::Children => {
+                    if children.is_some() {
+                        return Err(Error::duplicate_field(BLUEPRINT_FIELD_CHILDREN));
+                    }
+                    children = Some(map.next_value_seed(BlueprintAssetVecDeserializer {
+                        registry: self.registry,
+                    })?);
+                }
+            }
+        }
+
+        Ok(BlueprintAsset {
+            components: components.unwrap_or_default(),
+            blueprints: blueprints.unwrap_or_default(),
+            children: children.unwrap_or_default(),
+        })
+    }
+}
+
+struct BlueprintAssetVecDeserializer<'a> {
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for BlueprintAssetVecDeserializer<'a> {
+    type Value = Vec<BlueprintAsset>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(BlueprintAssetSeqVisitor {
+            registry: self.registry,
+        })
+    }
+}
+
+struct BlueprintAssetSeqVisitor<'a> {
+    pub registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for BlueprintAssetSeqVisitor<'a> {
+    type Value = Vec<BlueprintAsset>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("list of blueprints")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut blueprints = Vec::new();
+        while let Some(blueprint) = seq.next_element_seed(BlueprintAssetDeserializer {
+            type_registry: self.registry,
+        })? {
+            blueprints.push(blueprint);
+        }
+
+        Ok(blueprints)
+    }
+}
@@ -0,0 +1,207 @@
+use super::BuildChildren;
+use crate::components::{Children, Parent};
+use bevy_ecs::{
+    blueprint::{EntityAssertions, EntityBlueprint},
+    entity::Entity,
+    system::EntityCommands,
+};
+
+/// A blueprint that builds a parent blueprint, then spawns a child entity built from a child
+/// blueprint. Created with [`BlueprintChildren::with_child`].
+#[derive(Debug, Clone)]
+pub struct WithChild<P, C> {
+    pub parent: P,
+    pub child: C,
+}
+
+/// The output of a [`WithChild`] blueprint.
+#[derive(Debug, Clone)]
+pub struct WithChildOutput<P, C> {
+    /// The output of the parent blueprint.
+    pub parent: P,
+    /// The spawned child entity.
+    pub child: Entity,
+    /// The output of the child blueprint.
+    pub child_output: C,
+}
+
+impl<P: EntityBlueprint, C: EntityBlueprint> EntityBlueprint for WithChild<P, C> {
+    type Output = WithChildOutput<P::Output, C::Output>;
+
+    fn build(self, entity: &mut EntityCommands) -> Self::Output {
+        let parent = self.parent.build(entity);
+        let mut child_commands = entity.commands().spawn();
+        let child_output = self.child.build(&mut child_commands);
+        let child = child_commands.id();
+        entity.push_children(&[child]);
+        WithChildOutput {
+            parent,
+            child,
+            child_output,
+        }
+    }
+}
+
+/// Composes blueprints into small hierarchies.
+///
+/// # Example
+///
+/// ```
+/// use bevy_ecs::{blueprint::EntityBlueprint, prelude::*, system::EntityCommands};
+/// use bevy_transform::hierarchy::BlueprintChildren;
+///
+/// struct Name(&'static str);
+///
+/// struct NamedBlueprint(&'static str);
+///
+/// impl EntityBlueprint for NamedBlueprint {
+///     type Output = ();
+///
+///     fn build(self, entity: &mut EntityCommands) {
+///         entity.insert(Name(self.0));
+///     }
+/// }
+///
+/// fn spawn_car(mut commands: Commands) {
+///     // spawns a car entity with two wheel children
+///     commands.spawn_blueprint(
+///         NamedBlueprint("car")
+///             .with_child(NamedBlueprint("front wheel"))
+///             .with_child(NamedBlueprint("back wheel")),
+///     );
+/// }
+/// # spawn_car.system();
+/// ```
+pub trait BlueprintChildren: EntityBlueprint + Sized {
+    /// Spawns an entity built from `child` as a child of the entity this blueprint is built onto.
+    fn with_child<C: EntityBlueprint>(self, child: C) -> WithChild<Self, C> {
+        WithChild {
+            parent: self,
+            child,
+        }
+    }
+}
+
+impl<B: EntityBlueprint> BlueprintChildren for B {}
+
+/// Assertions about the hierarchy of an entity built by a
+/// [`BlueprintTester`](bevy_ecs::blueprint::BlueprintTester).
+pub trait HierarchyAssertions<'w> {
+    /// Returns assertions about each child of the entity, in order.
+    fn children(&self) -> Vec<EntityAssertions<'w>>;
+
+    /// Returns assertions about the child of the entity at `index`.
+    fn child(&self, index: usize) -> EntityAssertions<'w>;
+
+    /// Returns assertions about the parent of the entity.
+    fn parent(&self) -> EntityAssertions<'w>;
+
+    /// Asserts that the entity has `count` children.
+    fn assert_child_count(&self, count: usize) -> &Self;
+}
+
+impl<'w> HierarchyAssertions<'w> for EntityAssertions<'w> {
+    fn children(&self) -> Vec<EntityAssertions<'w>> {
+        self.world
+            .get::<Children>(self.entity)
+            .map_or(&[][..], |children| &**children)
+            .iter()
+            .map(|child| EntityAssertions {
+                world: self.world,
+                entity: *child,
+            })
+            .collect()
+    }
+
+    fn child(&self, index: usize) -> EntityAssertions<'w> {
+        let children = self.children();
+        let count = children.len();
+        children.get(index).copied().unwrap_or_else(|| {
+            panic!(
+                "{:?} has no child at index {} ({} children)",
+                self.entity, index, count
+            )
+        })
+    }
+
+    fn parent(&self) -> EntityAssertions<'w> {
+        let parent = self
+            .world
+            .get::<Parent>(self.entity)
+            .unwrap_or_else(|| panic!("{:?} has no parent", self.entity));
+        EntityAssertions {
+            world: self.world,
+            entity: parent.0,
+        }
+    }
+
+    fn assert_child_count(&self, count: usize) -> &Self {
+        assert_eq!(
+            self.children().len(),
+            count,
+            "unexpected number of children of {:?}",
+            self.entity
+        );
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BlueprintChildren, HierarchyAssertions};
+    use crate::prelude::{Children, Parent};
+    use bevy_ecs::{
+        blueprint::{BlueprintTester, EntityBlueprint},
+        system::{CommandQueue, Commands, EntityCommands},
+        world::World,
+    };
+
+    struct Value(u32);
+
+    impl EntityBlueprint for Value {
+        type Output = ();
+
+        fn build(self, entity: &mut EntityCommands) {
+            entity.insert(self.0);
+        }
+    }
+
+    #[test]
+    fn blueprint_children() {
+        let mut world = World::default();
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &world);
+        let mut parent_commands = commands.spawn();
+        let output = Value(1)
+            .with_child(Value(2).with_child(Value(3)))
+            .build(&mut parent_commands);
+        let parent = parent_commands.id();
+        queue.apply(&mut world);
+
+        let child = world.get::<Children>(parent).unwrap()[0];
+        assert_eq!(child, output.child);
+        assert_eq!(world.get::<u32>(child), Some(&2));
+        assert_eq!(*world.get::<Parent>(child).unwrap(), Parent(parent));
+        let grandchild = world.get::<Children>(child).unwrap()[0];
+        assert_eq!(grandchild, output.child_output.child);
+        assert_eq!(world.get::<u32>(grandchild), Some(&3));
+        assert_eq!(*world.get::<Parent>(grandchild).unwrap(), Parent(child));
+    }
+
+    #[test]
+    fn hierarchy_assertions() {
+        let mut tester = BlueprintTester::default();
+        let (parent, output) = tester.build(Value(1).with_child(Value(2).with_child(Value(3))));
+
+        let parent = tester.entity(parent);
+        parent.assert_component(&1u32).assert_child_count(1);
+        let child = parent.child(0);
+        assert_eq!(child.entity, output.child);
+        child.assert_component(&2u32).assert_child_count(1);
+        assert_eq!(child.parent().entity, parent.entity);
+        let grandchild = child.child(0);
+        assert_eq!(grandchild.entity, output.child_output.child);
+        grandchild.assert_component(&3u32);
+        assert_eq!(grandchild.parent().entity, child.entity);
+    }
+}
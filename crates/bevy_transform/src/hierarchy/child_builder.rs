@@ -1,5 +1,6 @@
 use crate::prelude::{Children, Parent, PreviousParent};
 use bevy_ecs::{
+    blueprint::EntityBlueprint,
     bundle::Bundle,
     entity::Entity,
     system::{Command, Commands, EntityCommands},
@@ -84,6 +85,18 @@ impl<'w, 's, 'a> ChildBuilder<'w, 's, 'a> {
         e
     }
 
+    /// Spawns a child entity and builds `blueprint` onto it, discarding the blueprint's output.
+    /// The blueprint can itself spawn children of that entity with
+    /// [`BuildChildren::with_children`].
+    pub fn spawn_blueprint(
+        &mut self,
+        blueprint: impl EntityBlueprint + 'static,
+    ) -> EntityCommands<'w, 's, '_> {
+        let mut e = self.spawn();
+        e.insert_blueprint(blueprint);
+        e
+    }
+
     pub fn parent_entity(&self) -> Entity {
         self.push_children.parent
     }
@@ -1,8 +1,10 @@
+mod blueprint;
 mod child_builder;
 #[allow(clippy::module_inception)]
 mod hierarchy;
 mod hierarchy_maintenance_system;
 
+pub use blueprint::*;
 pub use child_builder::*;
 pub use hierarchy::*;
 pub use hierarchy_maintenance_system::*;
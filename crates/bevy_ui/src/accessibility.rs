@@ -0,0 +1,89 @@
+use crate::widget::{Checkbox, Dropdown, Scrollbar, SelectedOption, Slider};
+use bevy_ecs::{
+    query::{Changed, Or},
+    reflect::ReflectComponent,
+    system::Query,
+};
+use bevy_reflect::{Reflect, ReflectDeserialize};
+use serde::{Deserialize, Serialize};
+
+/// The semantic role of a UI node, describing what kind of widget it is to assistive technology
+/// and test tooling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Reflect)]
+#[reflect_value(Component, PartialEq, Hash, Serialize, Deserialize)]
+pub enum AccessibleRole {
+    Button,
+    Checkbox,
+    Slider,
+    Scrollbar,
+    Dropdown,
+    /// An option in the list of a dropdown.
+    ListItem,
+    Image,
+    Label,
+    Group,
+}
+
+impl Default for AccessibleRole {
+    fn default() -> Self {
+        AccessibleRole::Group
+    }
+}
+
+/// The human readable name of a UI node, for example the text of a button's label.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Component, PartialEq, Hash)]
+pub struct AccessibleName(pub String);
+
+/// The current value of a UI node that holds one.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Reflect)]
+#[reflect_value(Component, PartialEq, Serialize, Deserialize)]
+pub enum AccessibleValue {
+    Toggled(bool),
+    Numeric { value: f32, min: f32, max: f32 },
+    Text(String),
+}
+
+impl Default for AccessibleValue {
+    fn default() -> Self {
+        AccessibleValue::Text(String::new())
+    }
+}
+
+/// Keeps the [`AccessibleValue`] of the built-in widgets in sync with their state.
+#[allow(clippy::type_complexity)]
+pub fn accessible_value_system(
+    mut checkbox_query: Query<(&Checkbox, &mut AccessibleValue), Changed<Checkbox>>,
+    mut slider_query: Query<(&Slider, &mut AccessibleValue), Changed<Slider>>,
+    mut scrollbar_query: Query<(&Scrollbar, &mut AccessibleValue), Changed<Scrollbar>>,
+    mut dropdown_query: Query<
+        (&Dropdown, &SelectedOption, &mut AccessibleValue),
+        Or<(Changed<Dropdown>, Changed<SelectedOption>)>,
+    >,
+) {
+    for (checkbox, mut value) in checkbox_query.iter_mut() {
+        *value = AccessibleValue::Toggled(checkbox.checked);
+    }
+    for (slider, mut value) in slider_query.iter_mut() {
+        *value = AccessibleValue::Numeric {
+            value: slider.value,
+            min: slider.min,
+            max: slider.max,
+        };
+    }
+    for (scrollbar, mut value) in scrollbar_query.iter_mut() {
+        *value = AccessibleValue::Numeric {
+            value: scrollbar.position,
+            min: 0.0,
+            max: scrollbar.max_position(),
+        };
+    }
+    for (dropdown, selected, mut value) in dropdown_query.iter_mut() {
+        let text = selected
+            .0
+            .and_then(|index| dropdown.options.get(index))
+            .cloned()
+            .unwrap_or_default();
+        *value = AccessibleValue::Text(text);
+    }
+}
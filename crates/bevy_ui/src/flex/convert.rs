@@ -1,6 +1,6 @@
 use crate::{
     AlignContent, AlignItems, AlignSelf, Direction, Display, FlexDirection, FlexWrap,
-    JustifyContent, PositionType, Style, Val,
+    JustifyContent, Overflow, PositionType, Style, Val,
 };
 use bevy_math::{Rect, Size};
 
@@ -36,7 +36,7 @@ pub fn from_val_size(
 
 pub fn from_style(scale_factor: f64, value: &Style) -> stretch::style::Style {
     stretch::style::Style {
-        overflow: stretch::style::Overflow::Visible,
+        overflow: value.overflow.into(),
         display: value.display.into(),
         position_type: value.position_type.into(),
         direction: value.direction.into(),
@@ -129,6 +129,15 @@ impl From<Display> for stretch::style::Display {
     }
 }
 
+impl From<Overflow> for stretch::style::Overflow {
+    fn from(value: Overflow) -> Self {
+        match value {
+            Overflow::Visible => stretch::style::Overflow::Visible,
+            Overflow::Hidden => stretch::style::Overflow::Hidden,
+        }
+    }
+}
+
 impl From<FlexDirection> for stretch::style::FlexDirection {
     fn from(value: FlexDirection) -> Self {
         match value {
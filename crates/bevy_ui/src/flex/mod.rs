@@ -1,23 +1,26 @@
 mod convert;
 
-use crate::{CalculatedSize, Node, Style};
-use bevy_app::EventReader;
+use crate::{
+    CalculatedSize, GridCell, GridContainer, GridPlacement, Node, Style, UiTargetWindow, Val,
+};
 use bevy_ecs::{
     entity::Entity,
     query::{Changed, FilterFetch, With, Without, WorldQuery},
-    system::{Query, Res, ResMut},
+    system::{Local, Query, RemovedComponents, Res, ResMut},
 };
 use bevy_log::warn;
 use bevy_math::Vec2;
 use bevy_transform::prelude::{Children, Parent, Transform};
 use bevy_utils::HashMap;
-use bevy_window::{Window, WindowId, WindowScaleFactorChanged, Windows};
+use bevy_window::{Window, WindowId, Windows};
 use std::fmt;
 use stretch::{number::Number, Stretch};
 
 pub struct FlexSurface {
     entity_to_stretch: HashMap<Entity, stretch::node::Node>,
     window_nodes: HashMap<WindowId, stretch::node::Node>,
+    node_windows: HashMap<Entity, WindowId>,
+    grid_items: HashMap<Entity, GridCell>,
     stretch: Stretch,
 }
 
@@ -38,6 +41,8 @@ impl fmt::Debug for FlexSurface {
         f.debug_struct("FlexSurface")
             .field("entity_to_stretch", &self.entity_to_stretch)
             .field("window_nodes", &self.window_nodes)
+            .field("node_windows", &self.node_windows)
+            .field("grid_items", &self.grid_items)
             .finish()
     }
 }
@@ -47,6 +52,8 @@ impl Default for FlexSurface {
         Self {
             entity_to_stretch: Default::default(),
             window_nodes: Default::default(),
+            node_windows: Default::default(),
+            grid_items: Default::default(),
             stretch: Stretch::new(),
         }
     }
@@ -54,6 +61,7 @@ impl Default for FlexSurface {
 
 impl FlexSurface {
     pub fn upsert_node(&mut self, entity: Entity, style: &Style, scale_factor: f64) {
+        self.grid_items.remove(&entity);
         let mut added = false;
         let stretch = &mut self.stretch;
         let stretch_style = convert::from_style(scale_factor, style);
@@ -76,6 +84,7 @@ impl FlexSurface {
         calculated_size: CalculatedSize,
         scale_factor: f64,
     ) {
+        self.grid_items.remove(&entity);
         let stretch = &mut self.stretch;
         let stretch_style = convert::from_style(scale_factor, style);
         let measure = Box::new(move |constraints: stretch::geometry::Size<Number>| {
@@ -130,7 +139,10 @@ without UI components as a child of an entity with UI components, results may be
             .unwrap();
     }
 
+    /// Lays out the root nodes of `window` in the content drawn by its cameras, see
+    /// [`Window::render_size`].
     pub fn update_window(&mut self, window: &Window) {
+        let size = window.render_size() * window.render_scale_factor() as f32;
         let stretch = &mut self.stretch;
         let node = self.window_nodes.entry(window.id()).or_insert_with(|| {
             stretch
@@ -143,8 +155,8 @@ without UI components as a child of an entity with UI components, results may be
                 *node,
                 stretch::style::Style {
                     size: stretch::geometry::Size {
-                        width: stretch::style::Dimension::Points(window.physical_width() as f32),
-                        height: stretch::style::Dimension::Points(window.physical_height() as f32),
+                        width: stretch::style::Dimension::Points(size.x),
+                        height: stretch::style::Dimension::Points(size.y),
                     },
                     ..Default::default()
                 },
@@ -166,6 +178,22 @@ without UI components as a child of an entity with UI components, results may be
             .unwrap();
     }
 
+    /// Removes the layout nodes of `entities`, for UI nodes that were despawned or lost their
+    /// [`Node`].
+    pub fn remove_entities(&mut self, entities: impl IntoIterator<Item = Entity>) {
+        for entity in entities {
+            self.grid_items.remove(&entity);
+            if let Some(stretch_node) = self.entity_to_stretch.remove(&entity) {
+                self.stretch.remove(stretch_node);
+            }
+        }
+    }
+
+    /// The window the UI node `entity` was laid out in, see [`UiTargetWindow`].
+    pub fn node_window(&self, entity: Entity) -> Option<WindowId> {
+        self.node_windows.get(&entity).copied()
+    }
+
     pub fn compute_window_layouts(&mut self) {
         for window_node in self.window_nodes.values() {
             self.stretch
@@ -174,6 +202,83 @@ without UI components as a child of an entity with UI components, results may be
         }
     }
 
+    /// Lays out the children of a [`GridContainer`] from the last computed layout of the container.
+    /// Returns `true` if any child moved to a different cell, in which case the layouts need to be
+    /// computed again.
+    pub fn update_grid(
+        &mut self,
+        entity: Entity,
+        grid: &GridContainer,
+        style: &Style,
+        items: &[(Entity, &Style, GridPlacement, Vec2)],
+        scale_factor: f64,
+    ) -> bool {
+        let container_size = match self.get_layout(entity) {
+            Ok(layout) => Vec2::new(layout.size.width, layout.size.height),
+            Err(_) => return false,
+        };
+        let resolve = |val: Val| match val {
+            Val::Px(value) => (scale_factor * value as f64) as f32,
+            Val::Percent(value) => container_size.x * value / 100.0,
+            Val::Auto | Val::Undefined => 0.0,
+        };
+        let insets = [style.padding, style.border];
+        let left = insets.iter().map(|rect| resolve(rect.left)).sum::<f32>();
+        let right = insets.iter().map(|rect| resolve(rect.right)).sum::<f32>();
+        let top = insets.iter().map(|rect| resolve(rect.top)).sum::<f32>();
+        let bottom = insets.iter().map(|rect| resolve(rect.bottom)).sum::<f32>();
+        let content_size = (container_size - Vec2::new(left + right, top + bottom)).max(Vec2::ZERO);
+
+        let placements = items
+            .iter()
+            .map(|(_, item_style, placement, calculated_size)| {
+                let size = Vec2::new(
+                    match item_style.size.width {
+                        Val::Px(width) => (scale_factor * width as f64) as f32,
+                        _ => calculated_size.x * scale_factor as f32,
+                    },
+                    match item_style.size.height {
+                        Val::Px(height) => (scale_factor * height as f64) as f32,
+                        _ => calculated_size.y * scale_factor as f32,
+                    },
+                );
+                (*placement, size)
+            })
+            .collect::<Vec<_>>();
+        let cells = grid.compute_cells(content_size, &placements, scale_factor as f32);
+
+        let mut changed = false;
+        for ((item, item_style, _, _), cell) in items.iter().zip(cells) {
+            if self.grid_items.get(item) == Some(&cell) {
+                continue;
+            }
+            let stretch_node = match self.entity_to_stretch.get(item) {
+                Some(stretch_node) => *stretch_node,
+                None => continue,
+            };
+            let mut stretch_style = convert::from_style(scale_factor, item_style);
+            stretch_style.position_type = stretch::style::PositionType::Absolute;
+            // NOTE: stretch has a flipped y-axis, so rows are laid out from the bottom
+            stretch_style.position = stretch::geometry::Rect {
+                start: stretch::style::Dimension::Points(left + cell.offset.x),
+                end: stretch::style::Dimension::Undefined,
+                top: stretch::style::Dimension::Points(
+                    content_size.y - cell.offset.y - cell.size.y + bottom,
+                ),
+                bottom: stretch::style::Dimension::Undefined,
+            };
+            stretch_style.margin = Default::default();
+            stretch_style.size = stretch::geometry::Size {
+                width: stretch::style::Dimension::Points(cell.size.x),
+                height: stretch::style::Dimension::Points(cell.size.y),
+            };
+            self.stretch.set_style(stretch_node, stretch_style).unwrap();
+            self.grid_items.insert(*item, cell);
+            changed = true;
+        }
+        changed
+    }
+
     pub fn get_layout(&self, entity: Entity) -> Result<&stretch::result::Layout, FlexError> {
         if let Some(stretch_node) = self.entity_to_stretch.get(&entity) {
             self.stretch
@@ -195,12 +300,13 @@ pub enum FlexError {
     StretchError(stretch::Error),
 }
 
+const MAX_GRID_LAYOUT_PASSES: usize = 4;
+
 #[allow(clippy::too_many_arguments, clippy::type_complexity)]
 pub fn flex_node_system(
     windows: Res<Windows>,
-    mut scale_factor_events: EventReader<WindowScaleFactorChanged>,
     mut flex_surface: ResMut<FlexSurface>,
-    root_node_query: Query<Entity, (With<Node>, Without<Parent>)>,
+    root_node_query: Query<(Entity, Option<&UiTargetWindow>), (With<Node>, Without<Parent>)>,
     node_query: Query<(Entity, &Style, Option<&CalculatedSize>), (With<Node>, Changed<Style>)>,
     full_node_query: Query<(Entity, &Style, Option<&CalculatedSize>), With<Node>>,
     changed_size_query: Query<
@@ -208,33 +314,54 @@ pub fn flex_node_system(
         (With<Node>, Changed<CalculatedSize>),
     >,
     children_query: Query<(Entity, &Children), (With<Node>, Changed<Children>)>,
+    all_children_query: Query<&Children, With<Node>>,
+    grid_query: Query<(Entity, &GridContainer, &Style, &Children), With<Node>>,
+    grid_item_query: Query<(&Style, Option<&GridPlacement>, Option<&CalculatedSize>), With<Node>>,
     mut node_transform_query: Query<(Entity, &mut Node, &mut Transform, Option<&Parent>)>,
+    removed_nodes: RemovedComponents<Node>,
+    mut last_scale_factors: Local<HashMap<WindowId, f64>>,
 ) {
     // update window root nodes
     for window in windows.iter() {
         flex_surface.update_window(window);
     }
 
-    // assume one window for time being...
-    let logical_to_physical_factor = if let Some(primary_window) = windows.get_primary() {
-        primary_window.scale_factor()
-    } else {
-        1.
+    // find the window of each node from the window targeted by its root
+    let mut node_windows = std::mem::take(&mut flex_surface.node_windows);
+    let windows_changed = update_node_windows(
+        &mut node_windows,
+        &root_node_query,
+        &all_children_query,
+        windows
+            .get_primary()
+            .map_or(WindowId::primary(), Window::id),
+    );
+    let scale_factors = windows
+        .iter()
+        .map(|window| (window.id(), window.render_scale_factor()))
+        .collect::<HashMap<_, _>>();
+    // also changed by the render policy of the window
+    let scale_factors_changed = *last_scale_factors != scale_factors;
+    if scale_factors_changed {
+        *last_scale_factors = scale_factors.clone();
+    }
+    let scale_factor = |entity: Entity| {
+        node_windows
+            .get(&entity)
+            .and_then(|window_id| scale_factors.get(window_id))
+            .copied()
+            .unwrap_or(1.)
     };
 
-    if scale_factor_events.iter().next_back().is_some() {
-        update_changed(
-            &mut *flex_surface,
-            logical_to_physical_factor,
-            full_node_query,
-        );
+    if windows_changed || scale_factors_changed {
+        update_changed(&mut *flex_surface, scale_factor, full_node_query);
     } else {
-        update_changed(&mut *flex_surface, logical_to_physical_factor, node_query);
+        update_changed(&mut *flex_surface, scale_factor, node_query);
     }
 
     fn update_changed<F: WorldQuery>(
         flex_surface: &mut FlexSurface,
-        scaling_factor: f64,
+        scale_factor: impl Fn(Entity) -> f64,
         query: Query<(Entity, &Style, Option<&CalculatedSize>), F>,
     ) where
         F::Fetch: FilterFetch,
@@ -243,22 +370,27 @@ pub fn flex_node_system(
         for (entity, style, calculated_size) in query.iter() {
             // TODO: remove node from old hierarchy if its root has changed
             if let Some(calculated_size) = calculated_size {
-                flex_surface.upsert_leaf(entity, style, *calculated_size, scaling_factor);
+                flex_surface.upsert_leaf(entity, style, *calculated_size, scale_factor(entity));
             } else {
-                flex_surface.upsert_node(entity, style, scaling_factor);
+                flex_surface.upsert_node(entity, style, scale_factor(entity));
             }
         }
     }
 
     for (entity, style, calculated_size) in changed_size_query.iter() {
-        flex_surface.upsert_leaf(entity, style, *calculated_size, logical_to_physical_factor);
+        flex_surface.upsert_leaf(entity, style, *calculated_size, scale_factor(entity));
     }
 
-    // TODO: handle removed nodes
+    // clean up removed nodes
+    flex_surface.remove_entities(removed_nodes.iter());
 
-    // update window children (for now assuming all Nodes live in the primary window)
-    if let Some(primary_window) = windows.get_primary() {
-        flex_surface.set_window_children(primary_window.id(), root_node_query.iter());
+    // update window children
+    for window in windows.iter() {
+        let roots = root_node_query
+            .iter()
+            .map(|(entity, _)| entity)
+            .filter(|entity| node_windows.get(entity) == Some(&window.id()));
+        flex_surface.set_window_children(window.id(), roots);
     }
 
     // update children
@@ -269,12 +401,41 @@ pub fn flex_node_system(
     // compute layouts
     flex_surface.compute_window_layouts();
 
-    let physical_to_logical_factor = 1. / logical_to_physical_factor;
-
-    let to_logical = |v| (physical_to_logical_factor * v as f64) as f32;
+    // lay out grid items in the cells of their container, and compute the layouts again if any of
+    // them moved. Each extra pass settles one more level of nested grids.
+    for _ in 0..MAX_GRID_LAYOUT_PASSES {
+        let mut changed = false;
+        for (entity, grid, style, children) in grid_query.iter() {
+            let items = children
+                .iter()
+                .filter_map(|child| {
+                    let (style, placement, calculated_size) = grid_item_query.get(*child).ok()?;
+                    let natural_size = calculated_size
+                        .map(|calculated_size| {
+                            Vec2::new(calculated_size.size.width, calculated_size.size.height)
+                        })
+                        .unwrap_or(Vec2::ZERO);
+                    Some((
+                        *child,
+                        style,
+                        placement.copied().unwrap_or_default(),
+                        natural_size,
+                    ))
+                })
+                .collect::<Vec<_>>();
+            changed |= flex_surface.update_grid(entity, grid, style, &items, scale_factor(entity));
+        }
+        if !changed {
+            break;
+        }
+        flex_surface.compute_window_layouts();
+    }
 
     // PERF: try doing this incrementally
     for (entity, mut node, mut transform, parent) in node_transform_query.iter_mut() {
+        let physical_to_logical_factor = 1. / scale_factor(entity);
+        let to_logical = |v| (physical_to_logical_factor * v as f64) as f32;
+
         let layout = flex_surface.get_layout(entity).unwrap();
         node.size = Vec2::new(
             to_logical(layout.size.width),
@@ -290,4 +451,205 @@ pub fn flex_node_system(
             }
         }
     }
+
+    flex_surface.node_windows = node_windows;
+}
+
+/// Assigns each node to the window targeted by its root, or `default_window`. Returns `true` if
+/// the window of any node changed.
+#[allow(clippy::type_complexity)]
+fn update_node_windows(
+    node_windows: &mut HashMap<Entity, WindowId>,
+    root_node_query: &Query<(Entity, Option<&UiTargetWindow>), (With<Node>, Without<Parent>)>,
+    children_query: &Query<&Children, With<Node>>,
+    default_window: WindowId,
+) -> bool {
+    let previous_len = node_windows.len();
+    let mut changed = false;
+    let mut visited = 0;
+    let mut stack = Vec::new();
+    for (root, target_window) in root_node_query.iter() {
+        let window_id = target_window.map_or(default_window, |target_window| target_window.0);
+        stack.push(root);
+        while let Some(entity) = stack.pop() {
+            visited += 1;
+            changed |= node_windows.insert(entity, window_id) != Some(window_id);
+            if let Ok(children) = children_query.get(entity) {
+                stack.extend(children.iter().copied());
+            }
+        }
+    }
+    if visited < previous_len {
+        // some nodes were removed or moved out of a UI hierarchy
+        node_windows.clear();
+        return update_node_windows(
+            node_windows,
+            root_node_query,
+            children_query,
+            default_window,
+        );
+    }
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{flex_node_system, FlexSurface};
+    use crate::{
+        update::ui_window_visibility_system, GridContainer, Node, Style, UiSystem, UiTargetWindow,
+    };
+    use bevy_app::{App, CoreStage, Events};
+    use bevy_core::{CorePlugin, FloatOrd};
+    use bevy_ecs::{entity::Entity, schedule::ParallelSystemDescriptorCoercion};
+    use bevy_render::camera::{Camera, VisibleEntities, VisibleEntity};
+    use bevy_transform::{
+        components::Transform,
+        hierarchy::{despawn_with_children_recursive, BuildWorldChildren},
+    };
+    use bevy_window::{CreateWindow, HeadlessWindowPlugin, WindowId, WindowPlugin};
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugin(CorePlugin)
+            .add_plugin(WindowPlugin::default())
+            .add_plugin(HeadlessWindowPlugin)
+            .init_resource::<FlexSurface>()
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                flex_node_system.label(UiSystem::Flex),
+            );
+        app
+    }
+
+    fn spawn_node(app: &mut App) -> Entity {
+        app.world
+            .spawn()
+            .insert_bundle((Node::default(), Style::default(), Transform::default()))
+            .id()
+    }
+
+    #[test]
+    fn despawned_grid_items_are_removed() {
+        let mut app = app();
+        let grid = spawn_node(&mut app);
+        app.world.entity_mut(grid).insert(GridContainer::default());
+        let item = spawn_node(&mut app);
+        app.world.entity_mut(grid).push_children(&[item]);
+        app.update();
+        let flex_surface = app.world.get_resource::<FlexSurface>().unwrap();
+        assert!(flex_surface.grid_items.contains_key(&item));
+        assert!(flex_surface.entity_to_stretch.contains_key(&item));
+
+        despawn_with_children_recursive(&mut app.world, item);
+        app.update();
+        let flex_surface = app.world.get_resource::<FlexSurface>().unwrap();
+        assert!(!flex_surface.grid_items.contains_key(&item));
+        assert!(!flex_surface.entity_to_stretch.contains_key(&item));
+        assert!(flex_surface.get_layout(grid).is_ok());
+    }
+
+    fn spawn_tree(app: &mut App) -> (Entity, Entity) {
+        let root = spawn_node(app);
+        let child = spawn_node(app);
+        app.world.entity_mut(root).push_children(&[child]);
+        (root, child)
+    }
+
+    fn create_window(app: &mut App) -> WindowId {
+        let id = WindowId::new();
+        app.world
+            .get_resource_mut::<Events<CreateWindow>>()
+            .unwrap()
+            .send(CreateWindow {
+                id,
+                descriptor: Default::default(),
+            });
+        id
+    }
+
+    #[test]
+    fn nodes_are_laid_out_in_the_window_of_their_root() {
+        let mut app = app();
+        let second_window = create_window(&mut app);
+        let (_, primary_child) = spawn_tree(&mut app);
+        let (second_root, second_child) = spawn_tree(&mut app);
+        app.world
+            .entity_mut(second_root)
+            .insert(UiTargetWindow(second_window));
+        app.update();
+        let flex_surface = app.world.get_resource::<FlexSurface>().unwrap();
+        assert_eq!(
+            flex_surface.node_window(primary_child),
+            Some(WindowId::primary())
+        );
+        assert_eq!(flex_surface.node_window(second_root), Some(second_window));
+        assert_eq!(flex_surface.node_window(second_child), Some(second_window));
+
+        // moving the child to the other tree moves it to the other window
+        app.world
+            .entity_mut(second_root)
+            .push_children(&[primary_child]);
+        app.update();
+        let flex_surface = app.world.get_resource::<FlexSurface>().unwrap();
+        assert_eq!(flex_surface.node_window(primary_child), Some(second_window));
+
+        app.world.entity_mut(second_root).remove::<UiTargetWindow>();
+        app.update();
+        let flex_surface = app.world.get_resource::<FlexSurface>().unwrap();
+        assert_eq!(
+            flex_surface.node_window(second_child),
+            Some(WindowId::primary())
+        );
+    }
+
+    #[test]
+    fn cameras_only_see_the_nodes_of_their_window() {
+        let mut app = app();
+        app.add_system_to_stage(
+            CoreStage::PostUpdate,
+            ui_window_visibility_system.after(UiSystem::Flex),
+        );
+        let second_window = create_window(&mut app);
+        let (_, primary_child) = spawn_tree(&mut app);
+        let (second_root, second_child) = spawn_tree(&mut app);
+        app.world
+            .entity_mut(second_root)
+            .insert(UiTargetWindow(second_window));
+        let other = app.world.spawn().id();
+        let mut spawn_camera = |window| {
+            let visible_entities = VisibleEntities {
+                value: [primary_child, second_child, other]
+                    .iter()
+                    .map(|&entity| VisibleEntity {
+                        entity,
+                        order: FloatOrd(0.0),
+                    })
+                    .collect(),
+            };
+            app.world
+                .spawn()
+                .insert_bundle((
+                    Camera {
+                        window,
+                        ..Default::default()
+                    },
+                    visible_entities,
+                ))
+                .id()
+        };
+        let primary_camera = spawn_camera(WindowId::primary());
+        let second_camera = spawn_camera(second_window);
+        app.update();
+
+        let visible = |camera| {
+            app.world
+                .get::<VisibleEntities>(camera)
+                .unwrap()
+                .iter()
+                .map(|visible_entity| visible_entity.entity)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(visible(primary_camera), vec![primary_child, other]);
+        assert_eq!(visible(second_camera), vec![second_child, other]);
+    }
 }
@@ -1,12 +1,21 @@
-use crate::Node;
+use crate::{FlexSurface, Node};
+use bevy_app::EventWriter;
 use bevy_core::FloatOrd;
 use bevy_ecs::{
     entity::Entity,
-    system::{Local, Query, Res},
+    query::{ChangeTrackers, Changed, With, Without},
+    system::{Local, Query, Res, ResMut},
 };
-use bevy_input::{mouse::MouseButton, touch::Touches, Input};
+use bevy_input::{
+    gamepad::{GamepadButton, GamepadButtonType},
+    keyboard::KeyCode,
+    mouse::MouseButton,
+    touch::Touches,
+    Input,
+};
+use bevy_math::Vec2;
 use bevy_transform::components::GlobalTransform;
-use bevy_window::Windows;
+use bevy_window::{WindowId, Windows};
 use smallvec::SmallVec;
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
@@ -34,6 +43,50 @@ impl Default for FocusPolicy {
     }
 }
 
+/// Marks a UI node as disabled: it does not react to the mouse or keyboard, and its
+/// [`Interaction`] stays [`Interaction::None`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Disabled;
+
+/// Marks a UI node that can receive keyboard focus.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Focusable;
+
+/// The UI node that currently has keyboard focus, if any.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FocusedEntity(pub Option<Entity>);
+
+/// Sent when the [`Interaction`] of a UI node changes, with the position of the cursor at that
+/// time, for widgets that depend on where they are clicked such as sliders or color pickers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InteractionChanged {
+    pub entity: Entity,
+    pub interaction: Interaction,
+    /// The window of the node, see [`UiTargetWindow`](crate::UiTargetWindow).
+    pub window: WindowId,
+    /// The position of the cursor in the window, in the coordinates of the UI, see
+    /// [`node_cursor_position`], or `None` if the cursor isn't over the content of the window.
+    pub cursor_position: Option<Vec2>,
+    /// The position of the cursor relative to the bottom left corner of the node.
+    pub node_cursor_position: Option<Vec2>,
+}
+
+/// The position of the cursor in the window of the UI node `entity`, see
+/// [`UiTargetWindow`](crate::UiTargetWindow). As the UI is laid out in the content drawn by the
+/// cameras of the window, it is mapped to the content with
+/// [`Window::render_cursor_position`](bevy_window::Window::render_cursor_position).
+pub fn node_cursor_position(
+    windows: &Windows,
+    flex_surface: &FlexSurface,
+    entity: Entity,
+) -> Option<Vec2> {
+    let window = match flex_surface.node_window(entity) {
+        Some(window_id) => windows.get(window_id),
+        None => windows.get_primary(),
+    };
+    window.and_then(|window| window.render_cursor_position())
+}
+
 #[derive(Default)]
 pub struct State {
     entities_to_reset: SmallVec<[Entity; 1]>,
@@ -43,6 +96,7 @@ pub struct State {
 pub fn ui_focus_system(
     mut state: Local<State>,
     windows: Res<Windows>,
+    flex_surface: Res<FlexSurface>,
     mouse_button_input: Res<Input<MouseButton>>,
     touches_input: Res<Touches>,
     mut node_query: Query<(
@@ -51,16 +105,15 @@ pub fn ui_focus_system(
         &GlobalTransform,
         Option<&mut Interaction>,
         Option<&FocusPolicy>,
+        Option<&Disabled>,
     )>,
 ) {
-    let cursor_position = if let Some(cursor_position) = windows
-        .get_primary()
-        .and_then(|window| window.cursor_position())
+    if windows
+        .iter()
+        .all(|window| window.cursor_position().is_none())
     {
-        cursor_position
-    } else {
         return;
-    };
+    }
 
     // reset entities that were both clicked and released in the last frame
     for entity in state.entities_to_reset.drain(..) {
@@ -69,10 +122,11 @@ pub fn ui_focus_system(
         }
     }
 
-    let mouse_released =
-        mouse_button_input.just_released(MouseButton::Left) || touches_input.just_released(0);
+    let mouse_released = mouse_button_input.just_released(MouseButton::Left)
+        || touches_input.iter_just_released().next().is_some();
     if mouse_released {
-        for (_entity, _node, _global_transform, interaction, _focus_policy) in node_query.iter_mut()
+        for (_entity, _node, _global_transform, interaction, _focus_policy, _disabled) in
+            node_query.iter_mut()
         {
             if let Some(mut interaction) = interaction {
                 if *interaction == Interaction::Clicked {
@@ -82,23 +136,34 @@ pub fn ui_focus_system(
         }
     }
 
-    let mouse_clicked =
-        mouse_button_input.just_pressed(MouseButton::Left) || touches_input.just_released(0);
+    let mouse_clicked = mouse_button_input.just_pressed(MouseButton::Left)
+        || touches_input.iter_just_released().next().is_some();
 
     let mut moused_over_z_sorted_nodes = node_query
         .iter_mut()
         .filter_map(
-            |(entity, node, global_transform, interaction, focus_policy)| {
+            |(entity, node, global_transform, mut interaction, focus_policy, disabled)| {
+                // disabled nodes still block the nodes below them, but never become interacted
+                if disabled.is_some() {
+                    if let Some(mut interaction) = interaction.take() {
+                        if *interaction != Interaction::None {
+                            *interaction = Interaction::None;
+                        }
+                    }
+                }
                 let position = global_transform.translation;
                 let ui_position = position.truncate();
                 let extents = node.size / 2.0;
                 let min = ui_position - extents;
                 let max = ui_position + extents;
-                // if the current cursor position is within the bounds of the node, consider it for
-                // clicking
-                if (min.x..max.x).contains(&cursor_position.x)
-                    && (min.y..max.y).contains(&cursor_position.y)
-                {
+                // if the cursor position in the window of the node is within the bounds of the
+                // node, consider it for clicking
+                let cursor_position = node_cursor_position(&windows, &flex_surface, entity);
+                if cursor_position.is_some_and(|cursor_position| {
+                    (min.x..max.x).contains(&cursor_position.x)
+                        && (min.y..max.y).contains(&cursor_position.y)
+                        && node.clip_contains(cursor_position)
+                }) {
                     Some((entity, focus_policy, interaction, FloatOrd(position.z)))
                 } else {
                     if let Some(mut interaction) = interaction {
@@ -149,3 +214,236 @@ pub fn ui_focus_system(
         }
     }
 }
+
+/// Gives keyboard focus to [`Focusable`] nodes when they are clicked, and removes it when the
+/// mouse is pressed anywhere else.
+#[allow(clippy::type_complexity)]
+pub fn focus_on_click_system(
+    mut focused_entity: ResMut<FocusedEntity>,
+    mouse_button_input: Res<Input<MouseButton>>,
+    touches_input: Res<Touches>,
+    query: Query<(Entity, &Interaction), (Changed<Interaction>, With<Focusable>)>,
+) {
+    let clicked = query
+        .iter()
+        .find(|(_, interaction)| **interaction == Interaction::Clicked)
+        .map(|(entity, _)| entity);
+    if clicked.is_some() {
+        focused_entity.0 = clicked;
+    } else if mouse_button_input.just_pressed(MouseButton::Left)
+        || touches_input.iter_just_released().next().is_some()
+    {
+        focused_entity.0 = None;
+    }
+}
+
+const ACTIVATION_KEYS: [KeyCode; 3] = [KeyCode::Return, KeyCode::NumpadEnter, KeyCode::Space];
+
+/// Activates the focused node when Enter or Space, or the South button of a gamepad, is pressed,
+/// by setting its [`Interaction`] to [`Interaction::Clicked`] until the key is released, exactly
+/// like a mouse click would.
+pub fn keyboard_activation_system(
+    mut activated: Local<Option<Entity>>,
+    focused_entity: Res<FocusedEntity>,
+    keyboard_input: Res<Input<KeyCode>>,
+    gamepad_input: Res<Input<GamepadButton>>,
+    mut query: Query<&mut Interaction, (With<Focusable>, Without<Disabled>)>,
+) {
+    let is_south = |button: &GamepadButton| button.1 == GamepadButtonType::South;
+    if let Some(entity) = *activated {
+        let released = ACTIVATION_KEYS
+            .iter()
+            .all(|key| !keyboard_input.pressed(*key))
+            && !gamepad_input.get_pressed().any(is_south);
+        if released || focused_entity.0 != Some(entity) {
+            if let Ok(mut interaction) = query.get_mut(entity) {
+                if *interaction == Interaction::Clicked {
+                    *interaction = Interaction::None;
+                }
+            }
+            *activated = None;
+        }
+    }
+
+    if activated.is_none()
+        && (ACTIVATION_KEYS
+            .iter()
+            .any(|key| keyboard_input.just_pressed(*key))
+            || gamepad_input.get_just_pressed().any(is_south))
+    {
+        if let Some(entity) = focused_entity.0 {
+            if let Ok(mut interaction) = query.get_mut(entity) {
+                *interaction = Interaction::Clicked;
+                *activated = Some(entity);
+            }
+        }
+    }
+}
+
+/// Sends an [`InteractionChanged`] event for each UI node whose [`Interaction`] changed. Adding an
+/// [`Interaction`] to a node doesn't count as a change.
+#[allow(clippy::type_complexity)]
+pub fn interaction_changed_system(
+    windows: Res<Windows>,
+    flex_surface: Res<FlexSurface>,
+    mut interaction_changed_events: EventWriter<InteractionChanged>,
+    query: Query<
+        (
+            Entity,
+            &Interaction,
+            ChangeTrackers<Interaction>,
+            &Node,
+            &GlobalTransform,
+        ),
+        Changed<Interaction>,
+    >,
+) {
+    for (entity, interaction, interaction_tracker, node, global_transform) in query.iter() {
+        if interaction_tracker.is_added() {
+            continue;
+        }
+        let window = flex_surface
+            .node_window(entity)
+            .or_else(|| windows.get_primary().map(|window| window.id()))
+            .unwrap_or_else(WindowId::primary);
+        let cursor_position = windows
+            .get(window)
+            .and_then(|window| window.render_cursor_position());
+        let node_min = global_transform.translation.truncate() - node.size / 2.0;
+        interaction_changed_events.send(InteractionChanged {
+            entity,
+            interaction: *interaction,
+            window,
+            cursor_position,
+            node_cursor_position: cursor_position.map(|cursor_position| cursor_position - node_min),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{interaction_changed_system, ui_focus_system, Interaction, InteractionChanged};
+    use crate::{flex_node_system, FlexSurface, Node, Size, Style, Val};
+    use bevy_app::{App, CoreStage, Events, ManualEventReader};
+    use bevy_core::CorePlugin;
+    use bevy_ecs::schedule::ParallelSystemDescriptorCoercion;
+    use bevy_input::{mouse::MouseButton, touch::Touches, Input};
+    use bevy_math::Vec2;
+    use bevy_transform::{
+        components::{GlobalTransform, Transform},
+        TransformPlugin, TransformSystem,
+    };
+    use bevy_window::{
+        HeadlessWindowPlugin, WindowDescriptor, WindowPlugin, WindowRenderPolicy, Windows,
+    };
+
+    #[test]
+    fn hit_test_in_letterboxed_content() {
+        let mut app = App::new();
+        app.insert_resource(WindowDescriptor {
+            width: 1000.0,
+            height: 700.0,
+            render_policy: WindowRenderPolicy::Letterbox {
+                width: 320.0,
+                height: 180.0,
+            },
+            ..Default::default()
+        })
+        .add_plugin(CorePlugin)
+        .add_plugin(TransformPlugin)
+        .add_plugin(WindowPlugin::default())
+        .add_plugin(HeadlessWindowPlugin)
+        .init_resource::<FlexSurface>()
+        .init_resource::<Input<MouseButton>>()
+        .init_resource::<Touches>()
+        .add_system(ui_focus_system)
+        .add_system_to_stage(
+            CoreStage::PostUpdate,
+            flex_node_system.before(TransformSystem::TransformPropagate),
+        );
+        // the node covers the bottom left quarter of the content, which is scaled by 3.125 and
+        // drawn from 69 to 631 physical pixels from the bottom of the window
+        let entity = app
+            .world
+            .spawn()
+            .insert_bundle((
+                Node::default(),
+                Style {
+                    size: Size::new(Val::Px(160.0), Val::Px(90.0)),
+                    ..Default::default()
+                },
+                Transform::default(),
+                GlobalTransform::default(),
+                Interaction::default(),
+            ))
+            .id();
+        app.update();
+
+        let mut interaction_at = |cursor_position: Vec2| {
+            app.world
+                .get_resource_mut::<Windows>()
+                .unwrap()
+                .get_primary_mut()
+                .unwrap()
+                .update_cursor_position_from_backend(Some(cursor_position));
+            app.update();
+            *app.world.get::<Interaction>(entity).unwrap()
+        };
+        assert_eq!(
+            interaction_at(Vec2::new(250.0, 210.0)),
+            Interaction::Hovered
+        );
+        // over the bar below the content
+        assert_eq!(interaction_at(Vec2::new(250.0, 40.0)), Interaction::None);
+        assert_eq!(
+            interaction_at(Vec2::new(490.0, 330.0)),
+            Interaction::Hovered
+        );
+        // in the content, right of the node
+        assert_eq!(interaction_at(Vec2::new(600.0, 210.0)), Interaction::None);
+    }
+
+    #[test]
+    fn interaction_changed_is_not_sent_on_insert() {
+        let mut app = App::new();
+        app.add_plugin(CorePlugin)
+            .add_plugin(WindowPlugin::default())
+            .add_plugin(HeadlessWindowPlugin)
+            .init_resource::<FlexSurface>()
+            .add_event::<InteractionChanged>()
+            .add_system_to_stage(CoreStage::PostUpdate, interaction_changed_system);
+        let mut reader = ManualEventReader::<InteractionChanged>::default();
+        let mut read_interactions = |app: &App| {
+            let events = app
+                .world
+                .get_resource::<Events<InteractionChanged>>()
+                .unwrap();
+            reader
+                .iter(events)
+                .map(|event| (event.entity, event.interaction))
+                .collect::<Vec<_>>()
+        };
+
+        let entity = app
+            .world
+            .spawn()
+            .insert_bundle((
+                Node::default(),
+                GlobalTransform::default(),
+                Interaction::default(),
+            ))
+            .id();
+        app.update();
+        assert_eq!(read_interactions(&app), vec![]);
+
+        *app.world.get_mut::<Interaction>(entity).unwrap() = Interaction::Clicked;
+        app.update();
+        assert_eq!(
+            read_interactions(&app),
+            vec![(entity, Interaction::Clicked)]
+        );
+
+        app.update();
+        assert_eq!(read_interactions(&app), vec![]);
+    }
+}
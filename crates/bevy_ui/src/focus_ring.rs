@@ -0,0 +1,160 @@
+use crate::{entity::NodeBundle, FocusPolicy, FocusedEntity, Node, PositionType, Style, UiTheme};
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::{
+    entity::Entity,
+    query::Without,
+    system::{Commands, Local, Query, Res, ResMut},
+};
+use bevy_math::{Vec2, Vec3};
+use bevy_render::draw::Visible;
+use bevy_sprite::ColorMaterial;
+use bevy_transform::components::{Parent, Transform};
+
+/// Configures the focus ring drawn around the node that has keyboard focus. Its look comes from
+/// [`UiTheme::focus_ring`](crate::UiTheme::focus_ring).
+#[derive(Debug, Clone)]
+pub struct FocusRingConfig {
+    /// Whether the focus ring is drawn. Applications that are only used with a mouse may want to
+    /// disable it.
+    pub enabled: bool,
+}
+
+impl Default for FocusRingConfig {
+    fn default() -> Self {
+        FocusRingConfig { enabled: true }
+    }
+}
+
+/// One of the four nodes the focus ring is made of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FocusRingEdge {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+impl FocusRingEdge {
+    const ALL: [FocusRingEdge; 4] = [
+        FocusRingEdge::Top,
+        FocusRingEdge::Bottom,
+        FocusRingEdge::Left,
+        FocusRingEdge::Right,
+    ];
+}
+
+/// Draws the focus ring around the [`FocusedEntity`].
+///
+/// The ring is placed after the layout and before the transforms are propagated, so that it
+/// follows the focused node in the same frame, and is drawn just above that node.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn focus_ring_system(
+    mut commands: Commands,
+    mut spawned: Local<bool>,
+    config: Res<FocusRingConfig>,
+    theme: Res<UiTheme>,
+    focused_entity: Res<FocusedEntity>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    node_query: Query<(Option<&Node>, &Transform, Option<&Parent>), Without<FocusRingEdge>>,
+    mut edge_query: Query<(
+        &FocusRingEdge,
+        &mut Node,
+        &mut Transform,
+        &mut Visible,
+        &Handle<ColorMaterial>,
+    )>,
+) {
+    let ring_theme = &theme.focus_ring;
+    if !*spawned {
+        let material = materials.add(ring_theme.color.into());
+        for edge in FocusRingEdge::ALL.iter() {
+            let mut bundle = NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    ..Default::default()
+                },
+                material: material.clone(),
+                ..Default::default()
+            };
+            bundle.visible.is_visible = false;
+            commands
+                .spawn_bundle(bundle)
+                .insert_bundle((*edge, FocusPolicy::Pass));
+        }
+        *spawned = true;
+        return;
+    }
+
+    let focused = focused_entity
+        .0
+        .filter(|_| config.enabled)
+        .and_then(|entity| {
+            let node = node_query.get(entity).ok()?.0?;
+            Some((node, global_translation(&node_query, entity)))
+        });
+    for (edge, mut node, mut transform, mut visible, material) in edge_query.iter_mut() {
+        if theme.is_changed() {
+            if let Some(material) = materials.get_mut(material) {
+                material.color = ring_theme.color;
+            }
+        }
+        let (focused_node, center) = match focused {
+            Some(focused) => focused,
+            None => {
+                if visible.is_visible {
+                    visible.is_visible = false;
+                }
+                continue;
+            }
+        };
+        if !visible.is_visible {
+            visible.is_visible = true;
+        }
+
+        let half_size = focused_node.size / 2.0;
+        let inner = ring_theme.offset;
+        let width = ring_theme.width;
+        let (offset, size) = match edge {
+            FocusRingEdge::Top => (
+                Vec2::new(0.0, half_size.y + inner + width / 2.0),
+                Vec2::new(focused_node.size.x + 2.0 * (inner + width), width),
+            ),
+            FocusRingEdge::Bottom => (
+                Vec2::new(0.0, -(half_size.y + inner + width / 2.0)),
+                Vec2::new(focused_node.size.x + 2.0 * (inner + width), width),
+            ),
+            FocusRingEdge::Left => (
+                Vec2::new(-(half_size.x + inner + width / 2.0), 0.0),
+                Vec2::new(width, focused_node.size.y + 2.0 * inner),
+            ),
+            FocusRingEdge::Right => (
+                Vec2::new(half_size.x + inner + width / 2.0, 0.0),
+                Vec2::new(width, focused_node.size.y + 2.0 * inner),
+            ),
+        };
+        node.size = size;
+        transform.translation = Vec3::new(
+            center.x + offset.x,
+            center.y + offset.y,
+            center.z + crate::update::UI_Z_STEP / 2.0,
+        );
+    }
+}
+
+/// The translation `entity` will have once the transforms are propagated. UI nodes are only
+/// translated, so this is the sum of the translations of the entity and its ancestors.
+#[allow(clippy::type_complexity)]
+fn global_translation(
+    node_query: &Query<(Option<&Node>, &Transform, Option<&Parent>), Without<FocusRingEdge>>,
+    mut entity: Entity,
+) -> Vec3 {
+    let mut translation = Vec3::ZERO;
+    while let Ok((_, transform, parent)) = node_query.get(entity) {
+        translation += transform.translation;
+        match parent {
+            Some(parent) => entity = parent.0,
+            None => break,
+        }
+    }
+    translation
+}
@@ -0,0 +1,342 @@
+use bevy_ecs::reflect::ReflectComponent;
+use bevy_math::Vec2;
+use bevy_reflect::{Reflect, ReflectDeserialize};
+use serde::{Deserialize, Serialize};
+
+/// The size of a row or a column of a [`GridContainer`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Reflect)]
+#[reflect_value(PartialEq, Serialize, Deserialize)]
+pub enum GridTrack {
+    /// A fixed size in logical pixels.
+    Px(f32),
+    /// A percentage of the size of the container's content box.
+    Percent(f32),
+    /// A share of the space left over by the other tracks, like the `fr` unit of CSS grids.
+    Fraction(f32),
+    /// The size of the largest item placed in this track, taken from its `Style::size` when it
+    /// is given in pixels, or from its `CalculatedSize`. Items spanning several tracks are not
+    /// taken into account.
+    Auto,
+}
+
+impl Default for GridTrack {
+    fn default() -> Self {
+        GridTrack::Auto
+    }
+}
+
+/// Lays out the children of a UI node in a grid of rows and columns, instead of the default
+/// flexbox layout.
+///
+/// Children are placed in the cells given by their [`GridPlacement`], or in the first free cell
+/// (row by row) when they have none. Rows are added as needed to fit all children. Every child
+/// fills its cell: its position, margins and size are ignored.
+#[derive(Debug, Clone, Default, Reflect)]
+#[reflect(Component)]
+pub struct GridContainer {
+    pub columns: Vec<GridTrack>,
+    pub rows: Vec<GridTrack>,
+    /// The size of the rows added when the children don't fit in `rows`.
+    pub auto_rows: GridTrack,
+    /// The space between columns, in logical pixels.
+    pub column_gap: f32,
+    /// The space between rows, in logical pixels.
+    pub row_gap: f32,
+}
+
+/// The cell a child of a [`GridContainer`] is placed in.
+///
+/// Rows and columns are numbered from zero, starting at the top left of the grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect)]
+#[reflect(Component, PartialEq)]
+pub struct GridPlacement {
+    /// The first column of the item, or `None` to place it automatically.
+    pub column: Option<usize>,
+    /// The first row of the item, or `None` to place it automatically.
+    pub row: Option<usize>,
+    pub column_span: usize,
+    pub row_span: usize,
+}
+
+impl Default for GridPlacement {
+    fn default() -> Self {
+        GridPlacement {
+            column: None,
+            row: None,
+            column_span: 1,
+            row_span: 1,
+        }
+    }
+}
+
+impl GridPlacement {
+    pub fn cell(column: usize, row: usize) -> Self {
+        GridPlacement {
+            column: Some(column),
+            row: Some(row),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_span(mut self, column_span: usize, row_span: usize) -> Self {
+        self.column_span = column_span;
+        self.row_span = row_span;
+        self
+    }
+}
+
+/// The area of the grid an item was placed in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GridCell {
+    /// The offset of the cell from the top left of the container's content box.
+    pub offset: Vec2,
+    pub size: Vec2,
+}
+
+impl GridContainer {
+    /// Places the given items, described by their placement and their natural size, in the grid
+    /// and returns the cell of each of them.
+    ///
+    /// Pixel sizes of the tracks and gaps are multiplied by `scale_factor`, so `content_size` and
+    /// the item sizes must be given in the same (logical or physical) unit as the result.
+    pub fn compute_cells(
+        &self,
+        content_size: Vec2,
+        items: &[(GridPlacement, Vec2)],
+        scale_factor: f32,
+    ) -> Vec<GridCell> {
+        let column_count = self.columns.len().max(1);
+        let areas = self.place_items(column_count, items);
+
+        let row_count = areas
+            .iter()
+            .map(|(_, row, _, row_span)| row + row_span)
+            .max()
+            .unwrap_or(0)
+            .max(self.rows.len());
+        let columns = (0..column_count)
+            .map(|i| {
+                self.columns
+                    .get(i)
+                    .copied()
+                    .unwrap_or(GridTrack::Fraction(1.0))
+            })
+            .collect::<Vec<_>>();
+        let rows = (0..row_count)
+            .map(|i| self.rows.get(i).copied().unwrap_or(self.auto_rows))
+            .collect::<Vec<_>>();
+
+        let column_sizes = resolve_tracks(
+            &columns,
+            content_size.x,
+            self.column_gap * scale_factor,
+            scale_factor,
+            areas
+                .iter()
+                .zip(items)
+                .filter(|((_, _, column_span, _), _)| *column_span == 1)
+                .map(|((column, _, _, _), (_, size))| (*column, size.x)),
+        );
+        let row_sizes = resolve_tracks(
+            &rows,
+            content_size.y,
+            self.row_gap * scale_factor,
+            scale_factor,
+            areas
+                .iter()
+                .zip(items)
+                .filter(|((_, _, _, row_span), _)| *row_span == 1)
+                .map(|((_, row, _, _), (_, size))| (*row, size.y)),
+        );
+
+        let span = |sizes: &[f32], gap: f32, start: usize, span: usize| {
+            let offset = sizes[..start].iter().map(|size| size + gap).sum::<f32>();
+            let size = sizes[start..start + span].iter().sum::<f32>() + gap * (span - 1) as f32;
+            (offset, size)
+        };
+        areas
+            .into_iter()
+            .map(|(column, row, column_span, row_span)| {
+                let (x, width) = span(
+                    &column_sizes,
+                    self.column_gap * scale_factor,
+                    column,
+                    column_span,
+                );
+                let (y, height) = span(&row_sizes, self.row_gap * scale_factor, row, row_span);
+                GridCell {
+                    offset: Vec2::new(x, y),
+                    size: Vec2::new(width, height),
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the `(column, row, column_span, row_span)` area of each item.
+    fn place_items(
+        &self,
+        column_count: usize,
+        items: &[(GridPlacement, Vec2)],
+    ) -> Vec<(usize, usize, usize, usize)> {
+        let mut occupied: Vec<Vec<bool>> = Vec::new();
+        let is_free =
+            |occupied: &Vec<Vec<bool>>, column: usize, row: usize, column_span, row_span| {
+                (row..row + row_span).all(|row| {
+                    (column..column + column_span)
+                        .all(|column| !matches!(occupied.get(row), Some(cells) if cells[column]))
+                })
+            };
+        let occupy = |occupied: &mut Vec<Vec<bool>>, column, row, column_span, row_span| {
+            if occupied.len() < row + row_span {
+                occupied.resize(row + row_span, vec![false; column_count]);
+            }
+            for cells in occupied[row..row + row_span].iter_mut() {
+                for cell in cells[column..column + column_span].iter_mut() {
+                    *cell = true;
+                }
+            }
+        };
+
+        let mut areas = vec![None; items.len()];
+        // explicitly placed items first, so that automatically placed items flow around them
+        for (i, (placement, _)) in items.iter().enumerate() {
+            let column_span = placement.column_span.clamp(1, column_count);
+            let row_span = placement.row_span.max(1);
+            if let (Some(column), Some(row)) = (placement.column, placement.row) {
+                let column = column.min(column_count - column_span);
+                occupy(&mut occupied, column, row, column_span, row_span);
+                areas[i] = Some((column, row, column_span, row_span));
+            }
+        }
+        for (i, (placement, _)) in items.iter().enumerate() {
+            if areas[i].is_some() {
+                continue;
+            }
+            let column_span = placement.column_span.clamp(1, column_count);
+            let row_span = placement.row_span.max(1);
+            let columns = match placement.column {
+                Some(column) => {
+                    let column = column.min(column_count - column_span);
+                    column..column + 1
+                }
+                None => 0..column_count - column_span + 1,
+            };
+            let mut row = placement.row.unwrap_or(0);
+            let area = loop {
+                if let Some(column) = columns
+                    .clone()
+                    .find(|column| is_free(&occupied, *column, row, column_span, row_span))
+                {
+                    break (column, row, column_span, row_span);
+                }
+                row += 1;
+            };
+            occupy(&mut occupied, area.0, area.1, area.2, area.3);
+            areas[i] = Some(area);
+        }
+        areas.into_iter().map(Option::unwrap).collect()
+    }
+}
+
+fn resolve_tracks(
+    tracks: &[GridTrack],
+    available: f32,
+    gap: f32,
+    scale_factor: f32,
+    item_sizes: impl Iterator<Item = (usize, f32)>,
+) -> Vec<f32> {
+    let mut auto_sizes = vec![0.0f32; tracks.len()];
+    for (track, size) in item_sizes {
+        auto_sizes[track] = auto_sizes[track].max(size);
+    }
+    let mut sizes = tracks
+        .iter()
+        .zip(auto_sizes)
+        .map(|(track, auto_size)| match track {
+            GridTrack::Px(size) => size * scale_factor,
+            GridTrack::Percent(percent) => available * percent / 100.0,
+            GridTrack::Fraction(_) => 0.0,
+            GridTrack::Auto => auto_size,
+        })
+        .collect::<Vec<_>>();
+
+    let fractions = tracks
+        .iter()
+        .map(|track| match track {
+            GridTrack::Fraction(fraction) => fraction.max(0.0),
+            _ => 0.0,
+        })
+        .sum::<f32>();
+    if fractions > 0.0 {
+        let gaps = gap * tracks.len().saturating_sub(1) as f32;
+        let remaining = (available - gaps - sizes.iter().sum::<f32>()).max(0.0);
+        for (size, track) in sizes.iter_mut().zip(tracks) {
+            if let GridTrack::Fraction(fraction) = track {
+                *size = remaining * fraction.max(0.0) / fractions;
+            }
+        }
+    }
+    sizes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GridCell, GridContainer, GridPlacement, GridTrack};
+    use bevy_math::Vec2;
+
+    #[test]
+    fn grid_places_items_in_cells() {
+        let grid = GridContainer {
+            columns: vec![GridTrack::Px(100.0), GridTrack::Fraction(1.0)],
+            auto_rows: GridTrack::Px(20.0),
+            column_gap: 10.0,
+            row_gap: 5.0,
+            ..Default::default()
+        };
+        let items = [
+            (GridPlacement::default(), Vec2::ZERO),
+            (GridPlacement::cell(0, 1).with_span(2, 1), Vec2::ZERO),
+            (GridPlacement::default(), Vec2::ZERO),
+            (GridPlacement::default(), Vec2::ZERO),
+        ];
+        let cells = grid.compute_cells(Vec2::new(300.0, 200.0), &items, 1.0);
+        assert_eq!(
+            cells,
+            vec![
+                GridCell {
+                    offset: Vec2::new(0.0, 0.0),
+                    size: Vec2::new(100.0, 20.0),
+                },
+                GridCell {
+                    offset: Vec2::new(0.0, 25.0),
+                    size: Vec2::new(300.0, 20.0),
+                },
+                GridCell {
+                    offset: Vec2::new(110.0, 0.0),
+                    size: Vec2::new(190.0, 20.0),
+                },
+                GridCell {
+                    offset: Vec2::new(0.0, 50.0),
+                    size: Vec2::new(100.0, 20.0),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn auto_tracks_fit_their_largest_item() {
+        let grid = GridContainer {
+            columns: vec![GridTrack::Auto, GridTrack::Fraction(1.0)],
+            ..Default::default()
+        };
+        let items = [
+            (GridPlacement::default(), Vec2::new(40.0, 10.0)),
+            (GridPlacement::default(), Vec2::new(10.0, 30.0)),
+            (GridPlacement::default(), Vec2::new(60.0, 10.0)),
+        ];
+        let cells = grid.compute_cells(Vec2::new(200.0, 100.0), &items, 2.0);
+        assert_eq!(cells[0].size, Vec2::new(60.0, 30.0));
+        assert_eq!(cells[1].size, Vec2::new(140.0, 30.0));
+        assert_eq!(cells[2].offset, Vec2::new(0.0, 30.0));
+    }
+}
@@ -1,33 +1,66 @@
+mod accessibility;
 mod anchors;
 mod flex;
 mod focus;
+mod focus_ring;
+mod grid;
 mod margins;
+mod navigation;
 mod render;
+mod theme;
+mod theme_loader;
+mod transition;
 mod ui_node;
+mod virtual_cursor;
 
 pub mod entity;
 pub mod update;
 pub mod widget;
 
+pub use accessibility::*;
 pub use anchors::*;
 pub use flex::*;
 pub use focus::*;
+pub use focus_ring::*;
+pub use grid::*;
 pub use margins::*;
+pub use navigation::*;
 pub use render::*;
+pub use theme::*;
+pub use theme_loader::*;
+pub use transition::*;
 pub use ui_node::*;
+pub use virtual_cursor::*;
 
 pub mod prelude {
     #[doc(hidden)]
-    pub use crate::{entity::*, ui_node::*, widget::Button, Anchors, Interaction, Margins};
+    pub use crate::{
+        entity::*,
+        ui_node::*,
+        widget::{
+            Button, ButtonBlueprint, Checkbox, CheckboxBlueprint, Dropdown, DropdownBlueprint,
+            DropdownChanged, Image, LabeledCheckboxBlueprint, ScrollPosition, ScrollView,
+            ScrollViewBlueprint, Scrollbar, ScrollbarBlueprint, SelectedOption, Slider,
+            SliderBlueprint, ToggleChanged, WidgetBinding,
+        },
+        Anchors, Disabled, FocusGained, FocusLost, FocusRingConfig, Focusable, GridContainer,
+        GridPlacement, GridTrack, Interaction, InteractionChanged, Margins, ThemedColor,
+        ThemedFont, ThemedText, ThemedWidget, UiTargetWindow, UiTheme, UiThemeSource,
+        VirtualCursor, VirtualCursorImage, VirtualCursorPlugin, VisualTransition,
+    };
 }
 
 use bevy_app::prelude::*;
-use bevy_ecs::schedule::{ParallelSystemDescriptorCoercion, SystemLabel};
+use bevy_asset::AddAsset;
+use bevy_ecs::{
+    schedule::{ExclusiveSystemDescriptorCoercion, ParallelSystemDescriptorCoercion, SystemLabel},
+    system::IntoExclusiveSystem,
+};
 use bevy_input::InputSystem;
 use bevy_math::{Rect, Size};
-use bevy_render::RenderStage;
+use bevy_render::{RenderStage, RenderSystem};
 use bevy_transform::TransformSystem;
-use update::ui_z_system;
+use update::{ui_clip_system, ui_window_visibility_system, ui_z_system};
 
 #[derive(Default)]
 pub struct UiPlugin;
@@ -37,11 +70,48 @@ pub enum UiSystem {
     /// After this label, the ui flex state has been updated
     Flex,
     Focus,
+    /// After this label, keyboard focus has been updated and the focused node has been activated
+    /// by the keyboard
+    KeyboardFocus,
+    /// After this label, the [`UiTheme`] resource has been updated from its [`UiThemeSource`]
+    Theme,
+    /// After this label, the focus ring has been placed around the focused node, ready for the
+    /// transforms to be propagated
+    FocusRing,
+}
+
+/// The order of the systems sharing the [`UiSystem::KeyboardFocus`] label, which all change the
+/// [`FocusedEntity`] or use it.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, SystemLabel)]
+enum KeyboardFocusSystem {
+    FocusOnClick,
+    Navigation,
+}
+
+/// The widget systems that run before others.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, SystemLabel)]
+enum WidgetSystem {
+    /// The scroll views follow the scrollbars scrolled by the user.
+    Scrollbar,
 }
 
 impl Plugin for UiPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<FlexSurface>()
+            .init_resource::<FocusedEntity>()
+            .init_resource::<FocusRingConfig>()
+            .init_resource::<UiTheme>()
+            .init_resource::<UiThemeSource>()
+            .add_event::<InteractionChanged>()
+            .add_event::<FocusGained>()
+            .add_event::<FocusLost>()
+            .add_event::<widget::DropdownChanged>()
+            .add_event::<widget::ToggleChanged>()
+            .add_asset::<UiTheme>()
+            .init_asset_loader::<UiThemeLoader>()
+            .register_type::<AccessibleName>()
+            .register_type::<AccessibleRole>()
+            .register_type::<AccessibleValue>()
             .register_type::<AlignContent>()
             .register_type::<AlignItems>()
             .register_type::<AlignSelf>()
@@ -49,7 +119,12 @@ impl Plugin for UiPlugin {
             .register_type::<Display>()
             .register_type::<FlexDirection>()
             .register_type::<FlexWrap>()
+            .register_type::<GridContainer>()
+            .register_type::<GridPlacement>()
+            .register_type::<GridTrack>()
             .register_type::<JustifyContent>()
+            .register_type::<Orientation>()
+            .register_type::<Overflow>()
             .register_type::<Node>()
             .register_type::<PositionType>()
             .register_type::<Size<f32>>()
@@ -57,11 +132,99 @@ impl Plugin for UiPlugin {
             .register_type::<Rect<Val>>()
             .register_type::<Style>()
             .register_type::<Val>()
+            .register_type::<widget::Checkbox>()
+            .register_type::<widget::ScrollPosition>()
+            .register_type::<widget::ScrollView>()
+            .register_type::<widget::Scrollbar>()
+            .register_type::<widget::Slider>()
             .add_system_to_stage(
                 CoreStage::PreUpdate,
                 ui_focus_system.label(UiSystem::Focus).after(InputSystem),
             )
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                focus_on_click_system
+                    .label(UiSystem::KeyboardFocus)
+                    .label(KeyboardFocusSystem::FocusOnClick)
+                    .after(UiSystem::Focus),
+            )
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                keyboard_navigation_system
+                    .label(UiSystem::KeyboardFocus)
+                    .label(KeyboardFocusSystem::Navigation)
+                    .after(KeyboardFocusSystem::FocusOnClick),
+            )
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                keyboard_activation_system
+                    .label(UiSystem::KeyboardFocus)
+                    .after(KeyboardFocusSystem::Navigation),
+            )
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                focus_events_system.after(UiSystem::KeyboardFocus),
+            )
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                interaction_changed_system.after(UiSystem::KeyboardFocus),
+            )
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                widget::checkbox_system.after(UiSystem::KeyboardFocus),
+            )
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                widget::slider_system.after(UiSystem::KeyboardFocus),
+            )
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                widget::scrollbar_system
+                    .label(WidgetSystem::Scrollbar)
+                    .after(UiSystem::KeyboardFocus),
+            )
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                widget::dropdown_system.after(UiSystem::KeyboardFocus),
+            )
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                widget::scroll_view_system
+                    .after(UiSystem::KeyboardFocus)
+                    .after(WidgetSystem::Scrollbar),
+            )
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                widget::widget_binding_system::<widget::Checkbox>
+                    .exclusive_system()
+                    .at_end(),
+            )
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                widget::widget_binding_system::<widget::Slider>
+                    .exclusive_system()
+                    .at_end(),
+            )
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                widget::widget_binding_system::<widget::Scrollbar>
+                    .exclusive_system()
+                    .at_end(),
+            )
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                ui_theme_source_system.label(UiSystem::Theme),
+            )
             // add these stages to front because these must run before transform update systems
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                themed_text_system.before(UiSystem::Flex),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                themed_font_system.before(UiSystem::Flex),
+            )
+            .add_system_to_stage(CoreStage::PostUpdate, themed_color_system)
             .add_system_to_stage(
                 CoreStage::PostUpdate,
                 widget::text_system.before(UiSystem::Flex),
@@ -70,6 +233,35 @@ impl Plugin for UiPlugin {
                 CoreStage::PostUpdate,
                 widget::image_node_system.before(UiSystem::Flex),
             )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                widget::checkbox_mark_system.before(UiSystem::Flex),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                widget::slider_thumb_system.before(UiSystem::Flex),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                widget::scrollbar_thumb_system.before(UiSystem::Flex),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                widget::dropdown_list_system.before(UiSystem::Flex),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                widget::dropdown_label_system.before(UiSystem::Flex),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                widget::scroll_content_system.before(UiSystem::Flex),
+            )
+            .add_system_to_stage(CoreStage::PostUpdate, accessible_value_system)
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                visual_transition_system.before(TransformSystem::TransformPropagate),
+            )
             .add_system_to_stage(
                 CoreStage::PostUpdate,
                 flex_node_system
@@ -80,8 +272,24 @@ impl Plugin for UiPlugin {
                 CoreStage::PostUpdate,
                 ui_z_system
                     .after(UiSystem::Flex)
+                    .before(UiSystem::FocusRing)
                     .before(TransformSystem::TransformPropagate),
             )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                ui_clip_system.after(TransformSystem::TransformPropagate),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                focus_ring_system
+                    .label(UiSystem::FocusRing)
+                    .after(UiSystem::Flex)
+                    .before(TransformSystem::TransformPropagate),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                ui_window_visibility_system.after(RenderSystem::VisibleEntities),
+            )
             .add_system_to_stage(RenderStage::Draw, widget::draw_text_system);
 
         crate::render::add_ui_graph(&mut app.world);
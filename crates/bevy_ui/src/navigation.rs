@@ -0,0 +1,269 @@
+use crate::{Disabled, FlexSurface, Focusable, FocusedEntity, Node};
+use bevy_app::EventWriter;
+use bevy_core::FloatOrd;
+use bevy_ecs::{
+    entity::Entity,
+    query::{With, Without},
+    system::{Local, Query, Res, ResMut},
+};
+use bevy_input::{
+    gamepad::{GamepadButton, GamepadButtonType},
+    keyboard::KeyCode,
+    Input,
+};
+use bevy_math::Vec2;
+use bevy_transform::components::GlobalTransform;
+use bevy_window::{WindowId, Windows};
+
+/// Sent when a UI node gains the keyboard focus, see [`FocusedEntity`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FocusGained {
+    pub entity: Entity,
+}
+
+/// Sent when a UI node loses the keyboard focus, see [`FocusedEntity`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FocusLost {
+    pub entity: Entity,
+}
+
+/// How the keyboard focus is moved.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FocusMove {
+    Next,
+    Previous,
+    /// To the closest node in a direction, `y` pointing up.
+    Direction(Vec2),
+}
+
+const DIRECTIONS: [(KeyCode, GamepadButtonType, (f32, f32)); 4] = [
+    (KeyCode::Up, GamepadButtonType::DPadUp, (0.0, 1.0)),
+    (KeyCode::Down, GamepadButtonType::DPadDown, (0.0, -1.0)),
+    (KeyCode::Left, GamepadButtonType::DPadLeft, (-1.0, 0.0)),
+    (KeyCode::Right, GamepadButtonType::DPadRight, (1.0, 0.0)),
+];
+
+/// Moves the keyboard focus between the [`Focusable`] nodes of a window: to the next node in
+/// reading order with Tab, to the previous one with Shift+Tab, and to the closest node in a
+/// direction with the arrow keys or the D-pad of a gamepad.
+///
+/// The focus stays in the window of the focused node, or moves into the focused window with Tab
+/// when no node has the focus. The arrow keys and the D-pad only move an existing focus, so that
+/// they keep controlling the game until a node is focused, with Tab, a click or by setting the
+/// [`FocusedEntity`].
+#[allow(clippy::type_complexity)]
+pub fn keyboard_navigation_system(
+    windows: Res<Windows>,
+    flex_surface: Res<FlexSurface>,
+    keyboard_input: Res<Input<KeyCode>>,
+    gamepad_input: Res<Input<GamepadButton>>,
+    mut focused_entity: ResMut<FocusedEntity>,
+    query: Query<(Entity, &Node, &GlobalTransform), (With<Focusable>, Without<Disabled>)>,
+) {
+    let focus_move = if keyboard_input.just_pressed(KeyCode::Tab) {
+        if keyboard_input.pressed(KeyCode::LShift) || keyboard_input.pressed(KeyCode::RShift) {
+            FocusMove::Previous
+        } else {
+            FocusMove::Next
+        }
+    } else {
+        let direction = DIRECTIONS.iter().find(|(key, button, _)| {
+            keyboard_input.just_pressed(*key)
+                || gamepad_input
+                    .get_just_pressed()
+                    .any(|GamepadButton(_, button_type)| button_type == button)
+        });
+        match direction {
+            Some((_, _, direction)) => FocusMove::Direction((*direction).into()),
+            None => return,
+        }
+    };
+
+    let node_window = |entity| {
+        flex_surface
+            .node_window(entity)
+            .unwrap_or_else(WindowId::primary)
+    };
+    let focused = focused_entity.0.filter(|entity| query.get(*entity).is_ok());
+    if focused.is_none() && matches!(focus_move, FocusMove::Direction(_)) {
+        return;
+    }
+    let window = match focused {
+        Some(entity) => node_window(entity),
+        None => windows
+            .iter()
+            .find(|window| window.is_focused())
+            .map_or_else(WindowId::primary, |window| window.id()),
+    };
+    // the nodes in reading order, from the top left
+    let mut nodes = query
+        .iter()
+        // hidden nodes, such as the options of a closed dropdown, have no size
+        .filter(|(entity, node, _)| node.size != Vec2::ZERO && node_window(*entity) == window)
+        .map(|(entity, node, global_transform)| {
+            (entity, global_transform.translation.truncate(), node.size)
+        })
+        .collect::<Vec<_>>();
+    nodes.sort_by_key(|(_, center, size)| {
+        (
+            FloatOrd(-(center.y + size.y / 2.0)),
+            FloatOrd(center.x - size.x / 2.0),
+        )
+    });
+    if nodes.is_empty() {
+        return;
+    }
+
+    let index =
+        focused.and_then(|focused| nodes.iter().position(|(entity, ..)| *entity == focused));
+    let next = match (focus_move, index) {
+        (FocusMove::Next, Some(index)) => nodes[(index + 1) % nodes.len()].0,
+        (FocusMove::Previous, Some(index)) => nodes[(index + nodes.len() - 1) % nodes.len()].0,
+        (FocusMove::Direction(direction), Some(index)) => {
+            let from = nodes[index].1;
+            match closest_in_direction(&nodes, from, direction) {
+                Some(entity) => entity,
+                None => return,
+            }
+        }
+        (FocusMove::Previous, None) => nodes[nodes.len() - 1].0,
+        (FocusMove::Next, None) => nodes[0].0,
+        (FocusMove::Direction(_), None) => return,
+    };
+    focused_entity.0 = Some(next);
+}
+
+/// The node whose center is the closest to `from` in `direction`, favoring the nodes aligned with
+/// `from` over the closer ones off to the side.
+fn closest_in_direction(
+    nodes: &[(Entity, Vec2, Vec2)],
+    from: Vec2,
+    direction: Vec2,
+) -> Option<Entity> {
+    nodes
+        .iter()
+        .filter_map(|(entity, center, _)| {
+            let offset = *center - from;
+            let distance = offset.dot(direction);
+            let deviation = (offset - distance * direction).length();
+            (distance > 0.0).then_some((*entity, FloatOrd(distance + 2.0 * deviation)))
+        })
+        .min_by_key(|(_, score)| *score)
+        .map(|(entity, _)| entity)
+}
+
+/// Sends the [`FocusGained`] and [`FocusLost`] events when the [`FocusedEntity`] changes.
+pub fn focus_events_system(
+    mut previous: Local<Option<Entity>>,
+    focused_entity: Res<FocusedEntity>,
+    mut focus_gained_events: EventWriter<FocusGained>,
+    mut focus_lost_events: EventWriter<FocusLost>,
+) {
+    if focused_entity.0 == *previous {
+        return;
+    }
+    if let Some(entity) = *previous {
+        focus_lost_events.send(FocusLost { entity });
+    }
+    if let Some(entity) = focused_entity.0 {
+        focus_gained_events.send(FocusGained { entity });
+    }
+    *previous = focused_entity.0;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{closest_in_direction, keyboard_navigation_system};
+    use crate::{FlexSurface, Focusable, FocusedEntity, Node};
+    use bevy_app::App;
+    use bevy_core::CorePlugin;
+    use bevy_ecs::entity::Entity;
+    use bevy_input::{gamepad::GamepadButton, keyboard::KeyCode, Input};
+    use bevy_math::{Vec2, Vec3};
+    use bevy_transform::components::GlobalTransform;
+    use bevy_window::{HeadlessWindowPlugin, WindowPlugin};
+
+    fn press(app: &mut App, keys: &[KeyCode]) -> Option<Entity> {
+        let mut keyboard_input = app.world.get_resource_mut::<Input<KeyCode>>().unwrap();
+        for key in keys {
+            keyboard_input.press(*key);
+        }
+        app.update();
+        let mut keyboard_input = app.world.get_resource_mut::<Input<KeyCode>>().unwrap();
+        for key in keys {
+            keyboard_input.release(*key);
+        }
+        keyboard_input.clear();
+        app.world.get_resource::<FocusedEntity>().unwrap().0
+    }
+
+    #[test]
+    fn keyboard_navigation() {
+        let mut app = App::new();
+        app.add_plugin(CorePlugin)
+            .add_plugin(WindowPlugin::default())
+            .add_plugin(HeadlessWindowPlugin)
+            .init_resource::<FlexSurface>()
+            .init_resource::<FocusedEntity>()
+            .init_resource::<Input<KeyCode>>()
+            .init_resource::<Input<GamepadButton>>()
+            .add_system(keyboard_navigation_system);
+        // a column of two nodes, with a third one right of the first
+        let mut spawn = |x, y| {
+            app.world
+                .spawn()
+                .insert_bundle((
+                    Node {
+                        size: Vec2::new(100.0, 40.0),
+                        ..Default::default()
+                    },
+                    GlobalTransform::from_translation(Vec3::new(x, y, 0.0)),
+                    Focusable,
+                ))
+                .id()
+        };
+        let top = spawn(100.0, 300.0);
+        let bottom = spawn(100.0, 200.0);
+        let right = spawn(300.0, 300.0);
+        app.update();
+
+        // the arrows don't take the focus
+        assert_eq!(press(&mut app, &[KeyCode::Down]), None);
+        assert_eq!(press(&mut app, &[KeyCode::Tab]), Some(top));
+        assert_eq!(press(&mut app, &[KeyCode::Tab]), Some(right));
+        assert_eq!(press(&mut app, &[KeyCode::Tab]), Some(bottom));
+        assert_eq!(
+            press(&mut app, &[KeyCode::LShift, KeyCode::Tab]),
+            Some(right)
+        );
+        assert_eq!(press(&mut app, &[KeyCode::Left]), Some(top));
+        assert_eq!(press(&mut app, &[KeyCode::Down]), Some(bottom));
+        // nothing below
+        assert_eq!(press(&mut app, &[KeyCode::Down]), Some(bottom));
+    }
+
+    #[test]
+    fn closest_in_direction_prefers_aligned_nodes() {
+        let size = Vec2::new(100.0, 40.0);
+        let nodes = [
+            (Entity::new(0), Vec2::new(100.0, 300.0), size),
+            (Entity::new(1), Vec2::new(100.0, 200.0), size),
+            (Entity::new(2), Vec2::new(300.0, 280.0), size),
+            (Entity::new(3), Vec2::new(120.0, 100.0), size),
+        ];
+        let from = nodes[0].1;
+        assert_eq!(
+            closest_in_direction(&nodes, from, -Vec2::Y),
+            Some(Entity::new(1))
+        );
+        assert_eq!(
+            closest_in_direction(&nodes, from, Vec2::X),
+            Some(Entity::new(2))
+        );
+        assert_eq!(closest_in_direction(&nodes, from, Vec2::Y), None);
+        assert_eq!(
+            closest_in_direction(&nodes, nodes[1].1, -Vec2::Y),
+            Some(Entity::new(3))
+        );
+    }
+}
@@ -1,9 +1,22 @@
 #version 450
 
 layout(location = 0) in vec2 v_Uv;
+layout(location = 1) in vec2 v_Position;
 
 layout(location = 0) out vec4 o_Target;
 
+layout(set = 1, binding = 1) uniform Node_size {
+    vec2 NodeSize;
+};
+// (min.x, min.y, max.x, max.y)
+layout(set = 1, binding = 2) uniform Node_clip {
+    vec4 NodeClip;
+};
+// the borders of the texture that are not stretched: (left, right, top, bottom)
+layout(set = 1, binding = 3) uniform Node_slice {
+    vec4 NodeSlice;
+};
+
 layout(set = 2, binding = 0) uniform ColorMaterial_color {
     vec4 Color;
 };
@@ -13,12 +26,40 @@ layout(set = 2, binding = 1) uniform texture2D ColorMaterial_texture;
 layout(set = 2, binding = 2) uniform sampler ColorMaterial_texture_sampler;
 # endif
 
+# ifdef COLORMATERIAL_TEXTURE
+// Maps the uv of the node to the uv of its texture, keeping the size of the borders of the texture
+// and stretching its center. Without borders, the uv is unchanged.
+vec2 slice_uv(vec2 uv, vec2 texture_size) {
+    vec2 start = NodeSlice.xz;
+    vec2 end = NodeSlice.yw;
+    // shrink the borders when the node is smaller than them
+    vec2 scale = min(vec2(1.0), NodeSize / max(start + end, vec2(0.0001)));
+    vec2 node_start = start * scale;
+    vec2 node_end = end * scale;
+    vec2 position = uv * NodeSize;
+    vec2 middle = (position - node_start) / max(NodeSize - node_start - node_end, vec2(0.0001));
+    vec2 texel = mix(start, texture_size - end, middle);
+    texel = mix(texel, position / scale, step(position, node_start));
+    texel = mix(
+        texel,
+        texture_size - (NodeSize - position) / scale,
+        step(NodeSize - node_end, position));
+    return texel / texture_size;
+}
+# endif
+
 void main() {
+    if (v_Position.x < NodeClip.x || v_Position.y < NodeClip.y
+        || v_Position.x >= NodeClip.z || v_Position.y >= NodeClip.w) {
+        discard;
+    }
     vec4 color = Color;
 # ifdef COLORMATERIAL_TEXTURE
+    vec2 texture_size = vec2(textureSize(
+        sampler2D(ColorMaterial_texture, ColorMaterial_texture_sampler), 0));
     color *= texture(
         sampler2D(ColorMaterial_texture, ColorMaterial_texture_sampler),
-        v_Uv);
+        slice_uv(v_Uv, texture_size));
 # endif
     o_Target = color;
 }
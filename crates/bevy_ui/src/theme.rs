@@ -0,0 +1,454 @@
+use bevy_app::EventReader;
+use bevy_asset::{AssetEvent, AssetServer, Assets, Handle};
+use bevy_ecs::{
+    query::{ChangeTrackers, With},
+    system::{Query, Res, ResMut},
+};
+use bevy_reflect::TypeUuid;
+use bevy_render::color::Color;
+use bevy_sprite::ColorMaterial;
+use bevy_text::Text;
+use bevy_utils::{tracing::warn, HashMap};
+use serde::{Deserialize, Serialize};
+
+/// The visual style of the UI widgets.
+///
+/// Changes made to this resource are picked up by all [`ThemedWidget`](crate::ThemedWidget)s
+/// and [`ThemedText`]s.
+///
+/// A theme can also be loaded from a `.theme.ron` asset and made active through the
+/// [`UiThemeSource`] resource, in which case it is re-applied every time the asset is reloaded.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TypeUuid)]
+#[uuid = "9c7d9cd1-f058-460b-a960-ab2caa911e94"]
+#[serde(default)]
+pub struct UiTheme {
+    /// Colors shared by the UI of the app, such as `"accent"`, applied with [`ThemedColor`].
+    pub colors: HashMap<String, Color>,
+    /// The asset paths of fonts shared by the UI of the app, such as `"title"`, applied with
+    /// [`ThemedFont`].
+    pub fonts: HashMap<String, String>,
+    pub button: WidgetTheme,
+    pub checkbox: WidgetTheme,
+    pub slider: WidgetTheme,
+    pub text: TextTheme,
+    pub focus_ring: FocusRingTheme,
+    /// How widgets transition from one [`VisualState`] to another.
+    pub transition: Transition,
+}
+
+impl Default for UiTheme {
+    fn default() -> Self {
+        let widget = WidgetTheme {
+            normal: StateVisuals::new(Color::rgb(0.15, 0.15, 0.15), 1.0),
+            hovered: StateVisuals::new(Color::rgb(0.25, 0.25, 0.25), 1.0),
+            pressed: StateVisuals::new(Color::rgb(0.35, 0.75, 0.35), 0.95),
+            disabled: StateVisuals::new(Color::rgba(0.15, 0.15, 0.15, 0.5), 1.0),
+        };
+        UiTheme {
+            colors: Default::default(),
+            fonts: Default::default(),
+            button: widget.clone(),
+            checkbox: widget.clone(),
+            slider: WidgetTheme {
+                pressed: StateVisuals::new(Color::rgb(0.25, 0.25, 0.25), 1.0),
+                ..widget
+            },
+            text: Default::default(),
+            focus_ring: Default::default(),
+            transition: Default::default(),
+        }
+    }
+}
+
+/// The style of [`ThemedText`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TextTheme {
+    /// The asset path of the font. If `None`, the font of the text is left unchanged.
+    pub font: Option<String>,
+    pub font_size: f32,
+    pub color: Color,
+}
+
+impl Default for TextTheme {
+    fn default() -> Self {
+        TextTheme {
+            font: None,
+            font_size: 30.0,
+            color: Color::rgb(0.9, 0.9, 0.9),
+        }
+    }
+}
+
+/// The look of the focus ring drawn around the node that has keyboard focus.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FocusRingTheme {
+    pub color: Color,
+    /// The width of the ring, in logical pixels.
+    pub width: f32,
+    /// The space between the ring and the focused node, in logical pixels.
+    pub offset: f32,
+}
+
+impl Default for FocusRingTheme {
+    fn default() -> Self {
+        FocusRingTheme {
+            color: Color::rgb(0.3, 0.6, 1.0),
+            width: 2.0,
+            offset: 2.0,
+        }
+    }
+}
+
+/// The visual states a widget can be in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum VisualState {
+    Normal,
+    Hovered,
+    Pressed,
+    Disabled,
+}
+
+impl Default for VisualState {
+    fn default() -> Self {
+        VisualState::Normal
+    }
+}
+
+/// The look of a widget in each of its [`VisualState`]s.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WidgetTheme {
+    pub normal: StateVisuals,
+    pub hovered: StateVisuals,
+    pub pressed: StateVisuals,
+    pub disabled: StateVisuals,
+}
+
+impl WidgetTheme {
+    pub fn get(&self, state: VisualState) -> &StateVisuals {
+        match state {
+            VisualState::Normal => &self.normal,
+            VisualState::Hovered => &self.hovered,
+            VisualState::Pressed => &self.pressed,
+            VisualState::Disabled => &self.disabled,
+        }
+    }
+}
+
+/// The look of a widget in a single [`VisualState`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct StateVisuals {
+    /// The color of the widget's material.
+    pub color: Color,
+    /// The scale of the widget around its center.
+    pub scale: f32,
+}
+
+impl Default for StateVisuals {
+    fn default() -> Self {
+        StateVisuals {
+            color: Color::WHITE,
+            scale: 1.0,
+        }
+    }
+}
+
+impl StateVisuals {
+    pub fn new(color: Color, scale: f32) -> Self {
+        StateVisuals { color, scale }
+    }
+
+    /// Linearly interpolates between `self` and `other`, where a `t` of `0.0` gives `self` and
+    /// a `t` of `1.0` gives `other`.
+    pub fn lerp(&self, other: &StateVisuals, t: f32) -> StateVisuals {
+        let from = self.color.as_rgba_f32();
+        let to = other.color.as_rgba_f32();
+        let channel = |i: usize| from[i] + (to[i] - from[i]) * t;
+        StateVisuals {
+            color: Color::rgba(channel(0), channel(1), channel(2), channel(3)),
+            scale: self.scale + (other.scale - self.scale) * t,
+        }
+    }
+}
+
+/// Describes how a widget animates between two [`VisualState`]s.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Transition {
+    /// The duration of the transition, in seconds. A duration of `0.0` switches instantly.
+    pub duration: f32,
+    pub easing: Easing,
+}
+
+impl Default for Transition {
+    fn default() -> Self {
+        Transition {
+            duration: 0.1,
+            easing: Easing::EaseOut,
+        }
+    }
+}
+
+/// An easing function, mapping the linear progress of a transition to its eased progress.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Easing {
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+}
+
+impl Default for Easing {
+    fn default() -> Self {
+        Easing::Linear
+    }
+}
+
+impl Easing {
+    /// Eases `t`, which is clamped to the `0.0..=1.0` range.
+    pub fn ease(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t,
+            Easing::EaseOut => t * (2.0 - t),
+            Easing::EaseInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    -1.0 + (4.0 - 2.0 * t) * t
+                }
+            }
+        }
+    }
+}
+
+/// Marks a [`Text`] whose sections are styled with [`UiTheme::text`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ThemedText;
+
+/// Sets the color of the material of a UI node to the color of [`UiTheme::colors`] with this
+/// name, when it is added or when the theme changes.
+///
+/// The color is written into the [`ColorMaterial`] asset itself, so every node that shares the
+/// material is recoloured too. As with [`ThemedWidget`](crate::ThemedWidget)s, give the node its
+/// own material.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ThemedColor(pub String);
+
+/// Sets the font of the sections of a [`Text`] to the font of [`UiTheme::fonts`] with this name,
+/// when it is added or when the theme changes.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ThemedFont(pub String);
+
+/// The theme asset that is copied into the [`UiTheme`] resource whenever it is loaded or
+/// modified.
+#[derive(Debug, Clone, Default)]
+pub struct UiThemeSource(pub Option<Handle<UiTheme>>);
+
+/// Copies the [`UiThemeSource`] asset into the [`UiTheme`] resource when it is loaded, reloaded
+/// or when the source changes.
+pub fn ui_theme_source_system(
+    mut asset_events: EventReader<AssetEvent<UiTheme>>,
+    source: Res<UiThemeSource>,
+    themes: Res<Assets<UiTheme>>,
+    mut theme: ResMut<UiTheme>,
+) {
+    let handle = match &source.0 {
+        Some(handle) => handle,
+        None => return,
+    };
+    let mut changed = source.is_changed();
+    for event in asset_events.iter() {
+        match event {
+            AssetEvent::Created { handle: h } | AssetEvent::Modified { handle: h }
+                if h == handle =>
+            {
+                changed = true;
+            }
+            _ => {}
+        }
+    }
+    if changed {
+        if let Some(source_theme) = themes.get(handle) {
+            if *theme != *source_theme {
+                *theme = source_theme.clone();
+            }
+        }
+    }
+}
+
+/// Applies [`UiTheme::text`] to every [`ThemedText`] when it is added or when the theme changes.
+/// The font of the texts with a [`ThemedFont`] is left to it.
+#[allow(clippy::type_complexity)]
+pub fn themed_text_system(
+    theme: Res<UiTheme>,
+    asset_server: Res<AssetServer>,
+    mut query: Query<
+        (&mut Text, ChangeTrackers<ThemedText>, Option<&ThemedFont>),
+        With<ThemedText>,
+    >,
+) {
+    let font = theme
+        .text
+        .font
+        .as_ref()
+        .map(|path| asset_server.load(path.as_str()));
+    for (mut text, themed_text, themed_font) in query.iter_mut() {
+        if !theme.is_changed() && !themed_text.is_added() {
+            continue;
+        }
+        for section in text.sections.iter_mut() {
+            if let (Some(font), None) = (&font, themed_font) {
+                section.style.font = font.clone();
+            }
+            section.style.font_size = theme.text.font_size;
+            section.style.color = theme.text.color;
+        }
+    }
+}
+
+/// Applies the named colors of the [`UiTheme`] to every [`ThemedColor`] when it is added or when
+/// the theme changes.
+pub fn themed_color_system(
+    theme: Res<UiTheme>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    query: Query<(
+        &ThemedColor,
+        &Handle<ColorMaterial>,
+        ChangeTrackers<ThemedColor>,
+    )>,
+) {
+    for (themed_color, material, changes) in query.iter() {
+        if !theme.is_changed() && !changes.is_changed() {
+            continue;
+        }
+        match theme.colors.get(&themed_color.0) {
+            Some(color) => {
+                if let Some(material) = materials.get_mut(material) {
+                    material.color = *color;
+                }
+            }
+            None => warn!("The UI theme has no color named {:?}.", themed_color.0),
+        }
+    }
+}
+
+/// Applies the named fonts of the [`UiTheme`] to every [`ThemedFont`] when it is added or when
+/// the theme changes.
+pub fn themed_font_system(
+    theme: Res<UiTheme>,
+    asset_server: Res<AssetServer>,
+    mut query: Query<(&ThemedFont, &mut Text, ChangeTrackers<ThemedFont>)>,
+) {
+    for (themed_font, mut text, changes) in query.iter_mut() {
+        if !theme.is_changed() && !changes.is_changed() {
+            continue;
+        }
+        match theme.fonts.get(&themed_font.0) {
+            Some(path) => {
+                let font = asset_server.load(path.as_str());
+                for section in text.sections.iter_mut() {
+                    section.style.font = font.clone();
+                }
+            }
+            None => warn!("The UI theme has no font named {:?}.", themed_font.0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_app::App;
+    use bevy_asset::{AddAsset, AssetPlugin};
+    use bevy_core::CorePlugin;
+    use bevy_text::TextStyle;
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugin(CorePlugin)
+            .add_plugin(AssetPlugin)
+            .add_asset::<ColorMaterial>()
+            .init_resource::<UiTheme>()
+            .add_system(themed_color_system)
+            .add_system(themed_font_system);
+        app
+    }
+
+    #[test]
+    fn themed_colors_are_applied_on_add_and_on_theme_change() {
+        let mut app = app();
+        app.world
+            .get_resource_mut::<UiTheme>()
+            .unwrap()
+            .colors
+            .insert("accent".to_string(), Color::RED);
+        let material = app
+            .world
+            .get_resource_mut::<Assets<ColorMaterial>>()
+            .unwrap()
+            .add(Color::WHITE.into());
+        app.world
+            .spawn()
+            .insert(ThemedColor("accent".to_string()))
+            .insert(material.clone());
+
+        app.update();
+        let color = |app: &App| {
+            app.world
+                .get_resource::<Assets<ColorMaterial>>()
+                .unwrap()
+                .get(&material)
+                .unwrap()
+                .color
+        };
+        assert_eq!(color(&app), Color::RED);
+
+        app.world
+            .get_resource_mut::<UiTheme>()
+            .unwrap()
+            .colors
+            .insert("accent".to_string(), Color::BLUE);
+        app.update();
+        assert_eq!(color(&app), Color::BLUE);
+    }
+
+    #[test]
+    fn themed_fonts_are_applied_on_add_and_on_theme_change() {
+        let mut app = app();
+        app.world
+            .get_resource_mut::<UiTheme>()
+            .unwrap()
+            .fonts
+            .insert("title".to_string(), "fonts/title.ttf".to_string());
+        let entity = app
+            .world
+            .spawn()
+            .insert(ThemedFont("title".to_string()))
+            .insert(Text::with_section(
+                "Title",
+                TextStyle::default(),
+                Default::default(),
+            ))
+            .id();
+
+        app.update();
+        let asset_server = app.world.get_resource::<AssetServer>().unwrap().clone();
+        let font = |app: &App| {
+            app.world.get::<Text>(entity).unwrap().sections[0]
+                .style
+                .font
+                .clone()
+        };
+        assert_eq!(font(&app), asset_server.get_handle("fonts/title.ttf"));
+
+        app.world
+            .get_resource_mut::<UiTheme>()
+            .unwrap()
+            .fonts
+            .insert("title".to_string(), "fonts/bold.ttf".to_string());
+        app.update();
+        assert_eq!(font(&app), asset_server.get_handle("fonts/bold.ttf"));
+    }
+}
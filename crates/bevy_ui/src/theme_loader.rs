@@ -0,0 +1,28 @@
+use crate::UiTheme;
+use anyhow::Result;
+use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
+use bevy_utils::BoxedFuture;
+
+/// Loads a [`UiTheme`] from a RON file with the `.theme.ron` extension.
+///
+/// Fields missing from the file keep their default value.
+#[derive(Default)]
+pub struct UiThemeLoader;
+
+impl AssetLoader for UiThemeLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let theme = ron::de::from_bytes::<UiTheme>(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(theme));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["theme.ron"]
+    }
+}
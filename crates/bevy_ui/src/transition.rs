@@ -0,0 +1,108 @@
+use crate::{Disabled, Interaction, StateVisuals, UiTheme, VisualState, WidgetTheme};
+use bevy_asset::{Assets, Handle};
+use bevy_core::Time;
+use bevy_ecs::system::{Query, Res, ResMut};
+use bevy_math::Vec3;
+use bevy_sprite::ColorMaterial;
+use bevy_transform::components::Transform;
+
+/// Styles a UI node with one of the [`WidgetTheme`]s of the [`UiTheme`].
+///
+/// The color of the node's material and the scale of the node follow its [`VisualState`], and
+/// are animated according to [`UiTheme::transition`] whenever that state changes. Because the
+/// color is written to the material itself, every themed node should have its own material.
+///
+/// Themed nodes also need an [`Interaction`] and a [`VisualTransition`] component.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ThemedWidget {
+    Button,
+    Checkbox,
+    Slider,
+}
+
+impl ThemedWidget {
+    pub fn theme<'a>(&self, theme: &'a UiTheme) -> &'a WidgetTheme {
+        match self {
+            ThemedWidget::Button => &theme.button,
+            ThemedWidget::Checkbox => &theme.checkbox,
+            ThemedWidget::Slider => &theme.slider,
+        }
+    }
+}
+
+/// The progress of a [`ThemedWidget`] transitioning to its current [`VisualState`].
+#[derive(Debug, Clone, Default)]
+pub struct VisualTransition {
+    state: VisualState,
+    from: StateVisuals,
+    current: Option<StateVisuals>,
+    elapsed: f32,
+}
+
+impl VisualTransition {
+    /// The state the widget is in, or transitioning to.
+    pub fn state(&self) -> VisualState {
+        self.state
+    }
+}
+
+/// Animates the color and scale of every [`ThemedWidget`] towards the look of its current
+/// [`VisualState`].
+#[allow(clippy::type_complexity)]
+pub fn visual_transition_system(
+    time: Res<Time>,
+    theme: Res<UiTheme>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut query: Query<(
+        &ThemedWidget,
+        &Interaction,
+        Option<&Disabled>,
+        &Handle<ColorMaterial>,
+        &mut Transform,
+        &mut VisualTransition,
+    )>,
+) {
+    let transition = &theme.transition;
+    for (widget, interaction, disabled, material, mut transform, mut visual_transition) in
+        query.iter_mut()
+    {
+        let state = match (disabled, interaction) {
+            (Some(_), _) => VisualState::Disabled,
+            (None, Interaction::Clicked) => VisualState::Pressed,
+            (None, Interaction::Hovered) => VisualState::Hovered,
+            (None, Interaction::None) => VisualState::Normal,
+        };
+        let target = *widget.theme(&theme).get(state);
+
+        let visuals = match visual_transition.current {
+            // snap newly themed widgets to their state
+            None => target,
+            Some(current) => {
+                if state != visual_transition.state {
+                    visual_transition.from = current;
+                    visual_transition.elapsed = 0.0;
+                } else {
+                    visual_transition.elapsed += time.delta_seconds();
+                }
+                if visual_transition.elapsed >= transition.duration {
+                    target
+                } else {
+                    let t = visual_transition.elapsed / transition.duration;
+                    visual_transition
+                        .from
+                        .lerp(&target, transition.easing.ease(t))
+                }
+            }
+        };
+        visual_transition.state = state;
+        if visual_transition.current == Some(visuals) {
+            continue;
+        }
+        visual_transition.current = Some(visuals);
+
+        if let Some(material) = materials.get_mut(material) {
+            material.color = visuals.color;
+        }
+        transform.scale = Vec3::new(visuals.scale, visuals.scale, 1.0);
+    }
+}
@@ -1,16 +1,52 @@
 use bevy_ecs::reflect::ReflectComponent;
-use bevy_math::{Rect, Size, Vec2};
+use bevy_math::{const_vec4, Rect, Size, Vec2, Vec4};
 use bevy_reflect::{Reflect, ReflectDeserialize};
 use bevy_render::renderer::RenderResources;
+use bevy_window::WindowId;
 use serde::{Deserialize, Serialize};
 use std::ops::{Add, AddAssign};
 
-#[derive(Debug, Clone, Default, RenderResources, Reflect)]
+#[derive(Debug, Clone, RenderResources, Reflect)]
 #[reflect(Component)]
 pub struct Node {
     pub size: Vec2,
+    /// The area the node is clipped to, as `(min.x, min.y, max.x, max.y)` in the same space as
+    /// its [`GlobalTransform`](bevy_transform::components::GlobalTransform). Set from the
+    /// [`Overflow`] of its ancestors after the transforms are propagated.
+    pub clip: Vec4,
+    /// The borders of the texture of the node that are not stretched, as
+    /// `(left, right, top, bottom)` in texture pixels. Set from its
+    /// [`Image::Sliced`](crate::widget::Image::Sliced).
+    pub slice: Vec4,
 }
 
+impl Default for Node {
+    fn default() -> Self {
+        Node {
+            size: Vec2::ZERO,
+            clip: Node::UNCLIPPED,
+            slice: Vec4::ZERO,
+        }
+    }
+}
+
+impl Node {
+    /// The `clip` of the nodes that are not clipped.
+    pub const UNCLIPPED: Vec4 = const_vec4!([f32::MIN, f32::MIN, f32::MAX, f32::MAX]);
+
+    /// Whether `point` is in the clipped area of the node.
+    pub fn clip_contains(&self, point: Vec2) -> bool {
+        (self.clip.x..self.clip.z).contains(&point.x)
+            && (self.clip.y..self.clip.w).contains(&point.y)
+    }
+}
+
+/// Draws a UI node and its descendants above all the other nodes, where they also receive the
+/// clicks first, such as the open list of a [`Dropdown`](crate::widget::Dropdown). The node is
+/// still laid out in its parent.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UiOverlay;
+
 #[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize, Reflect)]
 #[reflect_value(PartialEq, Serialize, Deserialize)]
 pub enum Val {
@@ -72,6 +108,7 @@ pub struct Style {
     pub min_size: Size<Val>,
     pub max_size: Size<Val>,
     pub aspect_ratio: Option<f32>,
+    pub overflow: Overflow,
 }
 
 impl Default for Style {
@@ -97,6 +134,7 @@ impl Default for Style {
             min_size: Size::new(Val::Auto, Val::Auto),
             max_size: Size::new(Val::Auto, Val::Auto),
             aspect_ratio: Default::default(),
+            overflow: Default::default(),
         }
     }
 }
@@ -193,6 +231,20 @@ impl Default for FlexDirection {
     }
 }
 
+/// The axis along which a widget like a slider or a scrollbar is laid out.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize, Reflect)]
+#[reflect_value(PartialEq, Hash, Serialize, Deserialize)]
+pub enum Orientation {
+    Horizontal,
+    Vertical,
+}
+
+impl Default for Orientation {
+    fn default() -> Orientation {
+        Orientation::Horizontal
+    }
+}
+
 #[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize, Reflect)]
 #[reflect_value(PartialEq, Serialize, Deserialize)]
 pub enum JustifyContent {
@@ -210,19 +262,21 @@ impl Default for JustifyContent {
     }
 }
 
-// TODO: add support for overflow settings
-// #[derive(Copy, Clone, PartialEq, Debug)]
-// pub enum Overflow {
-//     Visible,
-//     Hidden,
-//     Scroll,
-// }
+/// Whether the children of a node are drawn past its bounds.
+#[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize, Reflect)]
+#[reflect_value(PartialEq, Serialize, Deserialize)]
+pub enum Overflow {
+    Visible,
+    /// The descendants of the node are clipped to its bounds, and can't be clicked outside of
+    /// them. Text is clipped a whole glyph at a time.
+    Hidden,
+}
 
-// impl Default for Overflow {
-//     fn default() -> Overflow {
-//         Overflow::Visible
-//     }
-// }
+impl Default for Overflow {
+    fn default() -> Overflow {
+        Overflow::Visible
+    }
+}
 
 #[derive(Copy, Clone, PartialEq, Debug, Serialize, Deserialize, Reflect)]
 #[reflect_value(PartialEq, Serialize, Deserialize)]
@@ -251,6 +305,15 @@ impl Default for FlexWrap {
     }
 }
 
+/// The window a root UI node and its descendants are laid out in, instead of the primary window.
+///
+/// The nodes use the size and scale factor of this window for their layout, and its cursor for
+/// their [`Interaction`](crate::Interaction). Drawing them in this window needs a UI camera whose
+/// [`Camera::window`](bevy_render::camera::Camera::window) is this window, and a UI pass rendering
+/// to it. The cameras of the other windows don't draw them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UiTargetWindow(pub WindowId);
+
 #[derive(Default, Copy, Clone, Debug)]
 pub struct CalculatedSize {
     pub size: Size,
@@ -1,33 +1,90 @@
-use super::Node;
+use super::{FlexSurface, Node, Overflow, Style, UiOverlay};
 use bevy_ecs::{
     entity::Entity,
     query::{With, Without},
-    system::Query,
+    system::{Query, Res},
 };
-use bevy_transform::prelude::{Children, Parent, Transform};
+use bevy_math::{Vec4, Vec4Swizzles};
+use bevy_render::camera::{Camera, VisibleEntities};
+use bevy_transform::prelude::{Children, GlobalTransform, Parent, Transform};
 
 pub const UI_Z_STEP: f32 = 0.001;
 
+/// Stacks the UI nodes in hierarchy order, each node above its parent and its previous
+/// siblings. The [`UiOverlay`]s and their descendants are stacked after all the other nodes.
 pub fn ui_z_system(
     root_node_query: Query<Entity, (With<Node>, Without<Parent>)>,
     mut node_query: Query<&mut Transform, With<Node>>,
     children_query: Query<&Children>,
+    overlay_query: Query<(), With<UiOverlay>>,
 ) {
+    let mut overlays = Overlays::default();
     let mut current_global_z = 0.0;
     for entity in root_node_query.iter() {
+        if overlay_query.get(entity).is_ok() {
+            overlays.pending.push((entity, 0.0));
+            continue;
+        }
+        overlays.root_offset = current_global_z;
         current_global_z = update_hierarchy(
             &children_query,
             &mut node_query,
+            &overlay_query,
+            &mut overlays,
             entity,
             current_global_z,
             current_global_z,
         );
     }
+    // the overlays nested in an overlay are pushed while it is stacked, and stacked after it
+    overlays.root_offset = 0.0;
+    let mut index = 0;
+    while let Some(&(entity, parent_global_z)) = overlays.pending.get(index) {
+        current_global_z = update_hierarchy(
+            &children_query,
+            &mut node_query,
+            &overlay_query,
+            &mut overlays,
+            entity,
+            parent_global_z,
+            current_global_z,
+        );
+        index += 1;
+    }
+}
+
+/// Removes the UI nodes laid out in another window from the visible entities of each camera, so
+/// that the UI pass of a window only draws the nodes of that window, see
+/// [`UiTargetWindow`](crate::UiTargetWindow).
+pub fn ui_window_visibility_system(
+    flex_surface: Res<FlexSurface>,
+    mut camera_query: Query<(&Camera, &mut VisibleEntities)>,
+) {
+    for (camera, mut visible_entities) in camera_query.iter_mut() {
+        visible_entities.value.retain(|visible_entity| {
+            !matches!(
+                flex_surface.node_window(visible_entity.entity),
+                Some(window_id) if window_id != camera.window
+            )
+        });
+    }
+}
+
+/// The [`UiOverlay`]s found while stacking the other nodes.
+#[derive(Default)]
+struct Overlays {
+    /// The overlays to stack after the other nodes, with the global z of their parent.
+    pending: Vec<(Entity, f32)>,
+    /// The difference between the z of the nodes being stacked and their global z, as each root
+    /// starts from the same z.
+    root_offset: f32,
 }
 
 fn update_hierarchy(
     children_query: &Query<&Children>,
     node_query: &mut Query<&mut Transform, With<Node>>,
+    overlay_query: &Query<(), With<UiOverlay>>,
+    overlays: &mut Overlays,
     entity: Entity,
     parent_global_z: f32,
     mut current_global_z: f32,
@@ -39,9 +96,17 @@ fn update_hierarchy(
     if let Ok(children) = children_query.get(entity) {
         let current_parent_global_z = current_global_z;
         for child in children.iter().cloned() {
+            if overlay_query.get(child).is_ok() {
+                overlays
+                    .pending
+                    .push((child, current_parent_global_z - overlays.root_offset));
+                continue;
+            }
             current_global_z = update_hierarchy(
                 children_query,
                 node_query,
+                overlay_query,
+                overlays,
                 child,
                 current_parent_global_z,
                 current_global_z,
@@ -50,6 +115,46 @@ fn update_hierarchy(
     }
     current_global_z
 }
+
+/// Clips the descendants of the nodes with [`Overflow::Hidden`] to the bounds of these nodes, by
+/// setting the `clip` of every [`Node`]. [`UiOverlay`]s are not clipped by their ancestors.
+pub fn ui_clip_system(
+    root_node_query: Query<Entity, (With<Node>, Without<Parent>)>,
+    mut node_query: Query<(&mut Node, &GlobalTransform, &Style, Option<&UiOverlay>)>,
+    children_query: Query<&Children>,
+) {
+    for entity in root_node_query.iter() {
+        update_clip(&mut node_query, &children_query, entity, Node::UNCLIPPED);
+    }
+}
+
+fn update_clip(
+    node_query: &mut Query<(&mut Node, &GlobalTransform, &Style, Option<&UiOverlay>)>,
+    children_query: &Query<&Children>,
+    entity: Entity,
+    mut clip: Vec4,
+) {
+    if let Ok((mut node, global_transform, style, overlay)) = node_query.get_mut(entity) {
+        if overlay.is_some() {
+            clip = Node::UNCLIPPED;
+        }
+        if node.clip != clip {
+            node.clip = clip;
+        }
+        if style.overflow == Overflow::Hidden {
+            let center = global_transform.translation.truncate();
+            let min = (center - node.size / 2.0).max(clip.xy());
+            let max = (center + node.size / 2.0).min(clip.zw());
+            clip = Vec4::new(min.x, min.y, max.x, max.y);
+        }
+    }
+    if let Ok(children) = children_query.get(entity) {
+        for child in children.iter() {
+            update_clip(node_query, children_query, *child, clip);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use bevy_ecs::{
@@ -57,11 +162,16 @@ mod tests {
         system::{CommandQueue, Commands},
         world::World,
     };
-    use bevy_transform::{components::Transform, hierarchy::BuildChildren};
+    use bevy_math::{Vec2, Vec4};
+    use bevy_transform::{
+        components::{GlobalTransform, Transform},
+        hierarchy::BuildChildren,
+    };
 
-    use crate::Node;
+    use crate::{Node, Overflow, Style, UiOverlay};
+    use bevy_utils::HashMap;
 
-    use super::{ui_z_system, UI_Z_STEP};
+    use super::{ui_clip_system, ui_z_system, UI_Z_STEP};
 
     fn node_with_transform(name: &str) -> (String, Node, Transform) {
         (name.to_owned(), Node::default(), Transform::identity())
@@ -153,4 +263,117 @@ mod tests {
         ];
         assert_eq!(actual_result, expected_result);
     }
+
+    #[test]
+    fn overlays_above_other_nodes() {
+        let mut world = World::default();
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &world);
+        commands
+            .spawn_bundle(node_with_transform("0"))
+            .with_children(|parent| {
+                parent
+                    .spawn_bundle(node_with_transform("0-0"))
+                    .insert(UiOverlay)
+                    .with_children(|parent| {
+                        parent.spawn_bundle(node_with_transform("0-0-0"));
+                    });
+                parent.spawn_bundle(node_with_transform("0-1"));
+            });
+        commands
+            .spawn_bundle(node_with_transform("1"))
+            .with_children(|parent| {
+                parent.spawn_bundle(node_with_transform("1-0"));
+                parent.spawn_bundle(node_with_transform("1-1"));
+            });
+        queue.apply(&mut world);
+
+        let mut schedule = Schedule::default();
+        let mut update_stage = SystemStage::parallel();
+        update_stage.add_system(ui_z_system);
+        schedule.add_stage("update", update_stage);
+        schedule.run(&mut world);
+
+        let z = world
+            .query::<(&String, &Transform)>()
+            .iter(&world)
+            .map(|(name, transform)| (name.clone(), transform.translation.z))
+            .collect::<HashMap<_, _>>();
+        let global_z = |path: &[&str]| path.iter().map(|name| z[*name]).sum::<f32>();
+        let overlay = global_z(&["0", "0-0"]);
+        assert!(global_z(&["0", "0-0", "0-0-0"]) > overlay);
+        for path in [&["0", "0-1"][..], &["1", "1-0"], &["1", "1-1"]] {
+            assert!(overlay > global_z(path));
+        }
+    }
+
+    #[test]
+    fn hidden_overflow_clips_descendants() {
+        let mut world = World::default();
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &world);
+        let node = |name: &str, center: Vec2, size: Vec2, overflow: Overflow| {
+            (
+                name.to_owned(),
+                Node {
+                    size,
+                    ..Default::default()
+                },
+                GlobalTransform::from_translation(center.extend(0.0)),
+                Style {
+                    overflow,
+                    ..Default::default()
+                },
+            )
+        };
+        commands
+            .spawn_bundle(node(
+                "0",
+                Vec2::new(100.0, 100.0),
+                Vec2::new(100.0, 100.0),
+                Overflow::Hidden,
+            ))
+            .with_children(|parent| {
+                parent
+                    .spawn_bundle(node(
+                        "0-0",
+                        Vec2::new(150.0, 100.0),
+                        Vec2::new(100.0, 40.0),
+                        Overflow::Hidden,
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn_bundle(node(
+                            "0-0-0",
+                            Vec2::new(150.0, 100.0),
+                            Vec2::new(100.0, 100.0),
+                            Overflow::Visible,
+                        ));
+                        parent
+                            .spawn_bundle(node(
+                                "0-0-1",
+                                Vec2::new(150.0, 100.0),
+                                Vec2::new(100.0, 100.0),
+                                Overflow::Visible,
+                            ))
+                            .insert(UiOverlay);
+                    });
+            });
+        queue.apply(&mut world);
+
+        let mut schedule = Schedule::default();
+        let mut update_stage = SystemStage::parallel();
+        update_stage.add_system(ui_clip_system);
+        schedule.add_stage("update", update_stage);
+        schedule.run(&mut world);
+
+        let clips = world
+            .query::<(&String, &Node)>()
+            .iter(&world)
+            .map(|(name, node)| (name.clone(), node.clip))
+            .collect::<HashMap<_, _>>();
+        assert_eq!(clips["0"], Node::UNCLIPPED);
+        assert_eq!(clips["0-0"], Vec4::new(50.0, 50.0, 150.0, 150.0));
+        assert_eq!(clips["0-0-0"], Vec4::new(100.0, 80.0, 150.0, 120.0));
+        assert_eq!(clips["0-0-1"], Node::UNCLIPPED);
+    }
 }
@@ -0,0 +1,202 @@
+use crate::{Style, UiSystem, Val};
+use bevy_app::{prelude::*, EventWriter};
+use bevy_core::Time;
+use bevy_ecs::{
+    query::With,
+    schedule::ParallelSystemDescriptorCoercion,
+    system::{Query, Res, ResMut},
+};
+use bevy_input::{
+    gamepad::{Gamepad, GamepadAxis, GamepadAxisType, GamepadButton, GamepadButtonType},
+    mouse::{MouseButton, MouseButtonInput},
+    Axis, ElementState, Input, InputKindSystem,
+};
+use bevy_math::Vec2;
+use bevy_render::draw::Visible;
+use bevy_window::{CursorMoved, WindowId, Windows};
+
+/// Lets a gamepad drive the cursor of a window, as configured by the [`VirtualCursor`] resource.
+///
+/// The virtual cursor generates the same [`CursorMoved`] and [`MouseButtonInput`] events as a
+/// real mouse, so UI and cursor-position-dependent gameplay work on controllers. The events are
+/// sent after the gamepad input is read and before the mouse input is, so that the clicks are
+/// seen in the same frame as the gamepad button presses.
+///
+/// The platform cursor doesn't move: the nodes with a [`VirtualCursorImage`] are drawn in its
+/// place.
+#[derive(Default)]
+pub struct VirtualCursorPlugin;
+
+impl Plugin for VirtualCursorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<VirtualCursor>()
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                virtual_cursor_system
+                    .after(InputKindSystem::Gamepad)
+                    .before(InputKindSystem::Mouse),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                virtual_cursor_image_system.before(UiSystem::Flex),
+            );
+    }
+}
+
+/// Settings of the gamepad-driven virtual cursor, which is disabled by default.
+///
+/// There is a single virtual cursor, which moves the cursor of one window.
+#[derive(Debug, Clone)]
+pub struct VirtualCursor {
+    pub enabled: bool,
+    /// The window whose cursor is moved.
+    pub window: WindowId,
+    pub gamepad: Gamepad,
+    pub x_axis: GamepadAxisType,
+    pub y_axis: GamepadAxisType,
+    /// The gamepad button that acts as the left mouse button.
+    pub click_button: GamepadButtonType,
+    /// The speed of the cursor at full stick deflection, in logical pixels per second.
+    pub speed: f32,
+}
+
+impl Default for VirtualCursor {
+    fn default() -> Self {
+        VirtualCursor {
+            enabled: false,
+            window: WindowId::primary(),
+            gamepad: Gamepad(0),
+            x_axis: GamepadAxisType::LeftStickX,
+            y_axis: GamepadAxisType::LeftStickY,
+            click_button: GamepadButtonType::South,
+            speed: 800.0,
+        }
+    }
+}
+
+/// Marks an absolutely positioned UI node, such as an image of an arrow, drawn at the position
+/// of the [`VirtualCursor`] with its top left corner on it. It is hidden while the virtual cursor
+/// is disabled.
+///
+/// The node should be in the UI of the window of the virtual cursor, and have a [`UiOverlay`]
+/// to be drawn above the other nodes.
+///
+/// [`UiOverlay`]: crate::UiOverlay
+#[derive(Debug, Clone, Default)]
+pub struct VirtualCursorImage;
+
+pub fn virtual_cursor_system(
+    virtual_cursor: Res<VirtualCursor>,
+    time: Res<Time>,
+    axes: Res<Axis<GamepadAxis>>,
+    buttons: Res<Input<GamepadButton>>,
+    mut windows: ResMut<Windows>,
+    mut cursor_moved_events: EventWriter<CursorMoved>,
+    mut mouse_button_input_events: EventWriter<MouseButtonInput>,
+) {
+    if !virtual_cursor.enabled {
+        return;
+    }
+    let window = match windows.get_mut(virtual_cursor.window) {
+        Some(window) => window,
+        None => return,
+    };
+
+    let stick = Vec2::new(
+        axes.get(GamepadAxis(virtual_cursor.gamepad, virtual_cursor.x_axis))
+            .unwrap_or(0.0),
+        axes.get(GamepadAxis(virtual_cursor.gamepad, virtual_cursor.y_axis))
+            .unwrap_or(0.0),
+    );
+    if stick != Vec2::ZERO {
+        let size = Vec2::new(window.width(), window.height());
+        let position = window.cursor_position().unwrap_or(size / 2.0)
+            + stick * virtual_cursor.speed * time.delta_seconds();
+        let position = position.max(Vec2::ZERO).min(size);
+        window.update_cursor_position_from_backend(Some(position));
+        cursor_moved_events.send(CursorMoved {
+            id: virtual_cursor.window,
+            position,
+        });
+    }
+
+    let click_button = GamepadButton(virtual_cursor.gamepad, virtual_cursor.click_button);
+    if buttons.just_pressed(click_button) {
+        mouse_button_input_events.send(MouseButtonInput {
+            button: MouseButton::Left,
+            state: ElementState::Pressed,
+        });
+    }
+    if buttons.just_released(click_button) {
+        mouse_button_input_events.send(MouseButtonInput {
+            button: MouseButton::Left,
+            state: ElementState::Released,
+        });
+    }
+}
+
+/// Moves the [`VirtualCursorImage`] nodes to the cursor of the window of the [`VirtualCursor`].
+pub fn virtual_cursor_image_system(
+    virtual_cursor: Res<VirtualCursor>,
+    windows: Res<Windows>,
+    mut query: Query<(&mut Style, &mut Visible), With<VirtualCursorImage>>,
+) {
+    let window = windows.get(virtual_cursor.window);
+    // the UI is laid out in the content drawn by the cameras of the window
+    let position =
+        window.and_then(|window| Some((window.render_cursor_position()?, window.render_size().y)));
+    for (mut style, mut visible) in query.iter_mut() {
+        let is_visible = virtual_cursor.enabled && position.is_some();
+        if visible.is_visible != is_visible {
+            visible.is_visible = is_visible;
+        }
+        if let Some((position, content_height)) = position.filter(|_| is_visible) {
+            // the UI is laid out with y pointing up, the bottom of the node in the layout is its
+            // top on the screen
+            let (left, bottom) = (Val::Px(position.x), Val::Px(content_height - position.y));
+            if style.position.left != left || style.position.bottom != bottom {
+                style.position.left = left;
+                style.position.bottom = bottom;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{VirtualCursor, VirtualCursorPlugin};
+    use bevy_app::{App, Events};
+    use bevy_core::CorePlugin;
+    use bevy_input::{
+        gamepad::{Gamepad, GamepadButtonType, GamepadEventRaw, GamepadEventType},
+        mouse::MouseButton,
+        Input, InputPlugin,
+    };
+    use bevy_window::{HeadlessWindowPlugin, WindowPlugin};
+
+    #[test]
+    fn click_in_the_same_frame() {
+        let mut app = App::new();
+        app.add_plugin(CorePlugin)
+            .add_plugin(InputPlugin)
+            .add_plugin(WindowPlugin::default())
+            .add_plugin(HeadlessWindowPlugin)
+            .add_plugin(VirtualCursorPlugin)
+            .insert_resource(VirtualCursor {
+                enabled: true,
+                ..Default::default()
+            });
+        app.update();
+
+        app.world
+            .get_resource_mut::<Events<GamepadEventRaw>>()
+            .unwrap()
+            .send(GamepadEventRaw(
+                Gamepad(0),
+                GamepadEventType::ButtonChanged(GamepadButtonType::South, 1.0),
+            ));
+        app.update();
+        let mouse_buttons = app.world.get_resource::<Input<MouseButton>>().unwrap();
+        assert!(mouse_buttons.just_pressed(MouseButton::Left));
+    }
+}
@@ -0,0 +1,182 @@
+use super::{Checkbox, Scrollbar, Slider};
+use bevy_ecs::{
+    change_detection::DetectChanges,
+    component::Component,
+    entity::Entity,
+    query::{Changed, With},
+    reflect::ReflectComponent,
+    world::World,
+};
+use bevy_log::warn;
+use bevy_reflect::{GetPath, Reflect, TypeRegistryArc};
+use bevy_utils::HashSet;
+
+/// Binds the value of a widget to a field of a component on another entity.
+///
+/// Every frame the bound field and the widget's value are kept in sync: changes made through
+/// the widget are written to the field, and changes made to the component are shown by the
+/// widget. If both changed in the same frame, the widget wins.
+///
+/// The bound component must be registered in the type registry with `#[reflect(Component)]`,
+/// and the bound field must have the same type as the widget's value.
+#[derive(Debug, Clone)]
+pub struct WidgetBinding {
+    pub target: Entity,
+    /// The type name of the bound component, as registered in the type registry.
+    pub component: String,
+    /// The reflection path to the bound field inside the component, for example `"volume"` or
+    /// `"audio.volume"`.
+    pub path: String,
+}
+
+impl WidgetBinding {
+    pub fn new<C: Component>(target: Entity, path: impl Into<String>) -> Self {
+        WidgetBinding {
+            target,
+            component: std::any::type_name::<C>().to_string(),
+            path: path.into(),
+        }
+    }
+}
+
+/// A widget whose value can be synchronized with a [`WidgetBinding`].
+pub trait BindableWidget: Component + Reflect {
+    /// The reflection path to the value of the widget.
+    const VALUE_PATH: &'static str;
+}
+
+impl BindableWidget for Checkbox {
+    const VALUE_PATH: &'static str = "checked";
+}
+
+impl BindableWidget for Slider {
+    const VALUE_PATH: &'static str = "value";
+}
+
+impl BindableWidget for Scrollbar {
+    const VALUE_PATH: &'static str = "position";
+}
+
+/// Synchronizes the value of every `W` widget with the field its [`WidgetBinding`] points to.
+pub fn widget_binding_system<W: BindableWidget>(world: &mut World) {
+    let type_registry = world.get_resource::<TypeRegistryArc>().unwrap().clone();
+    let type_registry = type_registry.read();
+
+    let changed_widgets = world
+        .query_filtered::<Entity, (With<WidgetBinding>, Changed<W>)>()
+        .iter(world)
+        .collect::<HashSet<_>>();
+    let bindings = world
+        .query_filtered::<(Entity, &WidgetBinding), With<W>>()
+        .iter(world)
+        .map(|(entity, binding)| (entity, binding.clone()))
+        .collect::<Vec<_>>();
+
+    for (entity, binding) in bindings {
+        let reflect_component = match type_registry
+            .get_with_name(&binding.component)
+            .and_then(|registration| registration.data::<ReflectComponent>())
+        {
+            Some(reflect_component) => reflect_component,
+            None => {
+                warn!(
+                    "Widget binding to {} is ignored because it is not a registered component.",
+                    binding.component
+                );
+                continue;
+            }
+        };
+
+        if changed_widgets.contains(&entity) {
+            let value = world
+                .get::<W>(entity)
+                .unwrap()
+                .path(W::VALUE_PATH)
+                .unwrap()
+                .clone_value();
+            let mut target = match reflect_component.reflect_component_mut(world, binding.target) {
+                Some(target) => target,
+                None => continue,
+            };
+            match target.path(&binding.path) {
+                Ok(field) if field.reflect_partial_eq(&*value) == Some(true) => {}
+                Ok(_) => target.path_mut(&binding.path).unwrap().apply(&*value),
+                Err(err) => warn!("Invalid widget binding path {}: {}", binding.path, err),
+            }
+        } else {
+            let target = match reflect_component.reflect_component_mut(world, binding.target) {
+                Some(target) if target.is_changed() => target,
+                _ => continue,
+            };
+            let value = match target.path(&binding.path) {
+                Ok(field) => field.clone_value(),
+                Err(err) => {
+                    warn!("Invalid widget binding path {}: {}", binding.path, err);
+                    continue;
+                }
+            };
+            let mut widget = world.get_mut::<W>(entity).unwrap();
+            if widget
+                .path(W::VALUE_PATH)
+                .unwrap()
+                .reflect_partial_eq(&*value)
+                != Some(true)
+            {
+                widget.path_mut(W::VALUE_PATH).unwrap().apply(&*value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{widget_binding_system, WidgetBinding};
+    use crate::widget::Slider;
+    use bevy_ecs::{
+        reflect::ReflectComponent,
+        schedule::{Stage, SystemStage},
+        system::IntoExclusiveSystem,
+        world::World,
+    };
+    use bevy_reflect::{Reflect, TypeRegistryArc};
+
+    #[derive(Default, Reflect)]
+    #[reflect(Component)]
+    struct Volume {
+        value: f32,
+    }
+
+    #[test]
+    fn slider_binding_syncs_both_ways() {
+        let mut world = World::default();
+        let type_registry = TypeRegistryArc::default();
+        type_registry.write().register::<Volume>();
+        world.insert_resource(type_registry);
+
+        let target = world.spawn().insert(Volume { value: 0.25 }).id();
+        let slider = world
+            .spawn()
+            .insert_bundle((
+                Slider {
+                    value: 0.5,
+                    ..Default::default()
+                },
+                WidgetBinding::new::<Volume>(target, "value"),
+            ))
+            .id();
+
+        let mut stage = SystemStage::single(widget_binding_system::<Slider>.exclusive_system());
+
+        // the newly spawned widget takes precedence over the bound field
+        stage.run(&mut world);
+        assert_eq!(world.get::<Volume>(target).unwrap().value, 0.5);
+
+        world.get_mut::<Volume>(target).unwrap().value = 0.75;
+        stage.run(&mut world);
+        assert_eq!(world.get::<Slider>(slider).unwrap().value, 0.75);
+
+        world.get_mut::<Slider>(slider).unwrap().value = 0.1;
+        stage.run(&mut world);
+        assert_eq!(world.get::<Volume>(target).unwrap().value, 0.1);
+    }
+}
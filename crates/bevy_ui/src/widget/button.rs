@@ -1,2 +1,69 @@
+use crate::{
+    entity::{ButtonBundle, TextBundle},
+    AccessibleName, AccessibleRole, AlignItems, Focusable, JustifyContent, Style,
+};
+use bevy_asset::Handle;
+use bevy_ecs::{blueprint::EntityBlueprint, entity::Entity, system::EntityCommands};
+use bevy_sprite::ColorMaterial;
+use bevy_text::Text;
+use bevy_transform::hierarchy::BuildChildren;
+
 #[derive(Debug, Clone)]
 pub struct Button;
+
+/// Builds a [`ButtonBundle`] with an optional centered text label as its child.
+///
+/// The text of the label is used as the [`AccessibleName`] of the button.
+#[derive(Debug, Clone)]
+pub struct ButtonBlueprint {
+    pub style: Style,
+    pub material: Handle<ColorMaterial>,
+    pub label: Option<Text>,
+}
+
+impl Default for ButtonBlueprint {
+    fn default() -> Self {
+        ButtonBlueprint {
+            style: Style {
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            material: Default::default(),
+            label: None,
+        }
+    }
+}
+
+impl EntityBlueprint for ButtonBlueprint {
+    /// The text label of the button, if it has one.
+    type Output = Option<Entity>;
+
+    fn build(self, entity: &mut EntityCommands) -> Option<Entity> {
+        entity.insert_bundle(ButtonBundle {
+            style: self.style,
+            material: self.material,
+            ..Default::default()
+        });
+        entity.insert_bundle((AccessibleRole::Button, Focusable));
+        if let Some(text) = self.label {
+            let name = text
+                .sections
+                .iter()
+                .map(|section| section.value.as_str())
+                .collect::<String>();
+            entity.insert(AccessibleName(name));
+            let label = entity
+                .commands()
+                .spawn_bundle(TextBundle {
+                    text,
+                    ..Default::default()
+                })
+                .id();
+            entity.push_children(&[label]);
+            Some(label)
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,377 @@
+use crate::{
+    entity::{NodeBundle, TextBundle},
+    AccessibleName, AccessibleRole, AccessibleValue, AlignItems, FlexDirection, FocusPolicy,
+    Focusable, Interaction, JustifyContent, Style, Val,
+};
+use bevy_app::EventWriter;
+use bevy_asset::Handle;
+use bevy_ecs::{
+    blueprint::EntityBlueprint,
+    entity::Entity,
+    query::{Changed, With, Without},
+    reflect::ReflectComponent,
+    system::{EntityCommands, Query},
+};
+use bevy_math::{Rect, Size};
+use bevy_reflect::Reflect;
+use bevy_render::draw::Visible;
+use bevy_sprite::ColorMaterial;
+use bevy_text::Text;
+use bevy_transform::{components::Children, hierarchy::BuildChildren};
+
+/// A widget that toggles between checked and unchecked when clicked.
+#[derive(Debug, Clone, Default, Reflect)]
+#[reflect(Component)]
+pub struct Checkbox {
+    pub checked: bool,
+}
+
+/// Marks the child node of a [`Checkbox`] that is only visible while the box is checked.
+#[derive(Debug, Clone, Default)]
+pub struct CheckboxMark;
+
+/// Builds a [`Checkbox`] with a [`CheckboxMark`] child.
+#[derive(Debug, Clone)]
+pub struct CheckboxBlueprint {
+    pub checked: bool,
+    pub style: Style,
+    pub material: Handle<ColorMaterial>,
+    pub mark_style: Style,
+    pub mark_material: Handle<ColorMaterial>,
+}
+
+impl Default for CheckboxBlueprint {
+    fn default() -> Self {
+        CheckboxBlueprint {
+            checked: false,
+            style: Style {
+                size: Size::new(Val::Px(24.0), Val::Px(24.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            material: Default::default(),
+            mark_style: Style {
+                size: Size::new(Val::Percent(60.0), Val::Percent(60.0)),
+                ..Default::default()
+            },
+            mark_material: Default::default(),
+        }
+    }
+}
+
+impl EntityBlueprint for CheckboxBlueprint {
+    /// The [`CheckboxMark`] of the checkbox.
+    type Output = Entity;
+
+    fn build(self, entity: &mut EntityCommands) -> Entity {
+        let CheckboxBlueprint {
+            checked,
+            style,
+            material,
+            mark_style,
+            mark_material,
+        } = self;
+        entity
+            .insert_bundle(NodeBundle {
+                style,
+                material,
+                ..Default::default()
+            })
+            .insert_bundle((
+                Checkbox { checked },
+                AccessibleRole::Checkbox,
+                AccessibleValue::Toggled(checked),
+                Interaction::default(),
+                FocusPolicy::default(),
+                Focusable,
+            ));
+        let mut mark = NodeBundle {
+            style: mark_style,
+            material: mark_material,
+            ..Default::default()
+        };
+        mark.visible.is_visible = checked;
+        let mark = entity
+            .commands()
+            .spawn_bundle(mark)
+            .insert_bundle((CheckboxMark, FocusPolicy::Pass))
+            .id();
+        entity.push_children(&[mark]);
+        mark
+    }
+}
+
+/// Builds a [`Checkbox`] made of a box followed by a text label, so that clicking the label
+/// toggles the checkbox too.
+///
+/// The [`Checkbox`] component is on the row containing the box and the label, and the text of
+/// the label is used as its [`AccessibleName`].
+#[derive(Debug, Clone)]
+pub struct LabeledCheckboxBlueprint {
+    /// The box of the checkbox.
+    pub checkbox: CheckboxBlueprint,
+    pub label: Text,
+    pub style: Style,
+    pub material: Handle<ColorMaterial>,
+    pub label_style: Style,
+}
+
+impl Default for LabeledCheckboxBlueprint {
+    fn default() -> Self {
+        LabeledCheckboxBlueprint {
+            checkbox: Default::default(),
+            label: Default::default(),
+            style: Style {
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            material: Default::default(),
+            label_style: Style {
+                margin: Rect {
+                    left: Val::Px(8.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        }
+    }
+}
+
+/// The entities spawned by a [`LabeledCheckboxBlueprint`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LabeledCheckboxParts {
+    /// The box of the checkbox.
+    pub box_node: Entity,
+    /// The [`CheckboxMark`], a child of `box_node`.
+    pub mark: Entity,
+    /// The text label.
+    pub label: Entity,
+}
+
+impl EntityBlueprint for LabeledCheckboxBlueprint {
+    type Output = LabeledCheckboxParts;
+
+    fn build(self, entity: &mut EntityCommands) -> LabeledCheckboxParts {
+        let LabeledCheckboxBlueprint {
+            checkbox:
+                CheckboxBlueprint {
+                    checked,
+                    style: box_style,
+                    material: box_material,
+                    mark_style,
+                    mark_material,
+                },
+            label,
+            style,
+            material,
+            label_style,
+        } = self;
+        let name = label
+            .sections
+            .iter()
+            .map(|section| section.value.as_str())
+            .collect::<String>();
+        entity
+            .insert_bundle(NodeBundle {
+                style,
+                material,
+                ..Default::default()
+            })
+            .insert_bundle((
+                Checkbox { checked },
+                AccessibleRole::Checkbox,
+                AccessibleName(name),
+                AccessibleValue::Toggled(checked),
+                Interaction::default(),
+                FocusPolicy::default(),
+                Focusable,
+            ));
+        let mut mark = NodeBundle {
+            style: mark_style,
+            material: mark_material,
+            ..Default::default()
+        };
+        mark.visible.is_visible = checked;
+        let commands = entity.commands();
+        let mark = commands
+            .spawn_bundle(mark)
+            .insert_bundle((CheckboxMark, FocusPolicy::Pass))
+            .id();
+        let box_node = commands
+            .spawn_bundle(NodeBundle {
+                style: box_style,
+                material: box_material,
+                ..Default::default()
+            })
+            .insert(FocusPolicy::Pass)
+            .push_children(&[mark])
+            .id();
+        let label = commands
+            .spawn_bundle(TextBundle {
+                style: label_style,
+                text: label,
+                ..Default::default()
+            })
+            .insert(FocusPolicy::Pass)
+            .id();
+        entity.push_children(&[box_node, label]);
+        LabeledCheckboxParts {
+            box_node,
+            mark,
+            label,
+        }
+    }
+}
+
+/// Sent when a [`Checkbox`] is toggled by a click or by the keyboard, but not when its state is
+/// changed by the app.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ToggleChanged {
+    pub entity: Entity,
+    pub checked: bool,
+}
+
+/// Toggles a [`Checkbox`] whenever it is clicked.
+pub fn checkbox_system(
+    mut toggle_changed_events: EventWriter<ToggleChanged>,
+    mut query: Query<(Entity, &Interaction, &mut Checkbox), Changed<Interaction>>,
+) {
+    for (entity, interaction, mut checkbox) in query.iter_mut() {
+        if *interaction == Interaction::Clicked {
+            checkbox.checked = !checkbox.checked;
+            toggle_changed_events.send(ToggleChanged {
+                entity,
+                checked: checkbox.checked,
+            });
+        }
+    }
+}
+
+/// Shows or hides the [`CheckboxMark`]s among the children and grandchildren of a [`Checkbox`] to
+/// match its state.
+pub fn checkbox_mark_system(
+    checkbox_query: Query<(&Checkbox, &Children), Changed<Checkbox>>,
+    children_query: Query<&Children, Without<Checkbox>>,
+    mut mark_query: Query<&mut Visible, With<CheckboxMark>>,
+) {
+    for (checkbox, children) in checkbox_query.iter() {
+        let grandchildren = children
+            .iter()
+            .filter_map(|child| children_query.get(*child).ok())
+            .flat_map(|children| children.iter());
+        for child in children.iter().chain(grandchildren) {
+            if let Ok(mut visible) = mark_query.get_mut(*child) {
+                visible.is_visible = checkbox.checked;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        checkbox_system, Checkbox, CheckboxBlueprint, CheckboxMark, LabeledCheckboxBlueprint,
+        ToggleChanged,
+    };
+    use crate::{ui_focus_system, AccessibleName, FlexSurface, FocusPolicy, Node};
+    use bevy_app::{App, CoreStage, Events, ManualEventReader};
+    use bevy_core::CorePlugin;
+    use bevy_ecs::blueprint::{BlueprintTester, CommandsBlueprint, WorldBlueprint};
+    use bevy_input::{mouse::MouseButton, touch::Touches, Input};
+    use bevy_math::Vec2;
+    use bevy_render::draw::Visible;
+    use bevy_text::Text;
+    use bevy_transform::{components::GlobalTransform, hierarchy::HierarchyAssertions};
+    use bevy_window::{HeadlessWindowPlugin, WindowPlugin, Windows};
+
+    #[test]
+    fn click_toggles_the_checkbox() {
+        let mut app = App::new();
+        app.add_plugin(CorePlugin)
+            .add_plugin(WindowPlugin::default())
+            .add_plugin(HeadlessWindowPlugin)
+            .init_resource::<FlexSurface>()
+            .init_resource::<Input<MouseButton>>()
+            .init_resource::<Touches>()
+            .add_event::<ToggleChanged>()
+            .add_system_to_stage(CoreStage::PreUpdate, ui_focus_system)
+            .add_system(checkbox_system);
+        let entity = app.world.spawn().id();
+        CommandsBlueprint(CheckboxBlueprint::default()).build(&mut app.world, entity);
+        app.world.get_mut::<Node>(entity).unwrap().size = Vec2::new(20.0, 20.0);
+        *app.world.get_mut::<GlobalTransform>(entity).unwrap() =
+            GlobalTransform::from_xyz(50.0, 50.0, 0.0);
+        app.update();
+
+        let mut reader = ManualEventReader::<ToggleChanged>::default();
+        let mut click = |app: &mut App| {
+            app.world
+                .get_resource_mut::<Windows>()
+                .unwrap()
+                .get_primary_mut()
+                .unwrap()
+                .update_cursor_position_from_backend(Some(Vec2::new(50.0, 50.0)));
+            // the input is cleared at the start of each frame, as by the input systems
+            let mut mouse_button_input =
+                app.world.get_resource_mut::<Input<MouseButton>>().unwrap();
+            mouse_button_input.clear();
+            mouse_button_input.press(MouseButton::Left);
+            app.update();
+            let mut mouse_button_input =
+                app.world.get_resource_mut::<Input<MouseButton>>().unwrap();
+            mouse_button_input.clear();
+            mouse_button_input.release(MouseButton::Left);
+            // the release resets the interaction
+            app.update();
+            let events = app.world.get_resource::<Events<ToggleChanged>>().unwrap();
+            reader.iter(events).copied().collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            click(&mut app),
+            vec![ToggleChanged {
+                entity,
+                checked: true
+            }]
+        );
+        assert!(app.world.get::<Checkbox>(entity).unwrap().checked);
+        assert_eq!(
+            click(&mut app),
+            vec![ToggleChanged {
+                entity,
+                checked: false
+            }]
+        );
+    }
+
+    #[test]
+    fn labeled_checkbox_blueprint() {
+        let mut tester = BlueprintTester::default();
+        let (entity, parts) = tester.build(LabeledCheckboxBlueprint {
+            checkbox: CheckboxBlueprint {
+                checked: true,
+                ..Default::default()
+            },
+            label: Text::with_section("Sound", Default::default(), Default::default()),
+            ..Default::default()
+        });
+
+        let checkbox = tester.entity(entity);
+        checkbox
+            .assert_has::<Checkbox>()
+            .assert_component(&AccessibleName("Sound".to_string()))
+            .assert_child_count(2);
+        assert!(checkbox.component::<Checkbox>().checked);
+
+        let box_node = checkbox.child(0);
+        assert_eq!(box_node.entity, parts.box_node);
+        assert_eq!(*box_node.component::<FocusPolicy>(), FocusPolicy::Pass);
+        let mark = box_node.child(0);
+        assert_eq!(mark.entity, parts.mark);
+        mark.assert_has::<CheckboxMark>();
+        assert!(mark.component::<Visible>().is_visible);
+        assert_eq!(checkbox.child(1).entity, parts.label);
+    }
+}
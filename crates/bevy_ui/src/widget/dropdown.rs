@@ -0,0 +1,513 @@
+use crate::{
+    entity::{NodeBundle, TextBundle},
+    node_cursor_position, AccessibleRole, AccessibleValue, AlignItems, Display, FlexDirection,
+    FlexSurface, FocusPolicy, Focusable, FocusedEntity, Interaction, JustifyContent, Node,
+    PositionType, Style, UiOverlay, Val,
+};
+use bevy_app::EventWriter;
+use bevy_asset::Handle;
+use bevy_ecs::{
+    blueprint::EntityBlueprint,
+    entity::Entity,
+    query::{Changed, Or, With},
+    system::{EntityCommands, Query, Res, ResMut},
+};
+use bevy_input::{mouse::MouseButton, touch::Touches, Input};
+use bevy_math::{Rect, Size, Vec2};
+use bevy_render::draw::Visible;
+use bevy_sprite::ColorMaterial;
+use bevy_text::{Text, TextStyle};
+use bevy_transform::{
+    components::{Children, GlobalTransform},
+    hierarchy::BuildChildren,
+};
+use bevy_window::Windows;
+
+/// A widget showing the selected option of a list, that opens the list below it when clicked to
+/// select another option.
+///
+/// The open list is a [`UiOverlay`], drawn above the other nodes. It is closed when an option is
+/// selected, or when the mouse is pressed outside of it.
+#[derive(Debug, Clone, Default)]
+pub struct Dropdown {
+    /// The text of the options. Changing them after the dropdown is built only changes its
+    /// label, not its list.
+    pub options: Vec<String>,
+    pub open: bool,
+}
+
+/// The index of the selected option of a [`Dropdown`], if any.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SelectedOption(pub Option<usize>);
+
+/// Sent when an option of a [`Dropdown`] is selected from its list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DropdownChanged {
+    pub entity: Entity,
+    /// The index of the selected option.
+    pub selected: usize,
+}
+
+/// Marks the text node of a [`Dropdown`] that shows its selected option.
+#[derive(Debug, Clone, Default)]
+pub struct DropdownLabel;
+
+/// Marks the list of options of a [`Dropdown`].
+#[derive(Debug, Clone, Default)]
+pub struct DropdownList;
+
+/// An option in the list of a [`Dropdown`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DropdownOption {
+    pub dropdown: Entity,
+    pub index: usize,
+}
+
+/// Builds a [`Dropdown`] with a [`DropdownLabel`] child, and a [`DropdownList`] child holding a
+/// [`DropdownOption`] node for each option.
+#[derive(Debug, Clone)]
+pub struct DropdownBlueprint {
+    pub options: Vec<String>,
+    pub selected: Option<usize>,
+    pub style: Style,
+    pub material: Handle<ColorMaterial>,
+    /// The style of the text of the label and of the options.
+    pub text_style: TextStyle,
+    pub list_material: Handle<ColorMaterial>,
+    pub option_style: Style,
+    pub option_material: Handle<ColorMaterial>,
+}
+
+impl Default for DropdownBlueprint {
+    fn default() -> Self {
+        DropdownBlueprint {
+            options: Vec::new(),
+            selected: None,
+            style: Style {
+                size: Size::new(Val::Px(200.0), Val::Px(40.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            material: Default::default(),
+            text_style: Default::default(),
+            list_material: Default::default(),
+            option_style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Px(40.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            option_material: Default::default(),
+        }
+    }
+}
+
+/// The entities spawned by a [`DropdownBlueprint`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DropdownParts {
+    pub label: Entity,
+    pub list: Entity,
+    /// The [`DropdownOption`]s, children of `list`.
+    pub options: Vec<Entity>,
+}
+
+impl EntityBlueprint for DropdownBlueprint {
+    type Output = DropdownParts;
+
+    fn build(self, entity: &mut EntityCommands) -> DropdownParts {
+        let DropdownBlueprint {
+            options,
+            selected,
+            style,
+            material,
+            text_style,
+            list_material,
+            option_style,
+            option_material,
+        } = self;
+        let dropdown = entity.id();
+        let selected_text = selected
+            .and_then(|index| options.get(index))
+            .cloned()
+            .unwrap_or_default();
+        entity
+            .insert_bundle(NodeBundle {
+                style,
+                material,
+                ..Default::default()
+            })
+            .insert_bundle((
+                Dropdown {
+                    options: options.clone(),
+                    open: false,
+                },
+                SelectedOption(selected),
+                AccessibleRole::Dropdown,
+                AccessibleValue::Text(selected_text.clone()),
+                Interaction::default(),
+                FocusPolicy::default(),
+                Focusable,
+            ));
+
+        let commands = entity.commands();
+        let label = commands
+            .spawn_bundle(TextBundle {
+                text: Text::with_section(selected_text, text_style.clone(), Default::default()),
+                ..Default::default()
+            })
+            .insert_bundle((DropdownLabel, FocusPolicy::Pass))
+            .id();
+        let option_entities = options
+            .iter()
+            .enumerate()
+            .map(|(index, option)| {
+                let mut option_text = TextBundle {
+                    text: Text::with_section(option, text_style.clone(), Default::default()),
+                    ..Default::default()
+                };
+                option_text.visible.is_visible = false;
+                let option_text = commands
+                    .spawn_bundle(option_text)
+                    .insert(FocusPolicy::Pass)
+                    .id();
+                let mut option_node = NodeBundle {
+                    style: option_style.clone(),
+                    material: option_material.clone(),
+                    ..Default::default()
+                };
+                option_node.visible.is_visible = false;
+                commands
+                    .spawn_bundle(option_node)
+                    .insert_bundle((
+                        DropdownOption { dropdown, index },
+                        AccessibleRole::ListItem,
+                        Interaction::default(),
+                        FocusPolicy::default(),
+                        Focusable,
+                    ))
+                    .push_children(&[option_text])
+                    .id()
+            })
+            .collect::<Vec<_>>();
+        let mut list = NodeBundle {
+            style: Style {
+                display: Display::None,
+                position_type: PositionType::Absolute,
+                // the UI is laid out with y pointing up, this puts the list below the dropdown
+                position: Rect {
+                    left: Val::Px(0.0),
+                    bottom: Val::Percent(100.0),
+                    ..Default::default()
+                },
+                size: Size::new(Val::Percent(100.0), Val::Auto),
+                flex_direction: FlexDirection::ColumnReverse,
+                ..Default::default()
+            },
+            material: list_material,
+            ..Default::default()
+        };
+        list.visible.is_visible = false;
+        let list = commands
+            .spawn_bundle(list)
+            .insert_bundle((DropdownList, UiOverlay, FocusPolicy::Block))
+            .push_children(&option_entities)
+            .id();
+        entity.push_children(&[label, list]);
+        DropdownParts {
+            label,
+            list,
+            options: option_entities,
+        }
+    }
+}
+
+/// Opens or closes a [`Dropdown`] when it is clicked, selects the option clicked in its list, and
+/// closes it when the mouse is pressed outside of it.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn dropdown_system(
+    windows: Res<Windows>,
+    flex_surface: Res<FlexSurface>,
+    mouse_button_input: Res<Input<MouseButton>>,
+    touches_input: Res<Touches>,
+    mut focused_entity: ResMut<FocusedEntity>,
+    mut dropdown_changed_events: EventWriter<DropdownChanged>,
+    mut dropdown_query: Query<(Entity, &mut Dropdown, &mut SelectedOption, &Children)>,
+    clicked_dropdown_query: Query<(Entity, &Interaction), (Changed<Interaction>, With<Dropdown>)>,
+    option_query: Query<(Entity, &DropdownOption, &Interaction), Changed<Interaction>>,
+    node_query: Query<(&Node, &GlobalTransform)>,
+    list_query: Query<(), With<DropdownList>>,
+) {
+    for (entity, interaction) in clicked_dropdown_query.iter() {
+        if *interaction == Interaction::Clicked {
+            if let Ok((_, mut dropdown, ..)) = dropdown_query.get_mut(entity) {
+                dropdown.open = !dropdown.open;
+            }
+        }
+    }
+
+    for (option_entity, option, interaction) in option_query.iter() {
+        if *interaction != Interaction::Clicked {
+            continue;
+        }
+        if let Ok((entity, mut dropdown, mut selected, _)) = dropdown_query.get_mut(option.dropdown)
+        {
+            dropdown.open = false;
+            if selected.0 != Some(option.index) {
+                selected.0 = Some(option.index);
+                dropdown_changed_events.send(DropdownChanged {
+                    entity,
+                    selected: option.index,
+                });
+            }
+            // give the focus back to the dropdown, as its list is closed
+            if focused_entity.0 == Some(option_entity) {
+                focused_entity.0 = Some(entity);
+            }
+        }
+    }
+
+    if !mouse_button_input.just_pressed(MouseButton::Left)
+        && touches_input.iter_just_released().next().is_none()
+    {
+        return;
+    }
+    let contains_cursor = |entity: Entity| {
+        let (node, global_transform) = match node_query.get(entity) {
+            Ok(node) => node,
+            Err(_) => return false,
+        };
+        let center = global_transform.translation.truncate();
+        let (min, max) = (center - node.size / 2.0, center + node.size / 2.0);
+        node_cursor_position(&windows, &flex_surface, entity).is_some_and(|cursor: Vec2| {
+            (min.x..max.x).contains(&cursor.x) && (min.y..max.y).contains(&cursor.y)
+        })
+    };
+    for (entity, mut dropdown, _, children) in dropdown_query.iter_mut() {
+        if !dropdown.open || contains_cursor(entity) {
+            continue;
+        }
+        let list = children
+            .iter()
+            .find(|child| list_query.get(**child).is_ok());
+        if !list.is_some_and(|list| contains_cursor(*list)) {
+            dropdown.open = false;
+        }
+    }
+}
+
+/// Shows the list of a [`Dropdown`] while it is open, and hides it when it is closed.
+pub fn dropdown_list_system(
+    dropdown_query: Query<(&Dropdown, &Children), Changed<Dropdown>>,
+    mut list_query: Query<&mut Style, With<DropdownList>>,
+    children_query: Query<&Children>,
+    mut visible_query: Query<&mut Visible>,
+) {
+    for (dropdown, children) in dropdown_query.iter() {
+        for child in children.iter() {
+            if let Ok(mut style) = list_query.get_mut(*child) {
+                style.display = if dropdown.open {
+                    Display::Flex
+                } else {
+                    Display::None
+                };
+                set_visible_recursive(*child, dropdown.open, &children_query, &mut visible_query);
+            }
+        }
+    }
+}
+
+fn set_visible_recursive(
+    entity: Entity,
+    is_visible: bool,
+    children_query: &Query<&Children>,
+    visible_query: &mut Query<&mut Visible>,
+) {
+    if let Ok(mut visible) = visible_query.get_mut(entity) {
+        visible.is_visible = is_visible;
+    }
+    if let Ok(children) = children_query.get(entity) {
+        for child in children.iter() {
+            set_visible_recursive(*child, is_visible, children_query, visible_query);
+        }
+    }
+}
+
+/// Shows the selected option of a [`Dropdown`] in its [`DropdownLabel`].
+#[allow(clippy::type_complexity)]
+pub fn dropdown_label_system(
+    dropdown_query: Query<
+        (&Dropdown, &SelectedOption, &Children),
+        Or<(Changed<Dropdown>, Changed<SelectedOption>)>,
+    >,
+    mut label_query: Query<&mut Text, With<DropdownLabel>>,
+) {
+    for (dropdown, selected, children) in dropdown_query.iter() {
+        let selected_text = selected
+            .0
+            .and_then(|index| dropdown.options.get(index))
+            .cloned()
+            .unwrap_or_default();
+        for child in children.iter() {
+            if let Ok(mut text) = label_query.get_mut(*child) {
+                if let Some(section) = text.sections.first_mut() {
+                    if section.value != selected_text {
+                        section.value = selected_text.clone();
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        dropdown_system, Dropdown, DropdownBlueprint, DropdownChanged, DropdownLabel, DropdownList,
+        DropdownOption, SelectedOption,
+    };
+    use crate::{Display, FlexSurface, FocusedEntity, Interaction, Node, Style, UiOverlay};
+    use bevy_app::{App, Events, ManualEventReader};
+    use bevy_core::CorePlugin;
+    use bevy_ecs::{
+        blueprint::{BlueprintTester, CommandsBlueprint, WorldBlueprint},
+        entity::Entity,
+    };
+    use bevy_input::{mouse::MouseButton, touch::Touches, Input};
+    use bevy_math::Vec2;
+    use bevy_render::draw::Visible;
+    use bevy_text::Text;
+    use bevy_transform::{components::GlobalTransform, hierarchy::HierarchyAssertions};
+    use bevy_window::{HeadlessWindowPlugin, WindowPlugin, Windows};
+
+    fn set_interaction(app: &mut App, entity: Entity, interaction: Interaction) {
+        *app.world.get_mut::<Interaction>(entity).unwrap() = interaction;
+    }
+
+    fn is_open(app: &App, entity: Entity) -> bool {
+        app.world.get::<Dropdown>(entity).unwrap().open
+    }
+
+    /// Presses the mouse with the cursor at `position`.
+    fn click_at(app: &mut App, position: Vec2) {
+        app.world
+            .get_resource_mut::<Windows>()
+            .unwrap()
+            .get_primary_mut()
+            .unwrap()
+            .update_cursor_position_from_backend(Some(position));
+        app.world
+            .get_resource_mut::<Input<MouseButton>>()
+            .unwrap()
+            .press(MouseButton::Left);
+        app.update();
+        let mut mouse_button_input = app.world.get_resource_mut::<Input<MouseButton>>().unwrap();
+        mouse_button_input.release(MouseButton::Left);
+        mouse_button_input.clear();
+    }
+
+    #[test]
+    fn open_select_and_close() {
+        let mut app = App::new();
+        app.add_plugin(CorePlugin)
+            .add_plugin(WindowPlugin::default())
+            .add_plugin(HeadlessWindowPlugin)
+            .init_resource::<FlexSurface>()
+            .init_resource::<FocusedEntity>()
+            .init_resource::<Input<MouseButton>>()
+            .init_resource::<Touches>()
+            .add_event::<DropdownChanged>()
+            .add_system(dropdown_system);
+        let entity = app.world.spawn().id();
+        let parts = CommandsBlueprint(DropdownBlueprint {
+            options: vec!["Low".to_string(), "High".to_string()],
+            ..Default::default()
+        })
+        .build(&mut app.world, entity);
+        // the dropdown, with its open list below it
+        let mut place = |entity, center: Vec2, size: Vec2| {
+            app.world.get_mut::<Node>(entity).unwrap().size = size;
+            *app.world.get_mut::<GlobalTransform>(entity).unwrap() =
+                GlobalTransform::from_translation(center.extend(0.0));
+        };
+        place(entity, Vec2::new(100.0, 300.0), Vec2::new(200.0, 40.0));
+        place(parts.list, Vec2::new(100.0, 240.0), Vec2::new(200.0, 80.0));
+        app.update();
+
+        set_interaction(&mut app, entity, Interaction::Clicked);
+        app.update();
+        assert!(is_open(&app, entity));
+
+        set_interaction(&mut app, entity, Interaction::None);
+        set_interaction(&mut app, parts.options[1], Interaction::Clicked);
+        app.update();
+        assert!(!is_open(&app, entity));
+        assert_eq!(
+            *app.world.get::<SelectedOption>(entity).unwrap(),
+            SelectedOption(Some(1))
+        );
+        let events = app.world.get_resource::<Events<DropdownChanged>>().unwrap();
+        let changes = ManualEventReader::<DropdownChanged>::default()
+            .iter(events)
+            .copied()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            changes,
+            vec![DropdownChanged {
+                entity,
+                selected: 1
+            }]
+        );
+
+        set_interaction(&mut app, parts.options[1], Interaction::None);
+        set_interaction(&mut app, entity, Interaction::Clicked);
+        app.update();
+        assert!(is_open(&app, entity));
+        // a click in the list keeps it open, a click outside closes it
+        click_at(&mut app, Vec2::new(100.0, 230.0));
+        assert!(is_open(&app, entity));
+        click_at(&mut app, Vec2::new(600.0, 500.0));
+        assert!(!is_open(&app, entity));
+    }
+
+    #[test]
+    fn dropdown_blueprint() {
+        let mut tester = BlueprintTester::default();
+        let (entity, parts) = tester.build(DropdownBlueprint {
+            options: vec!["Low".to_string(), "High".to_string()],
+            selected: Some(1),
+            ..Default::default()
+        });
+
+        let dropdown = tester.entity(entity);
+        dropdown.assert_has::<Dropdown>().assert_child_count(2);
+        assert!(!dropdown.component::<Dropdown>().open);
+
+        let label = dropdown.child(0);
+        assert_eq!(label.entity, parts.label);
+        label.assert_has::<DropdownLabel>();
+        assert_eq!(label.component::<Text>().sections[0].value, "High");
+
+        let list = dropdown.child(1);
+        assert_eq!(list.entity, parts.list);
+        list.assert_has::<DropdownList>()
+            .assert_has::<UiOverlay>()
+            .assert_child_count(2);
+        assert_eq!(list.component::<Style>().display, Display::None);
+        assert!(!list.component::<Visible>().is_visible);
+
+        let option = list.child(1);
+        assert_eq!(option.entity, parts.options[1]);
+        assert_eq!(
+            *option.component::<DropdownOption>(),
+            DropdownOption {
+                dropdown: entity,
+                index: 1
+            }
+        );
+        assert_eq!(
+            option.child(0).component::<Text>().sections[0].value,
+            "High"
+        );
+    }
+}
@@ -1,16 +1,21 @@
-use crate::CalculatedSize;
+use crate::{CalculatedSize, Node};
 use bevy_asset::{Assets, Handle};
-use bevy_ecs::{
-    query::With,
-    system::{Query, Res},
-};
-use bevy_math::Size;
+use bevy_ecs::system::{Query, Res};
+use bevy_math::{Rect, Size, Vec4};
 use bevy_render::texture::Texture;
 use bevy_sprite::ColorMaterial;
 
 #[derive(Debug, Clone)]
 pub enum Image {
+    /// The node is sized like the texture, unless its style sets its size.
     KeepAspect,
+    /// The texture is stretched to the size of the node without distorting its borders, given in
+    /// texture pixels: the corners keep their size, with a texture pixel per logical pixel, the
+    /// edges are only stretched along the edges of the node, and the center fills the rest. The
+    /// borders shrink if the node is smaller than them.
+    ///
+    /// This can be used on any node with a textured material, such as a button background.
+    Sliced(Rect<f32>),
 }
 
 impl Default for Image {
@@ -19,12 +24,33 @@ impl Default for Image {
     }
 }
 
+/// Sizes the nodes of [`Image::KeepAspect`] images like their texture, and sets the borders of
+/// [`Image::Sliced`] images.
 pub fn image_node_system(
     materials: Res<Assets<ColorMaterial>>,
     textures: Res<Assets<Texture>>,
-    mut query: Query<(&mut CalculatedSize, &Handle<ColorMaterial>), With<Image>>,
+    mut query: Query<(
+        &Image,
+        &mut Node,
+        Option<&mut CalculatedSize>,
+        &Handle<ColorMaterial>,
+    )>,
 ) {
-    for (mut calculated_size, material_handle) in query.iter_mut() {
+    for (image, mut node, calculated_size, material_handle) in query.iter_mut() {
+        let slice = match image {
+            Image::KeepAspect => Vec4::ZERO,
+            Image::Sliced(border) => {
+                Vec4::new(border.left, border.right, border.top, border.bottom)
+            }
+        };
+        if node.slice != slice {
+            node.slice = slice;
+        }
+
+        let mut calculated_size = match (image, calculated_size) {
+            (Image::KeepAspect, Some(calculated_size)) => calculated_size,
+            _ => continue,
+        };
         if let Some(texture) = materials
             .get(material_handle)
             .and_then(|material| material.texture.as_ref())
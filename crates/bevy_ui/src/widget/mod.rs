@@ -1,7 +1,19 @@
+mod binding;
 mod button;
+mod checkbox;
+mod dropdown;
 mod image;
+mod scroll_view;
+mod scrollbar;
+mod slider;
 mod text;
 
+pub use binding::*;
 pub use button::*;
+pub use checkbox::*;
+pub use dropdown::*;
 pub use image::*;
+pub use scroll_view::*;
+pub use scrollbar::*;
+pub use slider::*;
 pub use text::*;
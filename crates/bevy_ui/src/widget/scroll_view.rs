@@ -0,0 +1,462 @@
+use super::{Scrollbar, ScrollbarBlueprint};
+use crate::{
+    entity::NodeBundle, node_cursor_position, FlexDirection, FlexSurface, Interaction, Node,
+    Orientation, Overflow, PositionType, Style, Val,
+};
+use bevy_app::EventReader;
+use bevy_asset::Handle;
+use bevy_core::FloatOrd;
+use bevy_ecs::{
+    blueprint::EntityBlueprint,
+    change_detection::DetectChanges,
+    entity::Entity,
+    query::{Changed, With},
+    reflect::ReflectComponent,
+    system::{EntityCommands, Query, Res},
+};
+use bevy_input::{
+    mouse::{MouseScrollUnit, MouseWheel},
+    touch::Touches,
+};
+use bevy_math::{Rect, Size, Vec2};
+use bevy_reflect::Reflect;
+use bevy_sprite::ColorMaterial;
+use bevy_transform::{
+    components::{Children, GlobalTransform},
+    hierarchy::BuildChildren,
+};
+use bevy_window::Windows;
+
+/// A viewport showing a part of its [`ScrollContent`] child, scrolled with the mouse wheel over
+/// it, by dragging it on a touch screen, or with its [`Scrollbar`] children.
+///
+/// The viewport should have [`Overflow::Hidden`] so that the content is clipped to it.
+#[derive(Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct ScrollView {
+    pub horizontal: bool,
+    pub vertical: bool,
+    /// The distance scrolled for each line of mouse wheel movement.
+    pub line_length: f32,
+}
+
+impl Default for ScrollView {
+    fn default() -> Self {
+        ScrollView {
+            horizontal: false,
+            vertical: true,
+            line_length: 20.0,
+        }
+    }
+}
+
+/// The scroll offset of a [`ScrollView`], from the top left of its content with `y` pointing
+/// down. It is kept in the range where the viewport is filled by the content.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Reflect)]
+#[reflect(Component, PartialEq)]
+pub struct ScrollPosition {
+    pub offset: Vec2,
+}
+
+/// Marks the child of a [`ScrollView`] that is moved by its [`ScrollPosition`].
+#[derive(Debug, Clone, Default)]
+pub struct ScrollContent;
+
+/// Builds a [`ScrollView`] with a [`ScrollContent`] child, and a [`Scrollbar`] child for each
+/// scrolled axis if `scrollbar_materials` is set.
+///
+/// The content is absolutely positioned in the viewport, so `content_style` should give it the
+/// size of the viewport on the axes that are not scrolled.
+#[derive(Debug, Clone)]
+pub struct ScrollViewBlueprint {
+    pub scroll_view: ScrollView,
+    pub style: Style,
+    pub material: Handle<ColorMaterial>,
+    pub content_style: Style,
+    pub content_material: Handle<ColorMaterial>,
+    /// The materials of the track and of the thumb of the scrollbars.
+    pub scrollbar_materials: Option<(Handle<ColorMaterial>, Handle<ColorMaterial>)>,
+    pub scrollbar_thickness: f32,
+}
+
+impl Default for ScrollViewBlueprint {
+    fn default() -> Self {
+        ScrollViewBlueprint {
+            scroll_view: Default::default(),
+            style: Style {
+                size: Size::new(Val::Px(300.0), Val::Px(200.0)),
+                overflow: Overflow::Hidden,
+                ..Default::default()
+            },
+            material: Default::default(),
+            content_style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Auto),
+                flex_direction: FlexDirection::ColumnReverse,
+                ..Default::default()
+            },
+            content_material: Default::default(),
+            scrollbar_materials: None,
+            scrollbar_thickness: 12.0,
+        }
+    }
+}
+
+/// The entities spawned by a [`ScrollViewBlueprint`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScrollViewParts {
+    /// The [`ScrollContent`], to spawn the scrolled nodes into.
+    pub content: Entity,
+    pub horizontal_scrollbar: Option<Entity>,
+    pub vertical_scrollbar: Option<Entity>,
+}
+
+impl EntityBlueprint for ScrollViewBlueprint {
+    type Output = ScrollViewParts;
+
+    fn build(self, entity: &mut EntityCommands) -> ScrollViewParts {
+        let ScrollViewBlueprint {
+            scroll_view,
+            style,
+            material,
+            mut content_style,
+            content_material,
+            scrollbar_materials,
+            scrollbar_thickness,
+        } = self;
+        let (horizontal, vertical) = (scroll_view.horizontal, scroll_view.vertical);
+        entity
+            .insert_bundle(NodeBundle {
+                style,
+                material,
+                ..Default::default()
+            })
+            .insert_bundle((scroll_view, ScrollPosition::default()));
+
+        content_style.position_type = PositionType::Absolute;
+        update_content_position(&mut content_style, Vec2::ZERO);
+        let commands = entity.commands();
+        let content = commands
+            .spawn_bundle(NodeBundle {
+                style: content_style,
+                material: content_material,
+                ..Default::default()
+            })
+            .insert(ScrollContent)
+            .id();
+        let mut scrollbar = |orientation, enabled| {
+            let (material, thumb_material) = scrollbar_materials.clone().filter(|_| enabled)?;
+            // the UI is laid out with y pointing up, the scrollbars are on the right and bottom
+            // edges of the viewport
+            let style = match orientation {
+                Orientation::Horizontal => Style {
+                    position_type: PositionType::Absolute,
+                    position: Rect {
+                        left: Val::Px(0.0),
+                        top: Val::Px(0.0),
+                        ..Default::default()
+                    },
+                    size: Size::new(Val::Percent(100.0), Val::Px(scrollbar_thickness)),
+                    ..Default::default()
+                },
+                Orientation::Vertical => Style {
+                    position_type: PositionType::Absolute,
+                    position: Rect {
+                        right: Val::Px(0.0),
+                        bottom: Val::Px(0.0),
+                        ..Default::default()
+                    },
+                    size: Size::new(Val::Px(scrollbar_thickness), Val::Percent(100.0)),
+                    ..Default::default()
+                },
+            };
+            let mut scrollbar = commands.spawn();
+            ScrollbarBlueprint {
+                scrollbar: Scrollbar {
+                    orientation,
+                    ..Default::default()
+                },
+                style,
+                material,
+                thumb_material,
+            }
+            .build(&mut scrollbar);
+            Some(scrollbar.id())
+        };
+        let horizontal_scrollbar = scrollbar(Orientation::Horizontal, horizontal);
+        let vertical_scrollbar = scrollbar(Orientation::Vertical, vertical);
+        let children = std::iter::once(content)
+            .chain(horizontal_scrollbar)
+            .chain(vertical_scrollbar)
+            .collect::<Vec<_>>();
+        entity.push_children(&children);
+        ScrollViewParts {
+            content,
+            horizontal_scrollbar,
+            vertical_scrollbar,
+        }
+    }
+}
+
+fn update_content_position(style: &mut Style, offset: Vec2) {
+    // the UI is laid out with y pointing up, the top of the content is its bottom in the layout
+    style.position.left = Val::Px(-offset.x);
+    style.position.bottom = Val::Px(-offset.y);
+}
+
+/// Scrolls the [`ScrollView`]s with the mouse wheel over them, with the touches that started on
+/// them and with their [`Scrollbar`]s, and keeps their scrollbars in sync with their content.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn scroll_view_system(
+    windows: Res<Windows>,
+    flex_surface: Res<FlexSurface>,
+    touches_input: Res<Touches>,
+    mut mouse_wheel_events: EventReader<MouseWheel>,
+    mut view_query: Query<(Entity, &ScrollView, &mut ScrollPosition, &Node, &Children)>,
+    view_node_query: Query<(Entity, &Node, &GlobalTransform), With<ScrollView>>,
+    content_query: Query<&Node, With<ScrollContent>>,
+    mut scrollbar_query: Query<(&mut Scrollbar, &Interaction)>,
+) {
+    let mut wheel_lines = Vec2::ZERO;
+    let mut wheel_pixels = Vec2::ZERO;
+    for event in mouse_wheel_events.iter() {
+        match event.unit {
+            MouseScrollUnit::Line => wheel_lines += Vec2::new(event.x, event.y),
+            MouseScrollUnit::Pixel => wheel_pixels += Vec2::new(event.x, event.y),
+        }
+    }
+
+    // the innermost view under a point is the one drawn above the others
+    let view_at = |point: &dyn Fn(Entity) -> Option<Vec2>| {
+        view_node_query
+            .iter()
+            .filter(|(entity, node, global_transform)| {
+                let center = global_transform.translation.truncate();
+                let (min, max) = (center - node.size / 2.0, center + node.size / 2.0);
+                point(*entity).is_some_and(|point| {
+                    (min.x..max.x).contains(&point.x)
+                        && (min.y..max.y).contains(&point.y)
+                        && node.clip_contains(point)
+                })
+            })
+            .max_by_key(|(_, _, global_transform)| FloatOrd(global_transform.translation.z))
+            .map(|(entity, ..)| entity)
+    };
+    let wheel_view = if wheel_lines != Vec2::ZERO || wheel_pixels != Vec2::ZERO {
+        view_at(&|entity| node_cursor_position(&windows, &flex_surface, entity))
+    } else {
+        None
+    };
+    let touch_scrolls = touches_input
+        .iter()
+        .filter_map(|touch| Some((view_at(&|_| Some(touch.start_position()))?, touch.delta())))
+        .collect::<Vec<_>>();
+
+    for (entity, scroll_view, mut scroll_position, node, children) in view_query.iter_mut() {
+        let content_size = children
+            .iter()
+            .find_map(|child| content_query.get(*child).ok())
+            .map_or(Vec2::ZERO, |content| content.size);
+        let mut max_offset = (content_size - node.size).max(Vec2::ZERO);
+        if !scroll_view.horizontal {
+            max_offset.x = 0.0;
+        }
+        if !scroll_view.vertical {
+            max_offset.y = 0.0;
+        }
+
+        let mut offset = scroll_position.offset;
+        let mut scrollbar_used = false;
+        for child in children.iter() {
+            if let Ok((scrollbar, interaction)) = scrollbar_query.get_mut(*child) {
+                scrollbar_used |= *interaction != Interaction::None;
+                // the scrollbars changed by this system are not detected as changed
+                if scrollbar.is_changed() {
+                    match scrollbar.orientation {
+                        Orientation::Horizontal => offset.x = scrollbar.position,
+                        Orientation::Vertical => offset.y = scrollbar.position,
+                    }
+                }
+            }
+        }
+        // the scrollbars scroll with the mouse wheel themselves
+        if wheel_view == Some(entity) && !scrollbar_used {
+            let wheel = wheel_lines * scroll_view.line_length + wheel_pixels;
+            if scroll_view.vertical {
+                offset.x -= wheel.x;
+                offset.y -= wheel.y;
+            } else {
+                // horizontal views also scroll with the vertical wheel, for mice without a
+                // horizontal one
+                offset.x -= if wheel.x != 0.0 { wheel.x } else { wheel.y };
+            }
+        }
+        for (_, delta) in touch_scrolls.iter().filter(|(view, _)| *view == entity) {
+            // the touches are in the same space as the nodes, with y pointing up
+            offset += Vec2::new(-delta.x, delta.y);
+        }
+
+        let offset = offset.max(Vec2::ZERO).min(max_offset);
+        if scroll_position.offset != offset {
+            scroll_position.offset = offset;
+        }
+        for child in children.iter() {
+            if let Ok((mut scrollbar, _)) = scrollbar_query.get_mut(*child) {
+                let (position, content_length, viewport_length) = match scrollbar.orientation {
+                    Orientation::Horizontal => (offset.x, content_size.x, node.size.x),
+                    Orientation::Vertical => (offset.y, content_size.y, node.size.y),
+                };
+                #[allow(clippy::float_cmp)]
+                if scrollbar.position != position
+                    || scrollbar.content_length != content_length
+                    || scrollbar.viewport_length != viewport_length
+                {
+                    scrollbar.position = position;
+                    scrollbar.content_length = content_length;
+                    scrollbar.viewport_length = viewport_length;
+                }
+            }
+        }
+    }
+}
+
+/// Moves the [`ScrollContent`] of a [`ScrollView`] to match its [`ScrollPosition`].
+pub fn scroll_content_system(
+    view_query: Query<(&ScrollPosition, &Children), Changed<ScrollPosition>>,
+    mut content_query: Query<&mut Style, With<ScrollContent>>,
+) {
+    for (scroll_position, children) in view_query.iter() {
+        for child in children.iter() {
+            if let Ok(mut style) = content_query.get_mut(*child) {
+                update_content_position(&mut style, scroll_position.offset);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        scroll_content_system, scroll_view_system, ScrollPosition, ScrollViewBlueprint,
+        ScrollViewParts,
+    };
+    use crate::{widget::Scrollbar, FlexSurface, Node, Style, Val};
+    use bevy_app::{App, CoreStage, Events};
+    use bevy_core::CorePlugin;
+    use bevy_ecs::{
+        blueprint::{CommandsBlueprint, WorldBlueprint},
+        entity::Entity,
+    };
+    use bevy_input::{
+        mouse::{MouseScrollUnit, MouseWheel},
+        touch::{TouchPhase, Touches},
+    };
+    use bevy_math::Vec2;
+    use bevy_transform::components::GlobalTransform;
+    use bevy_window::{HeadlessWindowPlugin, WindowPlugin, Windows};
+
+    /// A 300x200 scroll view with a vertical scrollbar, whose content is 500 high, under the
+    /// cursor.
+    fn app() -> (App, Entity, ScrollViewParts) {
+        let mut app = App::new();
+        app.add_plugin(CorePlugin)
+            .add_plugin(WindowPlugin::default())
+            .add_plugin(HeadlessWindowPlugin)
+            .init_resource::<FlexSurface>()
+            .init_resource::<Touches>()
+            .add_event::<MouseWheel>()
+            .add_system(scroll_view_system)
+            .add_system_to_stage(CoreStage::PostUpdate, scroll_content_system);
+        let entity = app.world.spawn().id();
+        let parts = CommandsBlueprint(ScrollViewBlueprint {
+            scrollbar_materials: Some(Default::default()),
+            ..Default::default()
+        })
+        .build(&mut app.world, entity);
+        app.world.get_mut::<Node>(entity).unwrap().size = Vec2::new(300.0, 200.0);
+        *app.world.get_mut::<GlobalTransform>(entity).unwrap() =
+            GlobalTransform::from_xyz(150.0, 100.0, 0.0);
+        app.world.get_mut::<Node>(parts.content).unwrap().size = Vec2::new(300.0, 500.0);
+        // creates the window
+        app.update();
+        app.world
+            .get_resource_mut::<Windows>()
+            .unwrap()
+            .get_primary_mut()
+            .unwrap()
+            .update_cursor_position_from_backend(Some(Vec2::new(150.0, 100.0)));
+        (app, entity, parts)
+    }
+
+    fn scroll_lines(app: &mut App, lines: f32) {
+        app.world
+            .get_resource_mut::<Events<MouseWheel>>()
+            .unwrap()
+            .send(MouseWheel {
+                unit: MouseScrollUnit::Line,
+                x: 0.0,
+                y: lines,
+                phase: TouchPhase::Moved,
+                momentum: false,
+            });
+        app.update();
+    }
+
+    fn offset(app: &App, entity: Entity) -> Vec2 {
+        app.world.get::<ScrollPosition>(entity).unwrap().offset
+    }
+
+    #[test]
+    fn scroll_with_the_mouse_wheel() {
+        let (mut app, entity, parts) = app();
+        let scrollbar = parts.vertical_scrollbar.unwrap();
+
+        // scrolling the wheel down moves the content up
+        scroll_lines(&mut app, -2.0);
+        assert_eq!(offset(&app, entity), Vec2::new(0.0, 40.0));
+        assert_eq!(
+            app.world
+                .get::<Style>(parts.content)
+                .unwrap()
+                .position
+                .bottom,
+            Val::Px(-40.0)
+        );
+        let scrollbar_state = app.world.get::<Scrollbar>(scrollbar).unwrap();
+        assert_eq!(scrollbar_state.position, 40.0);
+        assert_eq!(scrollbar_state.content_length, 500.0);
+        assert_eq!(scrollbar_state.viewport_length, 200.0);
+
+        // the offset is clamped to keep the viewport filled
+        scroll_lines(&mut app, -100.0);
+        assert_eq!(offset(&app, entity), Vec2::new(0.0, 300.0));
+        scroll_lines(&mut app, 100.0);
+        assert_eq!(offset(&app, entity), Vec2::ZERO);
+    }
+
+    #[test]
+    fn scroll_with_the_scrollbar() {
+        let (mut app, entity, parts) = app();
+        let scrollbar = parts.vertical_scrollbar.unwrap();
+
+        app.world.get_mut::<Scrollbar>(scrollbar).unwrap().position = 120.0;
+        app.update();
+        assert_eq!(offset(&app, entity), Vec2::new(0.0, 120.0));
+        assert_eq!(
+            app.world
+                .get::<Style>(parts.content)
+                .unwrap()
+                .position
+                .bottom,
+            Val::Px(-120.0)
+        );
+
+        // the scrollbar is clamped with the view
+        app.world.get_mut::<Scrollbar>(scrollbar).unwrap().position = 1000.0;
+        app.update();
+        assert_eq!(offset(&app, entity), Vec2::new(0.0, 300.0));
+        assert_eq!(
+            app.world.get::<Scrollbar>(scrollbar).unwrap().position,
+            300.0
+        );
+    }
+}
@@ -0,0 +1,279 @@
+use crate::{
+    entity::NodeBundle, node_cursor_position, AccessibleRole, AccessibleValue, FlexSurface,
+    FocusPolicy, Interaction, Node, Orientation, PositionType, Style, Val,
+};
+use bevy_app::EventReader;
+use bevy_asset::Handle;
+use bevy_ecs::{
+    blueprint::EntityBlueprint,
+    entity::Entity,
+    query::{Changed, With},
+    reflect::ReflectComponent,
+    system::{EntityCommands, Local, Query, Res},
+};
+use bevy_input::mouse::{MouseScrollUnit, MouseWheel};
+use bevy_math::{Size, Vec2};
+use bevy_reflect::Reflect;
+use bevy_sprite::ColorMaterial;
+use bevy_transform::{
+    components::{Children, GlobalTransform},
+    hierarchy::BuildChildren,
+};
+use bevy_window::Windows;
+
+/// A widget showing which part of some content is visible in a viewport, and allowing to scroll
+/// it by dragging its thumb, clicking its track or using the mouse wheel over it.
+///
+/// Use a [`WidgetBinding`](super::WidgetBinding) to bind the `position` of the scrollbar to the
+/// scroll offset of the content.
+#[derive(Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct Scrollbar {
+    pub orientation: Orientation,
+    /// The scroll offset of the viewport, in the `0.0..=max_position()` range.
+    pub position: f32,
+    /// The length of the scrolled content.
+    pub content_length: f32,
+    /// The length of the visible part of the content.
+    pub viewport_length: f32,
+    /// The distance scrolled for each line of mouse wheel movement.
+    pub line_length: f32,
+}
+
+impl Default for Scrollbar {
+    fn default() -> Self {
+        Scrollbar {
+            orientation: Orientation::Vertical,
+            position: 0.0,
+            content_length: 1.0,
+            viewport_length: 1.0,
+            line_length: 20.0,
+        }
+    }
+}
+
+impl Scrollbar {
+    /// The largest scroll offset, where the end of the content is visible.
+    pub fn max_position(&self) -> f32 {
+        (self.content_length - self.viewport_length).max(0.0)
+    }
+
+    /// Sets the scroll offset, clamped to the `0.0..=max_position()` range.
+    pub fn set_position(&mut self, position: f32) {
+        self.position = position.clamp(0.0, self.max_position());
+    }
+
+    /// The length of the thumb relative to the length of the track, in the `0.0..=1.0` range.
+    pub fn thumb_length(&self) -> f32 {
+        if self.content_length > 0.0 {
+            (self.viewport_length / self.content_length).clamp(0.0, 1.0)
+        } else {
+            1.0
+        }
+    }
+
+    /// The start of the thumb relative to the length of the track, in the `0.0..=1.0` range.
+    pub fn thumb_start(&self) -> f32 {
+        let max_position = self.max_position();
+        if max_position > 0.0 {
+            (1.0 - self.thumb_length()) * (self.position / max_position).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Marks the child node of a [`Scrollbar`] that shows the visible part of the content.
+#[derive(Debug, Clone, Default)]
+pub struct ScrollbarThumb;
+
+/// Builds a [`Scrollbar`] track with a [`ScrollbarThumb`] child.
+///
+/// The position and size of the thumb are managed by the scrollbar.
+#[derive(Debug, Clone)]
+pub struct ScrollbarBlueprint {
+    pub scrollbar: Scrollbar,
+    pub style: Style,
+    pub material: Handle<ColorMaterial>,
+    pub thumb_material: Handle<ColorMaterial>,
+}
+
+impl Default for ScrollbarBlueprint {
+    fn default() -> Self {
+        ScrollbarBlueprint {
+            scrollbar: Default::default(),
+            style: Style {
+                size: Size::new(Val::Px(12.0), Val::Percent(100.0)),
+                ..Default::default()
+            },
+            material: Default::default(),
+            thumb_material: Default::default(),
+        }
+    }
+}
+
+impl EntityBlueprint for ScrollbarBlueprint {
+    /// The [`ScrollbarThumb`] of the scrollbar.
+    type Output = Entity;
+
+    fn build(self, entity: &mut EntityCommands) -> Entity {
+        let ScrollbarBlueprint {
+            scrollbar,
+            style,
+            material,
+            thumb_material,
+        } = self;
+        let mut thumb_style = Style {
+            position_type: PositionType::Absolute,
+            ..Default::default()
+        };
+        update_thumb_style(&mut thumb_style, &scrollbar);
+        entity
+            .insert_bundle(NodeBundle {
+                style,
+                material,
+                ..Default::default()
+            })
+            .insert_bundle((
+                AccessibleRole::Scrollbar,
+                AccessibleValue::Numeric {
+                    value: scrollbar.position,
+                    min: 0.0,
+                    max: scrollbar.max_position(),
+                },
+                scrollbar,
+                Interaction::default(),
+                FocusPolicy::default(),
+            ));
+        let thumb = entity
+            .commands()
+            .spawn_bundle(NodeBundle {
+                style: thumb_style,
+                material: thumb_material,
+                ..Default::default()
+            })
+            .insert_bundle((ScrollbarThumb, FocusPolicy::Pass))
+            .id();
+        entity.push_children(&[thumb]);
+        thumb
+    }
+}
+
+fn update_thumb_style(style: &mut Style, scrollbar: &Scrollbar) {
+    let start = Val::Percent(scrollbar.thumb_start() * 100.0);
+    let length = Val::Percent(scrollbar.thumb_length() * 100.0);
+    match scrollbar.orientation {
+        Orientation::Horizontal => {
+            style.position.left = start;
+            style.size = Size::new(length, Val::Percent(100.0));
+        }
+        Orientation::Vertical => {
+            // the UI is laid out with y pointing up, this starts the track at the top
+            style.position.bottom = start;
+            style.size = Size::new(Val::Percent(100.0), length);
+        }
+    }
+}
+
+/// Scrolls a [`Scrollbar`] when its thumb is dragged, when its track is clicked (by one
+/// viewport length towards the cursor) and when the mouse wheel is used over it.
+#[allow(clippy::type_complexity)]
+pub fn scrollbar_system(
+    mut dragged: Local<Option<(Entity, f32)>>,
+    windows: Res<Windows>,
+    flex_surface: Res<FlexSurface>,
+    mut mouse_wheel_events: EventReader<MouseWheel>,
+    mut query: Query<(
+        Entity,
+        &Interaction,
+        &Node,
+        &GlobalTransform,
+        &mut Scrollbar,
+    )>,
+    changed_query: Query<Entity, (Changed<Interaction>, With<Scrollbar>)>,
+) {
+    let mut wheel_lines = Vec2::ZERO;
+    let mut wheel_pixels = Vec2::ZERO;
+    for event in mouse_wheel_events.iter() {
+        match event.unit {
+            MouseScrollUnit::Line => wheel_lines += Vec2::new(event.x, event.y),
+            MouseScrollUnit::Pixel => wheel_pixels += Vec2::new(event.x, event.y),
+        }
+    }
+
+    for (entity, interaction, node, global_transform, mut scrollbar) in query.iter_mut() {
+        let cursor_position = node_cursor_position(&windows, &flex_surface, entity);
+        // the distance of the cursor from the start of the track, and the length of the track
+        let (cursor, track_length) = match scrollbar.orientation {
+            Orientation::Horizontal => (
+                cursor_position.map(|cursor_position| {
+                    cursor_position.x - (global_transform.translation.x - node.size.x / 2.0)
+                }),
+                node.size.x,
+            ),
+            Orientation::Vertical => (
+                cursor_position.map(|cursor_position| {
+                    global_transform.translation.y + node.size.y / 2.0 - cursor_position.y
+                }),
+                node.size.y,
+            ),
+        };
+
+        let mut position = scrollbar.position;
+        match (*interaction, cursor) {
+            (Interaction::Clicked, Some(cursor)) if track_length > 0.0 => {
+                let thumb_start = scrollbar.thumb_start() * track_length;
+                let thumb_length = scrollbar.thumb_length() * track_length;
+                if changed_query.get(entity).is_ok() {
+                    if (thumb_start..thumb_start + thumb_length).contains(&cursor) {
+                        *dragged = Some((entity, cursor - thumb_start));
+                    } else if cursor < thumb_start {
+                        position -= scrollbar.viewport_length;
+                    } else {
+                        position += scrollbar.viewport_length;
+                    }
+                } else if let Some((dragged_entity, grab_offset)) = *dragged {
+                    let free_length = track_length - thumb_length;
+                    if dragged_entity == entity && free_length > 0.0 {
+                        position = (cursor - grab_offset) / free_length * scrollbar.max_position();
+                    }
+                }
+            }
+            (Interaction::Hovered, _) => {
+                // horizontal scrollbars also scroll with the vertical wheel, for mice without a
+                // horizontal one
+                let wheel = match scrollbar.orientation {
+                    Orientation::Horizontal if wheel_lines.x != 0.0 || wheel_pixels.x != 0.0 => {
+                        Vec2::new(wheel_lines.x, wheel_pixels.x)
+                    }
+                    _ => Vec2::new(wheel_lines.y, wheel_pixels.y),
+                };
+                position -= wheel.x * scrollbar.line_length + wheel.y;
+            }
+            _ => {}
+        }
+        if *interaction != Interaction::Clicked && matches!(*dragged, Some((e, _)) if e == entity) {
+            *dragged = None;
+        }
+
+        let position = position.clamp(0.0, scrollbar.max_position());
+        #[allow(clippy::float_cmp)]
+        if scrollbar.position != position {
+            scrollbar.position = position;
+        }
+    }
+}
+
+/// Moves and resizes the [`ScrollbarThumb`] of a [`Scrollbar`] to match its state.
+pub fn scrollbar_thumb_system(
+    scrollbar_query: Query<(&Scrollbar, &Children), Changed<Scrollbar>>,
+    mut thumb_query: Query<&mut Style, With<ScrollbarThumb>>,
+) {
+    for (scrollbar, children) in scrollbar_query.iter() {
+        for child in children.iter() {
+            if let Ok(mut style) = thumb_query.get_mut(*child) {
+                update_thumb_style(&mut style, scrollbar);
+            }
+        }
+    }
+}
@@ -0,0 +1,224 @@
+use crate::{
+    entity::NodeBundle, node_cursor_position, AccessibleRole, AccessibleValue, FlexSurface,
+    FocusPolicy, Interaction, Node, Orientation, PositionType, Style, Val,
+};
+use bevy_asset::Handle;
+use bevy_ecs::{
+    blueprint::EntityBlueprint,
+    entity::Entity,
+    query::{Changed, With},
+    reflect::ReflectComponent,
+    system::{EntityCommands, Query, Res},
+};
+use bevy_math::{Rect, Size};
+use bevy_reflect::Reflect;
+use bevy_sprite::ColorMaterial;
+use bevy_transform::{
+    components::{Children, GlobalTransform},
+    hierarchy::BuildChildren,
+};
+use bevy_window::Windows;
+
+/// A widget holding a value in the `min..=max` range that can be changed by dragging along it.
+///
+/// Horizontal sliders grow from left to right, vertical sliders from bottom to top.
+#[derive(Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct Slider {
+    pub value: f32,
+    pub min: f32,
+    pub max: f32,
+    pub orientation: Orientation,
+}
+
+impl Default for Slider {
+    fn default() -> Self {
+        Slider {
+            value: 0.0,
+            min: 0.0,
+            max: 1.0,
+            orientation: Orientation::Horizontal,
+        }
+    }
+}
+
+impl Slider {
+    /// The value of the slider mapped to the `0.0..=1.0` range.
+    pub fn normalized_value(&self) -> f32 {
+        if self.max > self.min {
+            ((self.value - self.min) / (self.max - self.min)).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+
+    /// Sets the value of the slider from a value in the `0.0..=1.0` range.
+    pub fn set_normalized_value(&mut self, normalized_value: f32) {
+        self.value = self.min + normalized_value.clamp(0.0, 1.0) * (self.max - self.min);
+    }
+}
+
+/// Marks the child node of a [`Slider`] that is moved along the track to show its value.
+#[derive(Debug, Clone, Default)]
+pub struct SliderThumb;
+
+/// Builds a [`Slider`] track with a [`SliderThumb`] child.
+///
+/// The thumb is absolutely positioned at the slider's value, so its `margin` should be used to
+/// center it on that position (the default thumb style is offset by half its width). Use
+/// [`SliderBlueprint::vertical`] for the defaults of a vertical slider.
+#[derive(Debug, Clone)]
+pub struct SliderBlueprint {
+    pub slider: Slider,
+    pub style: Style,
+    pub material: Handle<ColorMaterial>,
+    pub thumb_style: Style,
+    pub thumb_material: Handle<ColorMaterial>,
+}
+
+impl Default for SliderBlueprint {
+    fn default() -> Self {
+        SliderBlueprint {
+            slider: Default::default(),
+            style: Style {
+                size: Size::new(Val::Px(200.0), Val::Px(20.0)),
+                ..Default::default()
+            },
+            material: Default::default(),
+            thumb_style: Style {
+                position_type: PositionType::Absolute,
+                size: Size::new(Val::Px(12.0), Val::Percent(100.0)),
+                margin: Rect {
+                    left: Val::Px(-6.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            thumb_material: Default::default(),
+        }
+    }
+}
+
+impl SliderBlueprint {
+    /// The default blueprint of a vertical slider.
+    pub fn vertical() -> Self {
+        SliderBlueprint {
+            slider: Slider {
+                orientation: Orientation::Vertical,
+                ..Default::default()
+            },
+            style: Style {
+                size: Size::new(Val::Px(20.0), Val::Px(200.0)),
+                ..Default::default()
+            },
+            thumb_style: Style {
+                position_type: PositionType::Absolute,
+                size: Size::new(Val::Percent(100.0), Val::Px(12.0)),
+                margin: Rect {
+                    top: Val::Px(-6.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+}
+
+impl EntityBlueprint for SliderBlueprint {
+    /// The [`SliderThumb`] of the slider.
+    type Output = Entity;
+
+    fn build(self, entity: &mut EntityCommands) -> Entity {
+        let SliderBlueprint {
+            slider,
+            style,
+            material,
+            thumb_style,
+            thumb_material,
+        } = self;
+        entity
+            .insert_bundle(NodeBundle {
+                style,
+                material,
+                ..Default::default()
+            })
+            .insert_bundle((
+                AccessibleRole::Slider,
+                AccessibleValue::Numeric {
+                    value: slider.value,
+                    min: slider.min,
+                    max: slider.max,
+                },
+                slider,
+                Interaction::default(),
+                FocusPolicy::default(),
+            ));
+        let thumb = entity
+            .commands()
+            .spawn_bundle(NodeBundle {
+                style: thumb_style,
+                material: thumb_material,
+                ..Default::default()
+            })
+            .insert_bundle((SliderThumb, FocusPolicy::Pass))
+            .id();
+        entity.push_children(&[thumb]);
+        thumb
+    }
+}
+
+/// Updates the value of a [`Slider`] from the cursor position while it is being dragged.
+pub fn slider_system(
+    windows: Res<Windows>,
+    flex_surface: Res<FlexSurface>,
+    mut query: Query<(Entity, &Interaction, &Node, &GlobalTransform, &mut Slider)>,
+) {
+    for (entity, interaction, node, global_transform, mut slider) in query.iter_mut() {
+        let cursor_position = match node_cursor_position(&windows, &flex_surface, entity) {
+            Some(cursor_position) => cursor_position,
+            None => continue,
+        };
+        let (cursor, center, length) = match slider.orientation {
+            Orientation::Horizontal => (
+                cursor_position.x,
+                global_transform.translation.x,
+                node.size.x,
+            ),
+            Orientation::Vertical => (
+                cursor_position.y,
+                global_transform.translation.y,
+                node.size.y,
+            ),
+        };
+        if *interaction != Interaction::Clicked || length <= 0.0 {
+            continue;
+        }
+        let start = center - length / 2.0;
+        let normalized_value = ((cursor - start) / length).clamp(0.0, 1.0);
+        #[allow(clippy::float_cmp)]
+        if slider.normalized_value() != normalized_value {
+            slider.set_normalized_value(normalized_value);
+        }
+    }
+}
+
+/// Moves the [`SliderThumb`] of a [`Slider`] to match its value.
+pub fn slider_thumb_system(
+    slider_query: Query<(&Slider, &Children), Changed<Slider>>,
+    mut thumb_query: Query<&mut Style, With<SliderThumb>>,
+) {
+    for (slider, children) in slider_query.iter() {
+        for child in children.iter() {
+            if let Ok(mut style) = thumb_query.get_mut(*child) {
+                let position = Val::Percent(slider.normalized_value() * 100.0);
+                match slider.orientation {
+                    Orientation::Horizontal => style.position.left = position,
+                    // the UI is laid out with y pointing up, this measures the value from the
+                    // bottom of the track like `slider_system`
+                    Orientation::Vertical => style.position.top = position,
+                }
+            }
+        }
+    }
+}
@@ -59,7 +59,7 @@ pub fn text_system(
     )>,
 ) {
     let scale_factor = if let Some(window) = windows.get_primary() {
-        window.scale_factor()
+        window.render_scale_factor()
     } else {
         1.
     };
@@ -153,7 +153,7 @@ pub fn draw_text_system(
     >,
 ) {
     let scale_factor = if let Some(window) = windows.get_primary() {
-        window.scale_factor()
+        window.render_scale_factor()
     } else {
         1.
     };
@@ -167,15 +167,33 @@ pub fn draw_text_system(
         }
 
         if let Some(text_glyphs) = text_pipeline.get_glyphs(&entity) {
+            let alignment_offset = node.size / -2.0;
+            // the glyphs are drawn by another pipeline than the nodes, so only the glyphs whose
+            // center is in the clipped area of the node are drawn
+            let clipped_glyphs;
+            let glyphs = if node.clip == Node::UNCLIPPED {
+                &text_glyphs.glyphs
+            } else {
+                let origin = global_transform.translation.truncate() + alignment_offset;
+                clipped_glyphs = text_glyphs
+                    .glyphs
+                    .iter()
+                    .filter(|glyph| {
+                        node.clip_contains(origin + glyph.position / scale_factor as f32)
+                    })
+                    .cloned()
+                    .collect();
+                &clipped_glyphs
+            };
             let mut drawable_text = DrawableText {
                 render_resource_bindings: &mut render_resource_bindings,
                 global_transform: *global_transform,
                 scale_factor: scale_factor as f32,
                 msaa: &msaa,
-                text_glyphs: &text_glyphs.glyphs,
+                text_glyphs: glyphs,
                 font_quad_vertex_layout: &vertex_buffer_layout,
                 sections: &text.sections,
-                alignment_offset: (node.size / -2.0).extend(0.0),
+                alignment_offset: alignment_offset.extend(0.0),
             };
 
             drawable_text.draw(&mut draw, &mut context).unwrap();
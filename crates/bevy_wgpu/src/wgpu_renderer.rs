@@ -95,6 +95,10 @@ impl WgpuRenderer {
             let window = windows
                 .get(window_created_event.id)
                 .expect("Received window created event for non-existent window.");
+            // offscreen windows are rendered to a texture, see `WindowSwapChainNode`
+            if window.is_offscreen() {
+                continue;
+            }
             #[cfg(feature = "bevy_winit")]
             {
                 let winit_windows = world.get_resource::<bevy_winit::WinitWindows>().unwrap();
@@ -0,0 +1,64 @@
+/// Identifies a read of the [`Clipboard`], answered by a [`ClipboardText`] or a
+/// [`ClipboardReadFailed`] event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ClipboardRequestId(u32);
+
+#[derive(Debug)]
+pub enum ClipboardCommand {
+    SetText(String),
+    ReadText(ClipboardRequestId),
+}
+
+/// A clipboard holding text, read and written through the window backend.
+///
+/// Only the web backend uses the clipboard of the platform. On the other platforms the winit
+/// backend keeps the clipboard in the app: it only holds the last text written with
+/// [`set_text`](Clipboard::set_text), and neither sees the text copied in other applications nor
+/// shares its text with them.
+///
+/// Reading the clipboard is asynchronous, as some platforms such as the web ask the user for
+/// permission first: [`request_text`](Clipboard::request_text) returns an id, and the window
+/// backend later sends a [`ClipboardText`] or a [`ClipboardReadFailed`] event with this id.
+#[derive(Debug, Default)]
+pub struct Clipboard {
+    next_id: u32,
+    command_queue: Vec<ClipboardCommand>,
+}
+
+impl Clipboard {
+    /// Writes `text` to the clipboard. Outside of the web, the text is only readable by the app.
+    ///
+    /// On the web, browsers may reject writes that are not triggered by a user interaction.
+    pub fn set_text(&mut self, text: impl Into<String>) {
+        self.command_queue
+            .push(ClipboardCommand::SetText(text.into()));
+    }
+
+    /// Requests the text of the clipboard.
+    pub fn request_text(&mut self) -> ClipboardRequestId {
+        let id = ClipboardRequestId(self.next_id);
+        self.next_id += 1;
+        self.command_queue.push(ClipboardCommand::ReadText(id));
+        id
+    }
+
+    #[inline]
+    pub fn drain_commands(&mut self) -> impl Iterator<Item = ClipboardCommand> + '_ {
+        self.command_queue.drain(..)
+    }
+}
+
+/// An event that is sent with the text read from the [`Clipboard`].
+#[derive(Debug, Clone)]
+pub struct ClipboardText {
+    pub id: ClipboardRequestId,
+    pub text: String,
+}
+
+/// An event that is sent when the [`Clipboard`] can't be read, such as when the user denies the
+/// permission or the clipboard doesn't hold text.
+#[derive(Debug, Clone)]
+pub struct ClipboardReadFailed {
+    pub id: ClipboardRequestId,
+    pub error: String,
+}
@@ -0,0 +1,157 @@
+use crate::{CloseWindow, WindowCloseRequested, WindowClosing, WindowId};
+use bevy_app::{EventReader, EventWriter};
+use bevy_ecs::system::{Res, ResMut};
+use bevy_utils::{Duration, HashMap, Instant};
+
+/// What happens when the close of a window is requested, such as with its close button.
+///
+/// Once the close is accepted, a [`CloseWindow`] event is sent, which exits the app if the
+/// `WindowPlugin` has `exit_on_close` set. The window backends don't close the window on this
+/// event: without `exit_on_close`, the app decides what to do with the window, such as hiding it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WindowClosePolicy {
+    /// The window is closed right away.
+    #[default]
+    CloseImmediately,
+    /// The window is closing until the app [confirms](ClosingWindows::confirm) or
+    /// [cancels](ClosingWindows::cancel) the close, for example from a "Save before quitting?"
+    /// dialog shown on the [`WindowClosing`] event. After the `grace_period`, if any, the window
+    /// is closed anyway.
+    RequireConfirmation { grace_period: Option<Duration> },
+}
+
+/// The windows whose close has been requested and awaits a confirmation, with the
+/// [`WindowClosePolicy::RequireConfirmation`] policy.
+#[derive(Debug, Default)]
+pub struct ClosingWindows {
+    /// When the close was requested, and whether it is confirmed.
+    windows: HashMap<WindowId, (Instant, bool)>,
+}
+
+impl ClosingWindows {
+    pub fn is_closing(&self, id: WindowId) -> bool {
+        self.windows.contains_key(&id)
+    }
+
+    /// The time since the close of the window was requested, if it is closing.
+    pub fn elapsed(&self, id: WindowId) -> Option<Duration> {
+        self.windows
+            .get(&id)
+            .map(|(requested_at, _)| requested_at.elapsed())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = WindowId> + '_ {
+        self.windows.keys().copied()
+    }
+
+    /// Closes the window, if it is closing.
+    pub fn confirm(&mut self, id: WindowId) {
+        if let Some((_, confirmed)) = self.windows.get_mut(&id) {
+            *confirmed = true;
+        }
+    }
+
+    /// Keeps the window open. Returns `false` if the window wasn't closing.
+    pub fn cancel(&mut self, id: WindowId) -> bool {
+        self.windows.remove(&id).is_some()
+    }
+}
+
+/// Applies the [`WindowClosePolicy`] to the close requests of the windows, and closes the
+/// windows whose close is confirmed or whose grace period is over.
+pub fn window_close_policy_system(
+    policy: Res<WindowClosePolicy>,
+    mut closing_windows: ResMut<ClosingWindows>,
+    mut window_close_requested_events: EventReader<WindowCloseRequested>,
+    mut window_closing_events: EventWriter<WindowClosing>,
+    mut close_window_events: EventWriter<CloseWindow>,
+) {
+    for event in window_close_requested_events.iter() {
+        match *policy {
+            WindowClosePolicy::CloseImmediately => {
+                close_window_events.send(CloseWindow { id: event.id });
+            }
+            WindowClosePolicy::RequireConfirmation { .. } => {
+                // requesting the close again, such as by pressing the close button twice, doesn't
+                // restart the grace period
+                if !closing_windows.is_closing(event.id) {
+                    closing_windows
+                        .windows
+                        .insert(event.id, (Instant::now(), false));
+                    window_closing_events.send(WindowClosing { id: event.id });
+                }
+            }
+        }
+    }
+
+    let grace_period = match *policy {
+        WindowClosePolicy::RequireConfirmation { grace_period } => grace_period,
+        WindowClosePolicy::CloseImmediately => Some(Duration::ZERO),
+    };
+    closing_windows
+        .windows
+        .retain(|&id, (requested_at, confirmed)| {
+            let closed = *confirmed
+                || grace_period.is_some_and(|grace_period| requested_at.elapsed() >= grace_period);
+            if closed {
+                close_window_events.send(CloseWindow { id });
+            }
+            !closed
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ClosingWindows, WindowClosePolicy};
+    use crate::{CloseWindow, WindowCloseRequested, WindowClosing, WindowId, WindowPlugin};
+    use bevy_app::{App, Events};
+    use bevy_utils::Duration;
+
+    fn close_window_events(app: &App) -> usize {
+        let events = app.world.get_resource::<Events<CloseWindow>>().unwrap();
+        events.iter_current_update_events().count()
+    }
+
+    #[test]
+    fn close_confirmation() {
+        let mut app = App::new();
+        app.insert_resource(WindowClosePolicy::RequireConfirmation { grace_period: None })
+            .add_plugin(WindowPlugin {
+                add_primary_window: false,
+                exit_on_close: false,
+            });
+        let id = WindowId::primary();
+        let request_close = |app: &mut App| {
+            app.world
+                .get_resource_mut::<Events<WindowCloseRequested>>()
+                .unwrap()
+                .send(WindowCloseRequested { id });
+            app.update();
+        };
+
+        request_close(&mut app);
+        let closing_events = app.world.get_resource::<Events<WindowClosing>>().unwrap();
+        assert_eq!(closing_events.iter_current_update_events().count(), 1);
+        assert_eq!(close_window_events(&app), 0);
+
+        let mut closing_windows = app.world.get_resource_mut::<ClosingWindows>().unwrap();
+        assert!(closing_windows.cancel(id));
+        app.update();
+        assert_eq!(close_window_events(&app), 0);
+
+        request_close(&mut app);
+        let mut closing_windows = app.world.get_resource_mut::<ClosingWindows>().unwrap();
+        closing_windows.confirm(id);
+        app.update();
+        assert_eq!(close_window_events(&app), 1);
+        let closing_windows = app.world.get_resource::<ClosingWindows>().unwrap();
+        assert!(!closing_windows.is_closing(id));
+
+        // the window is closed once the grace period is over
+        app.insert_resource(WindowClosePolicy::RequireConfirmation {
+            grace_period: Some(Duration::ZERO),
+        });
+        request_close(&mut app);
+        assert_eq!(close_window_events(&app), 1);
+    }
+}
@@ -0,0 +1,71 @@
+use crate::CloseAllWindows;
+use bevy_app::Events;
+use bevy_ecs::{
+    system::{Command, Commands},
+    world::World,
+};
+
+pub struct CloseAllWindowsCommand;
+
+impl Command for CloseAllWindowsCommand {
+    fn write(self, world: &mut World) {
+        let mut events = world.get_resource_mut::<Events<CloseAllWindows>>().unwrap();
+        events.send(CloseAllWindows);
+    }
+}
+
+pub trait CloseAllWindowsCommands {
+    /// Requests all the windows to close, see [`CloseAllWindows`].
+    fn close_all_windows(&mut self);
+}
+
+impl<'w, 's> CloseAllWindowsCommands for Commands<'w, 's> {
+    fn close_all_windows(&mut self) {
+        self.add(CloseAllWindowsCommand);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CloseAllWindowsCommands;
+    use crate::{
+        CreateWindow, HeadlessWindowPlugin, WindowCloseRequested, WindowDescriptor, WindowId,
+        WindowPlugin,
+    };
+    use bevy_app::{App, Events};
+    use bevy_ecs::system::Commands;
+
+    #[test]
+    fn close_all_windows() {
+        let mut app = App::new();
+        app.add_plugin(WindowPlugin {
+            add_primary_window: true,
+            exit_on_close: false,
+        })
+        .add_plugin(HeadlessWindowPlugin);
+        let second = WindowId::new();
+        app.world
+            .get_resource_mut::<Events<CreateWindow>>()
+            .unwrap()
+            .send(CreateWindow {
+                id: second,
+                descriptor: WindowDescriptor::default(),
+            });
+        app.update();
+
+        app.add_system(|mut commands: Commands| commands.close_all_windows());
+        app.update();
+        let events = app
+            .world
+            .get_resource::<Events<WindowCloseRequested>>()
+            .unwrap();
+        let ids = events
+            .get_reader()
+            .iter(events)
+            .map(|event| event.id)
+            .collect::<Vec<_>>();
+        assert_eq!(ids.len(), 2);
+        assert!(ids.contains(&WindowId::primary()));
+        assert!(ids.contains(&second));
+    }
+}
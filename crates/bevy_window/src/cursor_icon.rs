@@ -0,0 +1,133 @@
+/// The icon of the cursor in a window, set with [`Window::set_cursor_icon`].
+///
+/// The icons are the CSS cursors. The platforms that don't have an icon show its
+/// [fallback](CursorIcon::fallback) instead:
+/// - On Windows, `ContextMenu`, `Alias`, `Copy`, `ZoomIn` and `ZoomOut` fall back to the
+///   default cursor, `Cell` to the crosshair, and `Grab` and `Grabbing` to the hand.
+/// - On macOS and on the web, all the icons are available.
+/// - On Linux, the icons come from the cursor theme of the desktop, which may miss some of them.
+/// - On Android and iOS, there is no cursor.
+///
+/// [`Window::set_cursor_icon`]: crate::Window::set_cursor_icon
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum CursorIcon {
+    /// The default cursor of the platform, usually an arrow.
+    #[default]
+    Default,
+    Crosshair,
+    /// A pointing hand, for links.
+    Hand,
+    Arrow,
+    /// Something is to be moved.
+    Move,
+    /// Text that can be selected or edited.
+    Text,
+    /// The program is busy.
+    Wait,
+    /// Help is available.
+    Help,
+    /// The program is busy, but can still be interacted with.
+    Progress,
+    /// The action can't be done.
+    NotAllowed,
+    ContextMenu,
+    /// A table cell or set of cells can be selected.
+    Cell,
+    VerticalText,
+    /// An alias or shortcut is to be created.
+    Alias,
+    /// Something is to be copied.
+    Copy,
+    /// The dragged item can't be dropped here.
+    NoDrop,
+    /// Something can be grabbed.
+    Grab,
+    /// Something is grabbed.
+    Grabbing,
+    /// Something can be scrolled in any direction.
+    AllScroll,
+    ZoomIn,
+    ZoomOut,
+    /// An edge or corner of a box is to be moved, such as the bottom right corner for
+    /// `SeResize`.
+    EResize,
+    NResize,
+    NeResize,
+    NwResize,
+    SResize,
+    SeResize,
+    SwResize,
+    WResize,
+    /// A vertical or horizontal edge is to be moved in either direction.
+    EwResize,
+    NsResize,
+    /// A corner is to be moved in either diagonal direction.
+    NeswResize,
+    NwseResize,
+    /// A column or row is to be resized.
+    ColResize,
+    RowResize,
+}
+
+impl CursorIcon {
+    /// The closest icon shown instead of this one on the platforms that don't have it, or `None`
+    /// for the basic icons that all the platforms have.
+    ///
+    /// Following the fallbacks always ends with a basic icon.
+    pub fn fallback(&self) -> Option<CursorIcon> {
+        use CursorIcon::*;
+        match self {
+            Default | Crosshair | Hand | Move | Text | Wait | NotAllowed | EwResize | NsResize
+            | NeswResize | NwseResize => None,
+            Arrow | Help | ContextMenu | Alias | Copy | ZoomIn | ZoomOut => Some(Default),
+            Progress => Some(Wait),
+            Cell => Some(Crosshair),
+            VerticalText => Some(Text),
+            NoDrop => Some(NotAllowed),
+            Grab => Some(Hand),
+            Grabbing => Some(Grab),
+            AllScroll => Some(Move),
+            EResize | WResize | ColResize => Some(EwResize),
+            NResize | SResize | RowResize => Some(NsResize),
+            NeResize | SwResize => Some(NeswResize),
+            NwResize | SeResize => Some(NwseResize),
+        }
+    }
+
+    /// Follows the fallbacks of this icon until `available` accepts one. Returns the last
+    /// fallback if none is accepted.
+    pub fn or_fallback(self, available: impl Fn(CursorIcon) -> bool) -> CursorIcon {
+        let mut icon = self;
+        while !available(icon) {
+            match icon.fallback() {
+                Some(fallback) => icon = fallback,
+                None => break,
+            }
+        }
+        icon
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CursorIcon;
+
+    #[test]
+    fn fallbacks() {
+        let basic = |icon: CursorIcon| icon.fallback().is_none();
+        assert_eq!(CursorIcon::Grabbing.or_fallback(basic), CursorIcon::Hand);
+        assert_eq!(
+            CursorIcon::RowResize.or_fallback(basic),
+            CursorIcon::NsResize
+        );
+        assert_eq!(
+            CursorIcon::Grabbing.or_fallback(|icon| icon == CursorIcon::Grab),
+            CursorIcon::Grab
+        );
+        assert_eq!(
+            CursorIcon::Cell.or_fallback(|_| false),
+            CursorIcon::Crosshair
+        );
+    }
+}
@@ -1,10 +1,14 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use super::{WindowDescriptor, WindowId};
+use super::{
+    CursorGrabMode, MonitorInfo, ScreenOrientation, WindowDescriptor, WindowId,
+    WindowResizeConstraints, WindowState,
+};
 use bevy_math::{IVec2, Vec2};
 
 /// A window event that is sent whenever a window has been resized.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct WindowResized {
     pub id: WindowId,
     pub width: f32,
@@ -19,11 +23,52 @@ pub struct CreateWindow {
 }
 
 /// An event that indicates a window should be closed.
+///
+/// It is sent when the close of the window is requested, according to the
+/// [`WindowClosePolicy`](crate::WindowClosePolicy). The window backends don't close the window:
+/// the app exits on this event if the `WindowPlugin` has `exit_on_close` set, and otherwise
+/// handles it itself.
 #[derive(Debug, Clone)]
 pub struct CloseWindow {
     pub id: WindowId,
 }
 
+/// An event that is sent when the close of a window is requested and awaits a confirmation, see
+/// [`WindowClosePolicy::RequireConfirmation`](crate::WindowClosePolicy::RequireConfirmation).
+#[derive(Debug, Clone)]
+pub struct WindowClosing {
+    pub id: WindowId,
+}
+
+/// An event that requests all the windows to close, such as from a "Quit" menu item.
+///
+/// A [`WindowCloseRequested`] event is sent for each window, as if its close button was pressed,
+/// so the app closes them the same way.
+#[derive(Debug, Clone)]
+pub struct CloseAllWindows;
+
+/// An event that is sent when the [`WindowResizeConstraints`] of a window are invalid, such as a
+/// maximum width smaller than the minimum width. The window uses the `corrected` constraints
+/// instead, which can be shown to the user or saved in place of the `requested` ones.
+#[derive(Debug, Clone)]
+pub struct WindowConstraintViolation {
+    pub id: WindowId,
+    pub requested: WindowResizeConstraints,
+    pub corrected: WindowResizeConstraints,
+}
+
+/// An event that is sent when a monitor is connected, see [`Monitors`](crate::Monitors).
+#[derive(Debug, Clone)]
+pub struct MonitorConnected {
+    pub monitor: MonitorInfo,
+}
+
+/// An event that is sent when a monitor is disconnected, see [`Monitors`](crate::Monitors).
+#[derive(Debug, Clone)]
+pub struct MonitorDisconnected {
+    pub monitor: MonitorInfo,
+}
+
 /// An event that is sent whenever a new window is created.
 #[derive(Debug, Clone)]
 pub struct WindowCreated {
@@ -33,28 +78,79 @@ pub struct WindowCreated {
 /// An event that is sent whenever a close was requested for a window. For example: when the "close"
 /// button is pressed on a window.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct WindowCloseRequested {
     pub id: WindowId,
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct CursorMoved {
     pub id: WindowId,
     pub position: Vec2,
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct CursorEntered {
     pub id: WindowId,
 }
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct CursorLeft {
     pub id: WindowId,
 }
 
+/// An event that is sent when the cursor has been moved by [`Window::set_cursor_position`].
+///
+/// [`Window::set_cursor_position`]: crate::Window::set_cursor_position
+#[derive(Debug, Clone)]
+pub struct CursorWarped {
+    pub id: WindowId,
+    /// The new position of the cursor, in logical pixels from the bottom left of the window.
+    pub position: Vec2,
+}
+
+/// An event that is sent when the window backend failed to move the cursor as requested by
+/// [`Window::set_cursor_position`], for example because the platform doesn't support it.
+///
+/// [`Window::set_cursor_position`]: crate::Window::set_cursor_position
+#[derive(Debug, Clone)]
+pub struct CursorWarpFailed {
+    pub id: WindowId,
+    /// The requested position of the cursor.
+    pub position: Vec2,
+    pub error: String,
+}
+
+/// An event that is sent when the cursor of a window is grabbed or released.
+#[derive(Debug, Clone)]
+pub struct CursorGrabChanged {
+    pub id: WindowId,
+    /// The grab mode of the cursor, which may differ from the requested one on the platforms
+    /// that don't have it.
+    pub mode: CursorGrabMode,
+    pub reason: CursorGrabChangeReason,
+}
+
+/// Why the cursor of a window was grabbed or released.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CursorGrabChangeReason {
+    /// The change requested with [`Window::set_cursor_grab_mode`] has been applied.
+    ///
+    /// [`Window::set_cursor_grab_mode`]: crate::Window::set_cursor_grab_mode
+    Requested,
+    /// The platform rejected the requested change, and the cursor kept its previous state.
+    Rejected(String),
+    /// The platform released the cursor on its own, such as a browser exiting the pointer lock
+    /// when Escape is pressed.
+    Released,
+}
+
 /// An event that is sent whenever a window receives a character from the OS or underlying system.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct ReceivedCharacter {
     pub id: WindowId,
     pub char: char,
@@ -62,19 +158,52 @@ pub struct ReceivedCharacter {
 
 /// An event that indicates a window has received or lost focus.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct WindowFocused {
     pub id: WindowId,
     pub focused: bool,
 }
 
+/// An event that is sent when a window switches between portrait and landscape, such as when the
+/// device is rotated. `orientation` is either [`ScreenOrientation::Portrait`] or
+/// [`ScreenOrientation::Landscape`].
+///
+/// Only sent on iOS, Android and the web. Desktop windows resized to another aspect ratio are not
+/// rotated.
+#[derive(Debug, Clone)]
+pub struct WindowRotated {
+    pub id: WindowId,
+    pub orientation: ScreenOrientation,
+}
+
+/// An event that is sent when a window is minimized, maximized or restored.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct WindowStateChanged {
+    pub id: WindowId,
+    pub old: WindowState,
+    pub new: WindowState,
+}
+
+/// An event that indicates a window has been hidden from or shown to the user, such as when the
+/// browser tab holding its canvas is switched.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct WindowOccluded {
+    pub id: WindowId,
+    pub occluded: bool,
+}
+
 /// An event that indicates a window's scale factor has changed.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct WindowScaleFactorChanged {
     pub id: WindowId,
     pub scale_factor: f64,
 }
 /// An event that indicates a window's OS-reported scale factor has changed.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct WindowBackendScaleFactorChanged {
     pub id: WindowId,
     pub scale_factor: f64,
@@ -82,6 +211,7 @@ pub struct WindowBackendScaleFactorChanged {
 
 /// Events related to files being dragged and dropped on a window.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub enum FileDragAndDrop {
     DroppedFile { id: WindowId, path_buf: PathBuf },
 
@@ -90,9 +220,109 @@ pub enum FileDragAndDrop {
     HoveredFileCancelled { id: WindowId },
 }
 
+/// An event that is sent when files are dropped on a window, with all the files dropped at once.
+///
+/// It is sent after the [`FileDragAndDrop::DroppedFile`] event of each file, so the files can
+/// be handled together, for example as a single undoable action.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct FilesDropped {
+    pub id: WindowId,
+    pub paths: Vec<PathBuf>,
+    /// The position of the cursor in the window when the files were dropped, if known. Some
+    /// platforms don't report the cursor while files are dragged.
+    pub position: Option<Vec2>,
+}
+
+impl FilesDropped {
+    /// Iterates over the dropped files, with their [MIME type](mime_type).
+    pub fn files(&self) -> impl Iterator<Item = (&Path, Option<&'static str>)> {
+        self.paths
+            .iter()
+            .map(|path| (path.as_path(), mime_type(path)))
+    }
+}
+
+/// Guesses the MIME type of a file from its extension, for the common types of files. Returns
+/// `None` for directories and unknown extensions.
+pub fn mime_type(path: &Path) -> Option<&'static str> {
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    Some(match extension.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "bmp" => "image/bmp",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        "hdr" => "image/vnd.radiance",
+        "dds" => "image/vnd.ms-dds",
+        "tga" => "image/x-tga",
+        "ogg" => "audio/ogg",
+        "mp3" => "audio/mpeg",
+        "wav" => "audio/wav",
+        "flac" => "audio/flac",
+        "mp4" => "video/mp4",
+        "webm" => "video/webm",
+        "gltf" => "model/gltf+json",
+        "glb" => "model/gltf-binary",
+        "obj" => "model/obj",
+        "ttf" => "font/ttf",
+        "otf" => "font/otf",
+        "txt" => "text/plain",
+        "csv" => "text/csv",
+        "html" | "htm" => "text/html",
+        "json" => "application/json",
+        "ron" => "application/ron",
+        "toml" => "application/toml",
+        "zip" => "application/zip",
+        "pdf" => "application/pdf",
+        _ => return None,
+    })
+}
+
 /// An event that is sent when a window is repositioned in physical pixels.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct WindowMoved {
     pub id: WindowId,
     pub position: IVec2,
 }
+
+/// An event that makes an app using a reactive update mode of the window backend update again
+/// right away, for example while an animation is playing.
+#[derive(Debug, Clone)]
+pub struct RequestRedraw;
+
+#[cfg(test)]
+mod tests {
+    use super::{mime_type, FilesDropped};
+    use crate::WindowId;
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn mime_types() {
+        assert_eq!(mime_type(Path::new("image.png")), Some("image/png"));
+        assert_eq!(
+            mime_type(Path::new("dir/Texture.DDS")),
+            Some("image/vnd.ms-dds")
+        );
+        assert_eq!(mime_type(Path::new("scene.unknown")), None);
+        assert_eq!(mime_type(Path::new("directory")), None);
+    }
+
+    #[test]
+    fn dropped_files() {
+        let dropped = FilesDropped {
+            id: WindowId::primary(),
+            paths: vec![PathBuf::from("a.gltf"), PathBuf::from("b")],
+            position: None,
+        };
+        assert_eq!(
+            dropped.files().collect::<Vec<_>>(),
+            vec![
+                (Path::new("a.gltf"), Some("model/gltf+json")),
+                (Path::new("b"), None),
+            ]
+        );
+    }
+}
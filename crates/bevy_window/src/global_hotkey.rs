@@ -0,0 +1,101 @@
+use bevy_input::keyboard::{KeyCode, ModifierKeys};
+use bevy_utils::HashMap;
+
+/// A key combination that is listened to system-wide, even when none of the windows of the app
+/// has the focus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GlobalHotkey {
+    pub key: KeyCode,
+    pub modifiers: ModifierKeys,
+}
+
+impl GlobalHotkey {
+    pub fn new(key: KeyCode) -> Self {
+        GlobalHotkey {
+            key,
+            modifiers: ModifierKeys::default(),
+        }
+    }
+
+    pub fn with_modifiers(mut self, modifiers: ModifierKeys) -> Self {
+        self.modifiers = modifiers;
+        self
+    }
+}
+
+/// Identifies a [`GlobalHotkey`] registered in [`GlobalHotkeys`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GlobalHotkeyId(u32);
+
+#[derive(Debug)]
+pub enum GlobalHotkeyCommand {
+    Register {
+        id: GlobalHotkeyId,
+        hotkey: GlobalHotkey,
+    },
+    Unregister {
+        id: GlobalHotkeyId,
+        hotkey: GlobalHotkey,
+    },
+}
+
+/// The global hotkeys registered by the app.
+///
+/// When a registered hotkey is pressed, a [`GlobalHotkeyPressed`] event is sent. The window
+/// backend sends a [`GlobalHotkeyRegistrationFailed`] event instead if the hotkey can't be
+/// registered on the platform.
+///
+/// Global hotkeys are only implemented on X11, with the `x11` feature of `bevy_winit`. On the
+/// other platforms, and without an X server, every registration fails. Under Wayland, the
+/// hotkeys grabbed through XWayland are only pressed while an X11 window has the focus.
+#[derive(Debug, Default)]
+pub struct GlobalHotkeys {
+    next_id: u32,
+    hotkeys: HashMap<GlobalHotkeyId, GlobalHotkey>,
+    command_queue: Vec<GlobalHotkeyCommand>,
+}
+
+impl GlobalHotkeys {
+    pub fn register(&mut self, hotkey: GlobalHotkey) -> GlobalHotkeyId {
+        let id = GlobalHotkeyId(self.next_id);
+        self.next_id += 1;
+        self.hotkeys.insert(id, hotkey);
+        self.command_queue
+            .push(GlobalHotkeyCommand::Register { id, hotkey });
+        id
+    }
+
+    pub fn unregister(&mut self, id: GlobalHotkeyId) {
+        if let Some(hotkey) = self.hotkeys.remove(&id) {
+            self.command_queue
+                .push(GlobalHotkeyCommand::Unregister { id, hotkey });
+        }
+    }
+
+    pub fn get(&self, id: GlobalHotkeyId) -> Option<&GlobalHotkey> {
+        self.hotkeys.get(&id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (GlobalHotkeyId, &GlobalHotkey)> {
+        self.hotkeys.iter().map(|(id, hotkey)| (*id, hotkey))
+    }
+
+    #[inline]
+    pub fn drain_commands(&mut self) -> impl Iterator<Item = GlobalHotkeyCommand> + '_ {
+        self.command_queue.drain(..)
+    }
+}
+
+/// An event that is sent when a registered [`GlobalHotkey`] is pressed.
+#[derive(Debug, Clone)]
+pub struct GlobalHotkeyPressed {
+    pub id: GlobalHotkeyId,
+}
+
+/// An event that is sent when the window backend can't register a [`GlobalHotkey`], either
+/// because the platform doesn't support global hotkeys or because the key can't be grabbed.
+#[derive(Debug, Clone)]
+pub struct GlobalHotkeyRegistrationFailed {
+    pub id: GlobalHotkeyId,
+    pub hotkey: GlobalHotkey,
+}
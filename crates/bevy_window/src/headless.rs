@@ -0,0 +1,626 @@
+use crate::{
+    CreateWindow, CursorGrabChangeReason, CursorGrabChanged, CursorWarped, MonitorConnected,
+    MonitorDisconnected, MonitorInfo, MonitorSelection, Monitors, VideoMode, Window,
+    WindowBackendScaleFactorChanged, WindowCommand, WindowConstraintViolation, WindowCreated,
+    WindowMode, WindowMoved, WindowResized, WindowScaleFactorChanged, WindowStateChanged, Windows,
+};
+use bevy_app::{prelude::*, EventReader, EventWriter};
+use bevy_ecs::system::{Res, ResMut};
+use bevy_math::{IVec2, UVec2};
+
+/// A window backend that doesn't open any window on the platform.
+///
+/// Windows only exist in the [`Windows`] resource, and the commands sent to them are applied
+/// right away, with the same events a platform backend would send. This lets apps that depend on
+/// windows run in tests and on servers. Add it instead of a platform backend such as
+/// `WinitPlugin`.
+///
+/// The windows are displayed on the monitors of the [`SimulatedMonitors`] resource.
+#[derive(Default)]
+pub struct HeadlessWindowPlugin;
+
+impl Plugin for HeadlessWindowPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SimulatedMonitors>()
+            .add_system_to_stage(CoreStage::PreUpdate, update_headless_monitors_system)
+            .add_system_to_stage(CoreStage::PreUpdate, create_headless_window_system)
+            .add_system_to_stage(CoreStage::PostUpdate, change_headless_window_system);
+    }
+}
+
+/// The monitors of the [`HeadlessWindowPlugin`], to test how an app behaves on a given monitor
+/// layout. The [`Monitors`] are updated when it changes, the first monitor being the primary one.
+///
+/// New windows are placed at the top left of the first monitor unless they have a position, or
+/// centered on their monitor if they have one, like with winit. Fullscreen windows fill the
+/// monitor they are on, using its video modes in exclusive fullscreen. A window moved to a
+/// monitor with another scale factor gets the scale factor of that monitor, like on the
+/// platforms.
+///
+/// Defaults to a single 1920x1080 monitor with a scale factor of 1.
+#[derive(Debug, Clone)]
+pub struct SimulatedMonitors {
+    pub monitors: Vec<MonitorInfo>,
+}
+
+impl Default for SimulatedMonitors {
+    fn default() -> Self {
+        let size = UVec2::new(1920, 1080);
+        SimulatedMonitors {
+            monitors: vec![MonitorInfo {
+                name: Some("Simulated monitor".to_string()),
+                position: IVec2::ZERO,
+                physical_size: size,
+                scale_factor: 1.0,
+                video_modes: vec![VideoMode {
+                    size,
+                    bit_depth: 32,
+                    refresh_rate: 60,
+                }],
+            }],
+        }
+    }
+}
+
+impl SimulatedMonitors {
+    /// The monitor a window at `position` is on. Windows outside of all monitors are on the
+    /// first one.
+    fn monitor_at(&self, position: Option<IVec2>) -> Option<&MonitorInfo> {
+        position
+            .and_then(|position| {
+                self.monitors
+                    .iter()
+                    .find(|monitor| monitor.contains(position))
+            })
+            .or_else(|| self.monitors.first())
+    }
+
+    /// The monitor a window in `mode` is displayed on, when it is on the `current` monitor.
+    fn monitor_for_mode<'a>(
+        &'a self,
+        mode: WindowMode,
+        current: Option<&'a MonitorInfo>,
+    ) -> Option<&'a MonitorInfo> {
+        let selected = match mode.exclusive_fullscreen() {
+            Some((MonitorSelection::Primary, _)) => self.monitors.first(),
+            Some((MonitorSelection::Index(index), _)) => self.monitors.get(index),
+            _ => None,
+        };
+        selected.or(current)
+    }
+}
+
+/// The physical size and position of a window displayed in `mode` on `monitor`.
+fn window_placement(
+    monitor: Option<&MonitorInfo>,
+    mode: WindowMode,
+    physical_size: UVec2,
+) -> (UVec2, Option<IVec2>) {
+    let monitor = match monitor {
+        Some(monitor) => monitor,
+        None => return (physical_size, None),
+    };
+    let fullscreen_size = match (mode, mode.exclusive_fullscreen()) {
+        (WindowMode::Windowed, _) => {
+            return (
+                physical_size,
+                Some(monitor.centered_position(physical_size)),
+            )
+        }
+        (_, Some((_, video_mode))) => video_mode
+            .select(&monitor.video_modes, physical_size)
+            .map(|video_mode| video_mode.size),
+        (_, None) => None,
+    };
+    (
+        fullscreen_size.unwrap_or(monitor.physical_size),
+        Some(monitor.position),
+    )
+}
+
+/// The physical size and position of `window` when it isn't minimized: a maximized window fills
+/// `monitor`, and the other windows have their requested size.
+fn restored_placement(monitor: Option<&MonitorInfo>, window: &Window) -> (UVec2, Option<IVec2>) {
+    match monitor {
+        Some(monitor) if window.is_maximized() => (monitor.physical_size, Some(monitor.position)),
+        _ => (
+            physical_size(
+                window.requested_width(),
+                window.requested_height(),
+                window.scale_factor(),
+            ),
+            None,
+        ),
+    }
+}
+
+fn physical_size(logical_width: f32, logical_height: f32, scale_factor: f64) -> UVec2 {
+    UVec2::new(
+        (logical_width as f64 * scale_factor) as u32,
+        (logical_height as f64 * scale_factor) as u32,
+    )
+}
+
+pub fn update_headless_monitors_system(
+    simulated_monitors: Res<SimulatedMonitors>,
+    mut monitors: ResMut<Monitors>,
+    mut disconnected_events: EventWriter<MonitorDisconnected>,
+    mut connected_events: EventWriter<MonitorConnected>,
+) {
+    if !simulated_monitors.is_changed() {
+        return;
+    }
+    let primary = (!simulated_monitors.monitors.is_empty()).then_some(0);
+    let (disconnected, connected) =
+        monitors.update_from_backend(simulated_monitors.monitors.clone(), primary);
+    disconnected_events.send_batch(disconnected.into_iter());
+    connected_events.send_batch(connected.into_iter());
+}
+
+pub fn create_headless_window_system(
+    monitors: Res<SimulatedMonitors>,
+    mut windows: ResMut<Windows>,
+    mut create_window_events: EventReader<CreateWindow>,
+    mut window_created_events: EventWriter<WindowCreated>,
+    mut violation_events: EventWriter<WindowConstraintViolation>,
+) {
+    for create_window_event in create_window_events.iter() {
+        let descriptor = &create_window_event.descriptor;
+        let monitor = match (descriptor.position, descriptor.monitor) {
+            (None, Some(index)) => monitors
+                .monitors
+                .get(index)
+                .or_else(|| monitors.monitor_at(None)),
+            (position, _) => monitors.monitor_at(position),
+        };
+        let monitor = monitors.monitor_for_mode(descriptor.mode, monitor);
+        let backend_scale_factor = monitor.map_or(1.0, |monitor| monitor.scale_factor);
+        let scale_factor = descriptor
+            .scale_factor_override
+            .unwrap_or(backend_scale_factor);
+        let (size, mut position) = window_placement(
+            monitor,
+            descriptor.mode,
+            physical_size(descriptor.width, descriptor.height, scale_factor),
+        );
+        if descriptor.mode == WindowMode::Windowed {
+            match (descriptor.position, descriptor.monitor) {
+                (Some(_), _) => position = descriptor.position,
+                // like with winit, only the windows placed on a monitor are centered. The
+                // platforms choose where the other ones go, here the top left of the monitor.
+                (None, None) => position = monitor.map(|monitor| monitor.position),
+                (None, Some(_)) => {}
+            }
+        }
+        let window = Window::new(
+            create_window_event.id,
+            descriptor,
+            size.x,
+            size.y,
+            backend_scale_factor,
+            position,
+        );
+        windows.add(window);
+        if let Some(violation) = descriptor
+            .resize_constraints
+            .violation(create_window_event.id)
+        {
+            violation_events.send(violation);
+        }
+        window_created_events.send(WindowCreated {
+            id: create_window_event.id,
+        });
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn change_headless_window_system(
+    monitors: Res<SimulatedMonitors>,
+    mut windows: ResMut<Windows>,
+    mut window_resized_events: EventWriter<WindowResized>,
+    mut window_moved_events: EventWriter<WindowMoved>,
+    mut window_state_changed_events: EventWriter<WindowStateChanged>,
+    mut scale_factor_changed_events: EventWriter<WindowScaleFactorChanged>,
+    mut backend_scale_factor_changed_events: EventWriter<WindowBackendScaleFactorChanged>,
+    mut cursor_grab_changed_events: EventWriter<CursorGrabChanged>,
+    mut cursor_warped_events: EventWriter<CursorWarped>,
+    mut violation_events: EventWriter<WindowConstraintViolation>,
+) {
+    for window in windows.iter_mut() {
+        let id = window.id();
+        let commands = window.drain_commands().collect::<Vec<_>>();
+        for command in commands {
+            match command {
+                WindowCommand::SetWindowMode { mode, .. } => {
+                    let monitor =
+                        monitors.monitor_for_mode(mode, monitors.monitor_at(window.position()));
+                    let requested_size = physical_size(
+                        window.requested_width(),
+                        window.requested_height(),
+                        window.scale_factor(),
+                    );
+                    let (size, position) = window_placement(monitor, mode, requested_size);
+                    window.update_actual_size_from_backend(size.x, size.y);
+                    window_resized_events.send(WindowResized {
+                        id,
+                        width: window.width(),
+                        height: window.height(),
+                    });
+                    if let Some(position) = position {
+                        window.update_actual_position_from_backend(position);
+                        window_moved_events.send(WindowMoved { id, position });
+                    }
+                }
+                WindowCommand::SetScaleFactor { scale_factor } => {
+                    scale_factor_changed_events.send(WindowScaleFactorChanged { id, scale_factor });
+                }
+                WindowCommand::SetResolution {
+                    logical_resolution: (width, height),
+                    scale_factor,
+                } => {
+                    let size = physical_size(width, height, scale_factor);
+                    window.update_actual_size_from_backend(size.x, size.y);
+                    window_resized_events.send(WindowResized {
+                        id,
+                        width: window.width(),
+                        height: window.height(),
+                    });
+                }
+                WindowCommand::SetCursorGrabMode { mode } => {
+                    cursor_grab_changed_events.send(CursorGrabChanged {
+                        id,
+                        mode,
+                        reason: CursorGrabChangeReason::Requested,
+                    });
+                }
+                WindowCommand::SetCursorPosition { position } => {
+                    window.update_cursor_position_from_backend(Some(position));
+                    cursor_warped_events.send(CursorWarped { id, position });
+                }
+                WindowCommand::SetResizeConstraints { resize_constraints } => {
+                    if let Some(violation) = resize_constraints.violation(id) {
+                        violation_events.send(violation);
+                    }
+                }
+                WindowCommand::SetPosition { position } => {
+                    window.update_actual_position_from_backend(position);
+                    window_moved_events.send(WindowMoved { id, position });
+
+                    let scale_factor = match monitors.monitor_at(Some(position)) {
+                        Some(monitor) => monitor.scale_factor,
+                        None => continue,
+                    };
+                    #[allow(clippy::float_cmp)]
+                    if window.backend_scale_factor() != scale_factor {
+                        backend_scale_factor_changed_events
+                            .send(WindowBackendScaleFactorChanged { id, scale_factor });
+                        if window.scale_factor_override().is_none() {
+                            scale_factor_changed_events
+                                .send(WindowScaleFactorChanged { id, scale_factor });
+                        }
+                        let (width, height) =
+                            window.physical_size_for_backend_scale_factor(scale_factor);
+                        window.update_scale_factor_from_backend(scale_factor);
+                        window.update_actual_size_from_backend(width, height);
+                        window_resized_events.send(WindowResized {
+                            id,
+                            width: window.width(),
+                            height: window.height(),
+                        });
+                    }
+                }
+                WindowCommand::Snap { region } => {
+                    let monitor = match region.monitor() {
+                        Some(index) => monitors.monitors.get(index),
+                        None => monitors.monitor_at(window.position()),
+                    };
+                    let monitor = match monitor {
+                        Some(monitor) => monitor,
+                        None => continue,
+                    };
+                    let (position, size) = region.area(monitor.position, monitor.physical_size);
+                    window.update_actual_size_from_backend(size.x, size.y);
+                    window_resized_events.send(WindowResized {
+                        id,
+                        width: window.width(),
+                        height: window.height(),
+                    });
+                    window.update_actual_position_from_backend(position);
+                    window_moved_events.send(WindowMoved { id, position });
+                }
+                WindowCommand::SetMaximized { maximized } => {
+                    if maximized == window.is_maximized() {
+                        continue;
+                    }
+                    let old = window.state();
+                    window.update_maximized_from_backend(maximized);
+                    // a minimized window is maximized when it is restored
+                    if !window.is_minimized() {
+                        let (size, position) =
+                            restored_placement(monitors.monitor_at(window.position()), window);
+                        window.update_actual_size_from_backend(size.x, size.y);
+                        window_resized_events.send(WindowResized {
+                            id,
+                            width: window.width(),
+                            height: window.height(),
+                        });
+                        if let Some(position) = position {
+                            window.update_actual_position_from_backend(position);
+                            window_moved_events.send(WindowMoved { id, position });
+                        }
+                    }
+                    let new = window.state();
+                    if new != old {
+                        window_state_changed_events.send(WindowStateChanged { id, old, new });
+                    }
+                }
+                WindowCommand::SetMinimized { minimized } => {
+                    if minimized == window.is_minimized() {
+                        continue;
+                    }
+                    let old = window.state();
+                    let (size, position) = if minimized {
+                        (UVec2::ZERO, None)
+                    } else {
+                        restored_placement(monitors.monitor_at(window.position()), window)
+                    };
+                    window.update_actual_size_from_backend(size.x, size.y);
+                    window_resized_events.send(WindowResized {
+                        id,
+                        width: window.width(),
+                        height: window.height(),
+                    });
+                    if let Some(position) = position {
+                        window.update_actual_position_from_backend(position);
+                        window_moved_events.send(WindowMoved { id, position });
+                    }
+                    window_state_changed_events.send(WindowStateChanged {
+                        id,
+                        old,
+                        new: window.state(),
+                    });
+                }
+                // the other commands only change how the window is presented on the platform
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HeadlessWindowPlugin, SimulatedMonitors};
+    use crate::{
+        CreateWindow, MonitorInfo, MonitorSelection, RecordedEvents, ScaleFactorPolicy, VideoMode,
+        VideoModeSelection, WindowConstraintViolation, WindowDescriptor, WindowId, WindowMode,
+        WindowPlugin, WindowResizeConstraints, WindowScaleFactorChanged, WindowState,
+        WindowStateChanged, WindowTestScript, Windows,
+    };
+    use bevy_app::{App, Events};
+    use bevy_ecs::world::World;
+    use bevy_math::{IVec2, UVec2};
+
+    fn monitor(position: IVec2, scale_factor: f64, video_modes: &[(u32, u32, u16)]) -> MonitorInfo {
+        MonitorInfo {
+            name: None,
+            position,
+            physical_size: UVec2::new(2560, 1440),
+            scale_factor,
+            video_modes: video_modes
+                .iter()
+                .map(|&(width, height, refresh_rate)| VideoMode {
+                    size: UVec2::new(width, height),
+                    bit_depth: 32,
+                    refresh_rate,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn simulated_monitors() {
+        let mut app = App::new();
+        app.insert_resource(SimulatedMonitors {
+            monitors: vec![
+                monitor(
+                    IVec2::ZERO,
+                    1.0,
+                    &[(2560, 1440, 60), (1280, 720, 60), (1280, 720, 144)],
+                ),
+                monitor(IVec2::new(2560, 0), 2.0, &[(2560, 1440, 60)]),
+            ],
+        })
+        .add_plugin(WindowPlugin::default())
+        .add_plugin(HeadlessWindowPlugin);
+
+        WindowTestScript::new()
+            .record::<WindowScaleFactorChanged>()
+            .assert_after(0, |world| {
+                let window = world.get_resource::<Windows>().unwrap();
+                let window = window.get_primary().unwrap();
+                // the default window has no monitor, and isn't centered
+                assert_eq!(window.position(), Some(IVec2::ZERO));
+            })
+            .run_at(1, |world| {
+                let mut windows = world.get_resource_mut::<Windows>().unwrap();
+                let window = windows.get_primary_mut().unwrap();
+                window.set_mode(WindowMode::Fullscreen { use_size: true });
+            })
+            .assert_after(1, |world| {
+                let window = world.get_resource::<Windows>().unwrap();
+                let window = window.get_primary().unwrap();
+                assert_eq!(window.physical_width(), 1280);
+                assert_eq!(window.position(), Some(IVec2::ZERO));
+            })
+            .run_at(2, |world| {
+                let mut windows = world.get_resource_mut::<Windows>().unwrap();
+                let window = windows.get_primary_mut().unwrap();
+                window.set_mode(WindowMode::Windowed);
+                window.set_position(IVec2::new(3000, 100));
+            })
+            .assert_after(2, |world| {
+                let window = world.get_resource::<Windows>().unwrap();
+                let window = window.get_primary().unwrap();
+                assert_eq!(window.scale_factor(), 2.0);
+                assert_eq!(window.width(), 1280.0);
+                assert_eq!(window.physical_width(), 2560);
+            })
+            .run_at(3, |world| {
+                let mut windows = world.get_resource_mut::<Windows>().unwrap();
+                let window = windows.get_primary_mut().unwrap();
+                window.set_mode(WindowMode::ExclusiveFullscreen {
+                    monitor: MonitorSelection::Index(0),
+                    video_mode: VideoModeSelection::Closest {
+                        size: UVec2::new(1200, 700),
+                        refresh_rate: Some(60),
+                    },
+                });
+            })
+            .assert_after(3, |world| {
+                let window = world.get_resource::<Windows>().unwrap();
+                let window = window.get_primary().unwrap();
+                assert_eq!(window.physical_width(), 1280);
+                assert_eq!(window.position(), Some(IVec2::ZERO));
+            })
+            .run_at(4, |world| {
+                let mut create_window_events =
+                    world.get_resource_mut::<Events<CreateWindow>>().unwrap();
+                create_window_events.send(CreateWindow {
+                    id: WindowId::new(),
+                    descriptor: WindowDescriptor {
+                        width: 640.0,
+                        height: 360.0,
+                        monitor: Some(1),
+                        ..Default::default()
+                    },
+                });
+            })
+            .assert_after(5, |world| {
+                let windows = world.get_resource::<Windows>().unwrap();
+                let window = windows
+                    .iter()
+                    .find(|window| window.id() != WindowId::primary())
+                    .unwrap();
+                // centered on the second monitor
+                assert_eq!(window.physical_width(), 1280);
+                assert_eq!(window.position(), Some(IVec2::new(2560 + 640, 360)));
+            })
+            .run(&mut app, 6);
+    }
+
+    #[test]
+    fn scale_factor_policy() {
+        let mut app = App::new();
+        app.insert_resource(SimulatedMonitors {
+            monitors: vec![
+                monitor(IVec2::ZERO, 1.0, &[]),
+                monitor(IVec2::new(2560, 0), 2.0, &[]),
+            ],
+        })
+        .add_plugin(WindowPlugin::default())
+        .add_plugin(HeadlessWindowPlugin);
+
+        WindowTestScript::new()
+            .run_at(1, |world| {
+                let mut windows = world.get_resource_mut::<Windows>().unwrap();
+                let window = windows.get_primary_mut().unwrap();
+                window.set_scale_factor_policy(ScaleFactorPolicy::KeepPhysicalSize);
+                window.set_position(IVec2::new(3000, 100));
+            })
+            .assert_after(1, |world| {
+                let window = world.get_resource::<Windows>().unwrap();
+                let window = window.get_primary().unwrap();
+                assert_eq!(window.scale_factor(), 2.0);
+                assert_eq!(window.physical_width(), 1280);
+                assert_eq!(window.width(), 640.0);
+            })
+            .run(&mut app, 2);
+    }
+
+    #[test]
+    fn constraint_violations() {
+        let mut app = App::new();
+        app.add_plugin(WindowPlugin::default())
+            .add_plugin(HeadlessWindowPlugin);
+
+        WindowTestScript::new()
+            .record::<WindowConstraintViolation>()
+            .run_at(1, |world| {
+                let mut windows = world.get_resource_mut::<Windows>().unwrap();
+                windows.get_primary_mut().unwrap().set_resize_constraints(
+                    WindowResizeConstraints {
+                        min_width: 400.0,
+                        max_width: 200.0,
+                        ..Default::default()
+                    },
+                );
+            })
+            .assert_after(1, |world| {
+                let violations = world
+                    .get_resource::<RecordedEvents<WindowConstraintViolation>>()
+                    .unwrap();
+                let violation = violations.in_frame(1).next().unwrap();
+                assert_eq!(violation.requested.max_width, 200.0);
+                assert_eq!(violation.corrected.max_width, 400.0);
+            })
+            .run(&mut app, 2);
+    }
+
+    #[test]
+    fn window_state_changes() {
+        let mut app = App::new();
+        app.insert_resource(SimulatedMonitors {
+            monitors: vec![monitor(IVec2::ZERO, 1.0, &[])],
+        })
+        .add_plugin(WindowPlugin::default())
+        .add_plugin(HeadlessWindowPlugin);
+
+        let states = |world: &World, frame| {
+            world
+                .get_resource::<RecordedEvents<WindowStateChanged>>()
+                .unwrap()
+                .in_frame(frame)
+                .map(|event| (event.old, event.new))
+                .collect::<Vec<_>>()
+        };
+        WindowTestScript::new()
+            .record::<WindowStateChanged>()
+            .run_at(1, |world| {
+                let mut windows = world.get_resource_mut::<Windows>().unwrap();
+                windows.get_primary_mut().unwrap().set_maximized(true);
+            })
+            .assert_after(1, move |world| {
+                assert_eq!(
+                    states(world, 1),
+                    [(WindowState::Normal, WindowState::Maximized)]
+                );
+                let windows = world.get_resource::<Windows>().unwrap();
+                assert_eq!(windows.get_primary().unwrap().physical_width(), 2560);
+            })
+            .run_at(2, |world| {
+                let mut windows = world.get_resource_mut::<Windows>().unwrap();
+                windows.get_primary_mut().unwrap().set_minimized(true);
+            })
+            .assert_after(2, move |world| {
+                assert_eq!(
+                    states(world, 2),
+                    [(WindowState::Maximized, WindowState::Minimized)]
+                );
+            })
+            .run_at(3, |world| {
+                let mut windows = world.get_resource_mut::<Windows>().unwrap();
+                let window = windows.get_primary_mut().unwrap();
+                window.set_maximized(false);
+                window.set_minimized(false);
+            })
+            .assert_after(3, move |world| {
+                assert_eq!(
+                    states(world, 3),
+                    [(WindowState::Minimized, WindowState::Normal)]
+                );
+                let windows = world.get_resource::<Windows>().unwrap();
+                assert_eq!(windows.get_primary().unwrap().physical_width(), 1280);
+            })
+            .run(&mut app, 4);
+    }
+}
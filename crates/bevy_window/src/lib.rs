@@ -1,18 +1,47 @@
+mod clipboard;
+mod close;
+mod command;
+mod cursor_icon;
 mod event;
+mod global_hotkey;
+mod headless;
+mod monitor;
+mod overrides;
+mod power;
+#[cfg(feature = "serialize")]
+mod recording;
 mod system;
+mod testing;
+mod text_input;
+mod timestamp;
 mod window;
 mod windows;
 
+pub use clipboard::*;
+pub use close::*;
+pub use command::*;
+pub use cursor_icon::*;
 pub use event::*;
+pub use global_hotkey::*;
+pub use headless::*;
+pub use monitor::*;
+pub use overrides::*;
+pub use power::*;
+#[cfg(feature = "serialize")]
+pub use recording::*;
 pub use system::*;
+pub use testing::*;
+pub use text_input::*;
+pub use timestamp::*;
 pub use window::*;
 pub use windows::*;
 
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        CursorEntered, CursorLeft, CursorMoved, FileDragAndDrop, ReceivedCharacter, Window,
-        WindowDescriptor, WindowMoved, Windows,
+        CloseAllWindowsCommands, CursorEntered, CursorGrabMode, CursorIcon, CursorLeft,
+        CursorMoved, FileDragAndDrop, FilesDropped, Monitors, ReceivedCharacter, Window,
+        WindowClosePolicy, WindowClosing, WindowDescriptor, WindowMoved, Windows,
     };
 }
 
@@ -37,25 +66,58 @@ impl Plugin for WindowPlugin {
         app.add_event::<WindowResized>()
             .add_event::<CreateWindow>()
             .add_event::<WindowCreated>()
+            .add_event::<WindowConstraintViolation>()
+            .add_event::<MonitorConnected>()
+            .add_event::<MonitorDisconnected>()
             .add_event::<WindowCloseRequested>()
             .add_event::<CloseWindow>()
+            .add_event::<WindowClosing>()
+            .add_event::<CloseAllWindows>()
             .add_event::<CursorMoved>()
             .add_event::<CursorEntered>()
             .add_event::<CursorLeft>()
+            .add_event::<CursorGrabChanged>()
+            .add_event::<CursorWarped>()
+            .add_event::<CursorWarpFailed>()
             .add_event::<ReceivedCharacter>()
             .add_event::<WindowFocused>()
+            .add_event::<WindowOccluded>()
+            .add_event::<WindowRotated>()
+            .add_event::<WindowStateChanged>()
             .add_event::<WindowScaleFactorChanged>()
             .add_event::<WindowBackendScaleFactorChanged>()
             .add_event::<FileDragAndDrop>()
+            .add_event::<FilesDropped>()
             .add_event::<WindowMoved>()
-            .init_resource::<Windows>();
+            .add_event::<RequestRedraw>()
+            .add_event::<TextInputEvent>()
+            .add_event::<PowerStateChanged>()
+            .add_event::<GlobalHotkeyPressed>()
+            .add_event::<GlobalHotkeyRegistrationFailed>()
+            .add_event::<ClipboardText>()
+            .add_event::<ClipboardReadFailed>()
+            .init_resource::<Windows>()
+            .init_resource::<WindowClosePolicy>()
+            .init_resource::<ClosingWindows>()
+            .init_resource::<Monitors>()
+            .init_resource::<WindowBackendStats>()
+            .init_resource::<GlobalHotkeys>()
+            .init_resource::<Clipboard>()
+            .init_resource::<TextInputSettings>()
+            .init_resource::<PowerState>()
+            .add_system_to_stage(CoreStage::PreUpdate, window_close_policy_system)
+            .add_system_to_stage(CoreStage::PostUpdate, close_all_windows_system);
+        add_timestamped_events(app);
 
         if self.add_primary_window {
-            let window_descriptor = app
+            let mut window_descriptor = app
                 .world
                 .get_resource::<WindowDescriptor>()
                 .map(|descriptor| (*descriptor).clone())
                 .unwrap_or_else(WindowDescriptor::default);
+            if let Some(overrides) = app.world.get_resource::<WindowOverrides>() {
+                overrides.apply(&mut window_descriptor);
+            }
             let mut create_window_event = app
                 .world
                 .get_resource_mut::<Events<CreateWindow>>()
@@ -0,0 +1,420 @@
+use crate::{MonitorConnected, MonitorDisconnected};
+use bevy_math::{IVec2, UVec2};
+use std::cmp::Ordering;
+
+/// A video mode of a monitor, which a window can use in exclusive fullscreen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VideoMode {
+    /// The resolution, in physical pixels.
+    pub size: UVec2,
+    pub bit_depth: u16,
+    pub refresh_rate: u16,
+}
+
+/// The description of a monitor.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonitorInfo {
+    pub name: Option<String>,
+    /// The position of the top left corner of the monitor on the desktop, in physical pixels.
+    pub position: IVec2,
+    /// The resolution of the monitor, in physical pixels.
+    pub physical_size: UVec2,
+    pub scale_factor: f64,
+    pub video_modes: Vec<VideoMode>,
+}
+
+impl MonitorInfo {
+    /// Returns `true` if `position`, in physical pixels on the desktop, is on this monitor.
+    pub fn contains(&self, position: IVec2) -> bool {
+        let end = self.position + self.physical_size.as_i32();
+        position.x >= self.position.x
+            && position.y >= self.position.y
+            && position.x < end.x
+            && position.y < end.y
+    }
+
+    /// The position of a window of `physical_size` centered on this monitor.
+    pub fn centered_position(&self, physical_size: UVec2) -> IVec2 {
+        self.position + (self.physical_size.as_i32() - physical_size.as_i32()) / 2
+    }
+
+    /// The highest refresh rate of the video modes with the resolution of the monitor, which is
+    /// usually its current refresh rate. The platforms don't report the current one.
+    pub fn refresh_rate(&self) -> Option<u16> {
+        self.video_modes
+            .iter()
+            .filter(|video_mode| video_mode.size == self.physical_size)
+            .map(|video_mode| video_mode.refresh_rate)
+            .max()
+    }
+}
+
+/// The monitors connected to the device, kept up to date by the window backend.
+///
+/// The monitors are in the order of the platform, which is the order of the indices of
+/// [`SnapRegion::Monitor`] and [`WindowDescriptor::monitor`](crate::WindowDescriptor::monitor).
+/// The backend sends a [`MonitorConnected`] event for each monitor it detects, including the
+/// monitors detected at startup, and a [`MonitorDisconnected`] event for each monitor removed. A
+/// monitor whose settings change, such as its resolution, is reported as disconnected and
+/// connected again with its new settings.
+#[derive(Debug, Default, Clone)]
+pub struct Monitors {
+    monitors: Vec<MonitorInfo>,
+    primary: Option<usize>,
+}
+
+impl Monitors {
+    pub fn get(&self, index: usize) -> Option<&MonitorInfo> {
+        self.monitors.get(index)
+    }
+
+    /// The main monitor of the desktop, if the platform has one.
+    pub fn primary(&self) -> Option<&MonitorInfo> {
+        self.primary.and_then(|index| self.monitors.get(index))
+    }
+
+    /// The monitor `position`, in physical pixels on the desktop, is on.
+    pub fn at(&self, position: IVec2) -> Option<&MonitorInfo> {
+        self.monitors
+            .iter()
+            .find(|monitor| monitor.contains(position))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &MonitorInfo> {
+        self.monitors.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.monitors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.monitors.is_empty()
+    }
+
+    /// Replaces the monitors with the ones detected by the window backend, `primary` being the
+    /// index of the primary monitor, and returns the events of the monitors disconnected and
+    /// connected since the last update.
+    pub fn update_from_backend(
+        &mut self,
+        monitors: Vec<MonitorInfo>,
+        primary: Option<usize>,
+    ) -> (Vec<MonitorDisconnected>, Vec<MonitorConnected>) {
+        let disconnected = self
+            .monitors
+            .iter()
+            .filter(|monitor| !monitors.contains(monitor))
+            .map(|monitor| MonitorDisconnected {
+                monitor: monitor.clone(),
+            })
+            .collect();
+        let connected = monitors
+            .iter()
+            .filter(|monitor| !self.monitors.contains(monitor))
+            .map(|monitor| MonitorConnected {
+                monitor: monitor.clone(),
+            })
+            .collect();
+        self.monitors = monitors;
+        self.primary = primary;
+        (disconnected, connected)
+    }
+}
+
+/// A part of a monitor a window can be snapped to with [`Window::snap`](crate::Window::snap).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SnapRegion {
+    LeftHalf,
+    RightHalf,
+    TopHalf,
+    BottomHalf,
+    TopLeftQuarter,
+    TopRightQuarter,
+    BottomLeftQuarter,
+    BottomRightQuarter,
+    /// The tile `index` of a grid of `count` tiles covering the monitor, from left to right and
+    /// top to bottom. The grid has as many columns as rows, or one more column.
+    Tile {
+        index: u32,
+        count: u32,
+    },
+    /// The whole monitor at this index in the list of monitors of the platform, instead of the
+    /// current monitor of the window. Ignored if there is no such monitor.
+    Monitor(usize),
+}
+
+impl SnapRegion {
+    /// The position and size of this region of the monitor at `monitor_position` of
+    /// `monitor_size`, in physical pixels on the desktop.
+    pub fn area(&self, monitor_position: IVec2, monitor_size: UVec2) -> (IVec2, UVec2) {
+        match *self {
+            SnapRegion::Tile { index, count } => {
+                return tile_area(index, count, monitor_position, monitor_size)
+            }
+            SnapRegion::Monitor(_) => return (monitor_position, monitor_size),
+            _ => {}
+        }
+        let half = monitor_size / 2;
+        // the right and bottom parts get the extra pixel of odd sizes
+        let rest = monitor_size - half;
+        let (x, width) = match self {
+            SnapRegion::TopHalf | SnapRegion::BottomHalf => (0, monitor_size.x),
+            SnapRegion::LeftHalf | SnapRegion::TopLeftQuarter | SnapRegion::BottomLeftQuarter => {
+                (0, half.x)
+            }
+            _ => (half.x, rest.x),
+        };
+        let (y, height) = match self {
+            SnapRegion::LeftHalf | SnapRegion::RightHalf => (0, monitor_size.y),
+            SnapRegion::TopHalf | SnapRegion::TopLeftQuarter | SnapRegion::TopRightQuarter => {
+                (0, half.y)
+            }
+            _ => (half.y, rest.y),
+        };
+        (
+            monitor_position + IVec2::new(x as i32, y as i32),
+            UVec2::new(width, height),
+        )
+    }
+
+    /// The index of the monitor to snap to, or `None` for the current monitor of the window.
+    pub fn monitor(&self) -> Option<usize> {
+        match *self {
+            SnapRegion::Monitor(monitor) => Some(monitor),
+            _ => None,
+        }
+    }
+}
+
+fn tile_area(
+    index: u32,
+    count: u32,
+    monitor_position: IVec2,
+    monitor_size: UVec2,
+) -> (IVec2, UVec2) {
+    let count = count.max(1);
+    let index = index.min(count - 1);
+    let columns = (1..=count)
+        .find(|columns| columns * columns >= count)
+        .unwrap_or(count);
+    let rows = count.div_ceil(columns);
+    // the bounds are rounded down, so the tiles of a row cover the monitor without gaps
+    let bound =
+        |size: u32, parts: u32, part: u32| (size as u64 * part as u64 / parts as u64) as u32;
+    let (column, row) = (index % columns, index / columns);
+    let (x, y) = (
+        bound(monitor_size.x, columns, column),
+        bound(monitor_size.y, rows, row),
+    );
+    let size = UVec2::new(
+        bound(monitor_size.x, columns, column + 1) - x,
+        bound(monitor_size.y, rows, row + 1) - y,
+    );
+    (monitor_position + IVec2::new(x as i32, y as i32), size)
+}
+
+/// The monitor of a window in [`WindowMode::ExclusiveFullscreen`].
+///
+/// [`WindowMode::ExclusiveFullscreen`]: crate::WindowMode::ExclusiveFullscreen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum MonitorSelection {
+    /// The monitor the window is on, or the one it opens on.
+    #[default]
+    Current,
+    Primary,
+    /// The monitor at this index in the [`Monitors`].
+    Index(usize),
+}
+
+/// The video mode of its monitor a window in [`WindowMode::ExclusiveFullscreen`] uses.
+///
+/// [`WindowMode::ExclusiveFullscreen`]: crate::WindowMode::ExclusiveFullscreen
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum VideoModeSelection {
+    /// The highest resolution, then the highest refresh rate, see [`best_video_mode`].
+    #[default]
+    Best,
+    /// The resolution closest to the requested size of the window, see [`fitting_video_mode`].
+    FitWindow,
+    /// The resolution closest to `size`, then the refresh rate closest to `refresh_rate`, or the
+    /// highest refresh rate if it is `None`.
+    Closest {
+        size: UVec2,
+        refresh_rate: Option<u16>,
+    },
+}
+
+impl VideoModeSelection {
+    /// Selects one of the `video_modes` of a monitor for a window requesting `window_size`, in
+    /// physical pixels.
+    pub fn select<'a>(
+        &self,
+        video_modes: &'a [VideoMode],
+        window_size: UVec2,
+    ) -> Option<&'a VideoMode> {
+        match *self {
+            VideoModeSelection::Best => best_video_mode(video_modes),
+            VideoModeSelection::FitWindow => {
+                fitting_video_mode(video_modes, window_size.x, window_size.y)
+            }
+            VideoModeSelection::Closest { size, refresh_rate } => {
+                closest_video_mode(video_modes, size, refresh_rate)
+            }
+        }
+    }
+}
+
+/// Selects the video mode whose resolution is the closest to `width`x`height`, preferring the
+/// highest refresh rate. This is the mode used by [`WindowMode::Fullscreen`] with `use_size`.
+///
+/// [`WindowMode::Fullscreen`]: crate::WindowMode::Fullscreen
+pub fn fitting_video_mode(
+    video_modes: &[VideoMode],
+    width: u32,
+    height: u32,
+) -> Option<&VideoMode> {
+    closest_video_mode(video_modes, UVec2::new(width, height), None)
+}
+
+/// Selects the video mode whose resolution is the closest to `size`, then whose refresh rate is
+/// the closest to `refresh_rate`, or the highest if it is `None`.
+pub fn closest_video_mode(
+    video_modes: &[VideoMode],
+    size: UVec2,
+    refresh_rate: Option<u16>,
+) -> Option<&VideoMode> {
+    fn abs_diff(a: u32, b: u32) -> u32 {
+        if a > b {
+            return a - b;
+        }
+        b - a
+    }
+
+    let refresh_rate_distance = |video_mode: &VideoMode| match refresh_rate {
+        Some(refresh_rate) => abs_diff(video_mode.refresh_rate as u32, refresh_rate as u32),
+        None => u16::MAX as u32 - video_mode.refresh_rate as u32,
+    };
+    video_modes.iter().min_by(|a, b| {
+        match abs_diff(a.size.x, size.x).cmp(&abs_diff(b.size.x, size.x)) {
+            Ordering::Equal => match abs_diff(a.size.y, size.y).cmp(&abs_diff(b.size.y, size.y)) {
+                Ordering::Equal => refresh_rate_distance(a).cmp(&refresh_rate_distance(b)),
+                default => default,
+            },
+            default => default,
+        }
+    })
+}
+
+/// Selects the video mode with the highest resolution, then the highest refresh rate. This is
+/// the mode used by [`WindowMode::Fullscreen`] without `use_size`.
+///
+/// [`WindowMode::Fullscreen`]: crate::WindowMode::Fullscreen
+pub fn best_video_mode(video_modes: &[VideoMode]) -> Option<&VideoMode> {
+    video_modes
+        .iter()
+        .min_by(|a, b| match b.size.x.cmp(&a.size.x) {
+            Ordering::Equal => match b.size.y.cmp(&a.size.y) {
+                Ordering::Equal => b.refresh_rate.cmp(&a.refresh_rate),
+                default => default,
+            },
+            default => default,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MonitorInfo, Monitors, SnapRegion, VideoMode, VideoModeSelection};
+    use bevy_math::{IVec2, UVec2};
+
+    #[test]
+    fn snap_regions() {
+        let position = IVec2::new(100, 0);
+        let size = UVec2::new(1921, 1080);
+        assert_eq!(
+            SnapRegion::LeftHalf.area(position, size),
+            (IVec2::new(100, 0), UVec2::new(960, 1080))
+        );
+        assert_eq!(
+            SnapRegion::RightHalf.area(position, size),
+            (IVec2::new(1060, 0), UVec2::new(961, 1080))
+        );
+        assert_eq!(
+            SnapRegion::BottomHalf.area(position, size),
+            (IVec2::new(100, 540), UVec2::new(1921, 540))
+        );
+        assert_eq!(
+            SnapRegion::TopRightQuarter.area(position, size),
+            (IVec2::new(1060, 0), UVec2::new(961, 540))
+        );
+    }
+
+    #[test]
+    fn tiles() {
+        let position = IVec2::new(100, 0);
+        let size = UVec2::new(1920, 1080);
+        let tile = |index, count| SnapRegion::Tile { index, count }.area(position, size);
+        assert_eq!(tile(0, 1), (position, size));
+        assert_eq!(tile(1, 2), (IVec2::new(1060, 0), UVec2::new(960, 1080)));
+        // three tiles take a 2x2 grid
+        assert_eq!(tile(2, 3), (IVec2::new(100, 540), UVec2::new(960, 540)));
+        assert_eq!(tile(4, 6), (IVec2::new(740, 540), UVec2::new(640, 540)));
+    }
+
+    #[test]
+    fn monitor_updates() {
+        let monitor = |x: i32, width: u32| MonitorInfo {
+            name: None,
+            position: IVec2::new(x, 0),
+            physical_size: UVec2::new(width, 1080),
+            scale_factor: 1.0,
+            video_modes: vec![VideoMode {
+                size: UVec2::new(width, 1080),
+                bit_depth: 32,
+                refresh_rate: 60,
+            }],
+        };
+        let mut monitors = Monitors::default();
+        let (disconnected, connected) =
+            monitors.update_from_backend(vec![monitor(0, 1920), monitor(1920, 1920)], Some(0));
+        assert!(disconnected.is_empty());
+        assert_eq!(connected.len(), 2);
+        assert_eq!(
+            monitors.at(IVec2::new(2000, 10)),
+            Some(&monitor(1920, 1920))
+        );
+        assert_eq!(monitors.primary().unwrap().refresh_rate(), Some(60));
+
+        // the second monitor changes resolution
+        let (disconnected, connected) =
+            monitors.update_from_backend(vec![monitor(0, 1920), monitor(1920, 1280)], Some(0));
+        assert_eq!(disconnected[0].monitor, monitor(1920, 1920));
+        assert_eq!(connected[0].monitor, monitor(1920, 1280));
+        assert_eq!((disconnected.len(), connected.len()), (1, 1));
+    }
+
+    #[test]
+    fn video_mode_selection() {
+        let video_mode = |width, height, refresh_rate| VideoMode {
+            size: UVec2::new(width, height),
+            bit_depth: 32,
+            refresh_rate,
+        };
+        let video_modes = [
+            video_mode(1920, 1080, 60),
+            video_mode(1920, 1080, 144),
+            video_mode(1280, 720, 60),
+            video_mode(1280, 720, 120),
+        ];
+        let window_size = UVec2::new(1300, 700);
+        let select = |selection: VideoModeSelection| selection.select(&video_modes, window_size);
+        assert_eq!(select(VideoModeSelection::Best), Some(&video_modes[1]));
+        assert_eq!(select(VideoModeSelection::FitWindow), Some(&video_modes[3]));
+        assert_eq!(
+            select(VideoModeSelection::Closest {
+                size: UVec2::new(1920, 1080),
+                refresh_rate: Some(75),
+            }),
+            Some(&video_modes[0])
+        );
+    }
+}
@@ -0,0 +1,197 @@
+use crate::{WindowDescriptor, WindowMode};
+use bevy_math::{IVec2, Vec2};
+use bevy_utils::tracing::warn;
+use std::str::FromStr;
+
+/// The command line flags and environment variables of the [`WindowOverrides`], with the format
+/// of their value.
+const OPTIONS: [(&str, &str, &str); 5] = [
+    ("--window-size", "BEVY_WINDOW_SIZE", "1280x720"),
+    ("--window-position", "BEVY_WINDOW_POSITION", "100,200"),
+    ("--window-mode", "BEVY_WINDOW_MODE", "windowed"),
+    ("--present-mode", "BEVY_PRESENT_MODE", "vsync"),
+    ("--monitor", "BEVY_MONITOR", "1"),
+];
+
+/// Overrides of the settings of the primary window, read from the command line or the
+/// environment, to try an app with another window size or on another monitor without changing
+/// its code.
+///
+/// The overrides are applied to the [`WindowDescriptor`] of the primary window when they are
+/// inserted as a resource before the `WindowPlugin` is added, for example with
+/// `app.insert_resource(WindowOverrides::from_env_and_args())`.
+///
+/// | Flag                | Variable               | Value                                      |
+/// |---------------------|------------------------|--------------------------------------------|
+/// | `--window-size`     | `BEVY_WINDOW_SIZE`     | `1280x720`, in logical pixels              |
+/// | `--window-position` | `BEVY_WINDOW_POSITION` | `100,200`, in physical pixels              |
+/// | `--window-mode`     | `BEVY_WINDOW_MODE`     | `windowed`, `borderless`, `fullscreen` or  |
+/// |                     |                        | `fullscreen-sized`                         |
+/// | `--present-mode`    | `BEVY_PRESENT_MODE`    | `vsync` or `immediate`                     |
+/// | `--monitor`         | `BEVY_MONITOR`         | the index of the monitor, from 0           |
+///
+/// The flags are given as `--window-size=1280x720` or `--window-size 1280x720`. The other
+/// arguments are ignored, so the app can have its own. Invalid values are ignored with a
+/// warning.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WindowOverrides {
+    pub size: Option<Vec2>,
+    pub position: Option<IVec2>,
+    pub mode: Option<WindowMode>,
+    pub vsync: Option<bool>,
+    pub monitor: Option<usize>,
+}
+
+impl WindowOverrides {
+    /// Reads the overrides from the command line arguments, without the name of the program.
+    pub fn from_args(args: impl IntoIterator<Item = String>) -> Self {
+        let mut overrides = WindowOverrides::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let (flag, value) = match arg.split_once('=') {
+                Some((flag, value)) => (flag.to_string(), Some(value.to_string())),
+                None => (arg, None),
+            };
+            if !OPTIONS.iter().any(|(option, _, _)| *option == flag) {
+                continue;
+            }
+            match value.or_else(|| args.next()) {
+                Some(value) => overrides.set(&flag, &value),
+                None => warn!("The window override {} has no value.", flag),
+            }
+        }
+        overrides
+    }
+
+    /// Reads the overrides from the environment variables.
+    pub fn from_env() -> Self {
+        let mut overrides = WindowOverrides::default();
+        for (flag, variable, _) in OPTIONS {
+            if let Ok(value) = std::env::var(variable) {
+                overrides.set(flag, &value);
+            }
+        }
+        overrides
+    }
+
+    /// Reads the overrides from the environment variables and the command line arguments of the
+    /// app, the arguments taking precedence.
+    pub fn from_env_and_args() -> Self {
+        let env = WindowOverrides::from_env();
+        let args = WindowOverrides::from_args(std::env::args().skip(1));
+        WindowOverrides {
+            size: args.size.or(env.size),
+            position: args.position.or(env.position),
+            mode: args.mode.or(env.mode),
+            vsync: args.vsync.or(env.vsync),
+            monitor: args.monitor.or(env.monitor),
+        }
+    }
+
+    /// Applies the overrides to `descriptor`.
+    pub fn apply(&self, descriptor: &mut WindowDescriptor) {
+        if let Some(size) = self.size {
+            descriptor.width = size.x;
+            descriptor.height = size.y;
+        }
+        if let Some(position) = self.position {
+            descriptor.position = Some(position);
+        }
+        if let Some(mode) = self.mode {
+            descriptor.mode = mode;
+        }
+        if let Some(vsync) = self.vsync {
+            descriptor.vsync = vsync;
+        }
+        if let Some(monitor) = self.monitor {
+            descriptor.monitor = Some(monitor);
+        }
+    }
+
+    fn set(&mut self, flag: &str, value: &str) {
+        let valid = match flag {
+            "--window-size" => parse_pair::<f32>(value, 'x')
+                .filter(|(width, height)| *width > 0.0 && *height > 0.0)
+                .map(|(width, height)| self.size = Some(Vec2::new(width, height))),
+            "--window-position" => {
+                parse_pair::<i32>(value, ',').map(|(x, y)| self.position = Some(IVec2::new(x, y)))
+            }
+            "--window-mode" => parse_mode(value).map(|mode| self.mode = Some(mode)),
+            "--present-mode" => match value {
+                "vsync" => Some(true),
+                "immediate" => Some(false),
+                _ => None,
+            }
+            .map(|vsync| self.vsync = Some(vsync)),
+            "--monitor" => value
+                .parse()
+                .ok()
+                .map(|monitor| self.monitor = Some(monitor)),
+            _ => Some(()),
+        };
+        if valid.is_none() {
+            let (_, _, example) = OPTIONS
+                .iter()
+                .find(|(option, _, _)| *option == flag)
+                .unwrap();
+            warn!(
+                "Ignoring the window override {} {:?}, expected a value like {:?}.",
+                flag, value, example
+            );
+        }
+    }
+}
+
+fn parse_pair<T: FromStr>(value: &str, separator: char) -> Option<(T, T)> {
+    let (a, b) = value.split_once(separator)?;
+    Some((a.trim().parse().ok()?, b.trim().parse().ok()?))
+}
+
+fn parse_mode(value: &str) -> Option<WindowMode> {
+    Some(match value {
+        "windowed" => WindowMode::Windowed,
+        "borderless" => WindowMode::BorderlessFullscreen,
+        "fullscreen" => WindowMode::Fullscreen { use_size: false },
+        "fullscreen-sized" => WindowMode::Fullscreen { use_size: true },
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WindowOverrides;
+    use crate::{WindowDescriptor, WindowMode};
+    use bevy_math::{IVec2, Vec2};
+
+    #[test]
+    fn args() {
+        let args = [
+            "--verbose",
+            "--window-size=800x600",
+            "--window-position",
+            "-10,20",
+            "--window-mode=borderless",
+            "--present-mode=mailbox",
+            "--monitor",
+        ];
+        let overrides = WindowOverrides::from_args(args.iter().map(|arg| arg.to_string()));
+        assert_eq!(
+            overrides,
+            WindowOverrides {
+                size: Some(Vec2::new(800.0, 600.0)),
+                position: Some(IVec2::new(-10, 20)),
+                mode: Some(WindowMode::BorderlessFullscreen),
+                vsync: None,
+                monitor: None,
+            }
+        );
+
+        let mut descriptor = WindowDescriptor::default();
+        overrides.apply(&mut descriptor);
+        assert_eq!((descriptor.width, descriptor.height), (800.0, 600.0));
+        assert_eq!(descriptor.position, Some(IVec2::new(-10, 20)));
+        assert_eq!(descriptor.mode, WindowMode::BorderlessFullscreen);
+        assert!(descriptor.vsync);
+        assert_eq!(descriptor.monitor, None);
+    }
+}
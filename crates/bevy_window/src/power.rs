@@ -0,0 +1,23 @@
+/// The power source of the device, kept up to date by the window backend.
+///
+/// Stays at its default, on external power, on the platforms where the power source is unknown.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PowerState {
+    /// The device runs on its battery.
+    pub on_battery: bool,
+    /// The battery saver mode of the system is enabled.
+    pub power_saving: bool,
+}
+
+impl PowerState {
+    /// Returns `true` if the app should save power, on battery or in battery saver mode.
+    pub fn is_low_power(&self) -> bool {
+        self.on_battery || self.power_saving
+    }
+}
+
+/// An event that is sent when the [`PowerState`] changes.
+#[derive(Debug, Clone)]
+pub struct PowerStateChanged {
+    pub state: PowerState,
+}
@@ -0,0 +1,513 @@
+use crate::{
+    CursorEntered, CursorLeft, CursorMoved, FileDragAndDrop, FilesDropped, ReceivedCharacter,
+    WindowBackendScaleFactorChanged, WindowCloseRequested, WindowFocused, WindowId, WindowMoved,
+    WindowOccluded, WindowResized, WindowScaleFactorChanged, Windows,
+};
+use bevy_app::{prelude::*, AppExit, EventReader, Events};
+use bevy_ecs::{
+    component::Component,
+    schedule::{ParallelSystemDescriptorCoercion, SystemLabel},
+    system::{IntoExclusiveSystem, IntoSystem, ResMut},
+    world::World,
+};
+use bevy_input::{
+    gamepad::GamepadEventRaw,
+    keyboard::{KeyboardInput, ModifiersChanged},
+    mouse::{MouseButtonInput, MouseMotion, MouseWheel},
+    touch::TouchInput,
+};
+use bevy_utils::tracing::{error, info, warn};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+macro_rules! recorded_events {
+    ($($event:ident),* $(,)?) => {
+        /// A window or input event in an [`EventRecording`].
+        #[derive(Debug, Clone, Serialize, Deserialize)]
+        pub enum RecordedEvent {
+            $($event($event),)*
+        }
+
+        $(
+            impl From<$event> for RecordedEvent {
+                fn from(event: $event) -> Self {
+                    RecordedEvent::$event(event)
+                }
+            }
+        )*
+
+        fn add_record_event_systems(app: &mut App) {
+            $(
+                app.add_system_to_stage(
+                    CoreStage::Last,
+                    record_event_system::<$event>
+                        .system()
+                        .label(RecordingSystem::Record),
+                );
+            )*
+        }
+
+        fn send_recorded_event(world: &mut World, event: RecordedEvent) {
+            match event {
+                $(
+                    RecordedEvent::$event(event) => world
+                        .get_resource_mut::<Events<$event>>()
+                        .unwrap()
+                        .send(event),
+                )*
+            }
+        }
+    };
+}
+
+recorded_events!(
+    WindowResized,
+    WindowMoved,
+    WindowCloseRequested,
+    WindowFocused,
+    WindowOccluded,
+    WindowScaleFactorChanged,
+    WindowBackendScaleFactorChanged,
+    CursorMoved,
+    CursorEntered,
+    CursorLeft,
+    ReceivedCharacter,
+    FileDragAndDrop,
+    FilesDropped,
+    KeyboardInput,
+    ModifiersChanged,
+    MouseButtonInput,
+    MouseMotion,
+    MouseWheel,
+    TouchInput,
+    GamepadEventRaw,
+);
+
+impl RecordedEvent {
+    /// The window that received the event, if it is a window event.
+    pub fn window_id(&self) -> Option<WindowId> {
+        match self {
+            RecordedEvent::WindowResized(WindowResized { id, .. })
+            | RecordedEvent::WindowMoved(WindowMoved { id, .. })
+            | RecordedEvent::WindowCloseRequested(WindowCloseRequested { id })
+            | RecordedEvent::WindowFocused(WindowFocused { id, .. })
+            | RecordedEvent::WindowOccluded(WindowOccluded { id, .. })
+            | RecordedEvent::WindowScaleFactorChanged(WindowScaleFactorChanged { id, .. })
+            | RecordedEvent::WindowBackendScaleFactorChanged(WindowBackendScaleFactorChanged {
+                id,
+                ..
+            })
+            | RecordedEvent::CursorMoved(CursorMoved { id, .. })
+            | RecordedEvent::CursorEntered(CursorEntered { id })
+            | RecordedEvent::CursorLeft(CursorLeft { id })
+            | RecordedEvent::ReceivedCharacter(ReceivedCharacter { id, .. })
+            | RecordedEvent::FileDragAndDrop(FileDragAndDrop::DroppedFile { id, .. })
+            | RecordedEvent::FileDragAndDrop(FileDragAndDrop::HoveredFile { id, .. })
+            | RecordedEvent::FileDragAndDrop(FileDragAndDrop::HoveredFileCancelled { id })
+            | RecordedEvent::FilesDropped(FilesDropped { id, .. }) => Some(*id),
+            RecordedEvent::KeyboardInput(_)
+            | RecordedEvent::ModifiersChanged(_)
+            | RecordedEvent::MouseButtonInput(_)
+            | RecordedEvent::MouseMotion(_)
+            | RecordedEvent::MouseWheel(_)
+            | RecordedEvent::TouchInput(_)
+            | RecordedEvent::GamepadEventRaw(_) => None,
+        }
+    }
+}
+
+/// The events sent during one frame of an [`EventRecording`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedFrame {
+    /// The index of the frame, counted from the first update of the app.
+    pub frame: u32,
+    pub events: Vec<RecordedEvent>,
+}
+
+/// The window and input events received by an app, with the frame they were received in.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct EventRecording {
+    /// The frames with events, in order.
+    pub frames: Vec<RecordedFrame>,
+}
+
+#[derive(Error, Debug)]
+pub enum EventRecordingError {
+    #[error("failed to access the event recording file")]
+    Io(#[from] std::io::Error),
+    #[error("failed to parse the event recording")]
+    Ron(#[from] ron::Error),
+}
+
+impl EventRecording {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, EventRecordingError> {
+        let recording = std::fs::read_to_string(path)?;
+        Ok(ron::de::from_str(&recording)?)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), EventRecordingError> {
+        let recording = ron::ser::to_string_pretty(self, Default::default())?;
+        std::fs::write(path, recording)?;
+        Ok(())
+    }
+
+    /// Adds `event` to `frame`, which must not be before the last frame of the recording.
+    pub fn push(&mut self, frame: u32, event: impl Into<RecordedEvent>) {
+        match self.frames.last_mut() {
+            Some(last) if last.frame == frame => last.events.push(event.into()),
+            _ => self.frames.push(RecordedFrame {
+                frame,
+                events: vec![event.into()],
+            }),
+        }
+    }
+
+    /// Iterates over the events sent during `frame`.
+    pub fn events_in_frame(&self, frame: u32) -> impl Iterator<Item = &RecordedEvent> {
+        self.frames
+            .iter()
+            .filter(move |recorded| recorded.frame == frame)
+            .flat_map(|recorded| recorded.events.iter())
+    }
+}
+
+#[derive(Debug, Hash, PartialEq, Eq, Clone, SystemLabel)]
+enum RecordingSystem {
+    Record,
+}
+
+/// Records the window and input events received by the app in an [`EventRecording`], saved to
+/// `path` when the app exits.
+///
+/// The recording can be replayed with the [`EventReplayPlugin`] to reproduce a bug or test a UI
+/// flow. Saving to a file is not supported on the web.
+pub struct EventRecorderPlugin {
+    pub path: PathBuf,
+}
+
+impl Plugin for EventRecorderPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(EventRecorder {
+            path: self.path.clone(),
+            recording: EventRecording::default(),
+            frame: 0,
+        })
+        .add_system_to_stage(
+            CoreStage::Last,
+            save_recording_system.after(RecordingSystem::Record),
+        );
+        add_record_event_systems(app);
+    }
+}
+
+/// The recording in progress of the [`EventRecorderPlugin`].
+pub struct EventRecorder {
+    path: PathBuf,
+    recording: EventRecording,
+    frame: u32,
+}
+
+impl EventRecorder {
+    pub fn recording(&self) -> &EventRecording {
+        &self.recording
+    }
+
+    /// Saves the events recorded so far. This is done automatically when the app exits.
+    pub fn save(&self) -> Result<(), EventRecordingError> {
+        self.recording.save(&self.path)
+    }
+}
+
+fn record_event_system<T: Component + Clone + Into<RecordedEvent>>(
+    mut recorder: ResMut<EventRecorder>,
+    mut events: EventReader<T>,
+) {
+    let frame = recorder.frame;
+    for event in events.iter() {
+        recorder.recording.push(frame, event.clone());
+    }
+}
+
+fn save_recording_system(
+    mut recorder: ResMut<EventRecorder>,
+    mut app_exit_events: EventReader<AppExit>,
+) {
+    recorder.frame += 1;
+    if app_exit_events.iter().next().is_some() {
+        match recorder.save() {
+            Ok(()) => info!("Saved the event recording to {}", recorder.path.display()),
+            Err(err) => error!("Failed to save the event recording: {}", err),
+        }
+    }
+}
+
+/// Replays the events of an [`EventRecording`] loaded from `path`, each in the frame it was
+/// recorded in.
+///
+/// The state of the windows is updated as the window backend would when receiving the events,
+/// so for the replay to be deterministic, the app should run with the
+/// [`HeadlessWindowPlugin`](crate::HeadlessWindowPlugin) instead of a platform backend.
+///
+/// Only the events of the primary window are replayed: the ids of the other windows are random,
+/// so they differ between the recording and the replay. The events of the other windows are
+/// skipped, with a warning.
+pub struct EventReplayPlugin {
+    pub path: PathBuf,
+}
+
+impl Plugin for EventReplayPlugin {
+    fn build(&self, app: &mut App) {
+        let recording = EventRecording::load(&self.path).unwrap_or_else(|err| {
+            error!(
+                "Failed to load the event recording {}: {}",
+                self.path.display(),
+                err
+            );
+            EventRecording::default()
+        });
+        app.insert_resource(EventReplay::new(recording))
+            .add_system_to_stage(CoreStage::First, replay_events_system.exclusive_system());
+    }
+}
+
+/// The replay in progress of the [`EventReplayPlugin`].
+pub struct EventReplay {
+    recording: EventRecording,
+    frame: u32,
+    next_frame_index: usize,
+    skipped_secondary_windows: bool,
+}
+
+impl EventReplay {
+    pub fn new(recording: EventRecording) -> Self {
+        EventReplay {
+            recording,
+            frame: 0,
+            next_frame_index: 0,
+            skipped_secondary_windows: false,
+        }
+    }
+
+    /// Returns `true` once all the recorded events have been sent.
+    pub fn is_finished(&self) -> bool {
+        self.next_frame_index >= self.recording.frames.len()
+    }
+
+    fn next_events(&mut self) -> Vec<RecordedEvent> {
+        let mut events = Vec::new();
+        while let Some(recorded) = self.recording.frames.get(self.next_frame_index) {
+            if recorded.frame > self.frame {
+                break;
+            }
+            for event in &recorded.events {
+                if event.window_id().is_none_or(|id| id.is_primary()) {
+                    events.push(event.clone());
+                } else if !self.skipped_secondary_windows {
+                    warn!("Skipped the recorded events of windows other than the primary window");
+                    self.skipped_secondary_windows = true;
+                }
+            }
+            self.next_frame_index += 1;
+        }
+        self.frame += 1;
+        events
+    }
+}
+
+pub fn replay_events_system(world: &mut World) {
+    let events = world
+        .get_resource_mut::<EventReplay>()
+        .unwrap()
+        .next_events();
+    for event in events {
+        if let Some(mut windows) = world.get_resource_mut::<Windows>() {
+            apply_to_windows(&mut windows, &event);
+        }
+        send_recorded_event(world, event);
+    }
+}
+
+/// Updates the windows like the window backend does when it receives `event`.
+fn apply_to_windows(windows: &mut Windows, event: &RecordedEvent) {
+    match event {
+        RecordedEvent::WindowResized(WindowResized { id, width, height }) => {
+            if let Some(window) = windows.get_mut(*id) {
+                let scale_factor = window.scale_factor();
+                window.update_actual_size_from_backend(
+                    (*width as f64 * scale_factor) as u32,
+                    (*height as f64 * scale_factor) as u32,
+                );
+            }
+        }
+        RecordedEvent::WindowMoved(WindowMoved { id, position }) => {
+            if let Some(window) = windows.get_mut(*id) {
+                window.update_actual_position_from_backend(*position);
+            }
+        }
+        RecordedEvent::WindowFocused(WindowFocused { id, focused }) => {
+            if let Some(window) = windows.get_mut(*id) {
+                window.update_focused_status_from_backend(*focused);
+                if *focused {
+                    window.clear_attention_from_backend();
+                }
+            }
+        }
+        RecordedEvent::WindowOccluded(WindowOccluded { id, occluded }) => {
+            if let Some(window) = windows.get_mut(*id) {
+                window.update_occluded_from_backend(*occluded);
+            }
+        }
+        RecordedEvent::WindowBackendScaleFactorChanged(WindowBackendScaleFactorChanged {
+            id,
+            scale_factor,
+        }) => {
+            if let Some(window) = windows.get_mut(*id) {
+                window.update_scale_factor_from_backend(*scale_factor);
+            }
+        }
+        RecordedEvent::CursorMoved(CursorMoved { id, position }) => {
+            if let Some(window) = windows.get_mut(*id) {
+                window.update_cursor_position_from_backend(Some(*position));
+            }
+        }
+        RecordedEvent::CursorLeft(CursorLeft { id }) => {
+            if let Some(window) = windows.get_mut(*id) {
+                window.update_cursor_position_from_backend(None);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{replay_events_system, EventRecording, EventReplay, RecordedEvent};
+    use crate::{
+        AttentionType, CursorMoved, HeadlessWindowPlugin, RecordedEvents, WindowFocused, WindowId,
+        WindowPlugin, WindowTestScript, Windows,
+    };
+    use bevy_app::{App, CoreStage};
+    use bevy_ecs::{system::IntoExclusiveSystem, world::World};
+    use bevy_input::{
+        mouse::{MouseButton, MouseButtonInput},
+        ElementState,
+    };
+    use bevy_math::Vec2;
+
+    #[test]
+    fn save_and_load() {
+        let mut recording = EventRecording::default();
+        recording.push(
+            0,
+            CursorMoved {
+                id: WindowId::primary(),
+                position: Vec2::new(10.0, 20.0),
+            },
+        );
+        recording.push(
+            0,
+            MouseButtonInput {
+                button: MouseButton::Left,
+                state: ElementState::Pressed,
+            },
+        );
+        recording.push(
+            3,
+            MouseButtonInput {
+                button: MouseButton::Left,
+                state: ElementState::Released,
+            },
+        );
+
+        let path =
+            std::env::temp_dir().join(format!("bevy_window_recording_{}.ron", std::process::id()));
+        recording.save(&path).unwrap();
+        let loaded = EventRecording::load(&path);
+        std::fs::remove_file(&path).unwrap();
+        let loaded = loaded.unwrap();
+
+        assert_eq!(format!("{:?}", loaded), format!("{:?}", recording));
+        assert_eq!(loaded.events_in_frame(0).count(), 2);
+        assert!(matches!(
+            loaded.events_in_frame(3).next(),
+            Some(RecordedEvent::MouseButtonInput(MouseButtonInput {
+                state: ElementState::Released,
+                ..
+            }))
+        ));
+    }
+
+    #[test]
+    fn replay_primary_window() {
+        let mut recording = EventRecording::default();
+        recording.push(
+            1,
+            CursorMoved {
+                id: WindowId::primary(),
+                position: Vec2::new(10.0, 20.0),
+            },
+        );
+        recording.push(
+            1,
+            CursorMoved {
+                id: WindowId::new(),
+                position: Vec2::new(30.0, 40.0),
+            },
+        );
+
+        let mut app = App::new();
+        app.add_plugin(WindowPlugin::default())
+            .add_plugin(HeadlessWindowPlugin)
+            .insert_resource(EventReplay::new(recording))
+            .add_system_to_stage(CoreStage::First, replay_events_system.exclusive_system());
+
+        WindowTestScript::new()
+            .record::<CursorMoved>()
+            .assert_after(1, |world| {
+                let windows = world.get_resource::<Windows>().unwrap();
+                assert_eq!(
+                    windows.get_primary().unwrap().cursor_position(),
+                    Some(Vec2::new(10.0, 20.0))
+                );
+                let moved = world.get_resource::<RecordedEvents<CursorMoved>>().unwrap();
+                let ids = moved.in_frame(1).map(|moved| moved.id).collect::<Vec<_>>();
+                assert_eq!(ids, vec![WindowId::primary()]);
+                assert!(world.get_resource::<EventReplay>().unwrap().is_finished());
+            })
+            .run(&mut app, 2);
+    }
+
+    #[test]
+    fn replayed_focus_clears_the_attention_request() {
+        let mut recording = EventRecording::default();
+        let focused = |focused| WindowFocused {
+            id: WindowId::primary(),
+            focused,
+        };
+        recording.push(0, focused(false));
+        recording.push(2, focused(true));
+
+        let mut app = App::new();
+        app.add_plugin(WindowPlugin::default())
+            .add_plugin(HeadlessWindowPlugin)
+            .insert_resource(EventReplay::new(recording))
+            .add_system_to_stage(CoreStage::First, replay_events_system.exclusive_system());
+
+        let attention = |world: &World| {
+            let windows = world.get_resource::<Windows>().unwrap();
+            windows.get_primary().unwrap().attention()
+        };
+        WindowTestScript::new()
+            .run_at(1, |world| {
+                let mut windows = world.get_resource_mut::<Windows>().unwrap();
+                let window = windows.get_primary_mut().unwrap();
+                window.request_attention(Some(AttentionType::Informational));
+            })
+            .assert_after(1, move |world| {
+                assert_eq!(attention(world), Some(AttentionType::Informational))
+            })
+            .assert_after(2, move |world| assert_eq!(attention(world), None))
+            .run(&mut app, 3);
+    }
+}
@@ -1,11 +1,27 @@
-use crate::WindowCloseRequested;
+use crate::{CloseAllWindows, CloseWindow, WindowCloseRequested, Windows};
 use bevy_app::{AppExit, EventReader, EventWriter};
+use bevy_ecs::system::Res;
 
+/// Exits the app when a window is closed, once its close request has been handled according
+/// to the [`WindowClosePolicy`](crate::WindowClosePolicy).
 pub fn exit_on_window_close_system(
     mut app_exit_events: EventWriter<AppExit>,
-    mut window_close_requested_events: EventReader<WindowCloseRequested>,
+    mut close_window_events: EventReader<CloseWindow>,
 ) {
-    if window_close_requested_events.iter().next().is_some() {
+    if close_window_events.iter().next().is_some() {
         app_exit_events.send(AppExit);
     }
 }
+
+/// Sends a [`WindowCloseRequested`] event for each window when [`CloseAllWindows`] is sent.
+pub fn close_all_windows_system(
+    windows: Res<Windows>,
+    mut close_all_windows_events: EventReader<CloseAllWindows>,
+    mut window_close_requested_events: EventWriter<WindowCloseRequested>,
+) {
+    if close_all_windows_events.iter().next().is_some() {
+        for window in windows.iter() {
+            window_close_requested_events.send(WindowCloseRequested { id: window.id() });
+        }
+    }
+}
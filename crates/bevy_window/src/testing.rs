@@ -0,0 +1,178 @@
+use bevy_app::{prelude::*, EventReader, Events};
+use bevy_ecs::{
+    component::Component,
+    system::{IntoSystem, Res, ResMut},
+    world::World,
+};
+
+type ScriptAction = Box<dyn FnOnce(&mut World)>;
+type ScriptAssertion = Box<dyn FnOnce(&World)>;
+type ScriptSetup = Box<dyn FnOnce(&mut App)>;
+
+/// A script of synthetic window and input events that drives an [`App`] frame by frame, with
+/// assertions on the state of the app after some of the frames.
+///
+/// Use it with the [`HeadlessWindowPlugin`](crate::HeadlessWindowPlugin) to test how an app
+/// reacts to windowing and input without opening a window:
+///
+/// ```
+/// # use bevy_app::App;
+/// # use bevy_window::{
+/// #     HeadlessWindowPlugin, RecordedEvents, WindowPlugin, WindowResized, WindowTestScript, Windows,
+/// # };
+/// let mut app = App::new();
+/// app.add_plugin(WindowPlugin::default())
+///     .add_plugin(HeadlessWindowPlugin);
+///
+/// WindowTestScript::new()
+///     .record::<WindowResized>()
+///     .run_at(1, |world| {
+///         let mut windows = world.get_resource_mut::<Windows>().unwrap();
+///         windows.get_primary_mut().unwrap().set_resolution(640.0, 480.0);
+///     })
+///     .assert_after(1, |world| {
+///         let windows = world.get_resource::<Windows>().unwrap();
+///         assert_eq!(windows.get_primary().unwrap().width(), 640.0);
+///         let resized = world.get_resource::<RecordedEvents<WindowResized>>().unwrap();
+///         assert_eq!(resized.in_frame(1).count(), 1);
+///     })
+///     .run(&mut app, 2);
+/// ```
+///
+/// Frames are counted from 0, the first update of the app run by the script.
+#[derive(Default)]
+pub struct WindowTestScript {
+    setup: Vec<ScriptSetup>,
+    actions: Vec<(u32, ScriptAction)>,
+    assertions: Vec<(u32, ScriptAssertion)>,
+}
+
+impl WindowTestScript {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sends `event` right before the update of `frame`.
+    pub fn send<T: Component>(self, frame: u32, event: T) -> Self {
+        self.run_at(frame, move |world| {
+            world
+                .get_resource_mut::<Events<T>>()
+                .expect("the event sent by the script is not registered in the app")
+                .send(event);
+        })
+    }
+
+    /// Runs `action` on the world of the app right before the update of `frame`.
+    pub fn run_at(mut self, frame: u32, action: impl FnOnce(&mut World) + 'static) -> Self {
+        self.actions.push((frame, Box::new(action)));
+        self
+    }
+
+    /// Runs `assertion` on the world of the app right after the update of `frame`.
+    pub fn assert_after(mut self, frame: u32, assertion: impl FnOnce(&World) + 'static) -> Self {
+        self.assertions.push((frame, Box::new(assertion)));
+        self
+    }
+
+    /// Records the events of type `T` sent while the script runs in the [`RecordedEvents<T>`]
+    /// resource, so assertions can check which events the app emitted.
+    pub fn record<T: Component + Clone>(mut self) -> Self {
+        self.setup.push(Box::new(|app| {
+            app.insert_resource(RecordedEvents::<T>::default())
+                .add_system_to_stage(CoreStage::Last, record_events_system::<T>.system());
+        }));
+        self
+    }
+
+    /// Runs `frames` updates of `app`, sending the events and checking the assertions of the
+    /// script.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an assertion fails, or if an action or assertion is scheduled after the last
+    /// frame.
+    pub fn run(mut self, app: &mut App, frames: u32) {
+        let last_frame = self
+            .actions
+            .iter()
+            .map(|(frame, _)| *frame)
+            .chain(self.assertions.iter().map(|(frame, _)| *frame))
+            .max();
+        if let Some(last_frame) = last_frame.filter(|last_frame| *last_frame >= frames) {
+            panic!(
+                "the script has steps at frame {}, but only runs {} frames",
+                last_frame, frames
+            );
+        }
+        for setup in self.setup.drain(..) {
+            setup(app);
+        }
+        app.insert_resource(ScriptFrame(0));
+        // the actions and assertions of a frame run in the order they were added
+        self.actions.sort_by_key(|(frame, _)| *frame);
+        self.assertions.sort_by_key(|(frame, _)| *frame);
+        let mut actions = self.actions.into_iter().peekable();
+        let mut assertions = self.assertions.into_iter().peekable();
+
+        for frame in 0..frames {
+            app.world.get_resource_mut::<ScriptFrame>().unwrap().0 = frame;
+            while let Some((_, action)) = actions.next_if(|(at, _)| *at == frame) {
+                action(&mut app.world);
+            }
+            app.update();
+            while let Some((_, assertion)) = assertions.next_if(|(at, _)| *at == frame) {
+                assertion(&app.world);
+            }
+        }
+    }
+}
+
+/// The frame of the [`WindowTestScript`] being run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScriptFrame(pub u32);
+
+/// The events of type `T` sent while a [`WindowTestScript`] runs, with the frame they were read
+/// in. Added by [`WindowTestScript::record`].
+#[derive(Debug)]
+pub struct RecordedEvents<T> {
+    events: Vec<(u32, T)>,
+}
+
+impl<T> Default for RecordedEvents<T> {
+    fn default() -> Self {
+        RecordedEvents { events: Vec::new() }
+    }
+}
+
+impl<T> RecordedEvents<T> {
+    /// Iterates over all the recorded events, with their frame.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, &T)> {
+        self.events.iter().map(|(frame, event)| (*frame, event))
+    }
+
+    /// Iterates over the events recorded in `frame`.
+    pub fn in_frame(&self, frame: u32) -> impl Iterator<Item = &T> {
+        self.events
+            .iter()
+            .filter(move |(at, _)| *at == frame)
+            .map(|(_, event)| event)
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+}
+
+fn record_events_system<T: Component + Clone>(
+    frame: Res<ScriptFrame>,
+    mut recorded: ResMut<RecordedEvents<T>>,
+    mut events: EventReader<T>,
+) {
+    for event in events.iter() {
+        recorded.events.push((frame.0, event.clone()));
+    }
+}
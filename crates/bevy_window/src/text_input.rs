@@ -0,0 +1,54 @@
+use crate::WindowId;
+
+/// The text typed in a window during a frame, sent by the window backend once per frame for each
+/// window that received text.
+///
+/// Unlike [`ReceivedCharacter`](crate::ReceivedCharacter), the control characters sent for keys
+/// such as backspace, enter or the arrows are filtered out, as text widgets handle these keys
+/// with the [`KeyboardInput`](bevy_input::keyboard::KeyboardInput) events, and the characters of
+/// repeated keys are only kept if [`TextInputSettings::key_repeat`] is set.
+///
+/// There is no separate stream of IME commits to merge: winit 0.25 doesn't report the IME
+/// composition, and delivers the committed text as received characters, which are included.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextInputEvent {
+    pub id: WindowId,
+    pub text: String,
+}
+
+/// Configures the [`TextInputEvent`]s.
+#[derive(Debug, Clone)]
+pub struct TextInputSettings {
+    /// Whether the characters typed by holding a key down are part of the text input.
+    pub key_repeat: bool,
+}
+
+impl Default for TextInputSettings {
+    fn default() -> Self {
+        TextInputSettings { key_repeat: true }
+    }
+}
+
+/// Returns `true` if `char` is text that can be inserted in a text widget.
+///
+/// This excludes the control characters, and the characters of the private use area that macOS
+/// sends for function and arrow keys.
+pub fn is_text_character(char: char) -> bool {
+    !char.is_control() && !('\u{f700}'..='\u{f8ff}').contains(&char)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::is_text_character;
+
+    #[test]
+    fn text_characters() {
+        assert!(is_text_character('a'));
+        assert!(is_text_character('é'));
+        assert!(is_text_character(' '));
+        assert!(!is_text_character('\u{8}'));
+        assert!(!is_text_character('\r'));
+        assert!(!is_text_character('\u{7f}'));
+        assert!(!is_text_character('\u{f702}'));
+    }
+}
@@ -0,0 +1,64 @@
+use crate::{
+    CursorEntered, CursorLeft, CursorMoved, FileDragAndDrop, FilesDropped, ReceivedCharacter,
+    TextInputEvent, WindowBackendScaleFactorChanged, WindowCloseRequested, WindowFocused,
+    WindowMoved, WindowResized, WindowRotated, WindowScaleFactorChanged, WindowStateChanged,
+};
+use bevy_app::App;
+use bevy_ecs::component::Component;
+use bevy_input::{
+    keyboard::{KeyboardInput, ModifiersChanged},
+    mouse::{MouseButtonInput, MouseMotion, MouseWheel},
+    touch::TouchInput,
+};
+use bevy_utils::Instant;
+
+/// When the window backend received an event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EventTimestamp {
+    /// The time at which the event was received from the platform.
+    pub instant: Instant,
+    /// The number of app updates before the event was received, which is the frame in which the
+    /// event is read.
+    pub frame: u64,
+}
+
+/// A copy of a window or input event, sent along with it by the window backend when timestamps
+/// are enabled.
+///
+/// Coalesced events, such as the [`TextInputEvent`]s and [`FilesDropped`], have the timestamp of
+/// the first platform event they contain, or of the last one for the coalesced [`CursorMoved`]
+/// events.
+#[derive(Debug, Clone)]
+pub struct Timestamped<T> {
+    pub event: T,
+    pub timestamp: EventTimestamp,
+}
+
+/// Adds the [`Timestamped`] events of the events sent by the window backend.
+pub(crate) fn add_timestamped_events(app: &mut App) {
+    add_timestamped_event::<WindowResized>(app);
+    add_timestamped_event::<WindowMoved>(app);
+    add_timestamped_event::<WindowRotated>(app);
+    add_timestamped_event::<WindowStateChanged>(app);
+    add_timestamped_event::<WindowCloseRequested>(app);
+    add_timestamped_event::<WindowFocused>(app);
+    add_timestamped_event::<WindowScaleFactorChanged>(app);
+    add_timestamped_event::<WindowBackendScaleFactorChanged>(app);
+    add_timestamped_event::<FileDragAndDrop>(app);
+    add_timestamped_event::<FilesDropped>(app);
+    add_timestamped_event::<CursorMoved>(app);
+    add_timestamped_event::<CursorEntered>(app);
+    add_timestamped_event::<CursorLeft>(app);
+    add_timestamped_event::<ReceivedCharacter>(app);
+    add_timestamped_event::<TextInputEvent>(app);
+    add_timestamped_event::<KeyboardInput>(app);
+    add_timestamped_event::<ModifiersChanged>(app);
+    add_timestamped_event::<MouseButtonInput>(app);
+    add_timestamped_event::<MouseMotion>(app);
+    add_timestamped_event::<MouseWheel>(app);
+    add_timestamped_event::<TouchInput>(app);
+}
+
+fn add_timestamped_event<T: Component>(app: &mut App) {
+    app.add_event::<Timestamped<T>>();
+}
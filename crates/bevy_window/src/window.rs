@@ -1,7 +1,12 @@
-use bevy_math::{IVec2, Vec2};
+use crate::{
+    CursorIcon, MonitorSelection, SnapRegion, VideoModeSelection, WindowConstraintViolation,
+};
+use bevy_math::{IVec2, UVec2, Vec2};
 use bevy_utils::{tracing::warn, Uuid};
+use std::path::PathBuf;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct WindowId(Uuid);
 
 impl WindowId {
@@ -38,7 +43,7 @@ impl Default for WindowId {
 /// Please note that if the window is resizable, then when the window is
 /// maximized it may have a size outside of these limits. The functionality
 /// required to disable maximizing is not yet exposed by winit.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct WindowResizeConstraints {
     pub min_width: f32,
     pub min_height: f32,
@@ -59,35 +64,45 @@ impl Default for WindowResizeConstraints {
 
 impl WindowResizeConstraints {
     pub fn check_constraints(&self) -> WindowResizeConstraints {
-        let WindowResizeConstraints {
-            mut min_width,
-            mut min_height,
-            mut max_width,
-            mut max_height,
-        } = self;
-        min_width = min_width.max(1.);
-        min_height = min_height.max(1.);
-        if max_width < min_width {
+        let corrected = self.corrected();
+        if corrected.max_width != self.max_width {
             warn!(
                 "The given maximum width {} is smaller than the minimum width {}",
-                max_width, min_width
+                self.max_width, corrected.min_width
             );
-            max_width = min_width;
         }
-        if max_height < min_height {
+        if corrected.max_height != self.max_height {
             warn!(
                 "The given maximum height {} is smaller than the minimum height {}",
-                max_height, min_height
+                self.max_height, corrected.min_height
             );
-            max_height = min_height;
         }
+        corrected
+    }
+
+    /// The constraints the window backends apply: the minimum size is at least 1, and the
+    /// maximum size at least the minimum size. Unlike [`Self::check_constraints`], nothing is
+    /// logged.
+    pub fn corrected(&self) -> WindowResizeConstraints {
+        let min_width = self.min_width.max(1.);
+        let min_height = self.min_height.max(1.);
         WindowResizeConstraints {
             min_width,
             min_height,
-            max_width,
-            max_height,
+            max_width: self.max_width.max(min_width),
+            max_height: self.max_height.max(min_height),
         }
     }
+
+    /// Returns the event to send when these constraints of the window `id` had to be corrected.
+    pub fn violation(&self, id: WindowId) -> Option<WindowConstraintViolation> {
+        let corrected = self.corrected();
+        (corrected != *self).then_some(WindowConstraintViolation {
+            id,
+            requested: *self,
+            corrected,
+        })
+    }
 }
 
 /// An operating system window that can present content and receive user input.
@@ -120,11 +135,28 @@ pub struct Window {
     vsync: bool,
     resizable: bool,
     decorations: bool,
+    level: WindowLevel,
     cursor_visible: bool,
-    cursor_locked: bool,
+    cursor_icon: CursorIcon,
+    cursor_grab_mode: CursorGrabMode,
+    raw_mouse_motion: bool,
+    scale_factor_policy: ScaleFactorPolicy,
+    render_policy: WindowRenderPolicy,
     cursor_position: Option<Vec2>,
     focused: bool,
+    occluded: bool,
+    maximized: bool,
     mode: WindowMode,
+    input_routing: InputRouting,
+    offscreen: bool,
+    taskbar_progress: Option<TaskbarProgress>,
+    badge: Option<WindowBadge>,
+    soft_keyboard_visible: bool,
+    soft_keyboard_inset: f32,
+    ime_position: Vec2,
+    attention: Option<AttentionType>,
+    represented_file: Option<PathBuf>,
+    screen_orientation: ScreenOrientation,
     #[cfg(target_arch = "wasm32")]
     pub canvas: Option<String>,
     command_queue: Vec<WindowCommand>,
@@ -155,12 +187,18 @@ pub enum WindowCommand {
     SetDecorations {
         decorations: bool,
     },
-    SetCursorLockMode {
-        locked: bool,
+    SetWindowLevel {
+        level: WindowLevel,
+    },
+    SetCursorGrabMode {
+        mode: CursorGrabMode,
     },
     SetCursorVisibility {
         visible: bool,
     },
+    SetCursorIcon {
+        icon: CursorIcon,
+    },
     SetCursorPosition {
         position: Vec2,
     },
@@ -176,6 +214,30 @@ pub enum WindowCommand {
     SetResizeConstraints {
         resize_constraints: WindowResizeConstraints,
     },
+    SetTaskbarProgress {
+        progress: Option<TaskbarProgress>,
+    },
+    SetBadge {
+        badge: Option<WindowBadge>,
+    },
+    Snap {
+        region: SnapRegion,
+    },
+    SetSoftKeyboardVisible {
+        visible: bool,
+    },
+    SetImePosition {
+        position: Vec2,
+    },
+    RequestAttention {
+        attention: Option<AttentionType>,
+    },
+    SetRepresentedFile {
+        path: Option<PathBuf>,
+    },
+    SetScreenOrientation {
+        orientation: ScreenOrientation,
+    },
 }
 
 /// Defines the way a window is displayed
@@ -187,7 +249,257 @@ pub enum WindowCommand {
 pub enum WindowMode {
     Windowed,
     BorderlessFullscreen,
-    Fullscreen { use_size: bool },
+    /// Exclusive fullscreen on the current monitor, the same as
+    /// [`ExclusiveFullscreen`](WindowMode::ExclusiveFullscreen) with
+    /// [`VideoModeSelection::FitWindow`] if `use_size` is set, and [`VideoModeSelection::Best`]
+    /// otherwise.
+    Fullscreen {
+        use_size: bool,
+    },
+    /// Exclusive fullscreen on the selected monitor, in the selected video mode of the monitor.
+    /// Falls back to the current monitor if the selected one doesn't exist.
+    ExclusiveFullscreen {
+        monitor: MonitorSelection,
+        video_mode: VideoModeSelection,
+    },
+}
+
+impl WindowMode {
+    /// The monitor and video mode of the exclusive fullscreen modes, or `None` for the other
+    /// modes.
+    pub fn exclusive_fullscreen(&self) -> Option<(MonitorSelection, VideoModeSelection)> {
+        match *self {
+            WindowMode::Fullscreen { use_size: true } => {
+                Some((MonitorSelection::Current, VideoModeSelection::FitWindow))
+            }
+            WindowMode::Fullscreen { use_size: false } => {
+                Some((MonitorSelection::Current, VideoModeSelection::Best))
+            }
+            WindowMode::ExclusiveFullscreen {
+                monitor,
+                video_mode,
+            } => Some((monitor, video_mode)),
+            WindowMode::Windowed | WindowMode::BorderlessFullscreen => None,
+        }
+    }
+}
+
+/// How the cursor is held by a window, set with [`Window::set_cursor_grab_mode`].
+///
+/// The platforms that don't have a mode use another one instead, which is the mode reported by
+/// [`Window::cursor_grab_mode`] and the [`CursorGrabChanged`](crate::CursorGrabChanged) event:
+/// - On Windows and X11, `Locked` is emulated by confining the cursor and moving it back to the
+///   center of the window whenever it moves.
+/// - On Wayland, where the cursor can't be moved, `Locked` only confines the cursor.
+/// - On macOS and on the web, `Confined` falls back to `Locked`.
+/// - On Android and iOS, the cursor can't be grabbed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum CursorGrabMode {
+    /// The cursor moves freely.
+    #[default]
+    None,
+    /// The cursor can't leave the window.
+    Confined,
+    /// The cursor doesn't move, only the `MouseMotion` events report the motion of the mouse,
+    /// for example to look around in a first person game.
+    Locked,
+}
+
+/// Defines how the size of a window changes when its scale factor changes, for example when it
+/// is moved to a monitor with another scale factor.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ScaleFactorPolicy {
+    /// The window keeps its size in logical pixels, so its content keeps the same size on the
+    /// screen.
+    #[default]
+    KeepLogicalSize,
+    /// The window keeps its size in physical pixels, so its content is displayed bigger or
+    /// smaller.
+    KeepPhysicalSize,
+    /// The window is resized to its [requested size](Window::requested_width) in logical pixels,
+    /// discarding the changes made by the user since the last
+    /// [`set_resolution`](Window::set_resolution).
+    KeepRequestedSize,
+}
+
+/// Defines how the cameras of a window present their content as the window is resized, such as
+/// for pixel art games rendered at a fixed resolution.
+///
+/// The fixed resolutions are in the pixels of the content: the projections of the cameras show
+/// that many units, which are scaled to the physical pixels of the window. The part of the window
+/// outside of the content is cleared with the clear color. The UI is laid out in the content too,
+/// and hit-tested with [`Window::render_cursor_position`].
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum WindowRenderPolicy {
+    /// The content fills the window at its own size.
+    #[default]
+    Native,
+    /// The content has a fixed resolution, stretched to fill the window without keeping its
+    /// aspect ratio.
+    Stretch { width: f32, height: f32 },
+    /// The content has a fixed resolution, scaled by the largest whole factor that fits in the
+    /// window, and centered.
+    IntegerScale { width: f32, height: f32 },
+    /// The content has a fixed resolution, scaled to the largest size that fits in the window
+    /// while keeping its aspect ratio, and centered.
+    Letterbox { width: f32, height: f32 },
+}
+
+impl WindowRenderPolicy {
+    /// The fixed resolution of the content, or `None` if it follows the size of the window.
+    pub fn resolution(&self) -> Option<Vec2> {
+        match *self {
+            WindowRenderPolicy::Native => None,
+            WindowRenderPolicy::Stretch { width, height }
+            | WindowRenderPolicy::IntegerScale { width, height }
+            | WindowRenderPolicy::Letterbox { width, height } => Some(Vec2::new(width, height)),
+        }
+    }
+
+    /// The position from the top left and the size of the content in a window of
+    /// `physical_size`, in physical pixels.
+    pub fn viewport(&self, physical_size: UVec2) -> (UVec2, UVec2) {
+        let window_size = physical_size.as_f32();
+        let size = match *self {
+            WindowRenderPolicy::Native | WindowRenderPolicy::Stretch { .. } => {
+                return (UVec2::ZERO, physical_size)
+            }
+            WindowRenderPolicy::IntegerScale { width, height } => {
+                let scale = (window_size.x / width)
+                    .min(window_size.y / height)
+                    .floor()
+                    .max(1.0);
+                Vec2::new(width, height) * scale
+            }
+            WindowRenderPolicy::Letterbox { width, height } => {
+                let scale = (window_size.x / width).min(window_size.y / height);
+                Vec2::new(width, height) * scale
+            }
+        };
+        let size = size.min(window_size).as_u32();
+        ((physical_size - size) / 2, size)
+    }
+}
+
+/// Defines which input events received by a window are delivered to the app.
+///
+/// This applies to the keyboard, mouse button, mouse wheel, touch and character events. With
+/// several windows open, [`FocusedWindow`](InputRouting::FocusedWindow) or
+/// [`HoveredWindow`](InputRouting::HoveredWindow) prevent a window from reacting to the input
+/// meant for another one.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum InputRouting {
+    /// All the input received by the window is delivered.
+    #[default]
+    Broadcast,
+    /// Input is only delivered while the window has the focus.
+    FocusedWindow,
+    /// Input is only delivered while the cursor is over the window.
+    HoveredWindow,
+}
+
+/// The orientations of the screen that a window can be displayed in, on mobile devices and on the
+/// web.
+///
+/// On iOS, the primary and secondary variants allow both orientations of the same kind.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ScreenOrientation {
+    /// Follows the rotation of the device.
+    #[default]
+    Auto,
+    /// Either portrait orientation.
+    Portrait,
+    /// Either landscape orientation.
+    Landscape,
+    /// The natural portrait orientation of the device.
+    PortraitPrimary,
+    /// The upside down portrait orientation.
+    PortraitSecondary,
+    /// The natural landscape orientation of the device.
+    LandscapePrimary,
+    /// The landscape orientation rotated the other way.
+    LandscapeSecondary,
+}
+
+/// Whether a window stays above or below the other windows, see [`Window::set_level`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum WindowLevel {
+    /// The window is above the other windows when it is focused.
+    #[default]
+    Normal,
+    /// The window stays above the windows of the other apps, such as for an overlay or a
+    /// debugging HUD, even when it isn't focused.
+    AlwaysOnTop,
+    /// The window stays below the windows of the other apps.
+    AlwaysOnBottom,
+}
+
+/// Whether a window is minimized, maximized or neither, see [`Window::state`].
+///
+/// A [`WindowStateChanged`](crate::WindowStateChanged) event is sent when it changes.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub enum WindowState {
+    #[default]
+    Normal,
+    Maximized,
+    Minimized,
+}
+
+/// The color theme of the decorations of a window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowTheme {
+    Light,
+    Dark,
+}
+
+/// The progress of a long operation, shown on the taskbar button of a window.
+///
+/// Only supported on Windows, it is ignored on the other platforms.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TaskbarProgress {
+    /// The completed fraction of the operation, from `0.0` to `1.0`. Ignored when the state is
+    /// [`Indeterminate`](TaskbarProgressState::Indeterminate).
+    pub value: f32,
+    pub state: TaskbarProgressState,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TaskbarProgressState {
+    #[default]
+    Normal,
+    Paused,
+    Error,
+    /// The operation is ongoing but its progress is unknown.
+    Indeterminate,
+}
+
+/// How insistently a window [requests the attention](Window::request_attention) of the user.
+///
+/// On Linux with X11, both types set the urgency hint of the window.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum AttentionType {
+    /// Bounces the dock icon until the app is focused on macOS, and flashes the window and its
+    /// taskbar button until it is focused on Windows.
+    Critical,
+    /// Bounces the dock icon once on macOS, and flashes the taskbar button until the window is
+    /// focused on Windows.
+    #[default]
+    Informational,
+}
+
+/// A badge shown on the icon of the app, such as a count of unread messages.
+///
+/// On macOS, it is shown on the dock icon. As the dock icon is shared by all the windows, the badge
+/// of the last window it was set on is shown. On Windows, it is drawn in an overlay icon on the
+/// taskbar button of the window. On the other platforms, the badge is ignored and a warning is
+/// logged the first time one is set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WindowBadge {
+    /// A number, the badge is hidden when it is `0`.
+    Count(u32),
+    Text(String),
 }
 
 impl Window {
@@ -213,11 +525,29 @@ impl Window {
             vsync: window_descriptor.vsync,
             resizable: window_descriptor.resizable,
             decorations: window_descriptor.decorations,
+            level: window_descriptor.level,
             cursor_visible: window_descriptor.cursor_visible,
-            cursor_locked: window_descriptor.cursor_locked,
+            cursor_icon: CursorIcon::Default,
+            cursor_grab_mode: window_descriptor.cursor_grab_mode,
+            raw_mouse_motion: window_descriptor.raw_mouse_motion,
+            scale_factor_policy: window_descriptor.scale_factor_policy,
+            render_policy: window_descriptor.render_policy,
             cursor_position: None,
-            focused: true,
+            // offscreen windows never receive input, so they can't have the focus
+            focused: !window_descriptor.offscreen,
+            occluded: false,
+            maximized: false,
             mode: window_descriptor.mode,
+            input_routing: window_descriptor.input_routing,
+            offscreen: window_descriptor.offscreen,
+            taskbar_progress: None,
+            badge: None,
+            soft_keyboard_visible: false,
+            soft_keyboard_inset: 0.0,
+            ime_position: Vec2::ZERO,
+            attention: None,
+            represented_file: None,
+            screen_orientation: window_descriptor.screen_orientation,
             #[cfg(target_arch = "wasm32")]
             canvas: window_descriptor.canvas.clone(),
             command_queue: Vec::new(),
@@ -400,6 +730,94 @@ impl Window {
         self.scale_factor_override
     }
 
+    #[inline]
+    pub fn scale_factor_policy(&self) -> ScaleFactorPolicy {
+        self.scale_factor_policy
+    }
+
+    /// Sets how the size of the window changes when its scale factor changes.
+    pub fn set_scale_factor_policy(&mut self, scale_factor_policy: ScaleFactorPolicy) {
+        self.scale_factor_policy = scale_factor_policy;
+    }
+
+    #[inline]
+    pub fn render_policy(&self) -> WindowRenderPolicy {
+        self.render_policy
+    }
+
+    /// Sets how the cameras of the window present their content as the window is resized.
+    pub fn set_render_policy(&mut self, render_policy: WindowRenderPolicy) {
+        self.render_policy = render_policy;
+    }
+
+    /// The position from the top left and the size in physical pixels of the part of the window
+    /// its cameras draw to, following its [`WindowRenderPolicy`].
+    pub fn render_viewport(&self) -> (UVec2, UVec2) {
+        self.render_policy
+            .viewport(UVec2::new(self.physical_width, self.physical_height))
+    }
+
+    /// The size of the content drawn by the cameras of the window: the fixed resolution of its
+    /// [`WindowRenderPolicy`], or its logical size.
+    pub fn render_size(&self) -> Vec2 {
+        self.render_policy
+            .resolution()
+            .unwrap_or_else(|| Vec2::new(self.width(), self.height()))
+    }
+
+    /// The ratio of physical pixels to the units of [`Window::render_size`]: the scale factor of
+    /// the window, or 1 with a fixed resolution, whose units are the pixels of the content.
+    pub fn render_scale_factor(&self) -> f64 {
+        match self.render_policy.resolution() {
+            Some(_) => 1.0,
+            None => self.scale_factor(),
+        }
+    }
+
+    /// The position of the cursor in the content drawn by the cameras of the window, from its
+    /// bottom left corner in the units of [`Window::render_size`]. It is `None` when the cursor is
+    /// outside of the content, such as over the bars of [`WindowRenderPolicy::Letterbox`].
+    pub fn render_cursor_position(&self) -> Option<Vec2> {
+        let cursor_position = self.cursor_position()?;
+        let resolution = match self.render_policy.resolution() {
+            Some(resolution) => resolution,
+            None => return Some(cursor_position),
+        };
+        let (viewport_position, viewport_size) = self.render_viewport();
+        let physical_position = cursor_position * self.scale_factor() as f32;
+        // the viewport is positioned from the top left of the window
+        let viewport_cursor_position = Vec2::new(
+            physical_position.x,
+            self.physical_height as f32 - physical_position.y,
+        ) - viewport_position.as_f32();
+        let viewport_size = viewport_size.as_f32();
+        if viewport_cursor_position.cmplt(Vec2::ZERO).any()
+            || viewport_cursor_position.cmpge(viewport_size).any()
+        {
+            return None;
+        }
+        let position = viewport_cursor_position / viewport_size * resolution;
+        Some(Vec2::new(position.x, resolution.y - position.y))
+    }
+
+    /// The size in physical pixels the window should have once the window backend changes its
+    /// scale factor to `backend_scale_factor`, following its
+    /// [`ScaleFactorPolicy`].
+    pub fn physical_size_for_backend_scale_factor(&self, backend_scale_factor: f64) -> (u32, u32) {
+        let scale_factor = self.scale_factor_override.unwrap_or(backend_scale_factor);
+        let (width, height) = match self.scale_factor_policy {
+            ScaleFactorPolicy::KeepLogicalSize => (self.width(), self.height()),
+            ScaleFactorPolicy::KeepPhysicalSize => {
+                return (self.physical_width, self.physical_height)
+            }
+            ScaleFactorPolicy::KeepRequestedSize => (self.requested_width, self.requested_height),
+        };
+        (
+            (width as f64 * scale_factor) as u32,
+            (height as f64 * scale_factor) as u32,
+        )
+    }
+
     #[inline]
     pub fn title(&self) -> &str {
         &self.title
@@ -437,21 +855,82 @@ impl Window {
         self.decorations
     }
 
+    /// Shows or hides the titlebar and borders of the window.
+    ///
+    /// On Wayland, the decorations are drawn by the compositor when it supports server-side
+    /// decorations, and by the window backend otherwise. The backend doesn't allow preferring
+    /// one over the other, nor report which one is used.
     pub fn set_decorations(&mut self, decorations: bool) {
         self.decorations = decorations;
         self.command_queue
             .push(WindowCommand::SetDecorations { decorations });
     }
 
+    #[inline]
+    pub fn level(&self) -> WindowLevel {
+        self.level
+    }
+
+    /// Keeps the window above or below the windows of the other apps.
+    ///
+    /// Not supported on the web, Android and iOS. The window backend of `bevy_winit` can't keep a
+    /// window below the others, [`WindowLevel::AlwaysOnBottom`] windows are normal windows there.
+    pub fn set_level(&mut self, level: WindowLevel) {
+        self.level = level;
+        self.command_queue
+            .push(WindowCommand::SetWindowLevel { level });
+    }
+
+    /// Returns `true` if the cursor is grabbed, whether it is confined or locked.
     #[inline]
     pub fn cursor_locked(&self) -> bool {
-        self.cursor_locked
+        self.cursor_grab_mode != CursorGrabMode::None
     }
 
+    /// Confines or releases the cursor, see [`Window::set_cursor_grab_mode`].
     pub fn set_cursor_lock_mode(&mut self, lock_mode: bool) {
-        self.cursor_locked = lock_mode;
+        self.set_cursor_grab_mode(if lock_mode {
+            CursorGrabMode::Confined
+        } else {
+            CursorGrabMode::None
+        });
+    }
+
+    #[inline]
+    pub fn cursor_grab_mode(&self) -> CursorGrabMode {
+        self.cursor_grab_mode
+    }
+
+    /// Grabs or releases the cursor. A [`CursorGrabChanged`](crate::CursorGrabChanged) event is
+    /// sent once the platform has applied or rejected the change, with the mode used on the
+    /// platform, see [`CursorGrabMode`].
+    pub fn set_cursor_grab_mode(&mut self, mode: CursorGrabMode) {
+        self.cursor_grab_mode = mode;
         self.command_queue
-            .push(WindowCommand::SetCursorLockMode { locked: lock_mode });
+            .push(WindowCommand::SetCursorGrabMode { mode });
+    }
+
+    #[inline]
+    pub fn raw_mouse_motion(&self) -> bool {
+        self.raw_mouse_motion
+    }
+
+    /// Sets whether only the raw `MouseMotion` events are used while the cursor is locked.
+    ///
+    /// `MouseMotion` reports the motion of the mouse device, before the pointer acceleration of
+    /// the platform is applied and without being clamped to the window. In raw mode, the cursor
+    /// position of a locked window is not tracked, and [`Window::cursor_position`] returns
+    /// `None`, so that code based on the cursor position (such as UI hovering) doesn't react to
+    /// the mouse used to look around.
+    pub fn set_raw_mouse_motion(&mut self, raw_mouse_motion: bool) {
+        self.raw_mouse_motion = raw_mouse_motion;
+    }
+
+    /// Returns `true` if the cursor is grabbed and
+    /// [raw mouse motion](Window::set_raw_mouse_motion) is enabled.
+    #[inline]
+    pub fn uses_raw_mouse_motion(&self) -> bool {
+        self.raw_mouse_motion && self.cursor_locked()
     }
 
     #[inline]
@@ -466,23 +945,70 @@ impl Window {
         });
     }
 
+    #[inline]
+    pub fn cursor_icon(&self) -> CursorIcon {
+        self.cursor_icon
+    }
+
+    /// Changes the icon of the cursor while it is over the window. See [`CursorIcon`] for the
+    /// icons shown on the platforms that don't have it.
+    pub fn set_cursor_icon(&mut self, icon: CursorIcon) {
+        self.cursor_icon = icon;
+        self.command_queue
+            .push(WindowCommand::SetCursorIcon { icon });
+    }
+
+    /// The position of the cursor in the window, or `None` if it is outside of the window or the
+    /// window [uses raw mouse motion](Window::uses_raw_mouse_motion).
     #[inline]
     #[doc(alias = "mouse position")]
     pub fn cursor_position(&self) -> Option<Vec2> {
-        self.cursor_position
+        if self.uses_raw_mouse_motion() {
+            None
+        } else {
+            self.cursor_position
+        }
     }
 
+    /// Moves the cursor to `position`, in logical pixels from the bottom left of the window.
+    ///
+    /// A [`CursorWarped`](crate::CursorWarped) event is sent once the cursor has been moved, or
+    /// a [`CursorWarpFailed`](crate::CursorWarpFailed) event if the platform refused it.
     pub fn set_cursor_position(&mut self, position: Vec2) {
         self.command_queue
             .push(WindowCommand::SetCursorPosition { position });
     }
 
+    #[allow(missing_docs)]
+    #[inline]
+    pub fn update_cursor_grab_mode_from_backend(&mut self, mode: CursorGrabMode) {
+        self.cursor_grab_mode = mode;
+    }
+
     #[allow(missing_docs)]
     #[inline]
     pub fn update_focused_status_from_backend(&mut self, focused: bool) {
         self.focused = focused;
     }
 
+    #[allow(missing_docs)]
+    #[inline]
+    pub fn update_soft_keyboard_inset_from_backend(&mut self, inset: f32) {
+        self.soft_keyboard_inset = inset;
+    }
+
+    #[allow(missing_docs)]
+    #[inline]
+    pub fn update_occluded_from_backend(&mut self, occluded: bool) {
+        self.occluded = occluded;
+    }
+
+    #[allow(missing_docs)]
+    #[inline]
+    pub fn update_maximized_from_backend(&mut self, maximized: bool) {
+        self.maximized = maximized;
+    }
+
     #[allow(missing_docs)]
     #[inline]
     pub fn update_cursor_position_from_backend(&mut self, cursor_position: Option<Vec2>) {
@@ -511,6 +1037,264 @@ impl Window {
     pub fn is_focused(&self) -> bool {
         self.focused
     }
+
+    /// Returns `true` if the window is known to be hidden from the user, such as a canvas in a
+    /// background tab of the browser. Only reported on the web.
+    #[inline]
+    pub fn is_occluded(&self) -> bool {
+        self.occluded
+    }
+
+    /// Returns `true` if the window is minimized, or for some other reason has no pixels to draw
+    /// to. The platforms report minimized windows as having a size of zero, and the renderer
+    /// skips them until they are restored.
+    #[inline]
+    pub fn is_minimized(&self) -> bool {
+        self.physical_width == 0 || self.physical_height == 0
+    }
+
+    /// Returns `true` if the window is maximized. A maximized window that is then minimized is
+    /// still maximized, and is restored to its maximized size.
+    #[inline]
+    pub fn is_maximized(&self) -> bool {
+        self.maximized
+    }
+
+    /// Whether the window is minimized, maximized or neither. Minimized takes precedence over
+    /// maximized.
+    #[inline]
+    pub fn state(&self) -> WindowState {
+        if self.is_minimized() {
+            WindowState::Minimized
+        } else if self.maximized {
+            WindowState::Maximized
+        } else {
+            WindowState::Normal
+        }
+    }
+
+    /// Returns `true` if this window has no platform window, see
+    /// [`WindowDescriptor::offscreen`].
+    #[inline]
+    pub fn is_offscreen(&self) -> bool {
+        self.offscreen
+    }
+
+    #[inline]
+    pub fn input_routing(&self) -> InputRouting {
+        self.input_routing
+    }
+
+    /// Sets which input events received by this window are delivered to the app.
+    #[inline]
+    pub fn set_input_routing(&mut self, input_routing: InputRouting) {
+        self.input_routing = input_routing;
+    }
+
+    #[inline]
+    pub fn taskbar_progress(&self) -> Option<TaskbarProgress> {
+        self.taskbar_progress
+    }
+
+    /// Shows the progress of a long operation on the taskbar button of this window, or hides it
+    /// with `None`.
+    pub fn set_taskbar_progress(&mut self, progress: Option<TaskbarProgress>) {
+        self.taskbar_progress = progress;
+        self.command_queue
+            .push(WindowCommand::SetTaskbarProgress { progress });
+    }
+
+    /// Moves and resizes the window to fill `region` of its current monitor, such as its left
+    /// half, for side by side windows. The window is restored first if it is maximized.
+    ///
+    /// The region includes the decorations of the window. The taskbar and the dock are not taken
+    /// into account.
+    pub fn snap(&mut self, region: SnapRegion) {
+        self.command_queue.push(WindowCommand::Snap { region });
+    }
+
+    #[inline]
+    pub fn badge(&self) -> Option<&WindowBadge> {
+        self.badge.as_ref()
+    }
+
+    /// Shows a badge on the icon of the app, or hides it with `None`.
+    pub fn set_badge(&mut self, badge: Option<WindowBadge>) {
+        self.badge = badge.clone();
+        self.command_queue.push(WindowCommand::SetBadge { badge });
+    }
+
+    #[inline]
+    pub fn soft_keyboard_visible(&self) -> bool {
+        self.soft_keyboard_visible
+    }
+
+    /// Shows or hides the on-screen keyboard, typically when a text field gains or loses the
+    /// focus.
+    ///
+    /// Only supported on Android.
+    pub fn set_soft_keyboard_visible(&mut self, visible: bool) {
+        self.soft_keyboard_visible = visible;
+        self.command_queue
+            .push(WindowCommand::SetSoftKeyboardVisible { visible });
+    }
+
+    /// The logical height of the bottom of the window that is covered by the on-screen keyboard,
+    /// so that UI can scroll the focused text field above it.
+    ///
+    /// Only reported on Android, when the activity is resized for the keyboard (the
+    /// `adjustResize` soft input mode).
+    #[inline]
+    pub fn soft_keyboard_inset(&self) -> f32 {
+        self.soft_keyboard_inset
+    }
+
+    #[inline]
+    pub fn ime_position(&self) -> Vec2 {
+        self.ime_position
+    }
+
+    /// Moves the candidate box of the input method editor to `position`, in logical pixels from
+    /// the bottom left of the window, typically below the text cursor of the focused text field.
+    pub fn set_ime_position(&mut self, position: Vec2) {
+        self.ime_position = position;
+        self.command_queue
+            .push(WindowCommand::SetImePosition { position });
+    }
+
+    /// The pending request for the attention of the user, cleared when the window gains the focus.
+    #[inline]
+    pub fn attention(&self) -> Option<AttentionType> {
+        self.attention
+    }
+
+    /// Requests the attention of the user, for example when it's their turn or a message is
+    /// received while the window isn't focused, or cancels the request with `None`. The request
+    /// is ignored if the window is focused, and is cleared when it gains the focus.
+    ///
+    /// Supported on Windows, macOS and Linux with X11.
+    pub fn request_attention(&mut self, attention: Option<AttentionType>) {
+        if attention.is_some() && self.focused {
+            return;
+        }
+        self.attention = attention;
+        self.command_queue
+            .push(WindowCommand::RequestAttention { attention });
+    }
+
+    pub fn clear_attention_from_backend(&mut self) {
+        self.attention = None;
+    }
+
+    #[inline]
+    pub fn screen_orientation(&self) -> ScreenOrientation {
+        self.screen_orientation
+    }
+
+    /// Restricts the orientations of the screen that the window is displayed in. A
+    /// [`WindowRotated`](crate::WindowRotated) event is sent when the window switches between
+    /// portrait and landscape.
+    ///
+    /// Supported on iOS, Android and the web, where browsers usually only allow it in fullscreen.
+    pub fn set_screen_orientation(&mut self, orientation: ScreenOrientation) {
+        self.screen_orientation = orientation;
+        self.command_queue
+            .push(WindowCommand::SetScreenOrientation { orientation });
+    }
+
+    #[inline]
+    pub fn represented_file(&self) -> Option<&PathBuf> {
+        self.represented_file.as_ref()
+    }
+
+    /// Sets the file that the window edits, or clears it with `None`. Its icon is shown next to
+    /// the title, and can be dragged like the file in the Finder.
+    ///
+    /// Only supported on macOS.
+    pub fn set_represented_file(&mut self, path: Option<PathBuf>) {
+        self.represented_file = path.clone();
+        self.command_queue
+            .push(WindowCommand::SetRepresentedFile { path });
+    }
+
+    /// Returns `true` if the input events received by this window are currently delivered,
+    /// according to its [`InputRouting`].
+    pub fn accepts_input(&self) -> bool {
+        match self.input_routing {
+            InputRouting::Broadcast => true,
+            InputRouting::FocusedWindow => self.focused,
+            InputRouting::HoveredWindow => self.cursor_position.is_some(),
+        }
+    }
+}
+
+/// The element of the page that the canvas of a window follows the size of, on the web.
+///
+/// The element is watched with a `ResizeObserver`, and the window is resized to the inner size
+/// of the element whenever it changes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CanvasResize {
+    /// The parent element of the canvas.
+    Parent,
+    /// The element matching a CSS selector.
+    Element(String),
+}
+
+/// Options of the canvas of a window on the web.
+///
+/// The default handling of the browser is only prevented for the events targeting the canvas,
+/// so the rest of the page keeps its usual behavior.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CanvasOptions {
+    /// Prevents the context menu of the browser from opening on right click.
+    pub prevent_context_menu: bool,
+    /// Prevents the browser from scrolling the page with the space bar, arrow keys, Page Up,
+    /// Page Down, Home and End.
+    pub prevent_scroll_keys: bool,
+    /// Prevents the shortcuts of the browser using Ctrl, or Command on macOS, such as Ctrl+S.
+    pub prevent_shortcuts: bool,
+    /// The `touch-action` CSS property of the canvas, such as `"none"` to keep the browser from
+    /// panning and zooming the page when the canvas is touched. The property is left untouched
+    /// when `None`.
+    pub touch_action: Option<String>,
+    /// Prevents the touch gestures of the browser by listening to the touch events of the canvas
+    /// with non-passive listeners, for the browsers that ignore `touch-action`. The wheel events
+    /// of the canvas never scroll the page.
+    pub prevent_touch_gestures: bool,
+}
+
+/// Options of the titlebar of a window on macOS, applied when the window is created.
+///
+/// Combining them lets the app draw its own titlebar under the traffic light buttons.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MacOsTitlebar {
+    /// Makes the titlebar transparent, showing the content of the window under it.
+    pub transparent: bool,
+    /// Hides the title, keeping the traffic light buttons.
+    pub title_hidden: bool,
+    /// Extends the content of the window under the titlebar.
+    pub fullsize_content_view: bool,
+}
+
+/// Options of the system UI around a window on iOS, applied when the window is created.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct IosOptions {
+    /// Hides the status bar at the top of the screen.
+    pub status_bar_hidden: bool,
+    /// Lets the system hide the home indicator at the bottom of the screen after a while.
+    pub home_indicator_auto_hidden: bool,
+    /// The edges of the screen where the system gestures, such as opening the control center,
+    /// need a second swipe, so that the app receives the first one.
+    pub deferred_gesture_edges: ScreenEdges,
+}
+
+/// A set of edges of the screen.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ScreenEdges {
+    pub top: bool,
+    pub left: bool,
+    pub bottom: bool,
+    pub right: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -522,12 +1306,63 @@ pub struct WindowDescriptor {
     pub title: String,
     pub vsync: bool,
     pub resizable: bool,
+    /// See [`Window::set_decorations`].
     pub decorations: bool,
+    /// See [`Window::set_level`].
+    pub level: WindowLevel,
     pub cursor_visible: bool,
-    pub cursor_locked: bool,
+    /// See [`Window::set_cursor_grab_mode`].
+    pub cursor_grab_mode: CursorGrabMode,
+    /// See [`Window::set_raw_mouse_motion`].
+    pub raw_mouse_motion: bool,
+    /// See [`Window::set_scale_factor_policy`].
+    pub scale_factor_policy: ScaleFactorPolicy,
+    /// See [`Window::set_render_policy`].
+    pub render_policy: WindowRenderPolicy,
     pub mode: WindowMode,
+    /// The position of the top left corner of the window on the desktop, in physical pixels, or
+    /// `None` to let the platform place the window.
+    ///
+    /// Ignored for fullscreen windows, and on the web and the mobile platforms.
+    pub position: Option<IVec2>,
+    /// The index of the monitor the window opens on in the list of monitors of the platform, as
+    /// for [`SnapRegion::Monitor`], or `None` for the primary monitor. The window is centered on
+    /// the monitor if it has no `position`, and fullscreen windows fill it.
+    pub monitor: Option<usize>,
+    pub input_routing: InputRouting,
+    /// Creates a virtual window, with no window on the platform. It has the requested size and
+    /// a scale factor of 1, or the `scale_factor_override`, and is rendered to a texture instead
+    /// of the screen, for thumbnails or captures. It never receives input, and is never focused.
+    pub offscreen: bool,
+    /// See [`Window::set_screen_orientation`].
+    pub screen_orientation: ScreenOrientation,
+    /// Forces the theme of the window, or follows the theme of the system if `None`. On Windows,
+    /// the titlebar of the window is dark with the dark theme.
+    ///
+    /// Only supported on Windows.
+    pub theme: Option<WindowTheme>,
+    /// The application id of the window on Linux, set as the `WM_CLASS` of the window on X11 and
+    /// as its app id on Wayland. It should match the name of the `.desktop` file of the app, so
+    /// that the desktop can show the right icon and group the windows of the app.
+    ///
+    /// Ignored on the other platforms.
+    pub application_id: Option<String>,
+    /// A CSS selector of the canvas element the window renders to.
+    ///
+    /// The window uses the first matching canvas that isn't used by another window, so several
+    /// windows can share a selector. If there is none, or no selector is given, a new canvas is
+    /// appended to the body of the page.
     #[cfg(target_arch = "wasm32")]
     pub canvas: Option<String>,
+    /// Makes the canvas of the window follow the size of an element of the page.
+    #[cfg(target_arch = "wasm32")]
+    pub canvas_resize: Option<CanvasResize>,
+    #[cfg(target_arch = "wasm32")]
+    pub canvas_options: CanvasOptions,
+    #[cfg(target_os = "macos")]
+    pub macos_titlebar: MacOsTitlebar,
+    #[cfg(target_os = "ios")]
+    pub ios_options: IosOptions,
 }
 
 impl Default for WindowDescriptor {
@@ -541,11 +1376,136 @@ impl Default for WindowDescriptor {
             vsync: true,
             resizable: true,
             decorations: true,
-            cursor_locked: false,
+            level: WindowLevel::Normal,
+            cursor_grab_mode: CursorGrabMode::None,
+            raw_mouse_motion: false,
+            scale_factor_policy: ScaleFactorPolicy::KeepLogicalSize,
+            render_policy: WindowRenderPolicy::Native,
             cursor_visible: true,
             mode: WindowMode::Windowed,
+            position: None,
+            monitor: None,
+            input_routing: InputRouting::Broadcast,
+            offscreen: false,
+            screen_orientation: ScreenOrientation::Auto,
+            theme: None,
+            application_id: None,
             #[cfg(target_arch = "wasm32")]
             canvas: None,
+            #[cfg(target_arch = "wasm32")]
+            canvas_resize: None,
+            #[cfg(target_arch = "wasm32")]
+            canvas_options: CanvasOptions::default(),
+            #[cfg(target_os = "macos")]
+            macos_titlebar: MacOsTitlebar::default(),
+            #[cfg(target_os = "ios")]
+            ios_options: IosOptions::default(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{AttentionType, Window, WindowDescriptor, WindowId, WindowRenderPolicy};
+    use bevy_math::{UVec2, Vec2};
+
+    #[test]
+    fn letterboxed_cursor_position() {
+        let mut window = Window::new(
+            WindowId::new(),
+            &WindowDescriptor {
+                render_policy: WindowRenderPolicy::Letterbox {
+                    width: 320.0,
+                    height: 180.0,
+                },
+                ..Default::default()
+            },
+            1000,
+            700,
+            2.0,
+            None,
+        );
+        assert_eq!(window.render_size(), Vec2::new(320.0, 180.0));
+        assert_eq!(window.render_scale_factor(), 1.0);
+        // the content is drawn from (0, 69) to (1000, 631) in physical pixels
+        window.update_cursor_position_from_backend(Some(Vec2::new(250.0, 175.0)));
+        let position = window.render_cursor_position().unwrap();
+        assert!((position - Vec2::new(160.0, 90.0)).length() < 0.5);
+        window.update_cursor_position_from_backend(Some(Vec2::new(0.0, 35.0)));
+        let position = window.render_cursor_position().unwrap();
+        assert!(position.abs_diff_eq(Vec2::ZERO, 0.5));
+        // over the bars
+        window.update_cursor_position_from_backend(Some(Vec2::new(250.0, 20.0)));
+        assert_eq!(window.render_cursor_position(), None);
+        window.update_cursor_position_from_backend(Some(Vec2::new(250.0, 340.0)));
+        assert_eq!(window.render_cursor_position(), None);
+    }
+
+    #[test]
+    fn attention_is_not_requested_while_focused() {
+        let mut window = Window::new(
+            WindowId::new(),
+            &WindowDescriptor::default(),
+            1280,
+            720,
+            1.0,
+            None,
+        );
+        window.request_attention(Some(AttentionType::Critical));
+        assert_eq!(window.attention(), None);
+        assert_eq!(window.drain_commands().count(), 0);
+
+        window.update_focused_status_from_backend(false);
+        window.request_attention(Some(AttentionType::Critical));
+        assert_eq!(window.attention(), Some(AttentionType::Critical));
+        assert_eq!(window.drain_commands().count(), 1);
+    }
+
+    #[test]
+    fn offscreen_windows_are_not_focused() {
+        let window = |offscreen| {
+            Window::new(
+                WindowId::new(),
+                &WindowDescriptor {
+                    offscreen,
+                    ..Default::default()
+                },
+                1280,
+                720,
+                1.0,
+                None,
+            )
+        };
+        assert!(window(false).is_focused());
+        assert!(!window(true).is_focused());
+    }
+
+    #[test]
+    fn render_policy_viewports() {
+        let window_size = UVec2::new(1000, 700);
+        assert_eq!(
+            WindowRenderPolicy::Stretch {
+                width: 320.0,
+                height: 180.0
+            }
+            .viewport(window_size),
+            (UVec2::ZERO, window_size)
+        );
+        assert_eq!(
+            WindowRenderPolicy::IntegerScale {
+                width: 320.0,
+                height: 180.0
+            }
+            .viewport(window_size),
+            (UVec2::new(20, 80), UVec2::new(960, 540))
+        );
+        assert_eq!(
+            WindowRenderPolicy::Letterbox {
+                width: 320.0,
+                height: 180.0
+            }
+            .viewport(window_size),
+            (UVec2::new(0, 69), UVec2::new(1000, 562))
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use super::{Window, WindowId};
-use bevy_utils::HashMap;
+use bevy_utils::{Duration, HashMap};
 
 #[derive(Debug, Default)]
 pub struct Windows {
@@ -35,3 +35,13 @@ impl Windows {
         self.windows.values_mut()
     }
 }
+
+/// Statistics of the window backend about the events it handled since the previous update of the
+/// app, for diagnostics.
+#[derive(Debug, Default, Clone)]
+pub struct WindowBackendStats {
+    /// The number of window events received from the platform.
+    pub window_events: u32,
+    /// The time spent handling the events of the platform, not including the update of the app.
+    pub event_handler_time: Duration,
+}
@@ -0,0 +1,648 @@
+use crate::{
+    badge, converters, get_selected_videomode, orientation, represented_file, select_monitor,
+    soft_keyboard, taskbar,
+};
+use bevy_app::Events;
+use bevy_ecs::world::WorldCell;
+use bevy_math::{IVec2, UVec2, Vec2};
+use bevy_utils::tracing::{error, warn};
+use bevy_window::{
+    AttentionType, CursorGrabChangeReason, CursorGrabChanged, CursorGrabMode, CursorIcon,
+    CursorWarpFailed, CursorWarped, ScreenOrientation, SnapRegion, TaskbarProgress, Window,
+    WindowBadge, WindowCommand, WindowConstraintViolation, WindowLevel, WindowMode,
+    WindowResizeConstraints, WindowResized, WindowScaleFactorChanged,
+};
+use std::path::Path;
+use winit::dpi::{LogicalSize, PhysicalPosition, PhysicalSize};
+
+/// The calls made on a window of the platform to apply the [`WindowCommand`]s of a [`Window`].
+///
+/// Implemented for the winit windows, and by a backend recording the calls in tests.
+pub(crate) trait WindowBackend {
+    fn set_window_mode(&self, mode: WindowMode, resolution: (u32, u32));
+    fn set_title(&self, title: &str);
+    fn set_inner_size(&self, logical_resolution: (f32, f32), scale_factor: f64);
+    fn set_resizable(&self, resizable: bool);
+    fn set_decorations(&self, decorations: bool);
+    fn set_level(&self, level: WindowLevel);
+    /// Grabs the cursor in the mode used on the platform for `mode`, which is returned.
+    fn set_cursor_grab_mode(&self, mode: CursorGrabMode) -> Result<CursorGrabMode, String>;
+    fn set_cursor_visible(&self, visible: bool);
+    fn set_cursor_icon(&self, icon: CursorIcon);
+    /// Moves the cursor to `position`, in logical pixels from the bottom left of the window.
+    fn set_cursor_position(&self, position: Vec2) -> Result<(), String>;
+    fn set_maximized(&self, maximized: bool);
+    fn set_minimized(&self, minimized: bool);
+    fn set_outer_position(&self, position: IVec2);
+    fn set_resize_constraints(&self, resize_constraints: WindowResizeConstraints);
+    fn set_screen_orientation(&self, orientation: ScreenOrientation) -> Result<(), String>;
+    fn set_represented_file(&self, path: Option<&Path>);
+    fn set_soft_keyboard_visible(&self, visible: bool);
+    /// Moves the candidate box of the IME to `position`, in logical pixels from the bottom left
+    /// of the window.
+    fn set_ime_position(&self, position: Vec2);
+    fn request_attention(&self, attention: Option<AttentionType>);
+    fn set_taskbar_progress(&self, progress: Option<TaskbarProgress>) -> Result<(), String>;
+    fn set_badge(&self, badge: Option<&WindowBadge>);
+    fn snap(&self, region: SnapRegion);
+}
+
+impl WindowBackend for winit::window::Window {
+    fn set_window_mode(&self, mode: WindowMode, (width, height): (u32, u32)) {
+        match mode {
+            WindowMode::BorderlessFullscreen => {
+                self.set_fullscreen(Some(winit::window::Fullscreen::Borderless(None)))
+            }
+            mode @ (WindowMode::Fullscreen { .. } | WindowMode::ExclusiveFullscreen { .. }) => {
+                let (monitor_selection, video_mode) = mode.exclusive_fullscreen().unwrap();
+                let monitor = select_monitor(
+                    monitor_selection,
+                    self.current_monitor(),
+                    self.primary_monitor(),
+                    self.available_monitors(),
+                );
+                self.set_fullscreen(Some(winit::window::Fullscreen::Exclusive(
+                    get_selected_videomode(&monitor.unwrap(), video_mode, width, height),
+                )))
+            }
+            WindowMode::Windowed => self.set_fullscreen(None),
+        }
+    }
+
+    fn set_title(&self, title: &str) {
+        winit::window::Window::set_title(self, title);
+    }
+
+    fn set_inner_size(&self, (width, height): (f32, f32), scale_factor: f64) {
+        winit::window::Window::set_inner_size(
+            self,
+            LogicalSize::new(width, height).to_physical::<f64>(scale_factor),
+        );
+    }
+
+    fn set_resizable(&self, resizable: bool) {
+        winit::window::Window::set_resizable(self, resizable);
+    }
+
+    fn set_decorations(&self, decorations: bool) {
+        winit::window::Window::set_decorations(self, decorations);
+    }
+
+    fn set_level(&self, level: WindowLevel) {
+        self.set_always_on_top(converters::convert_window_level(level));
+    }
+
+    fn set_cursor_grab_mode(&self, mode: CursorGrabMode) -> Result<CursorGrabMode, String> {
+        let mode = converters::platform_cursor_grab_mode(mode);
+        // winit doesn't support grabbing the cursor on the web, where the canvas gets the
+        // pointer lock instead
+        #[cfg(target_arch = "wasm32")]
+        {
+            use winit::platform::web::WindowExtWebSys;
+            match mode {
+                CursorGrabMode::None => web_sys::window()
+                    .and_then(|window| window.document())
+                    .ok_or("The page has no document")?
+                    .exit_pointer_lock(),
+                _ => self.canvas().request_pointer_lock(),
+            }
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        winit::window::Window::set_cursor_grab(self, mode != CursorGrabMode::None)
+            .map_err(|err| err.to_string())?;
+        Ok(mode)
+    }
+
+    fn set_cursor_visible(&self, visible: bool) {
+        winit::window::Window::set_cursor_visible(self, visible);
+    }
+
+    fn set_cursor_icon(&self, icon: CursorIcon) {
+        winit::window::Window::set_cursor_icon(
+            self,
+            converters::convert_cursor_icon(converters::platform_cursor_icon(icon)),
+        );
+    }
+
+    fn set_cursor_position(&self, position: Vec2) -> Result<(), String> {
+        let inner_size = self.inner_size().to_logical::<f32>(self.scale_factor());
+        winit::window::Window::set_cursor_position(
+            self,
+            winit::dpi::LogicalPosition::new(position.x, inner_size.height - position.y),
+        )
+        .map_err(|err| err.to_string())
+    }
+
+    fn set_maximized(&self, maximized: bool) {
+        winit::window::Window::set_maximized(self, maximized);
+    }
+
+    fn set_minimized(&self, minimized: bool) {
+        winit::window::Window::set_minimized(self, minimized);
+    }
+
+    fn set_outer_position(&self, position: IVec2) {
+        winit::window::Window::set_outer_position(
+            self,
+            PhysicalPosition {
+                x: position[0],
+                y: position[1],
+            },
+        );
+    }
+
+    fn set_resize_constraints(&self, resize_constraints: WindowResizeConstraints) {
+        let constraints = resize_constraints.check_constraints();
+        let min_inner_size = LogicalSize {
+            width: constraints.min_width,
+            height: constraints.min_height,
+        };
+        let max_inner_size = LogicalSize {
+            width: constraints.max_width,
+            height: constraints.max_height,
+        };
+
+        self.set_min_inner_size(Some(min_inner_size));
+        if constraints.max_width.is_finite() && constraints.max_height.is_finite() {
+            self.set_max_inner_size(Some(max_inner_size));
+        }
+    }
+
+    fn set_screen_orientation(&self, orientation: ScreenOrientation) -> Result<(), String> {
+        orientation::set_screen_orientation(self, orientation)
+    }
+
+    fn set_represented_file(&self, path: Option<&Path>) {
+        represented_file::set_represented_file(self, path);
+    }
+
+    fn set_soft_keyboard_visible(&self, visible: bool) {
+        soft_keyboard::set_soft_keyboard_visible(visible);
+    }
+
+    fn set_ime_position(&self, position: Vec2) {
+        let inner_size = self.inner_size().to_logical::<f32>(self.scale_factor());
+        winit::window::Window::set_ime_position(
+            self,
+            winit::dpi::LogicalPosition::new(position.x, inner_size.height - position.y),
+        );
+    }
+
+    fn request_attention(&self, attention: Option<AttentionType>) {
+        self.request_user_attention(attention.map(converters::convert_attention_type));
+    }
+
+    fn set_taskbar_progress(&self, progress: Option<TaskbarProgress>) -> Result<(), String> {
+        taskbar::set_taskbar_progress(self, progress)
+    }
+
+    fn set_badge(&self, badge: Option<&WindowBadge>) {
+        badge::set_badge(self, badge);
+    }
+
+    fn snap(&self, region: SnapRegion) {
+        let monitor = match region.monitor() {
+            Some(index) => self.available_monitors().nth(index),
+            None => self.current_monitor(),
+        };
+        let monitor = match monitor {
+            Some(monitor) => monitor,
+            None => return,
+        };
+        let (position, size) = region.area(
+            IVec2::new(monitor.position().x, monitor.position().y),
+            UVec2::new(monitor.size().width, monitor.size().height),
+        );
+        winit::window::Window::set_maximized(self, false);
+        // the region includes the decorations
+        let (inner_size, outer_size) = (self.inner_size(), self.outer_size());
+        winit::window::Window::set_inner_size(
+            self,
+            PhysicalSize::new(
+                size.x
+                    .saturating_sub(outer_size.width.saturating_sub(inner_size.width)),
+                size.y
+                    .saturating_sub(outer_size.height.saturating_sub(inner_size.height)),
+            ),
+        );
+        WindowBackend::set_outer_position(self, position);
+    }
+}
+
+/// Applies the pending commands of the offscreen window `bevy_window`, which has no platform
+/// window: only its size and scale factor can change.
+pub(crate) fn apply_offscreen_window_commands(bevy_window: &mut Window, world: &WorldCell) {
+    let id = bevy_window.id();
+    for command in coalesce_commands(bevy_window.drain_commands()) {
+        match command {
+            WindowCommand::SetResolution {
+                logical_resolution: (width, height),
+                scale_factor,
+            } => {
+                bevy_window.update_actual_size_from_backend(
+                    (width as f64 * scale_factor) as u32,
+                    (height as f64 * scale_factor) as u32,
+                );
+                let mut window_resized_events =
+                    world.get_resource_mut::<Events<WindowResized>>().unwrap();
+                window_resized_events.send(WindowResized {
+                    id,
+                    width: bevy_window.width(),
+                    height: bevy_window.height(),
+                });
+            }
+            WindowCommand::SetScaleFactor { scale_factor } => {
+                let mut window_dpi_changed_events = world
+                    .get_resource_mut::<Events<WindowScaleFactorChanged>>()
+                    .unwrap();
+                window_dpi_changed_events.send(WindowScaleFactorChanged { id, scale_factor });
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Replaces each run of consecutive commands of the same kind with the last command of the run,
+/// keeping the order of the commands.
+///
+/// Every command sets one property of the window, so a command directly overwritten by the next
+/// one would only make the platform apply an intermediate state, and send its events. Commands
+/// that are not consecutive are all kept, since some properties depend on each other, like the
+/// resolution and the window mode.
+fn coalesce_commands(commands: impl Iterator<Item = WindowCommand>) -> Vec<WindowCommand> {
+    let mut coalesced: Vec<WindowCommand> = Vec::new();
+    for command in commands {
+        if coalesced.last().is_some_and(|previous| {
+            std::mem::discriminant(previous) == std::mem::discriminant(&command)
+        }) {
+            coalesced.pop();
+        }
+        coalesced.push(command);
+    }
+    coalesced
+}
+
+/// Applies the pending commands of `bevy_window` to `backend`, sending the resulting events in
+/// `world`. Only the last command of each run of commands of the same kind is applied.
+pub(crate) fn apply_window_commands(
+    bevy_window: &mut Window,
+    backend: &impl WindowBackend,
+    world: &WorldCell,
+) {
+    let id = bevy_window.id();
+    let mut warped_cursor_position = None;
+    // the grab mode used by the platform, which may differ from the requested one
+    let mut applied_cursor_grab_mode = None;
+    for command in coalesce_commands(bevy_window.drain_commands()) {
+        match command {
+            WindowCommand::SetWindowMode { mode, resolution } => {
+                backend.set_window_mode(mode, resolution);
+            }
+            WindowCommand::SetTitle { title } => {
+                backend.set_title(&title);
+            }
+            WindowCommand::SetScaleFactor { scale_factor } => {
+                let mut window_dpi_changed_events = world
+                    .get_resource_mut::<Events<WindowScaleFactorChanged>>()
+                    .unwrap();
+                window_dpi_changed_events.send(WindowScaleFactorChanged { id, scale_factor });
+            }
+            WindowCommand::SetResolution {
+                logical_resolution,
+                scale_factor,
+            } => {
+                backend.set_inner_size(logical_resolution, scale_factor);
+            }
+            WindowCommand::SetVsync { .. } => (),
+            WindowCommand::SetResizable { resizable } => {
+                backend.set_resizable(resizable);
+            }
+            WindowCommand::SetDecorations { decorations } => {
+                backend.set_decorations(decorations);
+            }
+            WindowCommand::SetWindowLevel { level } => {
+                converters::warn_unsupported_level(level);
+                backend.set_level(level);
+            }
+            WindowCommand::SetCursorGrabMode { mode } => {
+                let mut cursor_grab_changed_events = world
+                    .get_resource_mut::<Events<CursorGrabChanged>>()
+                    .unwrap();
+                match backend.set_cursor_grab_mode(mode) {
+                    // on the web, the browser reports whether the pointer lock is granted
+                    // later on, see `web::pointer_lock_system`
+                    #[cfg(not(target_arch = "wasm32"))]
+                    Ok(mode) => {
+                        applied_cursor_grab_mode = Some(mode);
+                        cursor_grab_changed_events.send(CursorGrabChanged {
+                            id,
+                            mode,
+                            reason: CursorGrabChangeReason::Requested,
+                        })
+                    }
+                    #[cfg(target_arch = "wasm32")]
+                    Ok(mode) => applied_cursor_grab_mode = Some(mode),
+                    // the platforms only fail to grab the cursor, which is left free
+                    Err(e) => {
+                        error!("Unable to un/grab cursor: {}", e);
+                        applied_cursor_grab_mode = Some(CursorGrabMode::None);
+                        cursor_grab_changed_events.send(CursorGrabChanged {
+                            id,
+                            mode: CursorGrabMode::None,
+                            reason: CursorGrabChangeReason::Rejected(e),
+                        });
+                    }
+                }
+            }
+            WindowCommand::SetCursorVisibility { visible } => {
+                backend.set_cursor_visible(visible);
+            }
+            WindowCommand::SetCursorIcon { icon } => {
+                backend.set_cursor_icon(icon);
+            }
+            WindowCommand::SetCursorPosition { position } => {
+                match backend.set_cursor_position(position) {
+                    Ok(()) => {
+                        warped_cursor_position = Some(position);
+                        let mut cursor_warped_events =
+                            world.get_resource_mut::<Events<CursorWarped>>().unwrap();
+                        cursor_warped_events.send(CursorWarped { id, position });
+                    }
+                    Err(e) => {
+                        error!("Unable to set cursor position: {}", e);
+                        let mut cursor_warp_failed_events = world
+                            .get_resource_mut::<Events<CursorWarpFailed>>()
+                            .unwrap();
+                        cursor_warp_failed_events.send(CursorWarpFailed {
+                            id,
+                            position,
+                            error: e,
+                        });
+                    }
+                }
+            }
+            WindowCommand::SetMaximized { maximized } => {
+                backend.set_maximized(maximized);
+            }
+            WindowCommand::SetMinimized { minimized } => {
+                backend.set_minimized(minimized);
+            }
+            WindowCommand::SetPosition { position } => {
+                backend.set_outer_position(position);
+            }
+            WindowCommand::SetResizeConstraints { resize_constraints } => {
+                if let Some(violation) = resize_constraints.violation(id) {
+                    let mut violation_events = world
+                        .get_resource_mut::<Events<WindowConstraintViolation>>()
+                        .unwrap();
+                    violation_events.send(violation);
+                }
+                backend.set_resize_constraints(resize_constraints);
+            }
+            WindowCommand::SetScreenOrientation { orientation } => {
+                if let Err(err) = backend.set_screen_orientation(orientation) {
+                    warn!("Unable to set the screen orientation: {}", err);
+                }
+            }
+            WindowCommand::SetRepresentedFile { path } => {
+                backend.set_represented_file(path.as_deref());
+            }
+            WindowCommand::SetSoftKeyboardVisible { visible } => {
+                backend.set_soft_keyboard_visible(visible);
+            }
+            WindowCommand::SetImePosition { position } => {
+                backend.set_ime_position(position);
+            }
+            WindowCommand::RequestAttention { attention } => {
+                backend.request_attention(attention);
+            }
+            WindowCommand::SetTaskbarProgress { progress } => {
+                if let Err(err) = backend.set_taskbar_progress(progress) {
+                    warn!("Unable to set the taskbar progress: {}", err);
+                }
+            }
+            WindowCommand::SetBadge { badge } => {
+                backend.set_badge(badge.as_ref());
+            }
+            WindowCommand::Snap { region } => {
+                backend.snap(region);
+            }
+        }
+    }
+    // not all platforms send a `CursorMoved` event for warps
+    if let Some(position) = warped_cursor_position {
+        bevy_window.update_cursor_position_from_backend(Some(position));
+    }
+    if let Some(mode) = applied_cursor_grab_mode {
+        bevy_window.update_cursor_grab_mode_from_backend(mode);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_window_commands, WindowBackend};
+    use bevy_app::Events;
+    use bevy_ecs::world::World;
+    use bevy_math::{IVec2, Vec2};
+    use bevy_window::{
+        AttentionType, CursorGrabChangeReason, CursorGrabChanged, CursorGrabMode, CursorIcon,
+        CursorWarpFailed, CursorWarped, ScreenOrientation, SnapRegion, TaskbarProgress, Window,
+        WindowBadge, WindowDescriptor, WindowId, WindowLevel, WindowMode, WindowResizeConstraints,
+        WindowScaleFactorChanged,
+    };
+    use std::{cell::RefCell, path::Path};
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum BackendCall {
+        SetTitle(String),
+        SetInnerSize((f32, f32), f64),
+        SetCursorGrabMode(CursorGrabMode),
+        SetCursorPosition(Vec2),
+        SetOuterPosition(IVec2),
+        Other(&'static str),
+    }
+
+    /// Records the calls made on it, and rejects cursor grabs.
+    #[derive(Default)]
+    struct RecordingBackend {
+        calls: RefCell<Vec<BackendCall>>,
+    }
+
+    impl RecordingBackend {
+        fn record(&self, call: BackendCall) {
+            self.calls.borrow_mut().push(call);
+        }
+    }
+
+    impl WindowBackend for RecordingBackend {
+        fn set_window_mode(&self, _mode: WindowMode, _resolution: (u32, u32)) {
+            self.record(BackendCall::Other("set_window_mode"));
+        }
+        fn set_title(&self, title: &str) {
+            self.record(BackendCall::SetTitle(title.to_string()));
+        }
+        fn set_inner_size(&self, logical_resolution: (f32, f32), scale_factor: f64) {
+            self.record(BackendCall::SetInnerSize(logical_resolution, scale_factor));
+        }
+        fn set_resizable(&self, _resizable: bool) {
+            self.record(BackendCall::Other("set_resizable"));
+        }
+        fn set_decorations(&self, _decorations: bool) {
+            self.record(BackendCall::Other("set_decorations"));
+        }
+        fn set_level(&self, _level: WindowLevel) {
+            self.record(BackendCall::Other("set_level"));
+        }
+        fn set_cursor_grab_mode(&self, mode: CursorGrabMode) -> Result<CursorGrabMode, String> {
+            self.record(BackendCall::SetCursorGrabMode(mode));
+            Err("grab rejected".to_string())
+        }
+        fn set_cursor_visible(&self, _visible: bool) {
+            self.record(BackendCall::Other("set_cursor_visible"));
+        }
+
+        fn set_cursor_icon(&self, _icon: CursorIcon) {
+            self.record(BackendCall::Other("set_cursor_icon"));
+        }
+        fn set_cursor_position(&self, position: Vec2) -> Result<(), String> {
+            self.record(BackendCall::SetCursorPosition(position));
+            Ok(())
+        }
+        fn set_maximized(&self, _maximized: bool) {
+            self.record(BackendCall::Other("set_maximized"));
+        }
+        fn set_minimized(&self, _minimized: bool) {
+            self.record(BackendCall::Other("set_minimized"));
+        }
+        fn set_outer_position(&self, position: IVec2) {
+            self.record(BackendCall::SetOuterPosition(position));
+        }
+        fn set_resize_constraints(&self, _resize_constraints: WindowResizeConstraints) {
+            self.record(BackendCall::Other("set_resize_constraints"));
+        }
+        fn set_screen_orientation(&self, _orientation: ScreenOrientation) -> Result<(), String> {
+            self.record(BackendCall::Other("set_screen_orientation"));
+            Ok(())
+        }
+        fn set_represented_file(&self, _path: Option<&Path>) {
+            self.record(BackendCall::Other("set_represented_file"));
+        }
+        fn set_soft_keyboard_visible(&self, _visible: bool) {
+            self.record(BackendCall::Other("set_soft_keyboard_visible"));
+        }
+        fn set_ime_position(&self, _position: Vec2) {
+            self.record(BackendCall::Other("set_ime_position"));
+        }
+        fn request_attention(&self, _attention: Option<AttentionType>) {
+            self.record(BackendCall::Other("request_attention"));
+        }
+        fn set_taskbar_progress(&self, _progress: Option<TaskbarProgress>) -> Result<(), String> {
+            self.record(BackendCall::Other("set_taskbar_progress"));
+            Ok(())
+        }
+        fn set_badge(&self, _badge: Option<&WindowBadge>) {
+            self.record(BackendCall::Other("set_badge"));
+        }
+        fn snap(&self, _region: SnapRegion) {
+            self.record(BackendCall::Other("snap"));
+        }
+    }
+
+    #[test]
+    fn window_commands() {
+        let mut world = World::default();
+        world.insert_resource(Events::<WindowScaleFactorChanged>::default());
+        world.insert_resource(Events::<CursorGrabChanged>::default());
+        world.insert_resource(Events::<CursorWarped>::default());
+        world.insert_resource(Events::<CursorWarpFailed>::default());
+
+        let id = WindowId::primary();
+        let mut window = Window::new(id, &WindowDescriptor::default(), 1280, 720, 1.0, None);
+        window.set_title("title".to_string());
+        window.set_resolution(640.0, 480.0);
+        window.set_cursor_grab_mode(CursorGrabMode::Locked);
+        window.set_cursor_position(Vec2::new(10.0, 20.0));
+        window.set_position(IVec2::new(5, 6));
+
+        let backend = RecordingBackend::default();
+        apply_window_commands(&mut window, &backend, &world.cell());
+
+        assert_eq!(
+            backend.calls.into_inner(),
+            vec![
+                BackendCall::SetTitle("title".to_string()),
+                BackendCall::SetInnerSize((640.0, 480.0), 1.0),
+                BackendCall::SetCursorGrabMode(CursorGrabMode::Locked),
+                BackendCall::SetCursorPosition(Vec2::new(10.0, 20.0)),
+                BackendCall::SetOuterPosition(IVec2::new(5, 6)),
+            ]
+        );
+        assert_eq!(window.cursor_grab_mode(), CursorGrabMode::None);
+        assert_eq!(window.cursor_position(), Some(Vec2::new(10.0, 20.0)));
+
+        let grab_events = world.get_resource::<Events<CursorGrabChanged>>().unwrap();
+        let grab_events = grab_events
+            .get_reader()
+            .iter(grab_events)
+            .collect::<Vec<_>>();
+        assert_eq!(grab_events.len(), 1);
+        assert_eq!(grab_events[0].mode, CursorGrabMode::None);
+        assert_eq!(
+            grab_events[0].reason,
+            CursorGrabChangeReason::Rejected("grab rejected".to_string())
+        );
+    }
+
+    #[test]
+    fn coalesced_window_commands() {
+        let mut world = World::default();
+        world.insert_resource(Events::<CursorWarped>::default());
+
+        let id = WindowId::primary();
+        let mut window = Window::new(id, &WindowDescriptor::default(), 1280, 720, 1.0, None);
+        window.set_title("first".to_string());
+        window.set_title("second".to_string());
+        window.set_position(IVec2::new(5, 6));
+        window.set_cursor_position(Vec2::new(1.0, 2.0));
+        window.set_cursor_position(Vec2::new(10.0, 20.0));
+
+        let backend = RecordingBackend::default();
+        apply_window_commands(&mut window, &backend, &world.cell());
+
+        assert_eq!(
+            backend.calls.into_inner(),
+            vec![
+                BackendCall::SetTitle("second".to_string()),
+                BackendCall::SetOuterPosition(IVec2::new(5, 6)),
+                BackendCall::SetCursorPosition(Vec2::new(10.0, 20.0)),
+            ]
+        );
+        let warp_events = world.get_resource::<Events<CursorWarped>>().unwrap();
+        assert_eq!(warp_events.get_reader().iter(warp_events).count(), 1);
+    }
+
+    #[test]
+    fn interleaved_window_commands() {
+        let mut world = World::default();
+
+        let id = WindowId::primary();
+        let mut window = Window::new(id, &WindowDescriptor::default(), 1280, 720, 1.0, None);
+        window.set_mode(WindowMode::BorderlessFullscreen);
+        window.set_resolution(640.0, 480.0);
+        window.set_mode(WindowMode::Windowed);
+        window.set_resolution(800.0, 600.0);
+
+        let backend = RecordingBackend::default();
+        apply_window_commands(&mut window, &backend, &world.cell());
+
+        assert_eq!(
+            backend.calls.into_inner(),
+            vec![
+                BackendCall::Other("set_window_mode"),
+                BackendCall::SetInnerSize((640.0, 480.0), 1.0),
+                BackendCall::Other("set_window_mode"),
+                BackendCall::SetInnerSize((800.0, 600.0), 1.0),
+            ]
+        );
+    }
+}
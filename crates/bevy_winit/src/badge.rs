@@ -0,0 +1,56 @@
+use bevy_window::WindowBadge;
+
+/// Shows `badge` on the dock icon of the app on macOS, and in an overlay icon on the taskbar button
+/// of `window` on Windows. Does nothing on the other platforms, where a warning is logged the first
+/// time a badge is shown.
+pub(crate) fn set_badge(window: &winit::window::Window, badge: Option<&WindowBadge>) {
+    let label = match badge {
+        None | Some(WindowBadge::Count(0)) => None,
+        Some(WindowBadge::Count(count)) => Some(count.to_string()),
+        Some(WindowBadge::Text(text)) => Some(text.clone()),
+    };
+    platform::set_badge_label(window, label);
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use objc::{class, msg_send, runtime::Object, sel, sel_impl};
+    use std::{ffi::CString, ptr};
+
+    pub(super) fn set_badge_label(_window: &winit::window::Window, label: Option<String>) {
+        let label = match label.map(CString::new).transpose() {
+            Ok(label) => label,
+            Err(_) => return,
+        };
+        // SAFETY: the window commands are applied on the main thread, which owns the `NSApp`
+        unsafe {
+            let ns_label: *mut Object = match label {
+                Some(label) => msg_send![class!(NSString), stringWithUTF8String: label.as_ptr()],
+                // a nil label removes the badge
+                None => ptr::null_mut(),
+            };
+            let ns_app: *mut Object = msg_send![class!(NSApplication), sharedApplication];
+            let dock_tile: *mut Object = msg_send![ns_app, dockTile];
+            let _: () = msg_send![dock_tile, setBadgeLabel: ns_label];
+        }
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+mod platform {
+    use crate::taskbar;
+    use bevy_utils::tracing::warn;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    static WARNED: AtomicBool = AtomicBool::new(false);
+
+    pub(super) fn set_badge_label(window: &winit::window::Window, label: Option<String>) {
+        if cfg!(target_os = "windows") {
+            if let Err(err) = taskbar::set_overlay_icon(window, label.as_deref()) {
+                warn!("Unable to set the taskbar overlay icon: {}", err);
+            }
+        } else if label.is_some() && !WARNED.swap(true, Ordering::Relaxed) {
+            warn!("Window badges are only supported on macOS and Windows");
+        }
+    }
+}
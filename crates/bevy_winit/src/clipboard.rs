@@ -0,0 +1,63 @@
+use bevy_app::EventWriter;
+use bevy_ecs::system::{Local, NonSend, ResMut};
+use bevy_window::{Clipboard, ClipboardReadFailed, ClipboardRequestId, ClipboardText};
+use winit::event_loop::EventLoopProxy;
+
+#[cfg(target_arch = "wasm32")]
+pub(crate) use crate::web::ClipboardBackend;
+
+/// The text read from the clipboard, or the reason it couldn't be read.
+pub(crate) type ClipboardReadResult = (ClipboardRequestId, Result<String, String>);
+
+/// Applies the commands queued in the [`Clipboard`], and sends the text read from it.
+pub(crate) fn clipboard_system(
+    mut backend: Local<ClipboardBackend>,
+    event_loop_proxy: Option<NonSend<EventLoopProxy<()>>>,
+    mut clipboard: ResMut<Clipboard>,
+    mut text_events: EventWriter<ClipboardText>,
+    mut failed_events: EventWriter<ClipboardReadFailed>,
+) {
+    for command in clipboard.drain_commands() {
+        backend.apply(command, event_loop_proxy.as_deref());
+    }
+    for (id, result) in backend.read_results() {
+        match result {
+            Ok(text) => text_events.send(ClipboardText { id, text }),
+            Err(error) => failed_events.send(ClipboardReadFailed { id, error }),
+        }
+    }
+}
+
+/// winit has no access to the clipboard of the platform, so outside of the web the clipboard only
+/// holds the text written by the app.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Default)]
+pub(crate) struct ClipboardBackend {
+    text: Option<String>,
+    read_results: Vec<ClipboardReadResult>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl ClipboardBackend {
+    /// Applies `command`. The text read is available right away, so the event loop is not woken.
+    fn apply(
+        &mut self,
+        command: bevy_window::ClipboardCommand,
+        _wake: Option<&EventLoopProxy<()>>,
+    ) {
+        match command {
+            bevy_window::ClipboardCommand::SetText(text) => self.text = Some(text),
+            bevy_window::ClipboardCommand::ReadText(id) => {
+                let result = self
+                    .text
+                    .clone()
+                    .ok_or_else(|| "The clipboard doesn't hold text".to_string());
+                self.read_results.push((id, result));
+            }
+        }
+    }
+
+    fn read_results(&mut self) -> impl Iterator<Item = ClipboardReadResult> + '_ {
+        self.read_results.drain(..)
+    }
+}
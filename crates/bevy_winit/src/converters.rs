@@ -1,16 +1,27 @@
 use bevy_input::{
-    keyboard::{KeyCode, KeyboardInput},
+    keyboard::{KeyCode, KeyboardInput, ModifierKeys},
     mouse::MouseButton,
-    touch::{ForceTouch, TouchInput, TouchPhase},
+    touch::{ForceTouch, StylusTilt, TouchInput, TouchPhase},
     ElementState,
 };
-use bevy_math::Vec2;
+use bevy_math::{IVec2, UVec2, Vec2};
+use bevy_utils::tracing::warn;
+#[cfg(target_os = "windows")]
+use bevy_window::WindowTheme;
+use bevy_window::{AttentionType, CursorGrabMode, CursorIcon, MonitorInfo, VideoMode, WindowLevel};
+use std::sync::atomic::{AtomicBool, Ordering};
 
-pub fn convert_keyboard_input(keyboard_input: &winit::event::KeyboardInput) -> KeyboardInput {
+pub fn convert_keyboard_input(
+    keyboard_input: &winit::event::KeyboardInput,
+    repeat: bool,
+    synthetic: bool,
+) -> KeyboardInput {
     KeyboardInput {
         scan_code: keyboard_input.scancode,
         state: convert_element_state(keyboard_input.state),
         key_code: keyboard_input.virtual_keycode.map(convert_virtual_key_code),
+        repeat,
+        synthetic,
     }
 }
 
@@ -30,18 +41,182 @@ pub fn convert_mouse_button(mouse_button: winit::event::MouseButton) -> MouseBut
     }
 }
 
+pub fn convert_modifiers(modifiers: winit::event::ModifiersState) -> ModifierKeys {
+    ModifierKeys {
+        shift: modifiers.shift(),
+        ctrl: modifiers.ctrl(),
+        alt: modifiers.alt(),
+        logo: modifiers.logo(),
+    }
+}
+
+pub fn convert_touch_phase(touch_phase: winit::event::TouchPhase) -> TouchPhase {
+    match touch_phase {
+        winit::event::TouchPhase::Started => TouchPhase::Started,
+        winit::event::TouchPhase::Moved => TouchPhase::Moved,
+        winit::event::TouchPhase::Ended => TouchPhase::Ended,
+        winit::event::TouchPhase::Cancelled => TouchPhase::Cancelled,
+    }
+}
+
+#[cfg(target_os = "windows")]
+pub fn convert_window_theme(theme: WindowTheme) -> winit::window::Theme {
+    match theme {
+        WindowTheme::Light => winit::window::Theme::Light,
+        WindowTheme::Dark => winit::window::Theme::Dark,
+    }
+}
+
+/// Whether a window of `level` is always on top. winit can't keep a window below the others, so
+/// [`WindowLevel::AlwaysOnBottom`] windows are normal windows, see [`warn_unsupported_level`].
+pub fn convert_window_level(level: WindowLevel) -> bool {
+    level == WindowLevel::AlwaysOnTop
+}
+
+/// Warns the first time a window is requested to stay below the others, which winit doesn't
+/// support.
+pub fn warn_unsupported_level(level: WindowLevel) {
+    static WARNED: AtomicBool = AtomicBool::new(false);
+    if level == WindowLevel::AlwaysOnBottom && !WARNED.swap(true, Ordering::Relaxed) {
+        warn!("Windows that are always on bottom are not supported, showing a normal window.");
+    }
+}
+
+pub fn convert_attention_type(attention: AttentionType) -> winit::window::UserAttentionType {
+    match attention {
+        AttentionType::Critical => winit::window::UserAttentionType::Critical,
+        AttentionType::Informational => winit::window::UserAttentionType::Informational,
+    }
+}
+
+pub fn convert_cursor_icon(cursor_icon: CursorIcon) -> winit::window::CursorIcon {
+    match cursor_icon {
+        CursorIcon::Default => winit::window::CursorIcon::Default,
+        CursorIcon::Crosshair => winit::window::CursorIcon::Crosshair,
+        CursorIcon::Hand => winit::window::CursorIcon::Hand,
+        CursorIcon::Arrow => winit::window::CursorIcon::Arrow,
+        CursorIcon::Move => winit::window::CursorIcon::Move,
+        CursorIcon::Text => winit::window::CursorIcon::Text,
+        CursorIcon::Wait => winit::window::CursorIcon::Wait,
+        CursorIcon::Help => winit::window::CursorIcon::Help,
+        CursorIcon::Progress => winit::window::CursorIcon::Progress,
+        CursorIcon::NotAllowed => winit::window::CursorIcon::NotAllowed,
+        CursorIcon::ContextMenu => winit::window::CursorIcon::ContextMenu,
+        CursorIcon::Cell => winit::window::CursorIcon::Cell,
+        CursorIcon::VerticalText => winit::window::CursorIcon::VerticalText,
+        CursorIcon::Alias => winit::window::CursorIcon::Alias,
+        CursorIcon::Copy => winit::window::CursorIcon::Copy,
+        CursorIcon::NoDrop => winit::window::CursorIcon::NoDrop,
+        CursorIcon::Grab => winit::window::CursorIcon::Grab,
+        CursorIcon::Grabbing => winit::window::CursorIcon::Grabbing,
+        CursorIcon::AllScroll => winit::window::CursorIcon::AllScroll,
+        CursorIcon::ZoomIn => winit::window::CursorIcon::ZoomIn,
+        CursorIcon::ZoomOut => winit::window::CursorIcon::ZoomOut,
+        CursorIcon::EResize => winit::window::CursorIcon::EResize,
+        CursorIcon::NResize => winit::window::CursorIcon::NResize,
+        CursorIcon::NeResize => winit::window::CursorIcon::NeResize,
+        CursorIcon::NwResize => winit::window::CursorIcon::NwResize,
+        CursorIcon::SResize => winit::window::CursorIcon::SResize,
+        CursorIcon::SeResize => winit::window::CursorIcon::SeResize,
+        CursorIcon::SwResize => winit::window::CursorIcon::SwResize,
+        CursorIcon::WResize => winit::window::CursorIcon::WResize,
+        CursorIcon::EwResize => winit::window::CursorIcon::EwResize,
+        CursorIcon::NsResize => winit::window::CursorIcon::NsResize,
+        CursorIcon::NeswResize => winit::window::CursorIcon::NeswResize,
+        CursorIcon::NwseResize => winit::window::CursorIcon::NwseResize,
+        CursorIcon::ColResize => winit::window::CursorIcon::ColResize,
+        CursorIcon::RowResize => winit::window::CursorIcon::RowResize,
+    }
+}
+
+/// The grab mode used on the current platform for `mode`, see [`CursorGrabMode`].
+pub fn platform_cursor_grab_mode(mode: CursorGrabMode) -> CursorGrabMode {
+    // the cursor can only be locked on macOS and the web
+    if cfg!(any(target_os = "macos", target_arch = "wasm32")) && mode == CursorGrabMode::Confined {
+        CursorGrabMode::Locked
+    } else {
+        mode
+    }
+}
+
+/// Returns `true` if [`CursorGrabMode::Locked`] is emulated on the current platform by
+/// confining the cursor and moving it back to the center of the window.
+pub fn emulates_cursor_lock() -> bool {
+    cfg!(any(
+        target_os = "windows",
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    ))
+}
+
+/// Replaces the cursor icons winit doesn't have on the current platform with their fallbacks,
+/// instead of the default cursor winit would show.
+pub fn platform_cursor_icon(cursor_icon: CursorIcon) -> CursorIcon {
+    if cfg!(target_os = "windows") {
+        cursor_icon.or_fallback(|icon| {
+            !matches!(
+                icon,
+                CursorIcon::ContextMenu
+                    | CursorIcon::Cell
+                    | CursorIcon::Alias
+                    | CursorIcon::Copy
+                    | CursorIcon::ZoomIn
+                    | CursorIcon::ZoomOut
+                    | CursorIcon::Grab
+                    | CursorIcon::Grabbing
+            )
+        })
+    } else {
+        cursor_icon
+    }
+}
+
+pub fn convert_video_mode(video_mode: &winit::monitor::VideoMode) -> VideoMode {
+    let size = video_mode.size();
+    VideoMode {
+        size: UVec2::new(size.width, size.height),
+        bit_depth: video_mode.bit_depth(),
+        refresh_rate: video_mode.refresh_rate(),
+    }
+}
+
+pub fn convert_monitor(monitor: &winit::monitor::MonitorHandle) -> MonitorInfo {
+    let position = monitor.position();
+    let size = monitor.size();
+    MonitorInfo {
+        name: monitor.name(),
+        position: IVec2::new(position.x, position.y),
+        physical_size: UVec2::new(size.width, size.height),
+        scale_factor: monitor.scale_factor(),
+        video_modes: monitor
+            .video_modes()
+            .map(|mode| convert_video_mode(&mode))
+            .collect(),
+    }
+}
+
 pub fn convert_touch_input(
     touch_input: winit::event::Touch,
     location: winit::dpi::LogicalPosition<f32>,
 ) -> TouchInput {
     TouchInput {
-        phase: match touch_input.phase {
-            winit::event::TouchPhase::Started => TouchPhase::Started,
-            winit::event::TouchPhase::Moved => TouchPhase::Moved,
-            winit::event::TouchPhase::Ended => TouchPhase::Ended,
-            winit::event::TouchPhase::Cancelled => TouchPhase::Cancelled,
-        },
+        phase: convert_touch_phase(touch_input.phase),
         position: Vec2::new(location.x as f32, location.y as f32),
+        // winit doesn't report the contact area or the azimuth of the stylus
+        radius: None,
+        tilt: match touch_input.force {
+            Some(winit::event::Force::Calibrated {
+                altitude_angle: Some(altitude),
+                ..
+            }) => Some(StylusTilt {
+                altitude,
+                azimuth: None,
+            }),
+            _ => None,
+        },
         force: touch_input.force.map(|f| match f {
             winit::event::Force::Calibrated {
                 force,
@@ -0,0 +1,496 @@
+use bevy_app::EventWriter;
+use bevy_ecs::system::{NonSend, NonSendMut, ResMut};
+use bevy_window::{
+    GlobalHotkeyCommand, GlobalHotkeyPressed, GlobalHotkeyRegistrationFailed, GlobalHotkeys,
+};
+use winit::event_loop::EventLoopProxy;
+
+/// The connection to the platform that listens to the global hotkeys, opened when the first
+/// hotkey is registered.
+///
+/// winit doesn't support global hotkeys, so they are grabbed on a connection to the platform
+/// that is separate from the one of the windows. Only X11 is implemented.
+#[derive(Default)]
+pub(crate) enum HotkeyListener {
+    #[default]
+    NotStarted,
+    Running(Box<platform::Hotkeys>),
+    Unsupported,
+}
+
+impl HotkeyListener {
+    fn start(wake: Option<EventLoopProxy<()>>) -> Self {
+        match platform::Hotkeys::open(wake) {
+            Some(hotkeys) => HotkeyListener::Running(Box::new(hotkeys)),
+            None => HotkeyListener::Unsupported,
+        }
+    }
+}
+
+/// Forwards the changes to the [`GlobalHotkeys`] to the platform, and sends the events of the
+/// hotkeys.
+///
+/// The listener is a non-send resource, so that the hotkeys are grabbed on the main thread.
+pub(crate) fn global_hotkey_system(
+    mut listener: NonSendMut<HotkeyListener>,
+    event_loop_proxy: Option<NonSend<EventLoopProxy<()>>>,
+    mut hotkeys: ResMut<GlobalHotkeys>,
+    mut pressed_events: EventWriter<GlobalHotkeyPressed>,
+    mut failed_events: EventWriter<GlobalHotkeyRegistrationFailed>,
+) {
+    for command in hotkeys.drain_commands() {
+        if let HotkeyListener::NotStarted = *listener {
+            *listener = HotkeyListener::start(event_loop_proxy.as_deref().cloned());
+        }
+        match (&mut *listener, command) {
+            (HotkeyListener::Running(platform), GlobalHotkeyCommand::Register { id, hotkey }) => {
+                if !platform.register(id, hotkey) {
+                    failed_events.send(GlobalHotkeyRegistrationFailed { id, hotkey });
+                }
+            }
+            (HotkeyListener::Running(platform), GlobalHotkeyCommand::Unregister { id, hotkey }) => {
+                platform.unregister(id, hotkey);
+            }
+            (HotkeyListener::Unsupported, GlobalHotkeyCommand::Register { id, hotkey }) => {
+                failed_events.send(GlobalHotkeyRegistrationFailed { id, hotkey });
+            }
+            (HotkeyListener::Unsupported, GlobalHotkeyCommand::Unregister { .. }) => {}
+            (HotkeyListener::NotStarted, _) => unreachable!(),
+        }
+    }
+
+    if let HotkeyListener::Running(platform) = &mut *listener {
+        platform.read_events(|id| {
+            // the hotkey may have been unregistered since it was pressed
+            if hotkeys.get(id).is_some() {
+                pressed_events.send(GlobalHotkeyPressed { id });
+            }
+        });
+    }
+}
+
+#[cfg(all(
+    feature = "x11",
+    any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    )
+))]
+mod platform {
+    use bevy_input::keyboard::{KeyCode, ModifierKeys};
+    use bevy_utils::{
+        tracing::{error, warn},
+        HashMap,
+    };
+    use bevy_window::{GlobalHotkey, GlobalHotkeyId};
+    use crossbeam_channel::{Receiver, Sender};
+    use std::{
+        io, mem,
+        os::raw::{c_int, c_uint, c_ulong},
+        ptr,
+        sync::atomic::{AtomicBool, Ordering},
+        thread::{self, JoinHandle},
+    };
+    use winit::event_loop::EventLoopProxy;
+    use x11_dl::{keysym, xlib};
+
+    /// The modifiers that are ignored when matching a hotkey, so that hotkeys still work with
+    /// Caps Lock or Num Lock enabled.
+    const IGNORED_MODIFIERS: [c_uint; 4] = [
+        0,
+        xlib::LockMask,
+        xlib::Mod2Mask,
+        xlib::LockMask | xlib::Mod2Mask,
+    ];
+
+    /// Set by [`record_grab_error`] when a key can't be grabbed.
+    static GRAB_FAILED: AtomicBool = AtomicBool::new(false);
+
+    /// Records the errors of the key grabs. The default error handler of Xlib exits the process,
+    /// for example when the key is already grabbed by another client.
+    unsafe extern "C" fn record_grab_error(
+        _display: *mut xlib::Display,
+        _event: *mut xlib::XErrorEvent,
+    ) -> c_int {
+        GRAB_FAILED.store(true, Ordering::Relaxed);
+        0
+    }
+
+    /// The hotkeys grabbed on a connection to the X server.
+    ///
+    /// The connection is only used on the main thread. A thread waits for the connection to be
+    /// readable, wakes the event loop, and then waits until the events have been read on the main
+    /// thread.
+    pub(crate) struct Hotkeys {
+        xlib: xlib::Xlib,
+        display: *mut xlib::Display,
+        root: c_ulong,
+        grabbed: HashMap<(c_uint, c_uint), GlobalHotkeyId>,
+        readable: Receiver<()>,
+        read: Option<Sender<()>>,
+        shutdown: c_int,
+        waiter: Option<JoinHandle<()>>,
+    }
+
+    impl Hotkeys {
+        pub(super) fn open(wake: Option<EventLoopProxy<()>>) -> Option<Self> {
+            let xlib = match xlib::Xlib::open() {
+                Ok(xlib) => xlib,
+                Err(err) => {
+                    warn!("Global hotkeys are not available: {}", err);
+                    return None;
+                }
+            };
+            // SAFETY: the display is closed when dropped
+            let display = unsafe { (xlib.XOpenDisplay)(ptr::null()) };
+            if display.is_null() {
+                warn!("Global hotkeys are not available: failed to connect to the X server");
+                return None;
+            }
+            // SAFETY: the display is valid
+            let (root, connection) = unsafe {
+                (
+                    (xlib.XDefaultRootWindow)(display),
+                    (xlib.XConnectionNumber)(display),
+                )
+            };
+
+            let mut pipe = [0; 2];
+            // SAFETY: pipe has room for the two file descriptors
+            if unsafe { libc::pipe(pipe.as_mut_ptr()) } != 0 {
+                error!(
+                    "Failed to start the global hotkeys thread: {}",
+                    io::Error::last_os_error()
+                );
+                // SAFETY: the display is valid and not used after
+                unsafe { (xlib.XCloseDisplay)(display) };
+                return None;
+            }
+            let (readable_sender, readable) = crossbeam_channel::bounded(1);
+            let (read, read_receiver) = crossbeam_channel::bounded(1);
+            let [shutdown_receiver, shutdown] = pipe;
+            let waiter = thread::Builder::new()
+                .name("global hotkeys".to_string())
+                .spawn(move || {
+                    wait_for_events(
+                        connection,
+                        shutdown_receiver,
+                        wake,
+                        readable_sender,
+                        read_receiver,
+                    )
+                });
+            let waiter = match waiter {
+                Ok(waiter) => waiter,
+                Err(err) => {
+                    error!("Failed to start the global hotkeys thread: {}", err);
+                    // SAFETY: the file descriptors and display are valid and not used after
+                    unsafe {
+                        libc::close(shutdown);
+                        libc::close(shutdown_receiver);
+                        (xlib.XCloseDisplay)(display);
+                    }
+                    return None;
+                }
+            };
+
+            Some(Hotkeys {
+                xlib,
+                display,
+                root,
+                grabbed: Default::default(),
+                readable,
+                read: Some(read),
+                shutdown,
+                waiter: Some(waiter),
+            })
+        }
+
+        /// Grabs the key of `hotkey` on the root window. Returns `false` if it can't be grabbed,
+        /// such as when another client already grabbed it.
+        ///
+        /// The errors of the grabs are recorded with an error handler, which is set for the whole
+        /// process. It is only set while the grabs are synced, on the main thread where winit
+        /// sends its own requests, so no other request can fail in the meantime.
+        pub(super) fn register(&mut self, id: GlobalHotkeyId, hotkey: GlobalHotkey) -> bool {
+            let xlib = &self.xlib;
+            let (display, root) = (self.display, self.root);
+            // SAFETY: the display is valid, and only used on this thread
+            unsafe {
+                let key = match x11_key(xlib, display, hotkey) {
+                    Some(key) => key,
+                    None => return false,
+                };
+                // the errors are reported asynchronously, they are all received by the end of the
+                // sync
+                (xlib.XSync)(display, xlib::False);
+                GRAB_FAILED.store(false, Ordering::Relaxed);
+                let previous_handler = (xlib.XSetErrorHandler)(Some(record_grab_error));
+                for ignored in IGNORED_MODIFIERS.iter() {
+                    (xlib.XGrabKey)(
+                        display,
+                        key.0 as i32,
+                        key.1 | ignored,
+                        root,
+                        xlib::True,
+                        xlib::GrabModeAsync,
+                        xlib::GrabModeAsync,
+                    );
+                }
+                (xlib.XSync)(display, xlib::False);
+                let failed = GRAB_FAILED.load(Ordering::Relaxed);
+                if failed {
+                    // release the combinations with ignored modifiers that were grabbed
+                    ungrab_key(xlib, display, root, key);
+                    (xlib.XSync)(display, xlib::False);
+                }
+                (xlib.XSetErrorHandler)(previous_handler);
+                if !failed {
+                    self.grabbed.insert(key, id);
+                }
+                !failed
+            }
+        }
+
+        pub(super) fn unregister(&mut self, id: GlobalHotkeyId, hotkey: GlobalHotkey) {
+            // SAFETY: the display is valid, and only used on this thread
+            unsafe {
+                if let Some(key) = x11_key(&self.xlib, self.display, hotkey) {
+                    if self.grabbed.get(&key) == Some(&id) {
+                        self.grabbed.remove(&key);
+                        ungrab_key(&self.xlib, self.display, self.root, key);
+                        (self.xlib.XFlush)(self.display);
+                    }
+                }
+            }
+        }
+
+        /// Calls `pressed` with the hotkeys pressed since the last call.
+        pub(super) fn read_events(&mut self, mut pressed: impl FnMut(GlobalHotkeyId)) {
+            let waiting = self.readable.try_recv().is_ok();
+            // SAFETY: the display is valid, and only used on this thread
+            unsafe {
+                while (self.xlib.XPending)(self.display) > 0 {
+                    let mut event: xlib::XEvent = mem::zeroed();
+                    (self.xlib.XNextEvent)(self.display, &mut event);
+                    if event.get_type() == xlib::KeyPress {
+                        let key = (
+                            event.key.keycode,
+                            event.key.state & !(xlib::LockMask | xlib::Mod2Mask),
+                        );
+                        if let Some(id) = self.grabbed.get(&key) {
+                            pressed(*id);
+                        }
+                    }
+                }
+            }
+            if waiting {
+                if let Some(read) = &self.read {
+                    let _ = read.send(());
+                }
+            }
+        }
+    }
+
+    impl Drop for Hotkeys {
+        fn drop(&mut self) {
+            // stop the thread before closing the connection it waits on
+            self.read = None;
+            // SAFETY: the file descriptor is valid and not used after
+            unsafe { libc::close(self.shutdown) };
+            if let Some(waiter) = self.waiter.take() {
+                let _ = waiter.join();
+            }
+            // SAFETY: the display is valid and not used after
+            unsafe { (self.xlib.XCloseDisplay)(self.display) };
+        }
+    }
+
+    /// Blocks until the X connection is readable, wakes the event loop, and then blocks until
+    /// the events have been read on the main thread, until `shutdown` is closed.
+    fn wait_for_events(
+        connection: c_int,
+        shutdown: c_int,
+        wake: Option<EventLoopProxy<()>>,
+        readable: Sender<()>,
+        read: Receiver<()>,
+    ) {
+        let mut fds = [
+            libc::pollfd {
+                fd: connection,
+                events: libc::POLLIN,
+                revents: 0,
+            },
+            libc::pollfd {
+                fd: shutdown,
+                events: libc::POLLIN,
+                revents: 0,
+            },
+        ];
+        loop {
+            // SAFETY: fds holds the two file descriptors polled
+            if unsafe { libc::poll(fds.as_mut_ptr(), fds.len() as libc::nfds_t, -1) } < 0 {
+                let err = io::Error::last_os_error();
+                if err.kind() == io::ErrorKind::Interrupted {
+                    continue;
+                }
+                error!("Stopped listening to the global hotkeys: {}", err);
+                break;
+            }
+            if fds[1].revents != 0 {
+                break;
+            }
+            if fds[0].revents & (libc::POLLERR | libc::POLLHUP | libc::POLLNVAL) != 0 {
+                warn!(
+                    "Stopped listening to the global hotkeys: lost the connection to the X server"
+                );
+                break;
+            }
+            // the connection stays readable until the events are read
+            if readable.send(()).is_err() {
+                break;
+            }
+            if let Some(wake) = &wake {
+                // fails once the event loop exited
+                let _ = wake.send_event(());
+            }
+            if read.recv().is_err() {
+                break;
+            }
+        }
+        // SAFETY: the file descriptor is valid and not used after
+        unsafe { libc::close(shutdown) };
+    }
+
+    unsafe fn ungrab_key(
+        xlib: &xlib::Xlib,
+        display: *mut xlib::Display,
+        root: c_ulong,
+        key: (c_uint, c_uint),
+    ) {
+        for ignored in IGNORED_MODIFIERS.iter() {
+            (xlib.XUngrabKey)(display, key.0 as i32, key.1 | ignored, root);
+        }
+    }
+
+    unsafe fn x11_key(
+        xlib: &xlib::Xlib,
+        display: *mut xlib::Display,
+        hotkey: GlobalHotkey,
+    ) -> Option<(c_uint, c_uint)> {
+        let keycode = (xlib.XKeysymToKeycode)(display, convert_key_code(hotkey.key)? as c_ulong);
+        if keycode == 0 {
+            return None;
+        }
+        Some((keycode as c_uint, convert_modifiers(hotkey.modifiers)))
+    }
+
+    fn convert_modifiers(modifiers: ModifierKeys) -> c_uint {
+        let mut mask = 0;
+        if modifiers.shift {
+            mask |= xlib::ShiftMask;
+        }
+        if modifiers.ctrl {
+            mask |= xlib::ControlMask;
+        }
+        if modifiers.alt {
+            mask |= xlib::Mod1Mask;
+        }
+        if modifiers.logo {
+            mask |= xlib::Mod4Mask;
+        }
+        mask
+    }
+
+    fn convert_key_code(key_code: KeyCode) -> Option<c_uint> {
+        let code = key_code as c_uint;
+        let offset_from = |first: KeyCode, last: KeyCode| {
+            (first as c_uint..=last as c_uint)
+                .contains(&code)
+                .then(|| code - first as c_uint)
+        };
+        if let Some(offset) = offset_from(KeyCode::A, KeyCode::Z) {
+            return Some(keysym::XK_a + offset);
+        }
+        if let Some(offset) = offset_from(KeyCode::Key1, KeyCode::Key9) {
+            return Some(keysym::XK_1 + offset);
+        }
+        if let Some(offset) = offset_from(KeyCode::F1, KeyCode::F24) {
+            return Some(keysym::XK_F1 + offset);
+        }
+        if let Some(offset) = offset_from(KeyCode::Numpad0, KeyCode::Numpad9) {
+            return Some(keysym::XK_KP_0 + offset);
+        }
+        let keysym = match key_code {
+            KeyCode::Key0 => keysym::XK_0,
+            KeyCode::Escape => keysym::XK_Escape,
+            KeyCode::Snapshot => keysym::XK_Print,
+            KeyCode::Scroll => keysym::XK_Scroll_Lock,
+            KeyCode::Pause => keysym::XK_Pause,
+            KeyCode::Insert => keysym::XK_Insert,
+            KeyCode::Home => keysym::XK_Home,
+            KeyCode::Delete => keysym::XK_Delete,
+            KeyCode::End => keysym::XK_End,
+            KeyCode::PageDown => keysym::XK_Next,
+            KeyCode::PageUp => keysym::XK_Prior,
+            KeyCode::Left => keysym::XK_Left,
+            KeyCode::Up => keysym::XK_Up,
+            KeyCode::Right => keysym::XK_Right,
+            KeyCode::Down => keysym::XK_Down,
+            KeyCode::Back => keysym::XK_BackSpace,
+            KeyCode::Return => keysym::XK_Return,
+            KeyCode::Space => keysym::XK_space,
+            KeyCode::Tab => keysym::XK_Tab,
+            KeyCode::Mute => keysym::XF86XK_AudioMute,
+            KeyCode::VolumeDown => keysym::XF86XK_AudioLowerVolume,
+            KeyCode::VolumeUp => keysym::XF86XK_AudioRaiseVolume,
+            KeyCode::PlayPause => keysym::XF86XK_AudioPlay,
+            KeyCode::MediaStop => keysym::XF86XK_AudioStop,
+            KeyCode::NextTrack => keysym::XF86XK_AudioNext,
+            KeyCode::PrevTrack => keysym::XF86XK_AudioPrev,
+            _ => return None,
+        };
+        Some(keysym)
+    }
+}
+
+#[cfg(not(all(
+    feature = "x11",
+    any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    )
+)))]
+mod platform {
+    use bevy_utils::tracing::warn;
+    use bevy_window::{GlobalHotkey, GlobalHotkeyId};
+    use winit::event_loop::EventLoopProxy;
+
+    /// Global hotkeys are only implemented on X11.
+    pub(crate) enum Hotkeys {}
+
+    impl Hotkeys {
+        pub(super) fn open(_wake: Option<EventLoopProxy<()>>) -> Option<Self> {
+            warn!("Global hotkeys are not supported on this platform");
+            None
+        }
+
+        pub(super) fn register(&mut self, _id: GlobalHotkeyId, _hotkey: GlobalHotkey) -> bool {
+            match *self {}
+        }
+
+        pub(super) fn unregister(&mut self, _id: GlobalHotkeyId, _hotkey: GlobalHotkey) {
+            match *self {}
+        }
+
+        pub(super) fn read_events(&mut self, _pressed: impl FnMut(GlobalHotkeyId)) {
+            match *self {}
+        }
+    }
+}
@@ -1,31 +1,59 @@
+mod backend;
+mod badge;
+mod clipboard;
 mod converters;
+mod global_hotkeys;
+mod orientation;
+mod power;
+mod raw_window;
+mod represented_file;
+mod soft_keyboard;
+mod startup_notification;
+mod taskbar;
+mod theme;
+#[cfg(target_arch = "wasm32")]
+mod web;
 mod winit_config;
 mod winit_windows;
 
 use bevy_input::{
-    keyboard::KeyboardInput,
+    keyboard::{ModifierKeys, ModifiersChanged},
     mouse::{MouseButtonInput, MouseMotion, MouseScrollUnit, MouseWheel},
-    touch::TouchInput,
+    touch::TouchPhase,
 };
+pub use raw_window::*;
+// for the closures of `with_raw_window`, without depending on the same version of winit
+pub use winit;
 pub use winit_config::*;
 pub use winit_windows::*;
 
 use bevy_app::{App, AppExit, CoreStage, Events, ManualEventReader, Plugin};
-use bevy_ecs::{system::IntoExclusiveSystem, world::World};
+use bevy_ecs::{
+    component::Component,
+    system::IntoExclusiveSystem,
+    world::{World, WorldCell},
+};
 use bevy_math::{ivec2, Vec2};
-use bevy_utils::tracing::{error, trace, warn};
+use bevy_utils::{
+    tracing::{trace, warn},
+    Duration, HashMap, HashSet, Instant,
+};
 use bevy_window::{
-    CreateWindow, CursorEntered, CursorLeft, CursorMoved, FileDragAndDrop, ReceivedCharacter,
-    WindowBackendScaleFactorChanged, WindowCloseRequested, WindowCreated, WindowFocused,
-    WindowMoved, WindowResized, WindowScaleFactorChanged, Windows,
+    CreateWindow, CursorEntered, CursorGrabMode, CursorLeft, CursorMoved, EventTimestamp,
+    FileDragAndDrop, FilesDropped, MonitorConnected, MonitorDisconnected, Monitors, PowerState,
+    PowerStateChanged, ReceivedCharacter, RequestRedraw, ScreenOrientation, TextInputEvent,
+    TextInputSettings, Timestamped, Window, WindowBackendScaleFactorChanged, WindowBackendStats,
+    WindowCloseRequested, WindowConstraintViolation, WindowCreated, WindowFocused, WindowMoved,
+    WindowResized, WindowRotated, WindowScaleFactorChanged, WindowStateChanged, Windows,
 };
+use std::cell::Cell;
 use winit::{
-    dpi::PhysicalPosition,
-    event::{self, DeviceEvent, Event, WindowEvent},
+    dpi::{PhysicalPosition, PhysicalSize},
+    event::{self, DeviceEvent, Event, StartCause, WindowEvent},
     event_loop::{ControlFlow, EventLoop, EventLoopWindowTarget},
+    monitor::MonitorHandle,
 };
 
-use winit::dpi::LogicalSize;
 #[cfg(any(
     target_os = "linux",
     target_os = "dragonfly",
@@ -41,8 +69,19 @@ pub struct WinitPlugin;
 impl Plugin for WinitPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<WinitWindows>()
+            .init_resource::<RawWindowRequests>()
+            .init_non_send_resource::<global_hotkeys::HotkeyListener>()
             .set_runner(winit_runner)
-            .add_system_to_stage(CoreStage::PostUpdate, change_window.exclusive_system());
+            .add_system_to_stage(CoreStage::PostUpdate, change_window.exclusive_system())
+            .add_system_to_stage(CoreStage::PreUpdate, global_hotkeys::global_hotkey_system)
+            .add_system_to_stage(CoreStage::PreUpdate, clipboard::clipboard_system);
+        #[cfg(target_os = "android")]
+        app.add_system_to_stage(
+            CoreStage::PreUpdate,
+            soft_keyboard::soft_keyboard_inset_system,
+        );
+        #[cfg(target_arch = "wasm32")]
+        web::build(app);
     }
 }
 
@@ -52,115 +91,16 @@ fn change_window(world: &mut World) {
     let mut windows = world.get_resource_mut::<Windows>().unwrap();
 
     for bevy_window in windows.iter_mut() {
-        let id = bevy_window.id();
-        for command in bevy_window.drain_commands() {
-            match command {
-                bevy_window::WindowCommand::SetWindowMode {
-                    mode,
-                    resolution: (width, height),
-                } => {
-                    let window = winit_windows.get_window(id).unwrap();
-                    match mode {
-                        bevy_window::WindowMode::BorderlessFullscreen => {
-                            window.set_fullscreen(Some(winit::window::Fullscreen::Borderless(None)))
-                        }
-                        bevy_window::WindowMode::Fullscreen { use_size } => window.set_fullscreen(
-                            Some(winit::window::Fullscreen::Exclusive(match use_size {
-                                true => get_fitting_videomode(
-                                    &window.current_monitor().unwrap(),
-                                    width,
-                                    height,
-                                ),
-                                false => get_best_videomode(&window.current_monitor().unwrap()),
-                            })),
-                        ),
-                        bevy_window::WindowMode::Windowed => window.set_fullscreen(None),
-                    }
-                }
-                bevy_window::WindowCommand::SetTitle { title } => {
-                    let window = winit_windows.get_window(id).unwrap();
-                    window.set_title(&title);
-                }
-                bevy_window::WindowCommand::SetScaleFactor { scale_factor } => {
-                    let mut window_dpi_changed_events = world
-                        .get_resource_mut::<Events<WindowScaleFactorChanged>>()
-                        .unwrap();
-                    window_dpi_changed_events.send(WindowScaleFactorChanged { id, scale_factor });
-                }
-                bevy_window::WindowCommand::SetResolution {
-                    logical_resolution: (width, height),
-                    scale_factor,
-                } => {
-                    let window = winit_windows.get_window(id).unwrap();
-                    window.set_inner_size(
-                        winit::dpi::LogicalSize::new(width, height)
-                            .to_physical::<f64>(scale_factor),
-                    );
-                }
-                bevy_window::WindowCommand::SetVsync { .. } => (),
-                bevy_window::WindowCommand::SetResizable { resizable } => {
-                    let window = winit_windows.get_window(id).unwrap();
-                    window.set_resizable(resizable);
-                }
-                bevy_window::WindowCommand::SetDecorations { decorations } => {
-                    let window = winit_windows.get_window(id).unwrap();
-                    window.set_decorations(decorations);
-                }
-                bevy_window::WindowCommand::SetCursorLockMode { locked } => {
-                    let window = winit_windows.get_window(id).unwrap();
-                    window
-                        .set_cursor_grab(locked)
-                        .unwrap_or_else(|e| error!("Unable to un/grab cursor: {}", e));
-                }
-                bevy_window::WindowCommand::SetCursorVisibility { visible } => {
-                    let window = winit_windows.get_window(id).unwrap();
-                    window.set_cursor_visible(visible);
-                }
-                bevy_window::WindowCommand::SetCursorPosition { position } => {
-                    let window = winit_windows.get_window(id).unwrap();
-                    let inner_size = window.inner_size().to_logical::<f32>(window.scale_factor());
-                    window
-                        .set_cursor_position(winit::dpi::LogicalPosition::new(
-                            position.x,
-                            inner_size.height - position.y,
-                        ))
-                        .unwrap_or_else(|e| error!("Unable to set cursor position: {}", e));
-                }
-                bevy_window::WindowCommand::SetMaximized { maximized } => {
-                    let window = winit_windows.get_window(id).unwrap();
-                    window.set_maximized(maximized)
-                }
-                bevy_window::WindowCommand::SetMinimized { minimized } => {
-                    let window = winit_windows.get_window(id).unwrap();
-                    window.set_minimized(minimized)
-                }
-                bevy_window::WindowCommand::SetPosition { position } => {
-                    let window = winit_windows.get_window(id).unwrap();
-                    window.set_outer_position(PhysicalPosition {
-                        x: position[0],
-                        y: position[1],
-                    });
-                }
-                bevy_window::WindowCommand::SetResizeConstraints { resize_constraints } => {
-                    let window = winit_windows.get_window(id).unwrap();
-                    let constraints = resize_constraints.check_constraints();
-                    let min_inner_size = LogicalSize {
-                        width: constraints.min_width,
-                        height: constraints.min_height,
-                    };
-                    let max_inner_size = LogicalSize {
-                        width: constraints.max_width,
-                        height: constraints.max_height,
-                    };
-
-                    window.set_min_inner_size(Some(min_inner_size));
-                    if constraints.max_width.is_finite() && constraints.max_height.is_finite() {
-                        window.set_max_inner_size(Some(max_inner_size));
-                    }
-                }
-            }
+        if bevy_window.is_offscreen() {
+            backend::apply_offscreen_window_commands(bevy_window, &world);
+            continue;
         }
+        let window = winit_windows.get_window(bevy_window.id()).unwrap();
+        backend::apply_window_commands(bevy_window, window, &world);
     }
+
+    let mut raw_window_requests = world.get_resource_mut::<RawWindowRequests>().unwrap();
+    raw_window_requests.run(&winit_windows);
 }
 
 fn run<F>(event_loop: EventLoop<()>, event_handler: F) -> !
@@ -224,8 +164,31 @@ pub fn winit_runner_any_thread(app: App) {
 pub fn winit_runner_with(mut app: App, mut event_loop: EventLoop<()>) {
     let mut create_window_event_reader = ManualEventReader::<CreateWindow>::default();
     let mut app_exit_event_reader = ManualEventReader::<AppExit>::default();
+    let mut redraw_event_reader = ManualEventReader::<RequestRedraw>::default();
+    // the windows in which the scroll events since the end of a touchpad gesture are the momentum
+    // scrolling of the platform, until it stops or the next gesture starts
+    let mut scroll_momentum = HashSet::default();
+    let mut pressed_keys = PressedKeys::default();
+    // the text typed in each window since the last update
+    let mut text_inputs = HashMap::<_, (String, _)>::default();
+    // the last cursor position of each window, when the cursor moved events are coalesced
+    let mut coalesced_cursor_positions = HashMap::default();
+    // the files dropped on each window since the last update, and the cursor position
+    let mut dropped_files = HashMap::<_, (Vec<_>, Option<Vec2>, _)>::default();
+    // wakes the event loop from other threads, to update the app in the reactive modes
     app.world.insert_non_send(event_loop.create_proxy());
 
+    if let Some(app_user_model_id) = app
+        .world
+        .get_resource::<WinitConfig>()
+        .and_then(|config| config.app_user_model_id.as_ref())
+    {
+        // the id must be set before the windows are created
+        if let Err(err) = taskbar::set_app_user_model_id(app_user_model_id) {
+            warn!("Unable to set the application user model id: {}", err);
+        }
+    }
+
     trace!("Entering winit event loop");
 
     let should_return_from_run = app
@@ -234,11 +197,36 @@ pub fn winit_runner_with(mut app: App, mut event_loop: EventLoop<()>) {
         .map_or(false, |config| config.return_from_run);
 
     let mut active = true;
+    // what happened since the last update, to know whether to update in the reactive modes
+    let mut low_power_event = false;
+    let mut redraw_requested = false;
+    let mut timeout_reached = false;
+    // an update was skipped to respect the minimum frame time, and is due when it has elapsed
+    let mut update_delayed = false;
+    let mut last_update = Instant::now();
+    let mut frame = 0;
+    let mut last_power_check = None;
+    let mut last_monitor_check = None;
+    let mut monitor_layout = Vec::new();
+    let mut window_events = 0;
+    let event_handler_time = Cell::new(Duration::default());
 
     let event_handler = move |event: Event<()>,
                               event_loop: &EventLoopWindowTarget<()>,
                               control_flow: &mut ControlFlow| {
-        *control_flow = ControlFlow::Poll;
+        let event_start = Instant::now();
+        let event_handler_timer = EventHandlerTimer {
+            start: event_start,
+            total: &event_handler_time,
+        };
+        let timestamp = app
+            .world
+            .get_resource::<WinitConfig>()
+            .is_some_and(|config| config.timestamp_events)
+            .then_some(EventTimestamp {
+                instant: event_start,
+                frame,
+            });
 
         if let Some(app_exit_events) = app.world.get_resource_mut::<Events<AppExit>>() {
             if app_exit_event_reader
@@ -251,6 +239,31 @@ pub fn winit_runner_with(mut app: App, mut event_loop: EventLoop<()>) {
         }
 
         match event {
+            event::Event::NewEvents(start) => {
+                if last_power_check
+                    .is_none_or(|last_check: Instant| last_check.elapsed() >= POWER_CHECK_INTERVAL)
+                {
+                    last_power_check = Some(Instant::now());
+                    update_power_state(&mut app.world);
+                }
+                // winit doesn't report the monitors being connected and disconnected
+                if last_monitor_check.is_none_or(|last_check: Instant| {
+                    last_check.elapsed() >= MONITOR_CHECK_INTERVAL
+                }) {
+                    last_monitor_check = Some(Instant::now());
+                    update_monitors(&mut app.world, event_loop, &mut monitor_layout);
+                }
+
+                // the event loop may also have been woken up by an event shortly before the
+                // deadline, in which case `max_wait` has elapsed all the same
+                let max_wait = match update_mode(&app.world) {
+                    UpdateMode::Continuous => None,
+                    UpdateMode::Reactive { max_wait }
+                    | UpdateMode::ReactiveLowPower { max_wait } => max_wait,
+                };
+                timeout_reached = matches!(start, StartCause::ResumeTimeReached { .. })
+                    || max_wait.is_some_and(|max_wait| last_update.elapsed() >= max_wait);
+            }
             event::Event::WindowEvent {
                 event,
                 window_id: winit_window_id,
@@ -276,33 +289,95 @@ pub fn winit_runner_with(mut app: App, mut event_loop: EventLoop<()>) {
                     warn!("Skipped event for unknown Window Id {:?}", winit_window_id);
                     return;
                 };
+                let accepts_input = window.accepts_input();
+                window_events += 1;
+                low_power_event = true;
 
                 match event {
                     WindowEvent::Resized(size) => {
+                        let old_size = (window.physical_width(), window.physical_height());
+                        let old_state = window.state();
                         window.update_actual_size_from_backend(size.width, size.height);
-                        let mut resize_events =
-                            world.get_resource_mut::<Events<WindowResized>>().unwrap();
-                        resize_events.send(WindowResized {
-                            id: window_id,
-                            width: window.width(),
-                            height: window.height(),
-                        });
+                        // the platforms don't report when a window is maximized, but it is
+                        // resized when it is, and when it is minimized or restored
+                        if let Some(winit_window) = winit_windows.get_window(window_id) {
+                            window.update_maximized_from_backend(winit_window.is_maximized());
+                        }
+                        let new_state = window.state();
+                        if new_state != old_state {
+                            send_event(
+                                &world,
+                                timestamp,
+                                WindowStateChanged {
+                                    id: window_id,
+                                    old: old_state,
+                                    new: new_state,
+                                },
+                            );
+                        }
+                        // only the windows of mobile devices and browsers are rotated, desktop
+                        // windows are resized freely. Minimized windows have a zero size.
+                        let landscape = size.width > size.height;
+                        if cfg!(any(
+                            target_os = "android",
+                            target_os = "ios",
+                            target_arch = "wasm32"
+                        )) && size.width > 0
+                            && size.height > 0
+                            && old_size.0 > 0
+                            && old_size.1 > 0
+                            && landscape != (old_size.0 > old_size.1)
+                        {
+                            send_event(
+                                &world,
+                                timestamp,
+                                WindowRotated {
+                                    id: window_id,
+                                    orientation: if landscape {
+                                        ScreenOrientation::Landscape
+                                    } else {
+                                        ScreenOrientation::Portrait
+                                    },
+                                },
+                            );
+                        }
+                        send_event(
+                            &world,
+                            timestamp,
+                            WindowResized {
+                                id: window_id,
+                                width: window.width(),
+                                height: window.height(),
+                            },
+                        );
+                    }
+                    WindowEvent::ThemeChanged(_) => {
+                        if let Some(winit_window) = winit_windows.get_window(window_id) {
+                            theme::update_titlebar_theme(winit_window);
+                        }
                     }
                     WindowEvent::CloseRequested => {
-                        let mut window_close_requested_events = world
-                            .get_resource_mut::<Events<WindowCloseRequested>>()
-                            .unwrap();
-                        window_close_requested_events.send(WindowCloseRequested { id: window_id });
+                        send_event(&world, timestamp, WindowCloseRequested { id: window_id });
+                    }
+                    WindowEvent::KeyboardInput { .. }
+                    | WindowEvent::MouseInput { .. }
+                    | WindowEvent::Touch(_) => {
+                        send_routed_input(&world, window, timestamp, &mut pressed_keys, &event);
                     }
-                    WindowEvent::KeyboardInput { ref input, .. } => {
-                        let mut keyboard_input_events =
-                            world.get_resource_mut::<Events<KeyboardInput>>().unwrap();
-                        keyboard_input_events.send(converters::convert_keyboard_input(input));
+                    WindowEvent::ModifiersChanged(modifiers) => {
+                        send_event(
+                            &world,
+                            timestamp,
+                            ModifiersChanged(converters::convert_modifiers(modifiers)),
+                        );
+                    }
+                    WindowEvent::CursorMoved { position, .. } if window.uses_raw_mouse_motion() => {
+                        let winit_window = winit_windows.get_window(window_id).unwrap();
+                        recenter_locked_cursor(window, winit_window, position);
                     }
                     WindowEvent::CursorMoved { position, .. } => {
-                        let mut cursor_moved_events =
-                            world.get_resource_mut::<Events<CursorMoved>>().unwrap();
                         let winit_window = winit_windows.get_window(window_id).unwrap();
+                        recenter_locked_cursor(window, winit_window, position);
                         let position = position.to_logical(winit_window.scale_factor());
                         let inner_size = winit_window
                             .inner_size()
@@ -314,111 +389,168 @@ pub fn winit_runner_with(mut app: App, mut event_loop: EventLoop<()>) {
                         let position = Vec2::new(position.x, y_position);
                         window.update_cursor_position_from_backend(Some(position));
 
-                        cursor_moved_events.send(CursorMoved {
-                            id: window_id,
-                            position,
-                        });
+                        if world
+                            .get_resource::<WinitConfig>()
+                            .is_some_and(|config| config.coalesce_cursor_moved)
+                        {
+                            coalesced_cursor_positions.insert(window_id, (position, timestamp));
+                        } else {
+                            send_event(
+                                &world,
+                                timestamp,
+                                CursorMoved {
+                                    id: window_id,
+                                    position,
+                                },
+                            );
+                        }
                     }
                     WindowEvent::CursorEntered { .. } => {
-                        let mut cursor_entered_events =
-                            world.get_resource_mut::<Events<CursorEntered>>().unwrap();
-                        cursor_entered_events.send(CursorEntered { id: window_id });
+                        send_event(&world, timestamp, CursorEntered { id: window_id });
                     }
                     WindowEvent::CursorLeft { .. } => {
-                        let mut cursor_left_events =
-                            world.get_resource_mut::<Events<CursorLeft>>().unwrap();
                         window.update_cursor_position_from_backend(None);
-                        cursor_left_events.send(CursorLeft { id: window_id });
-                    }
-                    WindowEvent::MouseInput { state, button, .. } => {
-                        let mut mouse_button_input_events = world
-                            .get_resource_mut::<Events<MouseButtonInput>>()
-                            .unwrap();
-                        mouse_button_input_events.send(MouseButtonInput {
-                            button: converters::convert_mouse_button(button),
-                            state: converters::convert_element_state(state),
-                        });
+                        // the last position in the window is sent before the cursor leaves it
+                        if let Some((position, timestamp)) =
+                            coalesced_cursor_positions.remove(&window_id)
+                        {
+                            send_event(
+                                &world,
+                                timestamp,
+                                CursorMoved {
+                                    id: window_id,
+                                    position,
+                                },
+                            );
+                        }
+                        send_event(&world, timestamp, CursorLeft { id: window_id });
                     }
-                    WindowEvent::MouseWheel { delta, .. } => match delta {
-                        event::MouseScrollDelta::LineDelta(x, y) => {
-                            let mut mouse_wheel_input_events =
-                                world.get_resource_mut::<Events<MouseWheel>>().unwrap();
-                            mouse_wheel_input_events.send(MouseWheel {
-                                unit: MouseScrollUnit::Line,
-                                x,
-                                y,
-                            });
+                    WindowEvent::MouseWheel { delta, phase, .. } => {
+                        let phase = converters::convert_touch_phase(phase);
+                        let momentum = match phase {
+                            TouchPhase::Started | TouchPhase::Cancelled => {
+                                scroll_momentum.remove(&window_id);
+                                false
+                            }
+                            TouchPhase::Ended => {
+                                if cfg!(target_os = "macos") {
+                                    scroll_momentum.insert(window_id);
+                                }
+                                false
+                            }
+                            TouchPhase::Moved => {
+                                // the momentum scrolling ends with an event without movement
+                                let stopped = match delta {
+                                    event::MouseScrollDelta::LineDelta(x, y) => {
+                                        x == 0.0 && y == 0.0
+                                    }
+                                    event::MouseScrollDelta::PixelDelta(p) => {
+                                        p.x == 0.0 && p.y == 0.0
+                                    }
+                                };
+                                if stopped {
+                                    scroll_momentum.remove(&window_id)
+                                } else {
+                                    scroll_momentum.contains(&window_id)
+                                }
+                            }
+                        };
+                        if !accepts_input {
+                            return;
                         }
-                        event::MouseScrollDelta::PixelDelta(p) => {
-                            let mut mouse_wheel_input_events =
-                                world.get_resource_mut::<Events<MouseWheel>>().unwrap();
-                            mouse_wheel_input_events.send(MouseWheel {
-                                unit: MouseScrollUnit::Pixel,
-                                x: p.x as f32,
-                                y: p.y as f32,
-                            });
+                        match delta {
+                            event::MouseScrollDelta::LineDelta(x, y) => {
+                                send_event(
+                                    &world,
+                                    timestamp,
+                                    MouseWheel {
+                                        unit: MouseScrollUnit::Line,
+                                        x,
+                                        y,
+                                        phase,
+                                        momentum,
+                                    },
+                                );
+                            }
+                            event::MouseScrollDelta::PixelDelta(p) => {
+                                send_event(
+                                    &world,
+                                    timestamp,
+                                    MouseWheel {
+                                        unit: MouseScrollUnit::Pixel,
+                                        x: p.x as f32,
+                                        y: p.y as f32,
+                                        phase,
+                                        momentum,
+                                    },
+                                );
+                            }
                         }
-                    },
-                    WindowEvent::Touch(touch) => {
-                        let mut touch_input_events =
-                            world.get_resource_mut::<Events<TouchInput>>().unwrap();
-
-                        let winit_window = winit_windows.get_window(window_id).unwrap();
-                        let mut location = touch.location.to_logical(winit_window.scale_factor());
+                    }
+                    WindowEvent::ReceivedCharacter(c) if accepts_input => {
+                        send_event(
+                            &world,
+                            timestamp,
+                            ReceivedCharacter {
+                                id: window_id,
+                                char: c,
+                            },
+                        );
 
-                        // On a mobile window, the start is from the top while on PC/Linux/OSX from
-                        // bottom
-                        if cfg!(target_os = "android") || cfg!(target_os = "ios") {
-                            let window_height = windows.get_primary().unwrap().height();
-                            location.y = window_height - location.y;
+                        let key_repeat = world
+                            .get_resource::<TextInputSettings>()
+                            .is_none_or(|settings| settings.key_repeat);
+                        if bevy_window::is_text_character(c)
+                            && (key_repeat || !pressed_keys.last_key_repeat)
+                        {
+                            text_inputs
+                                .entry(window_id)
+                                .or_insert_with(|| (String::new(), timestamp))
+                                .0
+                                .push(c);
                         }
-                        touch_input_events.send(converters::convert_touch_input(touch, location));
-                    }
-                    WindowEvent::ReceivedCharacter(c) => {
-                        let mut char_input_events = world
-                            .get_resource_mut::<Events<ReceivedCharacter>>()
-                            .unwrap();
-
-                        char_input_events.send(ReceivedCharacter {
-                            id: window_id,
-                            char: c,
-                        })
                     }
                     WindowEvent::ScaleFactorChanged {
                         scale_factor,
                         new_inner_size,
                     } => {
-                        let mut backend_scale_factor_change_events = world
-                            .get_resource_mut::<Events<WindowBackendScaleFactorChanged>>()
-                            .unwrap();
-                        backend_scale_factor_change_events.send(WindowBackendScaleFactorChanged {
-                            id: window_id,
-                            scale_factor,
-                        });
-                        #[allow(clippy::float_cmp)]
-                        if window.scale_factor() != scale_factor {
-                            let mut scale_factor_change_events = world
-                                .get_resource_mut::<Events<WindowScaleFactorChanged>>()
-                                .unwrap();
-
-                            scale_factor_change_events.send(WindowScaleFactorChanged {
+                        send_event(
+                            &world,
+                            timestamp,
+                            WindowBackendScaleFactorChanged {
                                 id: window_id,
                                 scale_factor,
-                            });
+                            },
+                        );
+                        #[allow(clippy::float_cmp)]
+                        if window.scale_factor() != scale_factor {
+                            send_event(
+                                &world,
+                                timestamp,
+                                WindowScaleFactorChanged {
+                                    id: window_id,
+                                    scale_factor,
+                                },
+                            );
                         }
 
+                        let (width, height) =
+                            window.physical_size_for_backend_scale_factor(scale_factor);
+                        *new_inner_size = winit::dpi::PhysicalSize::new(width, height);
                         window.update_scale_factor_from_backend(scale_factor);
 
                         if window.physical_width() != new_inner_size.width
                             || window.physical_height() != new_inner_size.height
                         {
-                            let mut resize_events =
-                                world.get_resource_mut::<Events<WindowResized>>().unwrap();
-                            resize_events.send(WindowResized {
-                                id: window_id,
-                                width: window.width(),
-                                height: window.height(),
-                            });
+                            send_event(
+                                &world,
+                                timestamp,
+                                WindowResized {
+                                    id: window_id,
+                                    width: window.width(),
+                                    height: window.height(),
+                                },
+                            );
                         }
                         window.update_actual_size_from_backend(
                             new_inner_size.width,
@@ -427,42 +559,74 @@ pub fn winit_runner_with(mut app: App, mut event_loop: EventLoop<()>) {
                     }
                     WindowEvent::Focused(focused) => {
                         window.update_focused_status_from_backend(focused);
-                        let mut focused_events =
-                            world.get_resource_mut::<Events<WindowFocused>>().unwrap();
-                        focused_events.send(WindowFocused {
-                            id: window_id,
-                            focused,
-                        });
+                        // some platforms, such as X11, keep requesting the attention until the
+                        // request is cleared
+                        if focused && window.attention().is_some() {
+                            let winit_window = winit_windows.get_window(window_id).unwrap();
+                            winit_window.request_user_attention(None);
+                            window.clear_attention_from_backend();
+                        }
+                        send_event(
+                            &world,
+                            timestamp,
+                            WindowFocused {
+                                id: window_id,
+                                focused,
+                            },
+                        );
+                        // modifiers released in another window are not reported to this one
+                        if !focused {
+                            pressed_keys.scan_codes.clear();
+                            send_event(
+                                &world,
+                                timestamp,
+                                ModifiersChanged(ModifierKeys::default()),
+                            );
+                        }
                     }
                     WindowEvent::DroppedFile(path_buf) => {
-                        let mut events =
-                            world.get_resource_mut::<Events<FileDragAndDrop>>().unwrap();
-                        events.send(FileDragAndDrop::DroppedFile {
-                            id: window_id,
-                            path_buf,
-                        });
+                        dropped_files
+                            .entry(window_id)
+                            .or_insert_with(|| (Vec::new(), window.cursor_position(), timestamp))
+                            .0
+                            .push(path_buf.clone());
+                        send_event(
+                            &world,
+                            timestamp,
+                            FileDragAndDrop::DroppedFile {
+                                id: window_id,
+                                path_buf,
+                            },
+                        );
                     }
                     WindowEvent::HoveredFile(path_buf) => {
-                        let mut events =
-                            world.get_resource_mut::<Events<FileDragAndDrop>>().unwrap();
-                        events.send(FileDragAndDrop::HoveredFile {
-                            id: window_id,
-                            path_buf,
-                        });
+                        send_event(
+                            &world,
+                            timestamp,
+                            FileDragAndDrop::HoveredFile {
+                                id: window_id,
+                                path_buf,
+                            },
+                        );
                     }
                     WindowEvent::HoveredFileCancelled => {
-                        let mut events =
-                            world.get_resource_mut::<Events<FileDragAndDrop>>().unwrap();
-                        events.send(FileDragAndDrop::HoveredFileCancelled { id: window_id });
+                        send_event(
+                            &world,
+                            timestamp,
+                            FileDragAndDrop::HoveredFileCancelled { id: window_id },
+                        );
                     }
                     WindowEvent::Moved(position) => {
                         let position = ivec2(position.x, position.y);
                         window.update_actual_position_from_backend(position);
-                        let mut events = world.get_resource_mut::<Events<WindowMoved>>().unwrap();
-                        events.send(WindowMoved {
-                            id: window_id,
-                            position,
-                        });
+                        send_event(
+                            &world,
+                            timestamp,
+                            WindowMoved {
+                                id: window_id,
+                                position,
+                            },
+                        );
                     }
                     _ => {}
                 }
@@ -471,11 +635,17 @@ pub fn winit_runner_with(mut app: App, mut event_loop: EventLoop<()>) {
                 event: DeviceEvent::MouseMotion { delta },
                 ..
             } => {
-                let mut mouse_motion_events =
-                    app.world.get_resource_mut::<Events<MouseMotion>>().unwrap();
-                mouse_motion_events.send(MouseMotion {
-                    delta: Vec2::new(delta.0 as f32, delta.1 as f32),
-                });
+                send_event(
+                    &app.world.cell(),
+                    timestamp,
+                    MouseMotion {
+                        delta: Vec2::new(delta.0 as f32, delta.1 as f32),
+                    },
+                );
+            }
+            event::Event::UserEvent(()) => {
+                // sent through the `EventLoopProxy` to update the app
+                low_power_event = true;
             }
             event::Event::Suspended => {
                 active = false;
@@ -484,13 +654,87 @@ pub fn winit_runner_with(mut app: App, mut event_loop: EventLoop<()>) {
                 active = true;
             }
             event::Event::MainEventsCleared => {
+                {
+                    let world = app.world.cell();
+                    // coalesced events keep the timestamp of the winit event they came from
+                    for (id, (text, timestamp)) in text_inputs.drain() {
+                        send_event(&world, timestamp, TextInputEvent { id, text });
+                    }
+                    for (id, (position, timestamp)) in coalesced_cursor_positions.drain() {
+                        send_event(&world, timestamp, CursorMoved { id, position });
+                    }
+                    for (id, (paths, position, timestamp)) in dropped_files.drain() {
+                        send_event(
+                            &world,
+                            timestamp,
+                            FilesDropped {
+                                id,
+                                paths,
+                                position,
+                            },
+                        );
+                    }
+                }
                 handle_create_window_events(
                     &mut app.world,
                     event_loop,
                     &mut create_window_event_reader,
                 );
-                if active {
+                // the time spent in the update of the app is measured by the frame time
+                // diagnostics
+                let event_handler_time = event_handler_timer.finish();
+                if let Some(mut stats) = app.world.get_resource_mut::<WindowBackendStats>() {
+                    stats.window_events = window_events;
+                    stats.event_handler_time = event_handler_time;
+                }
+                window_events = 0;
+                let update = active
+                    && match update_mode(&app.world) {
+                        UpdateMode::Continuous | UpdateMode::Reactive { .. } => true,
+                        UpdateMode::ReactiveLowPower { .. } => {
+                            low_power_event || redraw_requested || timeout_reached
+                        }
+                    };
+                let frame_time_elapsed = min_frame_time(&app.world)
+                    .is_none_or(|frame_time| last_update.elapsed() >= frame_time);
+                update_delayed = update && !frame_time_elapsed;
+                if update && frame_time_elapsed {
+                    low_power_event = false;
+                    last_update = Instant::now();
                     app.update();
+                    frame += 1;
+                }
+            }
+            event::Event::RedrawEventsCleared => {
+                // after the update, to see the redraw requests sent during it
+                redraw_requested = app
+                    .world
+                    .get_resource::<Events<RequestRedraw>>()
+                    .is_some_and(|redraw_events| {
+                        redraw_event_reader.iter(redraw_events).last().is_some()
+                    });
+                if *control_flow != ControlFlow::Exit {
+                    *control_flow = match update_mode(&app.world) {
+                        _ if redraw_requested => ControlFlow::Poll,
+                        UpdateMode::Continuous => ControlFlow::Poll,
+                        UpdateMode::Reactive { max_wait }
+                        | UpdateMode::ReactiveLowPower { max_wait } => match max_wait {
+                            Some(max_wait) => ControlFlow::WaitUntil(last_update + max_wait),
+                            None => ControlFlow::Wait,
+                        },
+                    };
+                    if let Some(frame_time) = min_frame_time(&app.world) {
+                        // wait for the end of the frame time instead of polling, or instead of
+                        // waiting for another event when an update was skipped
+                        let next_update = last_update + frame_time;
+                        if update_delayed || *control_flow == ControlFlow::Poll {
+                            *control_flow = if next_update > Instant::now() {
+                                ControlFlow::WaitUntil(next_update)
+                            } else {
+                                ControlFlow::Poll
+                            };
+                        }
+                    }
                 }
             }
             _ => (),
@@ -503,6 +747,251 @@ pub fn winit_runner_with(mut app: App, mut event_loop: EventLoop<()>) {
     }
 }
 
+/// How often the power state of the device is checked.
+const POWER_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How often the monitors are checked.
+const MONITOR_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Adds the time spent handling an event of the platform to a total when dropped, so that the
+/// events handled with an early return are measured too.
+struct EventHandlerTimer<'a> {
+    start: Instant,
+    total: &'a Cell<Duration>,
+}
+
+impl EventHandlerTimer<'_> {
+    /// Takes the total, including the time spent on the current event so far, and stops measuring
+    /// the current event.
+    fn finish(self) -> Duration {
+        let total = self.total.take() + self.start.elapsed();
+        std::mem::forget(self);
+        total
+    }
+}
+
+impl Drop for EventHandlerTimer<'_> {
+    fn drop(&mut self) {
+        self.total.set(self.total.get() + self.start.elapsed());
+    }
+}
+
+/// Moves the cursor back to the center of `winit_window` after it moved to `position`, on the
+/// platforms where [`CursorGrabMode::Locked`] is emulated.
+fn recenter_locked_cursor(
+    window: &Window,
+    winit_window: &winit::window::Window,
+    position: PhysicalPosition<f64>,
+) {
+    if window.cursor_grab_mode() == CursorGrabMode::Locked && converters::emulates_cursor_lock() {
+        // the cursor is confined, and moved back to the center of the window
+        let size = winit_window.inner_size();
+        let center = PhysicalPosition::new(size.width / 2, size.height / 2);
+        if position.cast::<u32>() != center {
+            // fails on Wayland, where the cursor stays confined
+            let _ = winit_window.set_cursor_position(center);
+        }
+    }
+}
+
+/// The keys held down, as winit doesn't report key repeats: a key pressed again before being
+/// released is repeating.
+#[derive(Default)]
+struct PressedKeys {
+    scan_codes: HashSet<u32>,
+    /// Whether the last key pressed is repeating, for the characters it produces, until a key is
+    /// released.
+    last_key_repeat: bool,
+}
+
+/// Sends the keyboard, mouse button and touch input received by `window`, according to
+/// [`Window::accepts_input`].
+///
+/// Only the presses are dropped: the releases are always sent, so that the keys, buttons and
+/// touches pressed before the window stopped accepting input don't stay pressed.
+fn send_routed_input(
+    world: &WorldCell,
+    window: &Window,
+    timestamp: Option<EventTimestamp>,
+    pressed_keys: &mut PressedKeys,
+    event: &WindowEvent,
+) {
+    let accepts_input = window.accepts_input();
+    match *event {
+        WindowEvent::KeyboardInput {
+            ref input,
+            is_synthetic,
+            ..
+        } => {
+            let repeat = match input.state {
+                event::ElementState::Pressed => {
+                    pressed_keys.last_key_repeat = !pressed_keys.scan_codes.insert(input.scancode);
+                    pressed_keys.last_key_repeat
+                }
+                event::ElementState::Released => {
+                    pressed_keys.scan_codes.remove(&input.scancode);
+                    pressed_keys.last_key_repeat = false;
+                    false
+                }
+            };
+            if accepts_input || input.state == event::ElementState::Released {
+                send_event(
+                    world,
+                    timestamp,
+                    converters::convert_keyboard_input(input, repeat, is_synthetic),
+                );
+            }
+        }
+        WindowEvent::MouseInput { state, button, .. }
+            if accepts_input || state == event::ElementState::Released =>
+        {
+            send_event(
+                world,
+                timestamp,
+                MouseButtonInput {
+                    button: converters::convert_mouse_button(button),
+                    state: converters::convert_element_state(state),
+                },
+            );
+        }
+        WindowEvent::Touch(touch) if accepts_input || touch.phase != event::TouchPhase::Started => {
+            let mut location = touch.location.to_logical(window.backend_scale_factor());
+
+            // On a mobile window, the start is from the top while on PC/Linux/OSX from bottom
+            if cfg!(target_os = "android") || cfg!(target_os = "ios") {
+                location.y = window.height() - location.y;
+            }
+            send_event(
+                world,
+                timestamp,
+                converters::convert_touch_input(touch, location),
+            );
+        }
+        _ => {}
+    }
+}
+
+/// Sends an event, and its [`Timestamped`] copy if the timestamps are enabled.
+fn send_event<T: Component + Clone>(
+    world: &WorldCell,
+    timestamp: Option<EventTimestamp>,
+    event: T,
+) {
+    if let Some(timestamp) = timestamp {
+        let mut events = world.get_resource_mut::<Events<Timestamped<T>>>().unwrap();
+        events.send(Timestamped {
+            event: event.clone(),
+            timestamp,
+        });
+    }
+    let mut events = world.get_resource_mut::<Events<T>>().unwrap();
+    events.send(event);
+}
+
+/// A monitor, whether it is the primary one, and its position, size and scale factor: the
+/// properties checked to know whether the monitors changed.
+type MonitorLayout = (
+    MonitorHandle,
+    bool,
+    PhysicalPosition<i32>,
+    PhysicalSize<u32>,
+    f64,
+);
+
+/// Updates the [`Monitors`] when the monitors or their layout changed since `layout` was taken.
+fn update_monitors(
+    world: &mut World,
+    event_loop: &EventLoopWindowTarget<()>,
+    layout: &mut Vec<MonitorLayout>,
+) {
+    let primary_monitor = event_loop.primary_monitor();
+    let monitors = event_loop.available_monitors().collect::<Vec<_>>();
+    // listing the video modes of the monitors is slow on some platforms, so it is only done when
+    // the monitors changed
+    let current_layout = monitors
+        .iter()
+        .map(|monitor| {
+            (
+                monitor.clone(),
+                Some(monitor) == primary_monitor.as_ref(),
+                monitor.position(),
+                monitor.size(),
+                monitor.scale_factor(),
+            )
+        })
+        .collect::<Vec<_>>();
+    if *layout == current_layout {
+        return;
+    }
+    *layout = current_layout;
+    let primary = monitors
+        .iter()
+        .position(|monitor| Some(monitor) == primary_monitor.as_ref());
+    let monitors = monitors.iter().map(converters::convert_monitor).collect();
+    let (disconnected, connected) = world
+        .get_resource_mut::<Monitors>()
+        .unwrap()
+        .update_from_backend(monitors, primary);
+    if !disconnected.is_empty() {
+        world
+            .get_resource_mut::<Events<MonitorDisconnected>>()
+            .unwrap()
+            .extend(disconnected);
+    }
+    if !connected.is_empty() {
+        world
+            .get_resource_mut::<Events<MonitorConnected>>()
+            .unwrap()
+            .extend(connected);
+    }
+}
+
+fn update_power_state(world: &mut World) {
+    let state = match power::power_state() {
+        Some(state) => state,
+        None => return,
+    };
+    let mut power_state = world.get_resource_mut::<PowerState>().unwrap();
+    if *power_state != state {
+        *power_state = state;
+        world
+            .get_resource_mut::<Events<PowerStateChanged>>()
+            .unwrap()
+            .send(PowerStateChanged { state });
+    }
+}
+
+fn is_low_power(world: &World) -> bool {
+    world
+        .get_resource::<PowerState>()
+        .is_some_and(|power_state| power_state.is_low_power())
+}
+
+/// The update mode of the [`WinitConfig`] for the current focus of the windows and power state
+/// of the device.
+fn update_mode(world: &World) -> UpdateMode {
+    let low_power = is_low_power(world);
+    world
+        .get_resource::<WinitConfig>()
+        .map_or(UpdateMode::Continuous, |config| match config.battery_mode {
+            Some(battery_mode) if low_power => battery_mode,
+            _ => match world.get_resource::<Windows>() {
+                Some(windows) => config.update_mode_for_windows(windows),
+                None => *config.update_mode(false),
+            },
+        })
+}
+
+/// The minimum time between two updates, when the frame rate is capped to save power.
+fn min_frame_time(world: &World) -> Option<Duration> {
+    if !is_low_power(world) {
+        return None;
+    }
+    world
+        .get_resource::<WinitConfig>()
+        .and_then(|config| config.battery_frame_time)
+}
+
 fn handle_create_window_events(
     world: &mut World,
     event_loop: &EventLoopWindowTarget<()>,
@@ -514,14 +1003,178 @@ fn handle_create_window_events(
     let create_window_events = world.get_resource::<Events<CreateWindow>>().unwrap();
     let mut window_created_events = world.get_resource_mut::<Events<WindowCreated>>().unwrap();
     for create_window_event in create_window_event_reader.iter(&create_window_events) {
-        let window = winit_windows.create_window(
-            event_loop,
-            create_window_event.id,
-            &create_window_event.descriptor,
-        );
+        #[cfg(all(target_arch = "wasm32", feature = "web_storage"))]
+        let descriptor = &web::restore_window_descriptor(&create_window_event.descriptor);
+        #[cfg(not(all(target_arch = "wasm32", feature = "web_storage")))]
+        let descriptor = &create_window_event.descriptor;
+        if descriptor.offscreen {
+            let scale_factor = descriptor.scale_factor_override.unwrap_or(1.0);
+            windows.add(Window::new(
+                create_window_event.id,
+                descriptor,
+                (descriptor.width as f64 * scale_factor) as u32,
+                (descriptor.height as f64 * scale_factor) as u32,
+                1.0,
+                None,
+            ));
+            window_created_events.send(WindowCreated {
+                id: create_window_event.id,
+            });
+            continue;
+        }
+        let window = winit_windows.create_window(event_loop, create_window_event.id, descriptor);
         windows.add(window);
+        if let Some(violation) = descriptor
+            .resize_constraints
+            .violation(create_window_event.id)
+        {
+            let mut violation_events = world
+                .get_resource_mut::<Events<WindowConstraintViolation>>()
+                .unwrap();
+            violation_events.send(violation);
+        }
+        startup_notification::complete_startup(
+            winit_windows.get_window(create_window_event.id).unwrap(),
+        );
+        #[cfg(target_arch = "wasm32")]
+        {
+            use winit::platform::web::WindowExtWebSys;
+
+            let canvas = winit_windows
+                .get_window(create_window_event.id)
+                .unwrap()
+                .canvas();
+            web::apply_canvas_options(&canvas, &create_window_event.descriptor.canvas_options);
+            if let Some(canvas_resize) = &create_window_event.descriptor.canvas_resize {
+                world
+                    .get_resource_mut::<web::CanvasResizeObservers>()
+                    .unwrap()
+                    .observe(create_window_event.id, &canvas, canvas_resize);
+            }
+        }
         window_created_events.send(WindowCreated {
             id: create_window_event.id,
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{send_routed_input, PressedKeys};
+    use bevy_app::App;
+    use bevy_input::{keyboard::KeyCode, mouse::MouseButton, Input, InputPlugin};
+    use bevy_window::{InputRouting, Window, WindowDescriptor, WindowId};
+    use winit::event::{
+        DeviceId, ElementState, KeyboardInput, ModifiersState, MouseButton as WinitMouseButton,
+        VirtualKeyCode, WindowEvent,
+    };
+
+    fn mouse_input(state: ElementState) -> WindowEvent<'static> {
+        #[allow(deprecated)]
+        WindowEvent::MouseInput {
+            // SAFETY: the device id is not passed to winit
+            device_id: unsafe { DeviceId::dummy() },
+            state,
+            button: WinitMouseButton::Left,
+            modifiers: ModifiersState::empty(),
+        }
+    }
+
+    fn keyboard_input(state: ElementState) -> WindowEvent<'static> {
+        #[allow(deprecated)]
+        WindowEvent::KeyboardInput {
+            // SAFETY: the device id is not passed to winit
+            device_id: unsafe { DeviceId::dummy() },
+            input: KeyboardInput {
+                scancode: 30,
+                state,
+                virtual_keycode: Some(VirtualKeyCode::A),
+                modifiers: ModifiersState::empty(),
+            },
+            is_synthetic: false,
+        }
+    }
+
+    #[test]
+    fn route_input_to_the_focused_window() {
+        let mut app = App::new();
+        app.add_plugin(InputPlugin);
+        let mut window = Window::new(
+            WindowId::primary(),
+            &WindowDescriptor {
+                input_routing: InputRouting::FocusedWindow,
+                ..Default::default()
+            },
+            1280,
+            720,
+            1.0,
+            None,
+        );
+        window.update_focused_status_from_backend(false);
+        let mut pressed_keys = PressedKeys::default();
+        let mut receive = |window: &Window, event: WindowEvent| {
+            send_routed_input(&app.world.cell(), window, None, &mut pressed_keys, &event);
+            app.update();
+            let mouse_buttons = app.world.get_resource::<Input<MouseButton>>().unwrap();
+            let keys = app.world.get_resource::<Input<KeyCode>>().unwrap();
+            (
+                mouse_buttons.pressed(MouseButton::Left),
+                keys.pressed(KeyCode::A),
+            )
+        };
+
+        // presses are dropped while the window is not focused
+        assert_eq!(
+            receive(&window, mouse_input(ElementState::Pressed)),
+            (false, false)
+        );
+        assert_eq!(
+            receive(&window, keyboard_input(ElementState::Pressed)),
+            (false, false)
+        );
+
+        window.update_focused_status_from_backend(true);
+        assert_eq!(
+            receive(&window, mouse_input(ElementState::Pressed)),
+            (true, false)
+        );
+        assert_eq!(
+            receive(&window, keyboard_input(ElementState::Pressed)),
+            (true, true)
+        );
+
+        // releases are sent after the window lost the focus, so that nothing stays pressed
+        window.update_focused_status_from_backend(false);
+        assert_eq!(
+            receive(&window, mouse_input(ElementState::Released)),
+            (false, true)
+        );
+        assert_eq!(
+            receive(&window, keyboard_input(ElementState::Released)),
+            (false, false)
+        );
+    }
+
+    #[test]
+    fn key_repeat_ends_on_release() {
+        let mut app = App::new();
+        app.add_plugin(InputPlugin);
+        let window = Window::new(
+            WindowId::primary(),
+            &WindowDescriptor::default(),
+            1280,
+            720,
+            1.0,
+            None,
+        );
+        let mut pressed_keys = PressedKeys::default();
+        let mut receive = |event: WindowEvent| {
+            send_routed_input(&app.world.cell(), &window, None, &mut pressed_keys, &event);
+            pressed_keys.last_key_repeat
+        };
+
+        assert!(!receive(keyboard_input(ElementState::Pressed)));
+        assert!(receive(keyboard_input(ElementState::Pressed)));
+        assert!(!receive(keyboard_input(ElementState::Released)));
+    }
+}
@@ -0,0 +1,94 @@
+use bevy_window::ScreenOrientation;
+
+#[cfg(target_os = "ios")]
+pub(crate) use platform::convert_valid_orientations;
+
+/// Restricts the orientations of the screen that `window` is displayed in. Does nothing on the
+/// platforms without a screen orientation.
+pub(crate) fn set_screen_orientation(
+    window: &winit::window::Window,
+    orientation: ScreenOrientation,
+) -> Result<(), String> {
+    platform::set_screen_orientation(window, orientation)
+}
+
+#[cfg(target_os = "ios")]
+mod platform {
+    use bevy_window::ScreenOrientation;
+    use winit::platform::ios::{ValidOrientations, WindowExtIOS};
+
+    pub(crate) fn convert_valid_orientations(orientation: ScreenOrientation) -> ValidOrientations {
+        match orientation {
+            ScreenOrientation::Auto => ValidOrientations::LandscapeAndPortrait,
+            ScreenOrientation::Portrait
+            | ScreenOrientation::PortraitPrimary
+            | ScreenOrientation::PortraitSecondary => ValidOrientations::Portrait,
+            ScreenOrientation::Landscape
+            | ScreenOrientation::LandscapePrimary
+            | ScreenOrientation::LandscapeSecondary => ValidOrientations::Landscape,
+        }
+    }
+
+    pub(super) fn set_screen_orientation(
+        window: &winit::window::Window,
+        orientation: ScreenOrientation,
+    ) -> Result<(), String> {
+        window.set_valid_orientations(convert_valid_orientations(orientation));
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "android")]
+mod platform {
+    use bevy_window::ScreenOrientation;
+    use jni::{objects::JObject, objects::JValue, JavaVM};
+
+    /// The `SCREEN_ORIENTATION_*` constants of `android.content.pm.ActivityInfo`.
+    fn convert_orientation(orientation: ScreenOrientation) -> i32 {
+        match orientation {
+            ScreenOrientation::Auto => -1,
+            ScreenOrientation::Portrait => 7,
+            ScreenOrientation::Landscape => 6,
+            ScreenOrientation::PortraitPrimary => 1,
+            ScreenOrientation::PortraitSecondary => 9,
+            ScreenOrientation::LandscapePrimary => 0,
+            ScreenOrientation::LandscapeSecondary => 8,
+        }
+    }
+
+    pub(super) fn set_screen_orientation(
+        _window: &winit::window::Window,
+        orientation: ScreenOrientation,
+    ) -> Result<(), String> {
+        let activity = ndk_glue::native_activity();
+        // SAFETY: the virtual machine stays valid for the lifetime of the activity
+        let vm =
+            unsafe { JavaVM::from_raw(activity.vm() as *mut _) }.map_err(|err| err.to_string())?;
+        let env = vm.attach_current_thread().map_err(|err| err.to_string())?;
+        env.call_method(
+            JObject::from(activity.activity() as jni::sys::jobject),
+            "setRequestedOrientation",
+            "(I)V",
+            &[JValue::Int(convert_orientation(orientation))],
+        )
+        .map_err(|err| err.to_string())?;
+        Ok(())
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+mod platform {
+    pub(super) use crate::web::set_screen_orientation;
+}
+
+#[cfg(not(any(target_os = "ios", target_os = "android", target_arch = "wasm32")))]
+mod platform {
+    use bevy_window::ScreenOrientation;
+
+    pub(super) fn set_screen_orientation(
+        _window: &winit::window::Window,
+        _orientation: ScreenOrientation,
+    ) -> Result<(), String> {
+        Ok(())
+    }
+}
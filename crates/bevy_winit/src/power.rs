@@ -0,0 +1,65 @@
+use bevy_window::PowerState;
+
+/// The current power state of the device, or `None` if it is unknown on this platform.
+pub(crate) fn power_state() -> Option<PowerState> {
+    platform::power_state()
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use bevy_window::PowerState;
+    use winapi::um::winbase::{GetSystemPowerStatus, SYSTEM_POWER_STATUS};
+
+    pub(super) fn power_state() -> Option<PowerState> {
+        // SAFETY: the status is a plain struct filled by the call
+        let status = unsafe {
+            let mut status: SYSTEM_POWER_STATUS = std::mem::zeroed();
+            if GetSystemPowerStatus(&mut status) == 0 {
+                return None;
+            }
+            status
+        };
+        Some(PowerState {
+            on_battery: status.ACLineStatus == 0,
+            // named `SystemStatusFlag` in the current Windows headers
+            power_saving: status.Reserved1 == 1,
+        })
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use bevy_window::PowerState;
+    use std::fs;
+
+    const POWER_SUPPLIES: &str = "/sys/class/power_supply";
+
+    pub(super) fn power_state() -> Option<PowerState> {
+        let mut has_mains = false;
+        let mut mains_online = false;
+        for entry in fs::read_dir(POWER_SUPPLIES).ok()?.flatten() {
+            let path = entry.path();
+            match fs::read_to_string(path.join("type")) {
+                Ok(supply_type) if supply_type.trim() == "Mains" => {}
+                _ => continue,
+            }
+            has_mains = true;
+            mains_online |=
+                fs::read_to_string(path.join("online")).is_ok_and(|online| online.trim() == "1");
+        }
+        // desktops without a battery don't always report their power supply
+        Some(PowerState {
+            on_battery: has_mains && !mains_online,
+            power_saving: false,
+        })
+    }
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+mod platform {
+    use bevy_window::PowerState;
+
+    pub(super) fn power_state() -> Option<PowerState> {
+        None
+    }
+}
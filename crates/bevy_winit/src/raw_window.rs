@@ -0,0 +1,66 @@
+use crate::WinitWindows;
+use bevy_ecs::{
+    system::{Command, Commands},
+    world::World,
+};
+use bevy_window::WindowId;
+
+type RawWindowClosure = Box<dyn FnOnce(&winit::window::Window) + Send + Sync>;
+
+/// The closures of [`RawWindowCommands::with_raw_window`] waiting to run.
+#[derive(Default)]
+pub struct RawWindowRequests {
+    requests: Vec<(WindowId, RawWindowClosure)>,
+}
+
+impl RawWindowRequests {
+    /// Runs the closures whose window exists, and drops the others.
+    pub(crate) fn run(&mut self, winit_windows: &WinitWindows) {
+        for (id, closure) in self.requests.drain(..) {
+            if let Some(window) = winit_windows.get_window(id) {
+                closure(window);
+            }
+        }
+    }
+}
+
+pub struct WithRawWindow {
+    id: WindowId,
+    closure: RawWindowClosure,
+}
+
+impl Command for WithRawWindow {
+    fn write(self, world: &mut World) {
+        let mut requests = world.get_resource_mut::<RawWindowRequests>().unwrap();
+        requests.requests.push((self.id, self.closure));
+    }
+}
+
+pub trait RawWindowCommands {
+    /// Runs `closure` with the winit window of the window `id`, to use the features of winit that
+    /// have no equivalent in [`Window`](bevy_window::Window).
+    ///
+    /// The closure runs on the main thread after the commands of the
+    /// [`Window`](bevy_window::Window)s are applied, in `CoreStage::PostUpdate`. It doesn't run
+    /// if the window doesn't exist or has no winit window, such as an offscreen window. Changes
+    /// made to the winit window are not reflected in the [`Window`](bevy_window::Window), which
+    /// may undo them.
+    fn with_raw_window(
+        &mut self,
+        id: WindowId,
+        closure: impl FnOnce(&winit::window::Window) + Send + Sync + 'static,
+    );
+}
+
+impl<'w, 's> RawWindowCommands for Commands<'w, 's> {
+    fn with_raw_window(
+        &mut self,
+        id: WindowId,
+        closure: impl FnOnce(&winit::window::Window) + Send + Sync + 'static,
+    ) {
+        self.add(WithRawWindow {
+            id,
+            closure: Box::new(closure),
+        });
+    }
+}
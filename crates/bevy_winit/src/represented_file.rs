@@ -0,0 +1,37 @@
+use std::path::Path;
+
+/// Sets the file edited in `window`, shown as an icon next to its title. Does nothing on the
+/// platforms other than macOS.
+pub(crate) fn set_represented_file(window: &winit::window::Window, path: Option<&Path>) {
+    platform::set_represented_file(window, path);
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use objc::{class, msg_send, runtime::Object, sel, sel_impl};
+    use std::{ffi::CString, path::Path};
+    use winit::platform::macos::WindowExtMacOS;
+
+    pub(super) fn set_represented_file(window: &winit::window::Window, path: Option<&Path>) {
+        // an empty path removes the icon
+        let path = path.map(|path| path.to_string_lossy()).unwrap_or_default();
+        let path = match CString::new(path.as_bytes()) {
+            Ok(path) => path,
+            Err(_) => return,
+        };
+        let ns_window = window.ns_window() as *mut Object;
+        // SAFETY: the window commands are applied on the main thread, which owns the `NSWindow`
+        unsafe {
+            let ns_path: *mut Object =
+                msg_send![class!(NSString), stringWithUTF8String: path.as_ptr()];
+            let _: () = msg_send![ns_window, setRepresentedFilename: ns_path];
+        }
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+mod platform {
+    use std::path::Path;
+
+    pub(super) fn set_represented_file(_window: &winit::window::Window, _path: Option<&Path>) {}
+}
@@ -0,0 +1,45 @@
+/// Shows or hides the on-screen keyboard. Does nothing on the platforms without one.
+pub(crate) fn set_soft_keyboard_visible(visible: bool) {
+    #[cfg(target_os = "android")]
+    {
+        let activity = ndk_glue::native_activity();
+        if visible {
+            activity.show_soft_input(false);
+        } else {
+            activity.hide_soft_input(false);
+        }
+    }
+    #[cfg(not(target_os = "android"))]
+    let _ = visible;
+}
+
+/// Updates the part of the windows covered by the on-screen keyboard.
+///
+/// The activity is resized to the part of the screen that isn't covered by the keyboard, which
+/// is reported as its content rect.
+#[cfg(target_os = "android")]
+#[allow(clippy::float_cmp)]
+pub(crate) fn soft_keyboard_inset_system(
+    mut windows: bevy_ecs::system::ResMut<bevy_window::Windows>,
+) {
+    let content_rect = ndk_glue::content_rect();
+    if content_rect.bottom == 0 {
+        // not reported yet
+        return;
+    }
+    let changed_insets = windows
+        .iter()
+        .filter_map(|window| {
+            let inset = window.physical_height().saturating_sub(content_rect.bottom);
+            let inset = (inset as f64 / window.scale_factor()) as f32;
+            (window.soft_keyboard_inset() != inset).then(|| (window.id(), inset))
+        })
+        .collect::<Vec<_>>();
+    // only borrow the windows mutably on changes, to keep their change detection meaningful
+    for (id, inset) in changed_insets {
+        windows
+            .get_mut(id)
+            .unwrap()
+            .update_soft_keyboard_inset_from_backend(inset);
+    }
+}
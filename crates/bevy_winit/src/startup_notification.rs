@@ -0,0 +1,148 @@
+/// Tells the desktop that the app has started, once its first window is created, so that the
+/// launcher stops showing a busy cursor and gives the focus to the window.
+pub(crate) fn complete_startup(window: &winit::window::Window) {
+    platform::complete_startup(window);
+}
+
+#[cfg(all(
+    feature = "x11",
+    any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    )
+))]
+mod platform {
+    use bevy_utils::tracing::warn;
+    use std::{
+        env,
+        ffi::CString,
+        mem,
+        os::raw::{c_char, c_int, c_uint, c_ulong},
+        ptr,
+    };
+    use winit::platform::unix::WindowExtUnix;
+    use x11_dl::xlib;
+
+    /// Implements the startup notification protocol of freedesktop.org. The launcher passes the
+    /// id of the startup in the environment.
+    pub(super) fn complete_startup(window: &winit::window::Window) {
+        let startup_id = match env::var("DESKTOP_STARTUP_ID") {
+            Ok(startup_id) => startup_id,
+            Err(_) => return,
+        };
+        // the startup is completed once, and the processes started by the app must not inherit
+        // the id
+        env::remove_var("DESKTOP_STARTUP_ID");
+
+        // winit doesn't support the XDG activation protocol of Wayland
+        let (display, xwindow) = match (window.xlib_display(), window.xlib_window()) {
+            (Some(display), Some(xwindow)) => (display as *mut xlib::Display, xwindow),
+            _ => return,
+        };
+        let xlib = match xlib::Xlib::open() {
+            Ok(xlib) => xlib,
+            Err(err) => {
+                warn!("Failed to complete the startup notification: {}", err);
+                return;
+            }
+        };
+
+        // SAFETY: the display of the window stays open while the window exists
+        unsafe {
+            let intern_atom = |name: &str| {
+                let name = CString::new(name).unwrap();
+                (xlib.XInternAtom)(display, name.as_ptr(), xlib::False)
+            };
+            // lets the window manager match the window to the startup, to give it the focus
+            (xlib.XChangeProperty)(
+                display,
+                xwindow,
+                intern_atom("_NET_STARTUP_ID"),
+                intern_atom("UTF8_STRING"),
+                8,
+                xlib::PropModeReplace,
+                startup_id.as_ptr(),
+                startup_id.len() as c_int,
+            );
+
+            let message = format!(
+                "remove: ID=\"{}\"\0",
+                startup_id.replace('\\', "\\\\").replace('"', "\\\"")
+            );
+            send_message(
+                &xlib,
+                display,
+                intern_atom("_NET_STARTUP_INFO_BEGIN"),
+                intern_atom("_NET_STARTUP_INFO"),
+                message.as_bytes(),
+            );
+            (xlib.XFlush)(display);
+        }
+    }
+
+    /// Broadcasts `message` to the root window, in chunks of 20 bytes as the client messages
+    /// can't hold more.
+    unsafe fn send_message(
+        xlib: &xlib::Xlib,
+        display: *mut xlib::Display,
+        begin_type: c_ulong,
+        type_: c_ulong,
+        message: &[u8],
+    ) {
+        let root = (xlib.XDefaultRootWindow)(display);
+        // the messages are sent from a window of their own, as required by the protocol
+        let mut attributes: xlib::XSetWindowAttributes = mem::zeroed();
+        attributes.override_redirect = xlib::True;
+        let sender = (xlib.XCreateWindow)(
+            display,
+            root,
+            -100,
+            -100,
+            1,
+            1,
+            0,
+            0,
+            xlib::InputOnly as c_uint,
+            ptr::null_mut(),
+            xlib::CWOverrideRedirect,
+            &mut attributes,
+        );
+        for (i, chunk) in message.chunks(20).enumerate() {
+            let mut client_message: xlib::XClientMessageEvent = mem::zeroed();
+            client_message.type_ = xlib::ClientMessage;
+            client_message.display = display;
+            client_message.window = sender;
+            client_message.message_type = if i == 0 { begin_type } else { type_ };
+            client_message.format = 8;
+            for (j, byte) in chunk.iter().enumerate() {
+                client_message.data.set_byte(j, *byte as c_char);
+            }
+            let mut event = xlib::XEvent { client_message };
+            (xlib.XSendEvent)(
+                display,
+                root,
+                xlib::False,
+                xlib::PropertyChangeMask,
+                &mut event,
+            );
+        }
+        (xlib.XDestroyWindow)(display, sender);
+    }
+}
+
+#[cfg(not(all(
+    feature = "x11",
+    any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    )
+)))]
+mod platform {
+    pub(super) fn complete_startup(_window: &winit::window::Window) {}
+}
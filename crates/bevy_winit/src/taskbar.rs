@@ -0,0 +1,294 @@
+use bevy_window::TaskbarProgress;
+
+/// Shows `progress` on the taskbar button of `window`. Does nothing on the platforms without a
+/// taskbar progress.
+pub(crate) fn set_taskbar_progress(
+    window: &winit::window::Window,
+    progress: Option<TaskbarProgress>,
+) -> Result<(), String> {
+    platform::set_taskbar_progress(window, progress)
+}
+
+/// Shows `label` in an overlay icon on the taskbar button of `window`, or removes the overlay icon
+/// with `None`. Does nothing on the platforms other than Windows.
+pub(crate) fn set_overlay_icon(
+    window: &winit::window::Window,
+    label: Option<&str>,
+) -> Result<(), String> {
+    platform::set_overlay_icon(window, label)
+}
+
+/// Sets the application user model id of the process, which groups the windows on the taskbar.
+/// Does nothing on the platforms other than Windows.
+pub(crate) fn set_app_user_model_id(app_user_model_id: &str) -> Result<(), String> {
+    platform::set_app_user_model_id(app_user_model_id)
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use bevy_window::{TaskbarProgress, TaskbarProgressState};
+    use std::{ffi::OsStr, iter, mem, os::windows::ffi::OsStrExt, ptr, slice};
+    use winapi::{
+        ctypes::c_void,
+        shared::{
+            minwindef::{LPVOID, TRUE},
+            windef::{HGDIOBJ, HICON, HWND, RECT},
+            winerror::{FAILED, HRESULT},
+            wtypesbase::CLSCTX_INPROC_SERVER,
+        },
+        um::{
+            combaseapi::CoCreateInstance,
+            shobjidl_core::{
+                CLSID_TaskbarList, ITaskbarList3, TBPF_ERROR, TBPF_INDETERMINATE, TBPF_NOPROGRESS,
+                TBPF_NORMAL, TBPF_PAUSED,
+            },
+            wingdi::{
+                CreateBitmap, CreateCompatibleDC, CreateDIBSection, CreateSolidBrush, DeleteDC,
+                DeleteObject, Ellipse, GdiFlush, GetStockObject, SelectObject, SetBkMode,
+                SetTextColor, BITMAPINFO, BITMAPINFOHEADER, BI_RGB, DEFAULT_GUI_FONT,
+                DIB_RGB_COLORS, NULL_PEN, RGB, TRANSPARENT,
+            },
+            winuser::{
+                CreateIconIndirect, DestroyIcon, DrawTextW, GetSystemMetrics, DT_CENTER,
+                DT_SINGLELINE, DT_VCENTER, ICONINFO, SM_CXSMICON,
+            },
+        },
+        Interface,
+    };
+    use winit::platform::windows::WindowExtWindows;
+
+    #[link(name = "shell32")]
+    extern "system" {
+        // missing from winapi
+        fn SetCurrentProcessExplicitAppUserModelID(app_id: *const u16) -> HRESULT;
+    }
+
+    fn to_wide_string(string: &str) -> Vec<u16> {
+        OsStr::new(string)
+            .encode_wide()
+            .chain(iter::once(0))
+            .collect()
+    }
+
+    pub(super) fn set_app_user_model_id(app_user_model_id: &str) -> Result<(), String> {
+        let app_user_model_id = to_wide_string(app_user_model_id);
+        // SAFETY: the id is a null terminated wide string, copied by the call
+        let result = unsafe { SetCurrentProcessExplicitAppUserModelID(app_user_model_id.as_ptr()) };
+        if FAILED(result) {
+            return Err(format!("0x{:x}", result));
+        }
+        Ok(())
+    }
+
+    /// The resolution of the progress value passed to the taskbar.
+    const PROGRESS_TOTAL: u64 = 10_000;
+
+    thread_local! {
+        // created on first use, and kept for the lifetime of the thread
+        static TASKBAR_LIST: Result<*mut ITaskbarList3, HRESULT> = create_taskbar_list();
+    }
+
+    fn create_taskbar_list() -> Result<*mut ITaskbarList3, HRESULT> {
+        let mut taskbar_list: *mut ITaskbarList3 = ptr::null_mut();
+        // SAFETY: COM is initialized by winit on the thread of the event loop, which is the one
+        // applying the window commands
+        unsafe {
+            let result = CoCreateInstance(
+                &CLSID_TaskbarList,
+                ptr::null_mut(),
+                CLSCTX_INPROC_SERVER,
+                &ITaskbarList3::uuidof(),
+                &mut taskbar_list as *mut *mut ITaskbarList3 as *mut LPVOID,
+            );
+            if FAILED(result) {
+                return Err(result);
+            }
+            let result = (*taskbar_list).HrInit();
+            if FAILED(result) {
+                (*taskbar_list).Release();
+                return Err(result);
+            }
+        }
+        Ok(taskbar_list)
+    }
+
+    pub(super) fn set_taskbar_progress(
+        window: &winit::window::Window,
+        progress: Option<TaskbarProgress>,
+    ) -> Result<(), String> {
+        TASKBAR_LIST.with(|taskbar_list| {
+            let taskbar_list = (*taskbar_list)
+                .map_err(|result| format!("the taskbar is not available (0x{:x})", result))?;
+            let hwnd = window.hwnd() as HWND;
+            let state = match progress.map(|progress| progress.state) {
+                None => TBPF_NOPROGRESS,
+                Some(TaskbarProgressState::Normal) => TBPF_NORMAL,
+                Some(TaskbarProgressState::Paused) => TBPF_PAUSED,
+                Some(TaskbarProgressState::Error) => TBPF_ERROR,
+                Some(TaskbarProgressState::Indeterminate) => TBPF_INDETERMINATE,
+            };
+            // SAFETY: the taskbar list is only used on the thread that created it
+            let result = unsafe {
+                match progress {
+                    Some(progress) if progress.state != TaskbarProgressState::Indeterminate => {
+                        // setting the value also switches from the indeterminate state
+                        let completed =
+                            (progress.value.clamp(0.0, 1.0) as f64 * PROGRESS_TOTAL as f64) as u64;
+                        let result =
+                            (*taskbar_list).SetProgressValue(hwnd, completed, PROGRESS_TOTAL);
+                        if FAILED(result) {
+                            result
+                        } else {
+                            (*taskbar_list).SetProgressState(hwnd, state)
+                        }
+                    }
+                    _ => (*taskbar_list).SetProgressState(hwnd, state),
+                }
+            };
+            if FAILED(result) {
+                return Err(format!("0x{:x}", result));
+            }
+            Ok(())
+        })
+    }
+
+    pub(super) fn set_overlay_icon(
+        window: &winit::window::Window,
+        label: Option<&str>,
+    ) -> Result<(), String> {
+        TASKBAR_LIST.with(|taskbar_list| {
+            let taskbar_list = (*taskbar_list)
+                .map_err(|result| format!("the taskbar is not available (0x{:x})", result))?;
+            let hwnd = window.hwnd() as HWND;
+            // SAFETY: the taskbar list is only used on the thread that created it, and it keeps a
+            // copy of the icon, which can be destroyed after the call
+            let result = unsafe {
+                match label {
+                    // a null icon removes the overlay icon
+                    None => (*taskbar_list).SetOverlayIcon(hwnd, ptr::null_mut(), ptr::null()),
+                    Some(label) => {
+                        let icon = create_badge_icon(label)?;
+                        let description = to_wide_string(label);
+                        let result =
+                            (*taskbar_list).SetOverlayIcon(hwnd, icon, description.as_ptr());
+                        DestroyIcon(icon);
+                        result
+                    }
+                }
+            };
+            if FAILED(result) {
+                return Err(format!("0x{:x}", result));
+            }
+            Ok(())
+        })
+    }
+
+    /// Draws `label` in white on a red disc, in an icon of the size of the small icons.
+    unsafe fn create_badge_icon(label: &str) -> Result<HICON, String> {
+        let size = GetSystemMetrics(SM_CXSMICON).max(16);
+
+        // a top-down 32 bits color bitmap, whose alpha channel is the transparency of the icon
+        let mut bitmap_info: BITMAPINFO = mem::zeroed();
+        bitmap_info.bmiHeader.biSize = mem::size_of::<BITMAPINFOHEADER>() as u32;
+        bitmap_info.bmiHeader.biWidth = size;
+        bitmap_info.bmiHeader.biHeight = -size;
+        bitmap_info.bmiHeader.biPlanes = 1;
+        bitmap_info.bmiHeader.biBitCount = 32;
+        bitmap_info.bmiHeader.biCompression = BI_RGB;
+        let mut bits: *mut c_void = ptr::null_mut();
+        let color = CreateDIBSection(
+            ptr::null_mut(),
+            &bitmap_info,
+            DIB_RGB_COLORS,
+            &mut bits,
+            ptr::null_mut(),
+            0,
+        );
+        if color.is_null() {
+            return Err("unable to create the bitmap of the overlay icon".to_string());
+        }
+        // the mask is ignored when the color bitmap has an alpha channel, but is still required
+        let mask_row_bytes = (size as usize + 15) / 16 * 2;
+        let mask_bits = vec![0u8; mask_row_bytes * size as usize];
+        let mask = CreateBitmap(size, size, 1, 1, mask_bits.as_ptr() as *const c_void);
+
+        let dc = CreateCompatibleDC(ptr::null_mut());
+        let previous_bitmap = SelectObject(dc, color as HGDIOBJ);
+        let brush = CreateSolidBrush(RGB(0xd0, 0x20, 0x20));
+        let previous_brush = SelectObject(dc, brush as HGDIOBJ);
+        let previous_pen = SelectObject(dc, GetStockObject(NULL_PEN as i32));
+        Ellipse(dc, 0, 0, size, size);
+        let previous_font = SelectObject(dc, GetStockObject(DEFAULT_GUI_FONT as i32));
+        SetBkMode(dc, TRANSPARENT as i32);
+        SetTextColor(dc, RGB(0xff, 0xff, 0xff));
+        let text = label.encode_utf16().collect::<Vec<_>>();
+        let mut rect = RECT {
+            left: 0,
+            top: 0,
+            right: size,
+            bottom: size,
+        };
+        DrawTextW(
+            dc,
+            text.as_ptr(),
+            text.len() as i32,
+            &mut rect,
+            DT_CENTER | DT_VCENTER | DT_SINGLELINE,
+        );
+        GdiFlush();
+        SelectObject(dc, previous_font);
+        SelectObject(dc, previous_pen);
+        SelectObject(dc, previous_brush);
+        SelectObject(dc, previous_bitmap);
+        DeleteObject(brush as HGDIOBJ);
+        DeleteDC(dc);
+
+        // GDI leaves the alpha channel of the drawn pixels at zero, the bitmap being zeroed when
+        // created, the pixels left black are the transparent ones
+        let pixels = slice::from_raw_parts_mut(bits as *mut u32, (size * size) as usize);
+        for pixel in pixels {
+            if *pixel & 0x00ff_ffff != 0 {
+                *pixel |= 0xff00_0000;
+            }
+        }
+
+        let mut icon_info = ICONINFO {
+            fIcon: TRUE,
+            xHotspot: 0,
+            yHotspot: 0,
+            hbmMask: mask,
+            hbmColor: color,
+        };
+        let icon = CreateIconIndirect(&mut icon_info);
+        // the icon has its own copy of the bitmaps
+        DeleteObject(mask as HGDIOBJ);
+        DeleteObject(color as HGDIOBJ);
+        if icon.is_null() {
+            return Err("unable to create the overlay icon".to_string());
+        }
+        Ok(icon)
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+mod platform {
+    use bevy_window::TaskbarProgress;
+
+    pub(super) fn set_taskbar_progress(
+        _window: &winit::window::Window,
+        _progress: Option<TaskbarProgress>,
+    ) -> Result<(), String> {
+        Ok(())
+    }
+
+    pub(super) fn set_overlay_icon(
+        _window: &winit::window::Window,
+        _label: Option<&str>,
+    ) -> Result<(), String> {
+        Ok(())
+    }
+
+    pub(super) fn set_app_user_model_id(_app_user_model_id: &str) -> Result<(), String> {
+        Ok(())
+    }
+}
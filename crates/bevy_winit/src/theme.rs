@@ -0,0 +1,48 @@
+/// Makes the titlebar of `window` dark when the window uses the dark theme.
+///
+/// winit only applies the theme to the titlebar on the Windows versions released before the
+/// immersive dark mode attribute of the window manager. Does nothing on the other platforms.
+pub(crate) fn update_titlebar_theme(window: &winit::window::Window) {
+    platform::update_titlebar_theme(window);
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use std::mem;
+    use winapi::{
+        shared::{
+            minwindef::{BOOL, DWORD, LPCVOID},
+            windef::HWND,
+            winerror::SUCCEEDED,
+        },
+        um::dwmapi::DwmSetWindowAttribute,
+    };
+    use winit::{platform::windows::WindowExtWindows, window::Theme};
+
+    /// `DWMWA_USE_IMMERSIVE_DARK_MODE`, which is missing from winapi, then its value on the
+    /// Windows 10 versions before 20H1.
+    const USE_IMMERSIVE_DARK_MODE: [DWORD; 2] = [20, 19];
+
+    pub(super) fn update_titlebar_theme(window: &winit::window::Window) {
+        let dark = (window.theme() == Theme::Dark) as BOOL;
+        for attribute in USE_IMMERSIVE_DARK_MODE.iter() {
+            // SAFETY: the attribute is a `BOOL`, and `dark` outlives the call
+            let result = unsafe {
+                DwmSetWindowAttribute(
+                    window.hwnd() as HWND,
+                    *attribute,
+                    &dark as *const BOOL as LPCVOID,
+                    mem::size_of::<BOOL>() as DWORD,
+                )
+            };
+            if SUCCEEDED(result) {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+mod platform {
+    pub(super) fn update_titlebar_theme(_window: &winit::window::Window) {}
+}
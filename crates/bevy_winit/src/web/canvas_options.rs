@@ -0,0 +1,72 @@
+use bevy_window::CanvasOptions;
+use wasm_bindgen::{prelude::*, JsCast};
+
+/// The keys the browser scrolls the page with.
+const SCROLL_KEYS: [&str; 9] = [
+    " ",
+    "ArrowUp",
+    "ArrowDown",
+    "ArrowLeft",
+    "ArrowRight",
+    "PageUp",
+    "PageDown",
+    "Home",
+    "End",
+];
+
+/// Configures how the browser handles the events of `canvas`, as set by `options`.
+pub(crate) fn apply_canvas_options(canvas: &web_sys::HtmlCanvasElement, options: &CanvasOptions) {
+    if let Some(touch_action) = &options.touch_action {
+        canvas
+            .style()
+            .set_property("touch-action", touch_action)
+            .unwrap();
+    }
+
+    if options.prevent_context_menu {
+        let callback = Closure::wrap(Box::new(|event: web_sys::Event| {
+            event.prevent_default();
+        }) as Box<dyn FnMut(_)>);
+        canvas
+            .add_event_listener_with_callback("contextmenu", callback.as_ref().unchecked_ref())
+            .unwrap();
+        // the listener is kept for the lifetime of the canvas
+        callback.forget();
+    }
+
+    if options.prevent_scroll_keys || options.prevent_shortcuts {
+        let options = options.clone();
+        let callback = Closure::wrap(Box::new(move |event: web_sys::KeyboardEvent| {
+            let scroll_key = SCROLL_KEYS.contains(&event.key().as_str());
+            let shortcut = event.ctrl_key() || event.meta_key();
+            if (options.prevent_scroll_keys && scroll_key)
+                || (options.prevent_shortcuts && shortcut)
+            {
+                event.prevent_default();
+            }
+        }) as Box<dyn FnMut(_)>);
+        canvas
+            .add_event_listener_with_callback("keydown", callback.as_ref().unchecked_ref())
+            .unwrap();
+        callback.forget();
+    }
+
+    if options.prevent_touch_gestures {
+        let callback = Closure::wrap(Box::new(|event: web_sys::Event| {
+            event.prevent_default();
+        }) as Box<dyn FnMut(_)>);
+        // touch listeners are passive by default in some browsers, which ignore `prevent_default`
+        let mut listener_options = web_sys::AddEventListenerOptions::new();
+        listener_options.passive(false);
+        for event_type in ["touchstart", "touchmove"] {
+            canvas
+                .add_event_listener_with_callback_and_add_event_listener_options(
+                    event_type,
+                    callback.as_ref().unchecked_ref(),
+                    &listener_options,
+                )
+                .unwrap();
+        }
+        callback.forget();
+    }
+}
@@ -0,0 +1,77 @@
+use crate::clipboard::ClipboardReadResult;
+use bevy_utils::tracing::warn;
+use bevy_window::ClipboardCommand;
+use crossbeam_channel::{Receiver, Sender};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+use winit::event_loop::EventLoopProxy;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(catch, js_namespace = ["navigator", "clipboard"], js_name = writeText)]
+    fn write_text(text: &str) -> Result<js_sys::Promise, JsValue>;
+
+    #[wasm_bindgen(catch, js_namespace = ["navigator", "clipboard"], js_name = readText)]
+    fn read_text() -> Result<js_sys::Promise, JsValue>;
+}
+
+/// Accesses the clipboard through the asynchronous Clipboard API of the browser, which is only
+/// available to pages served over HTTPS or from localhost.
+pub(crate) struct ClipboardBackend {
+    read_sender: Sender<ClipboardReadResult>,
+    read_receiver: Receiver<ClipboardReadResult>,
+}
+
+impl Default for ClipboardBackend {
+    fn default() -> Self {
+        let (read_sender, read_receiver) = crossbeam_channel::unbounded();
+        ClipboardBackend {
+            read_sender,
+            read_receiver,
+        }
+    }
+}
+
+// WARNING: this only works under the assumption that wasm runtime is single threaded
+unsafe impl Send for ClipboardBackend {}
+unsafe impl Sync for ClipboardBackend {}
+
+impl ClipboardBackend {
+    /// Applies `command`. The text read is received asynchronously, after which `wake` wakes the
+    /// event loop to update the app.
+    pub(crate) fn apply(&mut self, command: ClipboardCommand, wake: Option<&EventLoopProxy<()>>) {
+        match command {
+            ClipboardCommand::SetText(text) => match write_text(&text) {
+                Ok(promise) => wasm_bindgen_futures::spawn_local(async move {
+                    if let Err(err) = JsFuture::from(promise).await {
+                        warn!("Failed to write to the clipboard: {:?}", err);
+                    }
+                }),
+                Err(err) => warn!("Failed to write to the clipboard: {:?}", err),
+            },
+            ClipboardCommand::ReadText(id) => {
+                let read_sender = self.read_sender.clone();
+                let wake = wake.cloned();
+                match read_text() {
+                    Ok(promise) => wasm_bindgen_futures::spawn_local(async move {
+                        let result = JsFuture::from(promise)
+                            .await
+                            .map(|text| text.as_string().unwrap_or_default())
+                            .map_err(|err| format!("{:?}", err));
+                        let _ = read_sender.send((id, result));
+                        if let Some(wake) = wake {
+                            let _ = wake.send_event(());
+                        }
+                    }),
+                    Err(err) => {
+                        let _ = read_sender.send((id, Err(format!("{:?}", err))));
+                    }
+                }
+            }
+        }
+    }
+
+    pub(crate) fn read_results(&mut self) -> impl Iterator<Item = ClipboardReadResult> + '_ {
+        self.read_receiver.try_iter()
+    }
+}
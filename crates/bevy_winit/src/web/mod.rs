@@ -0,0 +1,32 @@
+//! Integration of the windows with the web page, for the wasm32 target.
+
+mod canvas_options;
+mod clipboard;
+mod orientation;
+mod pointer_lock;
+mod resize;
+#[cfg(feature = "web_storage")]
+mod storage;
+mod visibility;
+
+pub(crate) use canvas_options::*;
+pub(crate) use clipboard::*;
+pub(crate) use orientation::*;
+pub(crate) use pointer_lock::*;
+pub(crate) use resize::*;
+#[cfg(feature = "web_storage")]
+pub(crate) use storage::*;
+pub(crate) use visibility::*;
+
+use bevy_app::{App, CoreStage};
+
+pub(crate) fn build(app: &mut App) {
+    app.init_resource::<CanvasResizeObservers>()
+        .init_resource::<PointerLockListener>()
+        .init_resource::<PageVisibilityListener>()
+        .add_system_to_stage(CoreStage::PreUpdate, canvas_resize_system)
+        .add_system_to_stage(CoreStage::PreUpdate, pointer_lock_system)
+        .add_system_to_stage(CoreStage::PreUpdate, page_visibility_system);
+    #[cfg(feature = "web_storage")]
+    app.add_system_to_stage(CoreStage::PostUpdate, window_storage_system);
+}
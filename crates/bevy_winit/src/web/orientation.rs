@@ -0,0 +1,39 @@
+use bevy_utils::tracing::warn;
+use bevy_window::ScreenOrientation;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::OrientationLockType;
+
+/// Locks the orientation of the screen with the Screen Orientation API. The browser answers
+/// asynchronously, so a rejected lock is only logged.
+pub(crate) fn set_screen_orientation(
+    _window: &winit::window::Window,
+    orientation: ScreenOrientation,
+) -> Result<(), String> {
+    let screen_orientation = web_sys::window()
+        .unwrap()
+        .screen()
+        .map_err(|err| format!("{:?}", err))?
+        .orientation();
+    let lock_type = match orientation {
+        ScreenOrientation::Auto => {
+            return screen_orientation
+                .unlock()
+                .map_err(|err| format!("{:?}", err));
+        }
+        ScreenOrientation::Portrait => OrientationLockType::Portrait,
+        ScreenOrientation::Landscape => OrientationLockType::Landscape,
+        ScreenOrientation::PortraitPrimary => OrientationLockType::PortraitPrimary,
+        ScreenOrientation::PortraitSecondary => OrientationLockType::PortraitSecondary,
+        ScreenOrientation::LandscapePrimary => OrientationLockType::LandscapePrimary,
+        ScreenOrientation::LandscapeSecondary => OrientationLockType::LandscapeSecondary,
+    };
+    let promise = screen_orientation
+        .lock(lock_type)
+        .map_err(|err| format!("{:?}", err))?;
+    wasm_bindgen_futures::spawn_local(async move {
+        if let Err(err) = JsFuture::from(promise).await {
+            warn!("Unable to set the screen orientation: {:?}", err);
+        }
+    });
+    Ok(())
+}
@@ -0,0 +1,120 @@
+use crate::WinitWindows;
+use bevy_app::EventWriter;
+use bevy_ecs::system::{Res, ResMut};
+use bevy_window::{CursorGrabChangeReason, CursorGrabChanged, CursorGrabMode, WindowId, Windows};
+use crossbeam_channel::Receiver;
+use wasm_bindgen::{prelude::*, JsCast};
+use winit::platform::web::WindowExtWebSys;
+
+#[derive(Clone, Copy)]
+enum PointerLockEvent {
+    Changed,
+    Error,
+}
+
+/// Listens to the pointer lock events of the page, as the browser can reject a pointer lock or
+/// exit it on its own.
+pub(crate) struct PointerLockListener {
+    events: Receiver<PointerLockEvent>,
+    /// The window whose canvas holds the pointer lock.
+    locked_window: Option<WindowId>,
+}
+
+impl Default for PointerLockListener {
+    fn default() -> Self {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        let document = web_sys::window().unwrap().document().unwrap();
+        for (event_type, event) in [
+            ("pointerlockchange", PointerLockEvent::Changed),
+            ("pointerlockerror", PointerLockEvent::Error),
+        ] {
+            let sender = sender.clone();
+            let callback = Closure::wrap(Box::new(move || {
+                let _ = sender.send(event);
+            }) as Box<dyn FnMut()>);
+            document
+                .add_event_listener_with_callback(event_type, callback.as_ref().unchecked_ref())
+                .unwrap();
+            // the listener is kept for the lifetime of the page
+            callback.forget();
+        }
+        PointerLockListener {
+            events: receiver,
+            locked_window: None,
+        }
+    }
+}
+
+// WARNING: this only works under the assumption that wasm runtime is single threaded
+unsafe impl Send for PointerLockListener {}
+unsafe impl Sync for PointerLockListener {}
+
+/// Keeps the cursor lock mode of the windows in sync with the pointer lock of the page.
+pub(crate) fn pointer_lock_system(
+    mut listener: ResMut<PointerLockListener>,
+    winit_windows: Res<WinitWindows>,
+    mut windows: ResMut<Windows>,
+    mut cursor_grab_changed_events: EventWriter<CursorGrabChanged>,
+) {
+    let listener = &mut *listener;
+    for event in listener.events.try_iter() {
+        let document = web_sys::window().unwrap().document().unwrap();
+        let locked_element = document.pointer_lock_element();
+        let locked_window = windows
+            .iter()
+            .find(|window| {
+                winit_windows
+                    .get_window(window.id())
+                    .is_some_and(|winit_window| {
+                        let canvas: web_sys::Element = winit_window.canvas().into();
+                        locked_element.as_ref() == Some(&canvas)
+                    })
+            })
+            .map(|window| window.id());
+
+        match event {
+            PointerLockEvent::Changed => {
+                if locked_window == listener.locked_window {
+                    continue;
+                }
+                if let Some(window) = listener.locked_window.and_then(|id| windows.get_mut(id)) {
+                    // the window still wants the lock, so the browser exited it on its own
+                    let reason = if window.cursor_locked() {
+                        window.update_cursor_grab_mode_from_backend(CursorGrabMode::None);
+                        CursorGrabChangeReason::Released
+                    } else {
+                        CursorGrabChangeReason::Requested
+                    };
+                    cursor_grab_changed_events.send(CursorGrabChanged {
+                        id: window.id(),
+                        mode: CursorGrabMode::None,
+                        reason,
+                    });
+                }
+                if let Some(window) = locked_window.and_then(|id| windows.get_mut(id)) {
+                    window.update_cursor_grab_mode_from_backend(CursorGrabMode::Locked);
+                    cursor_grab_changed_events.send(CursorGrabChanged {
+                        id: window.id(),
+                        mode: CursorGrabMode::Locked,
+                        reason: CursorGrabChangeReason::Requested,
+                    });
+                }
+                listener.locked_window = locked_window;
+            }
+            PointerLockEvent::Error => {
+                for window in windows.iter_mut() {
+                    if window.cursor_locked() && Some(window.id()) != locked_window {
+                        window.update_cursor_grab_mode_from_backend(CursorGrabMode::None);
+                        cursor_grab_changed_events.send(CursorGrabChanged {
+                            id: window.id(),
+                            mode: CursorGrabMode::None,
+                            reason: CursorGrabChangeReason::Rejected(
+                                "The browser rejected the pointer lock".to_string(),
+                            ),
+                        });
+                    }
+                }
+            }
+        }
+    }
+}
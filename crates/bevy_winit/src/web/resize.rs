@@ -0,0 +1,124 @@
+use bevy_app::EventReader;
+use bevy_ecs::system::{Res, ResMut};
+use bevy_utils::{tracing::warn, HashMap};
+use bevy_window::{CanvasResize, WindowBackendScaleFactorChanged, WindowId, Windows};
+use crossbeam_channel::{Receiver, Sender};
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+extern "C" {
+    type ResizeObserver;
+
+    #[wasm_bindgen(constructor)]
+    fn new(callback: &Closure<dyn FnMut()>) -> ResizeObserver;
+
+    #[wasm_bindgen(method)]
+    fn observe(this: &ResizeObserver, target: &web_sys::Element);
+
+    #[wasm_bindgen(method)]
+    fn disconnect(this: &ResizeObserver);
+}
+
+struct CanvasResizeObserver {
+    observer: ResizeObserver,
+    _callback: Closure<dyn FnMut()>,
+    element: web_sys::Element,
+}
+
+/// Watches the elements that the canvases of the windows follow the size of, as configured by
+/// [`WindowDescriptor::canvas_resize`](bevy_window::WindowDescriptor::canvas_resize).
+pub(crate) struct CanvasResizeObservers {
+    observers: HashMap<WindowId, CanvasResizeObserver>,
+    resize_sender: Sender<(WindowId, f32, f32)>,
+    resize_receiver: Receiver<(WindowId, f32, f32)>,
+}
+
+impl Default for CanvasResizeObservers {
+    fn default() -> Self {
+        let (resize_sender, resize_receiver) = crossbeam_channel::unbounded();
+        CanvasResizeObservers {
+            observers: Default::default(),
+            resize_sender,
+            resize_receiver,
+        }
+    }
+}
+
+impl CanvasResizeObservers {
+    pub(crate) fn observe(
+        &mut self,
+        window_id: WindowId,
+        canvas: &web_sys::HtmlCanvasElement,
+        canvas_resize: &CanvasResize,
+    ) {
+        let element = match canvas_resize {
+            CanvasResize::Parent => canvas.parent_element(),
+            CanvasResize::Element(selector) => web_sys::window()
+                .and_then(|window| window.document())
+                .and_then(|document| document.query_selector(selector).ok().flatten()),
+        };
+        let element = match element {
+            Some(element) => element,
+            None => {
+                warn!(
+                    "Cannot find the element to resize the canvas of window {} to: {:?}",
+                    window_id, canvas_resize
+                );
+                return;
+            }
+        };
+
+        let resize_sender = self.resize_sender.clone();
+        let observed = element.clone();
+        let callback = Closure::wrap(Box::new(move || {
+            let _ = resize_sender.send((
+                window_id,
+                observed.client_width() as f32,
+                observed.client_height() as f32,
+            ));
+        }) as Box<dyn FnMut()>);
+        let observer = ResizeObserver::new(&callback);
+        observer.observe(&element);
+        let observer = CanvasResizeObserver {
+            observer,
+            _callback: callback,
+            element,
+        };
+        if let Some(previous) = self.observers.insert(window_id, observer) {
+            previous.observer.disconnect();
+        }
+    }
+}
+
+// WARNING: this only works under the assumption that wasm runtime is single threaded
+unsafe impl Send for CanvasResizeObservers {}
+unsafe impl Sync for CanvasResizeObservers {}
+
+/// Resizes the windows to the size of the elements their canvas follows.
+///
+/// The windows are also resized when their scale factor changes, which winit detects by
+/// listening to `matchMedia` queries on the `devicePixelRatio` of the page. Zooming the page
+/// changes the scale factor, and the window would otherwise keep a size computed with the
+/// previous one.
+pub(crate) fn canvas_resize_system(
+    observers: Res<CanvasResizeObservers>,
+    mut scale_factor_events: EventReader<WindowBackendScaleFactorChanged>,
+    mut windows: ResMut<Windows>,
+) {
+    for (window_id, width, height) in observers.resize_receiver.try_iter() {
+        if let Some(window) = windows.get_mut(window_id) {
+            window.set_resolution(width, height);
+        }
+    }
+    for event in scale_factor_events.iter() {
+        if let (Some(window), Some(observer)) = (
+            windows.get_mut(event.id),
+            observers.observers.get(&event.id),
+        ) {
+            window.set_resolution(
+                observer.element.client_width() as f32,
+                observer.element.client_height() as f32,
+            );
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use bevy_app::EventReader;
+use bevy_ecs::system::{Local, Res};
+use bevy_utils::HashMap;
+use bevy_window::{WindowCreated, WindowDescriptor, WindowId, Windows};
+
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok().flatten()
+}
+
+/// The windows are saved under the title they are created with, so that the windows of an app
+/// are found again on the next page load.
+fn storage_key(title: &str) -> String {
+    format!("bevy_window:{}", title)
+}
+
+/// Returns `descriptor` with the logical size and the scale factor override saved by
+/// [`window_storage_system`] on a previous page load.
+pub(crate) fn restore_window_descriptor(descriptor: &WindowDescriptor) -> WindowDescriptor {
+    let mut descriptor = descriptor.clone();
+    let value = match local_storage()
+        .and_then(|storage| storage.get_item(&storage_key(&descriptor.title)).ok())
+        .flatten()
+    {
+        Some(value) => value,
+        None => return descriptor,
+    };
+    let mut fields = value.split(' ');
+    if let (Some(Ok(width)), Some(Ok(height)), Some(scale_factor_override)) = (
+        fields.next().map(str::parse),
+        fields.next().map(str::parse),
+        fields.next(),
+    ) {
+        descriptor.width = width;
+        descriptor.height = height;
+        descriptor.scale_factor_override = scale_factor_override.parse().ok();
+    }
+    descriptor
+}
+
+/// Saves the logical size and the scale factor override of the windows to the `localStorage` of
+/// the page whenever they change.
+pub(crate) fn window_storage_system(
+    mut saved: Local<HashMap<WindowId, (String, String)>>,
+    mut window_created_events: EventReader<WindowCreated>,
+    windows: Res<Windows>,
+) {
+    for event in window_created_events.iter() {
+        if let Some(window) = windows.get(event.id) {
+            saved.insert(event.id, (storage_key(window.title()), String::new()));
+        }
+    }
+    saved.retain(|id, _| windows.get(*id).is_some());
+
+    let storage = match local_storage() {
+        Some(storage) => storage,
+        None => return,
+    };
+    for (id, (key, saved_value)) in saved.iter_mut() {
+        let window = windows.get(*id).unwrap();
+        let value = format!(
+            "{} {} {}",
+            window.width(),
+            window.height(),
+            window
+                .scale_factor_override()
+                .map(|scale_factor| scale_factor.to_string())
+                .unwrap_or_default()
+        );
+        if *saved_value != value && storage.set_item(key, &value).is_ok() {
+            *saved_value = value;
+        }
+    }
+}
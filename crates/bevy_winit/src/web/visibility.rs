@@ -0,0 +1,62 @@
+use bevy_app::EventWriter;
+use bevy_ecs::system::{Res, ResMut};
+use bevy_window::{WindowFocused, WindowOccluded, Windows};
+use crossbeam_channel::Receiver;
+use wasm_bindgen::{prelude::*, JsCast};
+
+/// Listens to the visibility of the page, as the browser doesn't report to the canvases that
+/// their tab has been switched or minimized.
+pub(crate) struct PageVisibilityListener {
+    changes: Receiver<()>,
+}
+
+impl Default for PageVisibilityListener {
+    fn default() -> Self {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        let document = web_sys::window().unwrap().document().unwrap();
+        let callback = Closure::wrap(Box::new(move || {
+            let _ = sender.send(());
+        }) as Box<dyn FnMut()>);
+        document
+            .add_event_listener_with_callback("visibilitychange", callback.as_ref().unchecked_ref())
+            .unwrap();
+        // the listener is kept for the lifetime of the page
+        callback.forget();
+        PageVisibilityListener { changes: receiver }
+    }
+}
+
+// WARNING: this only works under the assumption that wasm runtime is single threaded
+unsafe impl Send for PageVisibilityListener {}
+unsafe impl Sync for PageVisibilityListener {}
+
+/// Updates the occlusion of the windows when the page is hidden or shown. A window loses the
+/// focus when its page is hidden.
+pub(crate) fn page_visibility_system(
+    listener: Res<PageVisibilityListener>,
+    mut windows: ResMut<Windows>,
+    mut occluded_events: EventWriter<WindowOccluded>,
+    mut focused_events: EventWriter<WindowFocused>,
+) {
+    if listener.changes.try_iter().count() == 0 {
+        return;
+    }
+    let document = web_sys::window().unwrap().document().unwrap();
+    let occluded = document.hidden();
+    for window in windows.iter_mut() {
+        if window.is_occluded() != occluded {
+            window.update_occluded_from_backend(occluded);
+            occluded_events.send(WindowOccluded {
+                id: window.id(),
+                occluded,
+            });
+        }
+        if occluded && window.is_focused() {
+            window.update_focused_status_from_backend(false);
+            focused_events.send(WindowFocused {
+                id: window.id(),
+                focused: false,
+            });
+        }
+    }
+}
@@ -1,3 +1,6 @@
+use bevy_utils::{Duration, HashMap};
+use bevy_window::{WindowId, Windows};
+
 /// A resource for configuring usage of the `rust_winit` library.
 #[derive(Debug, Default)]
 pub struct WinitConfig {
@@ -12,4 +15,197 @@ pub struct WinitConfig {
     /// `openbsd`. If set to true on an unsupported platform
     /// [run](bevy_app::App::run) will panic.
     pub return_from_run: bool,
+    /// The application user model id of the process on Windows, such as
+    /// `"CompanyName.ProductName"`. The windows of the processes sharing an id are grouped
+    /// together on the taskbar, and notifications are attributed to the app of this id.
+    ///
+    /// Must be set before the app is run, and is ignored on the other platforms.
+    pub app_user_model_id: Option<String>,
+    /// Sends a single [`CursorMoved`](bevy_window::CursorMoved) event per window and per frame,
+    /// with the last position of the cursor, instead of one for each movement reported by the
+    /// platform. This reduces the number of events to process when the mouse moves fast, for
+    /// apps that only need the current position.
+    ///
+    /// The cursor position of the [`Window`](bevy_window::Window)s is always up to date. The
+    /// coalesced events are sent after the other window events of the frame, or right before the
+    /// [`CursorLeft`](bevy_window::CursorLeft) event of their window.
+    pub coalesce_cursor_moved: bool,
+    /// Sends a [`Timestamped`](bevy_window::Timestamped) copy of each window and input event,
+    /// with the time at which it was received and the frame in which it is read, for latency
+    /// analysis, input smoothing or replays.
+    pub timestamp_events: bool,
+    /// How often the app updates while one of its windows is focused.
+    pub focused_mode: UpdateMode,
+    /// How often the app updates while none of its windows is focused.
+    pub unfocused_mode: UpdateMode,
+    /// The update modes of the windows that don't follow the focused and unfocused modes, such
+    /// as the tool windows of an editor that only need to update when they receive events. The
+    /// app updates as often as the most demanding mode of its windows requires.
+    pub window_update_modes: HashMap<WindowId, WindowUpdateMode>,
+    /// The update mode used instead of the focused and unfocused modes while the device runs on
+    /// its battery or in battery saver mode, see [`PowerState`](bevy_window::PowerState).
+    pub battery_mode: Option<UpdateMode>,
+    /// The minimum time between two updates while the device runs on its battery or in battery
+    /// saver mode, to cap the frame rate.
+    pub battery_frame_time: Option<Duration>,
+}
+
+impl WinitConfig {
+    /// The update mode used when a window of the app is focused or not.
+    pub fn update_mode(&self, focused: bool) -> &UpdateMode {
+        if focused {
+            &self.focused_mode
+        } else {
+            &self.unfocused_mode
+        }
+    }
+
+    /// The update mode required by the `windows`: the most demanding of the modes of the
+    /// windows with a [`WindowUpdateMode`], and of the focused or unfocused mode if any window
+    /// has none.
+    pub fn update_mode_for_windows(&self, windows: &Windows) -> UpdateMode {
+        let focused = windows.iter().any(|window| window.is_focused());
+        let default_mode = *self.update_mode(focused);
+        windows
+            .iter()
+            .map(|window| match self.window_update_modes.get(&window.id()) {
+                Some(window_mode) => *window_mode.update_mode(window.is_focused()),
+                None => default_mode,
+            })
+            .reduce(UpdateMode::most_demanding)
+            .unwrap_or(default_mode)
+    }
+}
+
+/// How often the app updates for a window, overriding the focused and unfocused modes of the
+/// [`WinitConfig`], see [`WinitConfig::window_update_modes`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WindowUpdateMode {
+    /// How often the app updates while this window is focused.
+    pub focused: UpdateMode,
+    /// How often the app updates while this window isn't focused.
+    pub unfocused: UpdateMode,
+}
+
+impl WindowUpdateMode {
+    /// The same update mode whether the window is focused or not.
+    pub fn new(mode: UpdateMode) -> Self {
+        WindowUpdateMode {
+            focused: mode,
+            unfocused: mode,
+        }
+    }
+
+    /// The update mode used when the window is focused or not.
+    pub fn update_mode(&self, focused: bool) -> &UpdateMode {
+        if focused {
+            &self.focused
+        } else {
+            &self.unfocused
+        }
+    }
+}
+
+/// When the app updates.
+///
+/// In the reactive modes, the app can send a
+/// [`RequestRedraw`](bevy_window::RequestRedraw) event to update again right away, for example
+/// while an animation is playing. Other threads can update the app with a clone of the
+/// `EventLoopProxy<()>` non-send resource, by sending it `()`. The window backend does so when a
+/// global hotkey is pressed, or when the clipboard is read on the web.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UpdateMode {
+    /// The app updates continuously, as fast as possible.
+    #[default]
+    Continuous,
+    /// The app updates when it receives a window or device event, or after `max_wait` without
+    /// any event.
+    ///
+    /// Without `max_wait`, an idle app waits for the next event and uses no CPU.
+    Reactive { max_wait: Option<Duration> },
+    /// The app updates when it receives a window event, or after `max_wait` without any window
+    /// event. Device events such as mouse motion don't update the app, which makes it use less
+    /// power than [`UpdateMode::Reactive`].
+    ///
+    /// Without `max_wait`, an idle app waits for the next window event and uses no CPU.
+    ReactiveLowPower { max_wait: Option<Duration> },
+}
+
+impl UpdateMode {
+    /// The mode that updates the app at least as often as both `self` and `other`: continuous if
+    /// either is, reactive to device events if either is, and with the shortest `max_wait`.
+    pub fn most_demanding(self, other: UpdateMode) -> UpdateMode {
+        let min_wait = |a: Option<Duration>, b: Option<Duration>| match (a, b) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        match (self, other) {
+            (UpdateMode::Continuous, _) | (_, UpdateMode::Continuous) => UpdateMode::Continuous,
+            (
+                UpdateMode::ReactiveLowPower { max_wait: a },
+                UpdateMode::ReactiveLowPower { max_wait: b },
+            ) => UpdateMode::ReactiveLowPower {
+                max_wait: min_wait(a, b),
+            },
+            (
+                UpdateMode::Reactive { max_wait: a } | UpdateMode::ReactiveLowPower { max_wait: a },
+                UpdateMode::Reactive { max_wait: b } | UpdateMode::ReactiveLowPower { max_wait: b },
+            ) => UpdateMode::Reactive {
+                max_wait: min_wait(a, b),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{UpdateMode, WindowUpdateMode, WinitConfig};
+    use bevy_utils::Duration;
+    use bevy_window::{Window, WindowDescriptor, WindowId, Windows};
+
+    #[test]
+    fn most_demanding_window_update_mode() {
+        let low_power = |millis| UpdateMode::ReactiveLowPower {
+            max_wait: Some(Duration::from_millis(millis)),
+        };
+        let tool_window = WindowId::new();
+        let mut config = WinitConfig {
+            focused_mode: UpdateMode::Continuous,
+            unfocused_mode: low_power(100),
+            ..Default::default()
+        };
+        config
+            .window_update_modes
+            .insert(tool_window, WindowUpdateMode::new(low_power(50)));
+
+        let mut windows = Windows::default();
+        let mut window = Window::new(tool_window, &WindowDescriptor::default(), 1, 1, 1.0, None);
+        window.update_focused_status_from_backend(true);
+        windows.add(window);
+        assert_eq!(config.update_mode_for_windows(&windows), low_power(50));
+
+        let mut window = Window::new(
+            WindowId::primary(),
+            &WindowDescriptor::default(),
+            1,
+            1,
+            1.0,
+            None,
+        );
+        window.update_focused_status_from_backend(false);
+        windows.add(window);
+        // the primary window follows the focused mode while the tool window is focused
+        assert_eq!(
+            config.update_mode_for_windows(&windows),
+            UpdateMode::Continuous
+        );
+
+        config.focused_mode = UpdateMode::Reactive { max_wait: None };
+        assert_eq!(
+            config.update_mode_for_windows(&windows),
+            UpdateMode::Reactive {
+                max_wait: Some(Duration::from_millis(50))
+            }
+        );
+    }
 }
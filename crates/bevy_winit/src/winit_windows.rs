@@ -1,7 +1,17 @@
-use bevy_math::IVec2;
-use bevy_utils::HashMap;
-use bevy_window::{Window, WindowDescriptor, WindowId, WindowMode};
-use winit::dpi::LogicalSize;
+#[cfg(target_os = "windows")]
+use crate::converters::convert_window_theme;
+use crate::{
+    backend::WindowBackend,
+    converters::{self, convert_video_mode},
+    theme,
+};
+use bevy_math::{IVec2, UVec2};
+use bevy_utils::{tracing::warn, HashMap};
+use bevy_window::{
+    best_video_mode, fitting_video_mode, CursorGrabMode, MonitorSelection, VideoMode,
+    VideoModeSelection, Window, WindowDescriptor, WindowId, WindowMode,
+};
+use winit::dpi::{LogicalSize, PhysicalPosition};
 
 #[derive(Debug, Default)]
 pub struct WinitWindows {
@@ -17,29 +27,50 @@ impl WinitWindows {
         window_id: WindowId,
         window_descriptor: &WindowDescriptor,
     ) -> Window {
+        converters::warn_unsupported_level(window_descriptor.level);
         #[cfg(target_os = "windows")]
         let mut winit_window_builder = {
             use winit::platform::windows::WindowBuilderExtWindows;
-            winit::window::WindowBuilder::new().with_drag_and_drop(false)
+            winit::window::WindowBuilder::new()
+                .with_drag_and_drop(false)
+                .with_theme(window_descriptor.theme.map(convert_window_theme))
         };
 
         #[cfg(not(target_os = "windows"))]
         let mut winit_window_builder = winit::window::WindowBuilder::new();
 
+        let monitor = window_descriptor
+            .monitor
+            .and_then(|index| event_loop.available_monitors().nth(index))
+            .or_else(|| event_loop.primary_monitor());
         winit_window_builder = match window_descriptor.mode {
-            WindowMode::BorderlessFullscreen => winit_window_builder.with_fullscreen(Some(
-                winit::window::Fullscreen::Borderless(event_loop.primary_monitor()),
-            )),
-            WindowMode::Fullscreen { use_size } => winit_window_builder.with_fullscreen(Some(
-                winit::window::Fullscreen::Exclusive(match use_size {
-                    true => get_fitting_videomode(
-                        &event_loop.primary_monitor().unwrap(),
-                        window_descriptor.width as u32,
-                        window_descriptor.height as u32,
+            WindowMode::BorderlessFullscreen => winit_window_builder
+                .with_fullscreen(Some(winit::window::Fullscreen::Borderless(monitor))),
+            mode @ (WindowMode::Fullscreen { .. } | WindowMode::ExclusiveFullscreen { .. }) => {
+                let (monitor_selection, video_mode) = mode.exclusive_fullscreen().unwrap();
+                let monitor = select_monitor(
+                    monitor_selection,
+                    monitor,
+                    event_loop.primary_monitor(),
+                    event_loop.available_monitors(),
+                );
+                let monitor = monitor.unwrap();
+                // the video modes are selected with the physical size of the window
+                let scale_factor = window_descriptor
+                    .scale_factor_override
+                    .unwrap_or_else(|| monitor.scale_factor());
+                let physical_size =
+                    winit::dpi::LogicalSize::new(window_descriptor.width, window_descriptor.height)
+                        .to_physical::<u32>(scale_factor);
+                winit_window_builder.with_fullscreen(Some(winit::window::Fullscreen::Exclusive(
+                    get_selected_videomode(
+                        &monitor,
+                        video_mode,
+                        physical_size.width,
+                        physical_size.height,
                     ),
-                    false => get_best_videomode(&event_loop.primary_monitor().unwrap()),
-                }),
-            )),
+                )))
+            }
             _ => {
                 let WindowDescriptor {
                     width,
@@ -47,17 +78,39 @@ impl WinitWindows {
                     scale_factor_override,
                     ..
                 } = window_descriptor;
-                if let Some(sf) = scale_factor_override {
+                let winit_window_builder = if let Some(sf) = scale_factor_override {
                     winit_window_builder.with_inner_size(
                         winit::dpi::LogicalSize::new(*width, *height).to_physical::<f64>(*sf),
                     )
                 } else {
                     winit_window_builder
                         .with_inner_size(winit::dpi::LogicalSize::new(*width, *height))
+                };
+                match (
+                    window_descriptor.position,
+                    window_descriptor.monitor,
+                    monitor,
+                ) {
+                    (Some(position), _, _) => winit_window_builder
+                        .with_position(PhysicalPosition::new(position.x, position.y)),
+                    // centers the window on the requested monitor
+                    (None, Some(_), Some(monitor)) => {
+                        let size = LogicalSize::new(*width, *height).to_physical::<i32>(
+                            scale_factor_override.unwrap_or_else(|| monitor.scale_factor()),
+                        );
+                        let position = monitor.position();
+                        let monitor_size = monitor.size();
+                        winit_window_builder.with_position(PhysicalPosition::new(
+                            position.x + (monitor_size.width as i32 - size.width) / 2,
+                            position.y + (monitor_size.height as i32 - size.height) / 2,
+                        ))
+                    }
+                    _ => winit_window_builder,
                 }
             }
             .with_resizable(window_descriptor.resizable)
-            .with_decorations(window_descriptor.decorations),
+            .with_decorations(window_descriptor.decorations)
+            .with_always_on_top(converters::convert_window_level(window_descriptor.level)),
         };
 
         let constraints = window_descriptor.resize_constraints.check_constraints();
@@ -82,35 +135,106 @@ impl WinitWindows {
         #[allow(unused_mut)]
         let mut winit_window_builder = winit_window_builder.with_title(&window_descriptor.title);
 
+        #[cfg(any(
+            target_os = "linux",
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "netbsd",
+            target_os = "openbsd"
+        ))]
+        if let Some(application_id) = &window_descriptor.application_id {
+            #[cfg(feature = "x11")]
+            {
+                use winit::platform::unix::WindowBuilderExtUnix;
+                winit_window_builder =
+                    winit_window_builder.with_class(application_id.clone(), application_id.clone());
+            }
+            #[cfg(feature = "wayland")]
+            {
+                use winit::platform::unix::WindowBuilderExtUnix;
+                winit_window_builder = winit_window_builder.with_app_id(application_id.clone());
+            }
+        }
+
+        #[cfg(target_os = "ios")]
+        {
+            use crate::orientation::convert_valid_orientations;
+            use winit::platform::ios::{ScreenEdge, WindowBuilderExtIOS};
+
+            let options = window_descriptor.ios_options;
+            let edges = options.deferred_gesture_edges;
+            let mut deferred_gesture_edges = ScreenEdge::NONE;
+            for (deferred, edge) in [
+                (edges.top, ScreenEdge::TOP),
+                (edges.left, ScreenEdge::LEFT),
+                (edges.bottom, ScreenEdge::BOTTOM),
+                (edges.right, ScreenEdge::RIGHT),
+            ] {
+                if deferred {
+                    deferred_gesture_edges |= edge;
+                }
+            }
+            winit_window_builder = winit_window_builder
+                .with_prefers_status_bar_hidden(options.status_bar_hidden)
+                .with_prefers_home_indicator_hidden(options.home_indicator_auto_hidden)
+                .with_preferred_screen_edges_deferring_system_gestures(deferred_gesture_edges)
+                .with_valid_orientations(convert_valid_orientations(
+                    window_descriptor.screen_orientation,
+                ));
+        }
+
+        #[cfg(target_os = "macos")]
+        {
+            use winit::platform::macos::WindowBuilderExtMacOS;
+
+            let titlebar = window_descriptor.macos_titlebar;
+            winit_window_builder = winit_window_builder
+                .with_titlebar_transparent(titlebar.transparent)
+                .with_title_hidden(titlebar.title_hidden)
+                .with_fullsize_content_view(titlebar.fullsize_content_view);
+        }
+
+        // whether the window renders to a canvas that is already in the page
+        #[cfg(target_arch = "wasm32")]
+        let mut attached_to_canvas = false;
         #[cfg(target_arch = "wasm32")]
         {
             use wasm_bindgen::JsCast;
-            use winit::platform::web::WindowBuilderExtWebSys;
+            use winit::platform::web::{WindowBuilderExtWebSys, WindowExtWebSys};
 
             if let Some(selector) = &window_descriptor.canvas {
                 let window = web_sys::window().unwrap();
                 let document = window.document().unwrap();
-                let canvas = document
-                    .query_selector(&selector)
+                let elements = document
+                    .query_selector_all(&selector)
                     .expect("Cannot query for canvas element.");
+                // each window gets its own canvas, so that several windows can use the same
+                // selector
+                let used_canvases = self
+                    .windows
+                    .values()
+                    .map(|window| window.canvas())
+                    .collect::<Vec<_>>();
+                let canvas = (0..elements.length())
+                    .filter_map(|i| elements.item(i))
+                    .filter_map(|node| node.dyn_into::<web_sys::HtmlCanvasElement>().ok())
+                    .find(|canvas| !used_canvases.contains(canvas));
                 if let Some(canvas) = canvas {
-                    let canvas = canvas.dyn_into::<web_sys::HtmlCanvasElement>().ok();
-                    winit_window_builder = winit_window_builder.with_canvas(canvas);
+                    winit_window_builder = winit_window_builder.with_canvas(Some(canvas));
+                    attached_to_canvas = true;
                 } else {
-                    panic!("Cannot find element: {}.", selector);
+                    bevy_utils::tracing::warn!(
+                        "Cannot find a free canvas matching {}, creating a new one.",
+                        selector
+                    );
                 }
             }
         }
 
         let winit_window = winit_window_builder.build(event_loop).unwrap();
 
-        match winit_window.set_cursor_grab(window_descriptor.cursor_locked) {
-            Ok(_) => {}
-            Err(winit::error::ExternalError::NotSupported(_)) => {}
-            Err(err) => Err(err).unwrap(),
-        }
-
         winit_window.set_cursor_visible(window_descriptor.cursor_visible);
+        theme::update_titlebar_theme(&winit_window);
 
         self.window_id_to_winit.insert(window_id, winit_window.id());
         self.winit_to_window_id.insert(winit_window.id(), window_id);
@@ -119,7 +243,7 @@ impl WinitWindows {
         {
             use winit::platform::web::WindowExtWebSys;
 
-            if window_descriptor.canvas.is_none() {
+            if !attached_to_canvas {
                 let canvas = winit_window.canvas();
 
                 let window = web_sys::window().unwrap();
@@ -131,6 +255,17 @@ impl WinitWindows {
             }
         }
 
+        // the canvas must be in the page to get the pointer lock
+        let cursor_grab_mode = match window_descriptor.cursor_grab_mode {
+            CursorGrabMode::None => CursorGrabMode::None,
+            mode => winit_window
+                .set_cursor_grab_mode(mode)
+                .unwrap_or_else(|err| {
+                    warn!("Unable to grab the cursor: {}", err);
+                    CursorGrabMode::None
+                }),
+        };
+
         let position = winit_window
             .outer_position()
             .ok()
@@ -138,14 +273,16 @@ impl WinitWindows {
         let inner_size = winit_window.inner_size();
         let scale_factor = winit_window.scale_factor();
         self.windows.insert(winit_window.id(), winit_window);
-        Window::new(
+        let mut window = Window::new(
             window_id,
             window_descriptor,
             inner_size.width,
             inner_size.height,
             scale_factor,
             position,
-        )
+        );
+        window.update_cursor_grab_mode_from_backend(cursor_grab_mode);
+        window
     }
 
     pub fn get_window(&self, id: WindowId) -> Option<&winit::window::Window> {
@@ -163,45 +300,56 @@ pub fn get_fitting_videomode(
     width: u32,
     height: u32,
 ) -> winit::monitor::VideoMode {
-    let mut modes = monitor.video_modes().collect::<Vec<_>>();
-
-    fn abs_diff(a: u32, b: u32) -> u32 {
-        if a > b {
-            return a - b;
-        }
-        b - a
-    }
+    select_videomode(monitor, |video_modes| {
+        fitting_video_mode(video_modes, width, height)
+    })
+}
 
-    modes.sort_by(|a, b| {
-        use std::cmp::Ordering::*;
-        match abs_diff(a.size().width, width).cmp(&abs_diff(b.size().width, width)) {
-            Equal => {
-                match abs_diff(a.size().height, height).cmp(&abs_diff(b.size().height, height)) {
-                    Equal => b.refresh_rate().cmp(&a.refresh_rate()),
-                    default => default,
-                }
-            }
-            default => default,
-        }
-    });
+pub fn get_best_videomode(monitor: &winit::monitor::MonitorHandle) -> winit::monitor::VideoMode {
+    select_videomode(monitor, best_video_mode)
+}
 
-    modes.first().unwrap().clone()
+/// Selects a video mode of `monitor` for a window of `width` by `height` physical pixels.
+pub fn get_selected_videomode(
+    monitor: &winit::monitor::MonitorHandle,
+    selection: VideoModeSelection,
+    width: u32,
+    height: u32,
+) -> winit::monitor::VideoMode {
+    select_videomode(monitor, |video_modes| {
+        selection.select(video_modes, UVec2::new(width, height))
+    })
 }
 
-pub fn get_best_videomode(monitor: &winit::monitor::MonitorHandle) -> winit::monitor::VideoMode {
-    let mut modes = monitor.video_modes().collect::<Vec<_>>();
-    modes.sort_by(|a, b| {
-        use std::cmp::Ordering::*;
-        match b.size().width.cmp(&a.size().width) {
-            Equal => match b.size().height.cmp(&a.size().height) {
-                Equal => b.refresh_rate().cmp(&a.refresh_rate()),
-                default => default,
-            },
-            default => default,
-        }
-    });
+/// The monitor selected by `selection`, or the `current` one if the selected one doesn't exist.
+pub fn select_monitor(
+    selection: MonitorSelection,
+    current: Option<winit::monitor::MonitorHandle>,
+    primary: Option<winit::monitor::MonitorHandle>,
+    mut available: impl Iterator<Item = winit::monitor::MonitorHandle>,
+) -> Option<winit::monitor::MonitorHandle> {
+    let selected = match selection {
+        MonitorSelection::Current => None,
+        MonitorSelection::Primary => primary,
+        MonitorSelection::Index(index) => available.nth(index),
+    };
+    selected.or(current)
+}
 
-    modes.first().unwrap().clone()
+/// Selects a video mode of `monitor` with one of the selection functions of `bevy_window`, which
+/// work on the bevy descriptions of the video modes.
+fn select_videomode(
+    monitor: &winit::monitor::MonitorHandle,
+    select: impl for<'a> Fn(&'a [VideoMode]) -> Option<&'a VideoMode>,
+) -> winit::monitor::VideoMode {
+    let modes = monitor.video_modes().collect::<Vec<_>>();
+    let video_modes = modes.iter().map(convert_video_mode).collect::<Vec<_>>();
+    let selected = select(&video_modes).unwrap();
+    let index = video_modes
+        .iter()
+        .position(|video_mode| video_mode == selected)
+        .unwrap();
+    modes[index].clone()
 }
 
 // WARNING: this only works under the assumption that wasm runtime is single threaded
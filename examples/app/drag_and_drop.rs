@@ -4,6 +4,7 @@ fn main() {
     App::new()
         .add_plugins(DefaultPlugins)
         .add_system(file_drag_and_drop_system)
+        .add_system(files_dropped_system)
         .run();
 }
 
@@ -12,3 +13,20 @@ fn file_drag_and_drop_system(mut events: EventReader<FileDragAndDrop>) {
         info!("{:?}", event);
     }
 }
+
+fn files_dropped_system(mut events: EventReader<FilesDropped>) {
+    for event in events.iter() {
+        info!(
+            "{} files dropped at {:?}",
+            event.paths.len(),
+            event.position
+        );
+        for (path, mime_type) in event.files() {
+            info!(
+                "{}: {}",
+                path.display(),
+                mime_type.unwrap_or("unknown type")
+            );
+        }
+    }
+}
@@ -1,55 +1,31 @@
 use bevy::prelude::*;
 
-/// This example illustrates how to create a button that changes color and text based on its
-/// interaction state.
+/// This example illustrates how to create a button that changes text based on its interaction
+/// state, and whose color is animated by the [`UiTheme`].
 fn main() {
     App::new()
         .add_plugins(DefaultPlugins)
-        .init_resource::<ButtonMaterials>()
         .add_startup_system(setup)
         .add_system(button_system)
         .run();
 }
 
-struct ButtonMaterials {
-    normal: Handle<ColorMaterial>,
-    hovered: Handle<ColorMaterial>,
-    pressed: Handle<ColorMaterial>,
-}
-
-impl FromWorld for ButtonMaterials {
-    fn from_world(world: &mut World) -> Self {
-        let mut materials = world.get_resource_mut::<Assets<ColorMaterial>>().unwrap();
-        ButtonMaterials {
-            normal: materials.add(Color::rgb(0.15, 0.15, 0.15).into()),
-            hovered: materials.add(Color::rgb(0.25, 0.25, 0.25).into()),
-            pressed: materials.add(Color::rgb(0.35, 0.75, 0.35).into()),
-        }
-    }
-}
-
+#[allow(clippy::type_complexity)]
 fn button_system(
-    button_materials: Res<ButtonMaterials>,
-    mut interaction_query: Query<
-        (&Interaction, &mut Handle<ColorMaterial>, &Children),
-        (Changed<Interaction>, With<Button>),
-    >,
+    interaction_query: Query<(&Interaction, &Children), (Changed<Interaction>, With<Button>)>,
     mut text_query: Query<&mut Text>,
 ) {
-    for (interaction, mut material, children) in interaction_query.iter_mut() {
+    for (interaction, children) in interaction_query.iter() {
         let mut text = text_query.get_mut(children[0]).unwrap();
         match *interaction {
             Interaction::Clicked => {
                 text.sections[0].value = "Press".to_string();
-                *material = button_materials.pressed.clone();
             }
             Interaction::Hovered => {
                 text.sections[0].value = "Hover".to_string();
-                *material = button_materials.hovered.clone();
             }
             Interaction::None => {
                 text.sections[0].value = "Button".to_string();
-                *material = button_materials.normal.clone();
             }
         }
     }
@@ -58,7 +34,7 @@ fn button_system(
 fn setup(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
-    button_materials: Res<ButtonMaterials>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
 ) {
     // ui camera
     commands.spawn_bundle(UiCameraBundle::default());
@@ -74,9 +50,11 @@ fn setup(
                 align_items: AlignItems::Center,
                 ..Default::default()
             },
-            material: button_materials.normal.clone(),
+            // the theme animates the color of this material, so the button needs its own
+            material: materials.add(ColorMaterial::default()),
             ..Default::default()
         })
+        .insert_bundle((ThemedWidget::Button, VisualTransition::default()))
         .with_children(|parent| {
             parent.spawn_bundle(TextBundle {
                 text: Text::with_section(
@@ -0,0 +1,86 @@
+use bevy::prelude::*;
+
+/// This example illustrates a list longer than its viewport, scrolled with the mouse wheel, by
+/// dragging it on a touch screen or with its scrollbar. The items are clipped to the viewport.
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let text_style = TextStyle {
+        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+        font_size: 30.0,
+        color: Color::rgb(0.9, 0.9, 0.9),
+    };
+    let item_materials = [
+        materials.add(Color::rgb(0.2, 0.2, 0.2).into()),
+        materials.add(Color::rgb(0.25, 0.25, 0.25).into()),
+    ];
+
+    // ui camera
+    commands.spawn_bundle(UiCameraBundle::default());
+    let root = commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            material: materials.add(Color::NONE.into()),
+            ..Default::default()
+        })
+        .id();
+
+    let mut scroll_view = commands.spawn();
+    let parts = ScrollViewBlueprint {
+        style: Style {
+            size: Size::new(Val::Px(400.0), Val::Px(300.0)),
+            overflow: Overflow::Hidden,
+            ..Default::default()
+        },
+        material: materials.add(Color::rgb(0.1, 0.1, 0.1).into()),
+        content_material: materials.add(Color::NONE.into()),
+        scrollbar_materials: Some((
+            materials.add(Color::rgb(0.15, 0.15, 0.15).into()),
+            materials.add(Color::rgb(0.35, 0.75, 0.35).into()),
+        )),
+        ..Default::default()
+    }
+    .build(&mut scroll_view);
+    let scroll_view = scroll_view.id();
+    commands.entity(root).push_children(&[scroll_view]);
+
+    commands.entity(parts.content).with_children(|parent| {
+        for index in 0..30 {
+            parent
+                .spawn_bundle(NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Percent(100.0), Val::Px(50.0)),
+                        padding: Rect::all(Val::Px(10.0)),
+                        align_items: AlignItems::Center,
+                        ..Default::default()
+                    },
+                    material: item_materials[index % 2].clone(),
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    parent.spawn_bundle(TextBundle {
+                        text: Text::with_section(
+                            format!("Item {}", index + 1),
+                            text_style.clone(),
+                            Default::default(),
+                        ),
+                        ..Default::default()
+                    });
+                });
+        }
+    });
+}
@@ -0,0 +1,210 @@
+use bevy::prelude::*;
+
+/// This example illustrates how to spawn the built-in widgets from their blueprints, how to bind
+/// their values to the fields of a component, and how to style them with a hot-reloaded theme.
+///
+/// Tab, the arrow keys or the D-pad of a gamepad move the keyboard focus between the widgets, and Enter, Space or the South button of a gamepad activate the focused widget.
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .register_type::<Settings>()
+        .add_startup_system(setup)
+        .add_system(settings_system)
+        .add_system(quality_system)
+        .run();
+}
+
+#[derive(Default, Reflect)]
+#[reflect(Component)]
+struct Settings {
+    muted: bool,
+    volume: f32,
+}
+
+struct ValueLabel;
+
+fn settings_system(
+    settings_query: Query<&Settings, Changed<Settings>>,
+    mut text_query: Query<&mut Text, With<ValueLabel>>,
+) {
+    for settings in settings_query.iter() {
+        for mut text in text_query.iter_mut() {
+            text.sections[0].value = if settings.muted {
+                "muted".to_string()
+            } else {
+                format!("{:.2}", settings.volume)
+            };
+        }
+    }
+}
+
+fn quality_system(
+    mut dropdown_changed_events: EventReader<DropdownChanged>,
+    dropdown_query: Query<&Dropdown>,
+) {
+    for event in dropdown_changed_events.iter() {
+        if let Ok(dropdown) = dropdown_query.get(event.entity) {
+            info!("Quality: {}", dropdown.options[event.selected]);
+        }
+    }
+}
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut theme_source: ResMut<UiThemeSource>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    // edit the theme file while the example is running to restyle the widgets
+    asset_server.watch_for_changes().unwrap();
+    theme_source.0 = Some(asset_server.load("themes/widgets.theme.ron"));
+
+    let text_style = TextStyle {
+        font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+        font_size: 30.0,
+        color: Color::rgb(0.9, 0.9, 0.9),
+    };
+    let widget_material = materials.add(Color::rgb(0.15, 0.15, 0.15).into());
+    let accent_material = materials.add(Color::rgb(0.35, 0.75, 0.35).into());
+    let settings = commands
+        .spawn()
+        .insert(Settings {
+            muted: false,
+            volume: 0.5,
+        })
+        .id();
+
+    // ui camera
+    commands.spawn_bundle(UiCameraBundle::default());
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                flex_direction: FlexDirection::ColumnReverse,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            // the color of this material is the "background" color of the theme
+            material: materials.add(ColorMaterial::default()),
+            ..Default::default()
+        })
+        .insert(ThemedColor("background".to_string()))
+        .with_children(|parent| {
+            parent
+                .spawn()
+                .insert_blueprint(ButtonBlueprint {
+                    style: Style {
+                        size: Size::new(Val::Px(150.0), Val::Px(65.0)),
+                        margin: Rect::all(Val::Px(10.0)),
+                        justify_content: JustifyContent::Center,
+                        align_items: AlignItems::Center,
+                        ..Default::default()
+                    },
+                    // the theme animates the color of this material, so the button needs its own
+                    material: materials.add(ColorMaterial::default()),
+                    label: Some(Text::with_section(
+                        "Button",
+                        text_style.clone(),
+                        Default::default(),
+                    )),
+                })
+                .insert_bundle((ThemedWidget::Button, VisualTransition::default()));
+
+            // a form with the labels in the first column and the widgets in the second
+            parent
+                .spawn_bundle(NodeBundle {
+                    style: Style {
+                        // grid containers are not sized by their items
+                        size: Size::new(Val::Px(350.0), Val::Px(190.0)),
+                        margin: Rect::all(Val::Px(10.0)),
+                        ..Default::default()
+                    },
+                    material: materials.add(Color::NONE.into()),
+                    ..Default::default()
+                })
+                .insert(GridContainer {
+                    columns: vec![GridTrack::Px(130.0), GridTrack::Fraction(1.0)],
+                    auto_rows: GridTrack::Px(40.0),
+                    column_gap: 20.0,
+                    row_gap: 10.0,
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    for label in ["Sound", "Volume", "Quality", "Value"] {
+                        parent
+                            .spawn_bundle(TextBundle {
+                                text: Text::with_section(
+                                    label,
+                                    text_style.clone(),
+                                    Default::default(),
+                                ),
+                                ..Default::default()
+                            })
+                            .insert(GridPlacement {
+                                column: Some(0),
+                                ..Default::default()
+                            });
+                    }
+
+                    // clicking the label of the checkbox toggles it too
+                    parent
+                        .spawn()
+                        .insert_blueprint(LabeledCheckboxBlueprint {
+                            checkbox: CheckboxBlueprint {
+                                material: widget_material.clone(),
+                                mark_material: accent_material.clone(),
+                                ..Default::default()
+                            },
+                            label: Text::with_section(
+                                "Muted",
+                                text_style.clone(),
+                                Default::default(),
+                            ),
+                            material: materials.add(Color::NONE.into()),
+                            ..Default::default()
+                        })
+                        .insert_bundle((
+                            GridPlacement::cell(1, 0),
+                            WidgetBinding::new::<Settings>(settings, "muted"),
+                        ));
+
+                    parent
+                        .spawn()
+                        .insert_blueprint(SliderBlueprint {
+                            material: widget_material.clone(),
+                            thumb_material: accent_material.clone(),
+                            ..Default::default()
+                        })
+                        .insert_bundle((
+                            GridPlacement::cell(1, 1),
+                            WidgetBinding::new::<Settings>(settings, "volume"),
+                        ));
+
+                    // the list of options is drawn above the value label when it is open
+                    parent
+                        .spawn()
+                        .insert_blueprint(DropdownBlueprint {
+                            options: ["Low", "Medium", "High"]
+                                .iter()
+                                .map(|option| option.to_string())
+                                .collect(),
+                            selected: Some(1),
+                            material: widget_material.clone(),
+                            text_style: text_style.clone(),
+                            list_material: widget_material.clone(),
+                            option_material: materials.add(Color::rgb(0.25, 0.25, 0.25).into()),
+                            ..Default::default()
+                        })
+                        .insert(GridPlacement::cell(1, 2));
+
+                    // the font, size and color of themed text come from the theme
+                    parent
+                        .spawn_bundle(TextBundle {
+                            text: Text::with_section("", Default::default(), Default::default()),
+                            ..Default::default()
+                        })
+                        .insert_bundle((ValueLabel, ThemedText, GridPlacement::cell(1, 3)));
+                });
+        });
+}
@@ -0,0 +1,42 @@
+use bevy::{prelude::*, render::camera::SplitscreenCamera};
+
+/// This example opens two windows tiled on the monitor, each with its own camera viewing the
+/// same mesh.
+fn main() {
+    App::new()
+        .insert_resource(Msaa { samples: 4 })
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup)
+        .add_system(place_cameras)
+        .run();
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn_splitscreen(
+        2,
+        SplitscreenLayout::Tiled,
+        WindowDescriptor {
+            title: "splitscreen".to_string(),
+            ..Default::default()
+        },
+    );
+
+    commands.spawn_scene(asset_server.load("models/monkey/Monkey.gltf#Scene0"));
+    commands.spawn_bundle(PointLightBundle {
+        transform: Transform::from_xyz(4.0, 5.0, 4.0),
+        ..Default::default()
+    });
+}
+
+fn place_cameras(
+    mut cameras: Query<(&SplitscreenCamera, &mut Transform), Added<SplitscreenCamera>>,
+) {
+    for (camera, mut transform) in cameras.iter_mut() {
+        let position = if camera.index == 0 {
+            Vec3::new(0.0, 0.0, 6.0)
+        } else {
+            Vec3::new(6.0, 0.0, 0.0)
+        };
+        *transform = Transform::from_translation(position).looking_at(Vec3::ZERO, Vec3::Y);
+    }
+}